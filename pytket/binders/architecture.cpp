@@ -13,6 +13,7 @@
 // limitations under the License.
 
 #include "Architecture/Architecture.hpp"
+#include "Architecture/ShuttlingArchitecture.hpp"
 
 #include <pybind11/eigen.h>
 #include <pybind11/operators.h>
@@ -48,6 +49,23 @@ PYBIND11_MODULE(architecture, m) {
           "between qubits.\n\n:param connections: A list of pairs "
           "representing Nodes that can perform two-qubit operations",
           py::arg("connections"))
+      .def(
+          py::init([](const std::vector<std::pair<Node, Node>> &connections,
+                      const std::map<std::pair<Node, Node>, OpTypeSet>
+                          &gate_sets) {
+            Architecture arc(connections);
+            for (const auto &[con, gate_set] : gate_sets) {
+              arc.set_gate_set(con.first, con.second, gate_set);
+            }
+            return arc;
+          }),
+          "The constructor for an architecture with connectivity "
+          "between qubits, whose connections may natively support different "
+          "two-qubit gates.\n\n:param connections: A list of pairs "
+          "representing Nodes that can perform two-qubit operations"
+          "\n:param gate_sets: A map from pairs of connected Nodes to the "
+          "two-qubit gate types native to their connection",
+          py::arg("connections"), py::arg("gate_sets"))
       .def(
           "__repr__",
           [](const Architecture &arc) {
@@ -76,6 +94,16 @@ PYBIND11_MODULE(architecture, m) {
           "coupling", &Architecture::get_all_edges_vec,
           "Returns the coupling map of the Architecture as "
           "UnitIDs. ")
+      .def_property_readonly(
+          "gate_sets", &Architecture::get_gate_sets,
+          "Returns the native two-qubit gate types of each connection "
+          "which has them, keyed by pairs with the lesser node first.")
+      .def(
+          "get_gate_set", &Architecture::get_gate_set,
+          "Given two nodes in Architecture, returns the native two-qubit "
+          "gate types of the connection between them, or None if they have "
+          "not been set.",
+          py::arg("node_0"), py::arg("node_1"))
       .def(
           "to_dict", [](const Architecture &arch) { return json(arch); },
           "Return a JSON serializable dict representation of "
@@ -159,6 +187,98 @@ PYBIND11_MODULE(architecture, m) {
       .def("__repr__", [](const RingArch &arc) {
         return "<tket::RingArch, nodes=" + std::to_string(arc.n_nodes()) + ">";
       });
+  py::class_<Zone>(
+      m, "Zone",
+      "A group of sites of a :py:class:`ShuttlingArchitecture`, each holding "
+      "at most one qubit.")
+      .def(
+          py::init([](const std::string &name, const std::vector<Node> &sites,
+                      bool interaction) {
+            return Zone{name, sites, interaction};
+          }),
+          "Construct a zone.\n\n:param name: Name of the zone"
+          "\n:param sites: Sites held by the zone"
+          "\n:param interaction: Whether multi-qubit gates can act on qubits "
+          "held in the zone",
+          py::arg("name"), py::arg("sites"), py::arg("interaction"))
+      .def_readonly("name", &Zone::name, "Name of the zone.")
+      .def_readonly("sites", &Zone::sites, "Sites held by the zone.")
+      .def_readonly(
+          "interaction", &Zone::interaction,
+          "Whether multi-qubit gates can act on qubits held in the zone.")
+      .def(py::self == py::self)
+      .def("__repr__", [](const Zone &zone) {
+        return "<tket::Zone, name=" + zone.name +
+               ", sites=" + std::to_string(zone.sites.size()) + ">";
+      });
+  py::class_<
+      ShuttlingArchitecture, std::shared_ptr<ShuttlingArchitecture>,
+      Architecture>(
+      m, "ShuttlingArchitecture",
+      "Inherited Architecture class for devices whose qubits are moved "
+      "between sites, such as QCCD ion-trap devices. Multi-qubit gates can "
+      "act on any qubits held in the same interaction zone, and qubits are "
+      "moved between sites by ``OpType.Transport`` operations.")
+      .def(
+          py::init([](const std::vector<Zone> &zones,
+                      const std::vector<std::tuple<
+                          std::pair<Node, Node>, double, double>> &transports) {
+            std::vector<ShuttlingArchitecture::Transport> ts;
+            for (const auto &[link, time, heating] : transports) {
+              ts.push_back({link, {time, heating}});
+            }
+            return ShuttlingArchitecture(zones, ts);
+          }),
+          "Construct a shuttling architecture."
+          "\n\n:param zones: Zones of the device, with disjoint sets of sites"
+          "\n:param transports: List of ((site_0, site_1), time, heating) "
+          "giving the pairs of sites between which qubits can be moved, in "
+          "either direction, with the time taken and heating caused",
+          py::arg("zones"), py::arg("transports"))
+      .def_property_readonly(
+          "zones", &ShuttlingArchitecture::get_zones, "Zones of the device.")
+      .def_property_readonly(
+          "transports",
+          [](const ShuttlingArchitecture &arc) {
+            std::vector<std::tuple<std::pair<Node, Node>, double, double>> ts;
+            for (const ShuttlingArchitecture::Transport &t :
+                 arc.get_transports()) {
+              ts.push_back({t.first, t.second.time, t.second.heating});
+            }
+            return ts;
+          },
+          "Transport connections as ((site_0, site_1), time, heating).")
+      .def(
+          "get_zone_index", &ShuttlingArchitecture::get_zone_index,
+          "Index of the zone holding a site.", py::arg("site"))
+      .def(
+          "transport_exists", &ShuttlingArchitecture::transport_exists,
+          "Whether a qubit can be moved between two sites.",
+          py::arg("site_0"), py::arg("site_1"))
+      .def(
+          "to_dict",
+          [](const ShuttlingArchitecture &arch) { return json(arch); },
+          "Return a JSON serializable dict representation of "
+          "the ShuttlingArchitecture.\n"
+          ":return: dict containing zones and transports.")
+      .def_static(
+          "from_dict",
+          [](const json &j) { return j.get<ShuttlingArchitecture>(); },
+          "Construct ShuttlingArchitecture instance from JSON serializable "
+          "dict representation of the ShuttlingArchitecture.")
+      .def(
+          "__deepcopy__",
+          [](const ShuttlingArchitecture &arc, py::dict = py::dict()) {
+            return arc;
+          })
+      .def(
+          "__repr__",
+          [](const ShuttlingArchitecture &arc) {
+            return "<tket::ShuttlingArchitecture, zones=" +
+                   std::to_string(arc.get_zones().size()) +
+                   ", sites=" + std::to_string(arc.n_nodes()) + ">";
+          })
+      .def(py::self == py::self);
   py::class_<FullyConnected>(
       m, "FullyConnected",
       "A specialised non-Architecture object emulating an architecture with "
@@ -23,6 +23,7 @@
 #include "Circuit/Boxes.hpp"
 #include "Circuit/Circuit.hpp"
 #include "Circuit/ClassicalExpBox.hpp"
+#include "Converters/DiagonalBox.hpp"
 #include "Converters/PhasePoly.hpp"
 #include "Gate/OpPtrFunctions.hpp"
 #include "Ops/Op.hpp"
@@ -226,6 +227,18 @@ void init_circuit_add_op(py::class_<Circuit, std::shared_ptr<Circuit>> &c) {
           "\n:return: the new :py:class:`Circuit`",
           py::arg("unitarybox"), py::arg("qubit_0"), py::arg("qubit_1"),
           py::arg("qubit_2"))
+      .def(
+          "add_unitarynqbox",
+          [](Circuit *circ, const UnitaryNqBox &box,
+             const std::vector<unsigned> &qubits, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<UnitaryNqBox>(box), qubits, kwargs);
+          },
+          "Append a :py:class:`UnitaryNqBox` to the circuit."
+          "\n\n:param unitarybox: box to append"
+          "\n:param qubits: indices of the target qubits"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("unitarybox"), py::arg("qubits"))
       .def(
           "add_expbox",
           [](Circuit *circ, const ExpBox &box, unsigned q0, unsigned q1,
@@ -251,6 +264,70 @@ void init_circuit_add_op(py::class_<Circuit, std::shared_ptr<Circuit>> &c) {
           "qubits: Indices of the qubits to append the box to"
           "\n:return: the new :py:class:`Circuit`",
           py::arg("pauliexpbox"), py::arg("qubits"))
+      .def(
+          "add_multiplexorbox",
+          [](Circuit *circ, const MultiplexorBox &box,
+             const std::vector<unsigned> &args, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<MultiplexorBox>(box), args, kwargs);
+          },
+          "Append a :py:class:`MultiplexorBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: Indices of the qubits to append the box to, "
+          "controls first"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_multiplexedrotationbox",
+          [](Circuit *circ, const MultiplexedRotationBox &box,
+             const std::vector<unsigned> &args, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<MultiplexedRotationBox>(box), args,
+                kwargs);
+          },
+          "Append a :py:class:`MultiplexedRotationBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: Indices of the qubits to append the box to, "
+          "controls first and the target last"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_diagonalbox",
+          [](Circuit *circ, const DiagonalBox &box,
+             const std::vector<unsigned> &args, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<DiagonalBox>(box), args, kwargs);
+          },
+          "Append a :py:class:`DiagonalBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: Indices of the qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_toffolibox",
+          [](Circuit *circ, const ToffoliBox &box,
+             const std::vector<unsigned> &args, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<ToffoliBox>(box), args, kwargs);
+          },
+          "Append a :py:class:`ToffoliBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: Indices of the qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_statepreparationbox",
+          [](Circuit *circ, const StatePreparationBox &box,
+             const std::vector<unsigned> &args, const py::kwargs &kwargs) {
+            return add_box_method<unsigned>(
+                circ, std::make_shared<StatePreparationBox>(box), args,
+                kwargs);
+          },
+          "Append a :py:class:`StatePreparationBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: Indices of the qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
       .def(
           "add_qcontrolbox",
           [](Circuit *circ, const QControlBox &box,
@@ -428,6 +505,19 @@ void init_circuit_add_op(py::class_<Circuit, std::shared_ptr<Circuit>> &c) {
           "\n:return: the new :py:class:`Circuit`",
           py::arg("unitarybox"), py::arg("qubit_0"), py::arg("qubit_1"),
           py::arg("qubit_2"))
+      .def(
+          "add_unitarynqbox",
+          [](Circuit *circ, const UnitaryNqBox &box,
+             const qubit_vector_t &qubits, const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<UnitaryNqBox>(box),
+                {qubits.begin(), qubits.end()}, kwargs);
+          },
+          "Append a :py:class:`UnitaryNqBox` to the circuit."
+          "\n\n:param unitarybox: box to append"
+          "\n:param qubits: the target qubits"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("unitarybox"), py::arg("qubits"))
       .def(
           "add_expbox",
           [](Circuit *circ, const ExpBox &box, const Qubit &q0, const Qubit &q1,
@@ -454,6 +544,72 @@ void init_circuit_add_op(py::class_<Circuit, std::shared_ptr<Circuit>> &c) {
           "qubits: The qubits to append the box to"
           "\n:return: the new :py:class:`Circuit`",
           py::arg("pauliexpbox"), py::arg("qubits"))
+      .def(
+          "add_multiplexorbox",
+          [](Circuit *circ, const MultiplexorBox &box,
+             const qubit_vector_t &args, const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<MultiplexorBox>(box),
+                {args.begin(), args.end()}, kwargs);
+          },
+          "Append a :py:class:`MultiplexorBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: The qubits to append the box to, controls first"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_multiplexedrotationbox",
+          [](Circuit *circ, const MultiplexedRotationBox &box,
+             const qubit_vector_t &args, const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<MultiplexedRotationBox>(box),
+                {args.begin(), args.end()}, kwargs);
+          },
+          "Append a :py:class:`MultiplexedRotationBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: The qubits to append the box to, controls first and "
+          "the target last"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_diagonalbox",
+          [](Circuit *circ, const DiagonalBox &box, const qubit_vector_t &args,
+             const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<DiagonalBox>(box),
+                {args.begin(), args.end()}, kwargs);
+          },
+          "Append a :py:class:`DiagonalBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: The qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_toffolibox",
+          [](Circuit *circ, const ToffoliBox &box, const qubit_vector_t &args,
+             const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<ToffoliBox>(box),
+                {args.begin(), args.end()}, kwargs);
+          },
+          "Append a :py:class:`ToffoliBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: The qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
+      .def(
+          "add_statepreparationbox",
+          [](Circuit *circ, const StatePreparationBox &box,
+             const qubit_vector_t &args, const py::kwargs &kwargs) {
+            return add_box_method<UnitID>(
+                circ, std::make_shared<StatePreparationBox>(box),
+                {args.begin(), args.end()}, kwargs);
+          },
+          "Append a :py:class:`StatePreparationBox` to the circuit.\n\n"
+          ":param box: The box to append\n"
+          ":param args: The qubits to append the box to"
+          "\n:return: the new :py:class:`Circuit`",
+          py::arg("box"), py::arg("args"))
       .def(
           "add_qcontrolbox",
           [](Circuit *circ, const QControlBox &box, const unit_vector_t &args,
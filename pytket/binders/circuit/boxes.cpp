@@ -19,6 +19,7 @@
 #include <pybind11/stl.h>
 
 #include "Circuit/Circuit.hpp"
+#include "Converters/DiagonalBox.hpp"
 #include "Converters/PhasePoly.hpp"
 #include "Utils/Json.hpp"
 #include "binder_json.hpp"
@@ -89,6 +90,27 @@ void init_boxes(py::module &m) {
       .def(
           "get_matrix", &Unitary3qBox::get_matrix,
           ":return: the unitary matrix (in ILO-BE format) as a numpy array");
+  py::class_<UnitaryNqBox, std::shared_ptr<UnitaryNqBox>, Op>(
+      m, "UnitaryNqBox",
+      "A user-defined operation on any number of qubits specified by a "
+      "unitary matrix. The circuit is synthesised using the quantum Shannon "
+      "decomposition.")
+      .def(
+          py::init<const Eigen::MatrixXcd &, BasisOrder>(),
+          "Construct from a unitary matrix.\n\n"
+          ":param m: The unitary matrix, of size :math:`2^n \\times 2^n` "
+          "for an :math:`n`-qubit operation\n"
+          ":param basis: Whether the provided unitary is in the ILO-BE "
+          "(increasing lexicographic order of qubit ids, big-endian "
+          "indexing) format, or DLO-BE (decreasing lexicographic order "
+          "of ids)",
+          py::arg("m"), py::arg("basis") = BasisOrder::ilo)
+      .def(
+          "get_circuit", [](UnitaryNqBox &ubox) { return *ubox.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def(
+          "get_matrix", &UnitaryNqBox::get_matrix,
+          ":return: the unitary matrix (in ILO-BE format) as a numpy array");
   py::class_<ExpBox, std::shared_ptr<ExpBox>, Op>(
       m, "ExpBox",
       "A user-defined two-qubit operation whose corresponding unitary "
@@ -146,6 +168,134 @@ void init_boxes(py::module &m) {
           "get_n_controls", &QControlBox::get_n_controls,
           ":return: the number of control qubits");
 
+  py::class_<MultiplexorBox, std::shared_ptr<MultiplexorBox>, Op>(
+      m, "MultiplexorBox",
+      "A user-defined multiplexed operation, which applies a different "
+      ":py:class:`Op` to the target qubits for each basis state of the "
+      "control qubits.")
+      .def(
+          py::init<const ctrl_op_map_t &>(),
+          "Construct from a map from basis states of the controls to ops. "
+          "Basis states not in the map act as the identity. The controls "
+          "occupy the low-index ports of the resulting operation.\n\n"
+          ":param op_map: Map from tuples of bools (one for each control) "
+          "to :py:class:`Op` s, all acting on the same number of qubits",
+          py::arg("op_map"))
+      .def(
+          "get_circuit", [](MultiplexorBox &box) { return *box.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def(
+          "get_op_map",
+          [](MultiplexorBox &box) {
+            py::dict op_map;
+            for (const auto &[state, op] : box.get_op_map()) {
+              op_map[py::tuple(py::cast(state))] = op;
+            }
+            return op_map;
+          },
+          ":return: the map from basis states of the controls to ops")
+      .def(
+          "get_n_controls", &MultiplexorBox::get_n_controls,
+          ":return: the number of control qubits");
+
+  py::class_<
+      MultiplexedRotationBox, std::shared_ptr<MultiplexedRotationBox>, Op>(
+      m, "MultiplexedRotationBox",
+      "A multiplexed (uniformly-controlled) rotation, which rotates the "
+      "target qubit about a fixed axis by a different angle for each basis "
+      "state of the control qubits.")
+      .def(
+          py::init<const std::vector<double> &, OpType>(),
+          "Construct from a list of angles and an axis. The controls occupy "
+          "the low-index ports of the resulting operation and the target the "
+          "last port.\n\n"
+          ":param angles: Rotation angles in half-turns, one for each basis "
+          "state of the controls (in ILO-BE order), so :math:`2^n` angles "
+          "for :math:`n` controls\n"
+          ":param axis: The rotation type (``OpType.Rx``, ``OpType.Ry`` or "
+          "``OpType.Rz``)",
+          py::arg("angles"), py::arg("axis"))
+      .def(
+          "get_circuit",
+          [](MultiplexedRotationBox &box) { return *box.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def(
+          "get_angles", &MultiplexedRotationBox::get_angles,
+          ":return: the rotation angles")
+      .def(
+          "get_axis", &MultiplexedRotationBox::get_axis,
+          ":return: the rotation type")
+      .def(
+          "get_n_controls", &MultiplexedRotationBox::get_n_controls,
+          ":return: the number of control qubits");
+
+  py::class_<StatePreparationBox, std::shared_ptr<StatePreparationBox>, Op>(
+      m, "StatePreparationBox",
+      "A box for preparing a given quantum state, synthesised using "
+      "uniformly-controlled rotations.")
+      .def(
+          py::init<const Eigen::VectorXcd &, bool>(),
+          "Construct from a normalised statevector.\n\n"
+          ":param statevector: The normalised statevector, of size "
+          ":math:`2^n` for :math:`n` qubits (in ILO-BE order)\n"
+          ":param with_initial_reset: Whether to reset all qubits before "
+          "preparing the state. If false, the box only prepares the state "
+          "when applied to qubits in the all-zero state.",
+          py::arg("statevector"), py::arg("with_initial_reset") = false)
+      .def(
+          "get_circuit",
+          [](StatePreparationBox &box) { return *box.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def(
+          "get_statevector", &StatePreparationBox::get_statevector,
+          ":return: the statevector")
+      .def(
+          "with_initial_reset", &StatePreparationBox::with_initial_reset,
+          ":return: whether the qubits are reset before preparing the state");
+
+  py::enum_<ToffoliBoxSynthStrat>(
+      m, "ToffoliBoxSynthStrat",
+      "Strategies for synthesising a :py:class:`ToffoliBox`")
+      .value(
+          "Naive", ToffoliBoxSynthStrat::Naive,
+          "Decompose each cycle of the permutation into transpositions in "
+          "the order given")
+      .value(
+          "CostAware", ToffoliBoxSynthStrat::CostAware,
+          "Decompose each cycle of the permutation into transpositions so as "
+          "to minimise the number of multi-controlled X gates");
+
+  py::class_<ToffoliBox, std::shared_ptr<ToffoliBox>, Op>(
+      m, "ToffoliBox",
+      "A permutation of computational basis states, synthesised as a "
+      "network of multi-controlled X gates.")
+      .def(
+          py::init<const state_perm_t &, ToffoliBoxSynthStrat>(),
+          "Construct from a permutation of basis states. Basis states not in "
+          "the map are left unchanged.\n\n"
+          ":param permutation: Map from tuples of bools (one for each qubit) "
+          "to their images, which must be a rearrangement of the keys\n"
+          ":param strat: The synthesis strategy",
+          py::arg("permutation"),
+          py::arg("strat") = ToffoliBoxSynthStrat::CostAware)
+      .def(
+          "get_circuit", [](ToffoliBox &box) { return *box.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def(
+          "get_permutation",
+          [](ToffoliBox &box) {
+            py::dict permutation;
+            for (const auto &[state, image] : box.get_permutation()) {
+              permutation[py::tuple(py::cast(state))] =
+                  py::tuple(py::cast(image));
+            }
+            return permutation;
+          },
+          ":return: the permutation of basis states")
+      .def(
+          "get_strat", &ToffoliBox::get_strat,
+          ":return: the synthesis strategy");
+
   py::class_<CompositeGateDef, composite_def_ptr_t>(
       m, "CustomGateDef",
       "A custom unitary gate definition, given as a composition of other "
@@ -256,6 +406,31 @@ void init_boxes(py::module &m) {
             return outmap;
           },
           "Map from Qubit to index in polynomial.");
+  py::class_<DiagonalBox, std::shared_ptr<DiagonalBox>, Op>(
+      m, "DiagonalBox",
+      "A diagonal unitary, synthesised as a phase polynomial using "
+      "GraySynth.")
+      .def(
+          py::init<const std::vector<Expr> &>(),
+          "Construct from a list of (possibly symbolic) phases. The box maps "
+          "basis state :math:`|j\\rangle` to "
+          ":math:`e^{i\\pi \\theta_j} |j\\rangle`.\n\n"
+          ":param phases: Phases :math:`\\theta_j` in half-turns, one for "
+          "each basis state (in ILO-BE order), so :math:`2^n` phases for "
+          ":math:`n` qubits",
+          py::arg("phases"))
+      .def(
+          "get_circuit", [](DiagonalBox &box) { return *box.to_circuit(); },
+          ":return: the :py:class:`Circuit` described by the box")
+      .def("get_phases", &DiagonalBox::get_phases, ":return: the phases")
+      .def(
+          "get_global_phase", &DiagonalBox::get_global_phase,
+          ":return: the global phase (in half-turns) of the box not "
+          "accounted for by :py:meth:`to_phase_poly_box`")
+      .def(
+          "to_phase_poly_box", &DiagonalBox::to_phase_poly_box,
+          ":return: a :py:class:`PhasePolyBox` equal to the box up to the "
+          "global phase given by :py:meth:`get_global_phase`");
   py::class_<ProjectorAssertionBox, std::shared_ptr<ProjectorAssertionBox>, Op>(
       m, "ProjectorAssertionBox",
       "A user-defined assertion specified by a 2x2, 4x4, or 8x8 projector "
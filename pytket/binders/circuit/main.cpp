@@ -264,6 +264,10 @@ PYBIND11_MODULE(circuit, m) {
           "Barrier", OpType::Barrier,
           "Meta-operation preventing compilation through it. Not "
           "automatically stripped by the compiler")
+      .value(
+          "Delay", OpType::Delay,
+          "Meta-operation waiting on some qubits for a fixed duration, "
+          "see :py:class:`DelayOp`")
       .value(
           "Label", OpType::Label,
           "Label for control flow jumps. Does not appear within a "
@@ -310,6 +314,10 @@ PYBIND11_MODULE(circuit, m) {
       .value(
           "Unitary3qBox", OpType::Unitary3qBox,
           "Represents an arbitrary three-qubit unitary operation by its matrix")
+      .value(
+          "UnitaryNqBox", OpType::UnitaryNqBox,
+          "Represents an arbitrary unitary operation on any number of qubits "
+          "by its matrix")
       .value(
           "ExpBox", OpType::ExpBox,
           "A two-qubit operation corresponding to a unitary matrix "
@@ -323,6 +331,24 @@ PYBIND11_MODULE(circuit, m) {
       .value(
           "QControlBox", OpType::QControlBox,
           "An arbitrary n-controlled operation")
+      .value(
+          "MultiplexorBox", OpType::MultiplexorBox,
+          "A multiplexed operation, applying a different operation for each "
+          "basis state of the controls")
+      .value(
+          "MultiplexedRotationBox", OpType::MultiplexedRotationBox,
+          "A multiplexed rotation, rotating the target by a different angle "
+          "for each basis state of the controls")
+      .value(
+          "StatePreparationBox", OpType::StatePreparationBox,
+          "A box for preparing an arbitrary quantum state")
+      .value(
+          "DiagonalBox", OpType::DiagonalBox,
+          "A diagonal unitary, specified by its phase on each basis state")
+      .value(
+          "ToffoliBox", OpType::ToffoliBox,
+          "A permutation of basis states, implemented with multi-controlled X "
+          "gates")
       .value(
           "CustomGate", OpType::CustomGate,
           ":math:`(\\alpha, \\beta, \\ldots) \\mapsto` A user-defined "
@@ -404,6 +430,10 @@ PYBIND11_MODULE(circuit, m) {
           ":math:`(\\alpha)` := n-controlled "
           ":math:`\\mathrm{Ry}(\\alpha)` gate.")
       .value("CnX", OpType::CnX, "n-controlled X gate.")
+      .value(
+          "Transport", OpType::Transport,
+          "Moves a qubit between two sites of a ShuttlingArchitecture. "
+          "Acts as a SWAP on the qubits held at the two sites")
       .value(
           "ZZMax", OpType::ZZMax,
           ":math:`e^{-\\frac{i\\pi}{4}(\\mathrm{Z} \\otimes "
@@ -529,6 +559,18 @@ PYBIND11_MODULE(circuit, m) {
           py::arg("type"), py::arg("signature"), py::arg("data"))
       .def_property_readonly("data", &MetaOp::get_data, "Get data from MetaOp");
 
+  py::class_<DelayOp, std::shared_ptr<DelayOp>, MetaOp>(
+      m, "DelayOp", "Meta operation waiting on some qubits for a duration")
+      .def(
+          py::init<double, unsigned>(),
+          "Construct DelayOp with a duration and number of qubits"
+          "\n\n:param duration: length of the delay, in the units of the gate "
+          "durations used for scheduling"
+          "\n:param n_qubits: number of qubits on which to wait",
+          py::arg("duration"), py::arg("n_qubits") = 1)
+      .def_property_readonly(
+          "duration", &DelayOp::get_duration, "Get duration of the DelayOp");
+
   init_library(m);
   init_boxes(m);
   init_classical(m);
@@ -22,11 +22,15 @@
 #include "Mapping/AASLabelling.hpp"
 #include "Mapping/AASRoute.hpp"
 #include "Mapping/BoxDecomposition.hpp"
+#include "Mapping/ExactRoutingMethod.hpp"
 #include "Mapping/LexiLabelling.hpp"
 #include "Mapping/LexiRoute.hpp"
 #include "Mapping/MappingManager.hpp"
 #include "Mapping/MultiGateReorder.hpp"
+#include "Mapping/NoiseAwareRoutingMethod.hpp"
 #include "Mapping/RoutingMethodCircuit.hpp"
+#include "Mapping/SabreRoutingMethod.hpp"
+#include "Mapping/ShuttlingRoutingMethod.hpp"
 #include "binder_utils.hpp"
 
 namespace py = pybind11;
@@ -81,6 +85,94 @@ PYBIND11_MODULE(mapping, m) {
           "physical mapping.",
           py::arg("lookahead") = 10);
 
+  py::class_<
+      NoiseAwareRoutingMethod, std::shared_ptr<NoiseAwareRoutingMethod>,
+      RoutingMethod>(
+      m, "NoiseAwareRoutingMethod",
+      "Defines a RoutingMethod object for mapping circuits that chooses SWAP "
+      "and BRIDGE gates to minimise the estimated infidelity of upcoming "
+      "two-qubit gates and measurements, using gate and readout errors of the "
+      "device. Only supports 1-qubit, 2-qubit and barrier gates.")
+      .def(
+          py::init([](const avg_node_errors_t &node_errors,
+                      const avg_link_errors_t &link_errors,
+                      const avg_readout_errors_t &readout_errors,
+                      unsigned lookahead) {
+            return NoiseAwareRoutingMethod(
+                DeviceCharacterisation(
+                    node_errors, link_errors, readout_errors),
+                lookahead);
+          }),
+          "NoiseAwareRoutingMethod constructor. If no error is given for a "
+          "node or pair of nodes, the fidelity is assumed to be 1."
+          "\n\n:param node_errors: a dictionary mapping nodes in the "
+          "architecture to average single-qubit gate errors"
+          "\n:param link_errors: a dictionary mapping pairs of nodes in the "
+          "architecture to average two-qubit gate errors"
+          "\n:param readout_errors: a dictionary mapping nodes in the "
+          "architecture to average measurement readout errors"
+          "\n:param lookahead: Maximum number of layers of two-qubit gates "
+          "considered when picking SWAP or BRIDGE gates.",
+          py::arg("node_errors") = py::dict(),
+          py::arg("link_errors") = py::dict(),
+          py::arg("readout_errors") = py::dict(), py::arg("lookahead") = 10);
+
+  py::class_<
+      ExactRoutingMethod, std::shared_ptr<ExactRoutingMethod>, RoutingMethod>(
+      m, "ExactRoutingMethod",
+      "Defines a RoutingMethod object for mapping circuits that finds the "
+      "fewest SWAP gates needed to route each subcircuit by an A* search, "
+      "falling back to LexiRouteRoutingMethod if the search takes too long. "
+      "Only routes placed qubits, so should be used after a labelling method.")
+      .def(
+          py::init<unsigned, unsigned, unsigned, unsigned>(),
+          "ExactRoutingMethod constructor.\n\n:param timeout: Time budget "
+          "for each search, in milliseconds."
+          "\n:param max_depth: Maximum depth of the subcircuit routed at once."
+          "\n:param max_size: Maximum number of gates in the subcircuit "
+          "routed at once."
+          "\n:param lookahead: Maximum depth used by LexiRouteRoutingMethod "
+          "when the time budget runs out.",
+          py::arg("timeout") = 1000, py::arg("max_depth") = 100,
+          py::arg("max_size") = 100, py::arg("lookahead") = 10);
+
+  py::class_<
+      SabreRoutingMethod, std::shared_ptr<SabreRoutingMethod>, RoutingMethod>(
+      m, "SabreRoutingMethod",
+      "Defines a RoutingMethod object for mapping circuits that places "
+      "unplaced qubits and chooses SWAP gates together, refining the layout "
+      "of the qubits by routing the circuit forwards and backwards in the "
+      "style of SABRE. Qubits which are already placed keep their nodes.")
+      .def(
+          py::init<unsigned, unsigned, unsigned, unsigned, unsigned>(),
+          "SabreRoutingMethod constructor.\n\n:param max_depth: Maximum "
+          "depth of the subcircuit routed at once."
+          "\n:param max_size: Maximum number of gates in the subcircuit "
+          "routed at once."
+          "\n:param iterations: Number of forward and backward passes "
+          "refining each starting layout."
+          "\n:param restarts: Number of random starting layouts tried in "
+          "addition to a greedy one."
+          "\n:param seed: Seed for the random starting layouts.",
+          py::arg("max_depth") = 100, py::arg("max_size") = 100,
+          py::arg("iterations") = 3, py::arg("restarts") = 4,
+          py::arg("seed") = 0);
+
+  py::class_<
+      ShuttlingRoutingMethod, std::shared_ptr<ShuttlingRoutingMethod>,
+      RoutingMethod>(
+      m, "ShuttlingRoutingMethod",
+      "Defines a RoutingMethod object for mapping circuits to a "
+      "ShuttlingArchitecture, inserting Transport operations which move "
+      "qubits into a shared interaction zone rather than SWAP gates. Qubits "
+      "are moved along the cheapest paths of free sites, costing the time "
+      "of each transport plus a multiple of its heating.")
+      .def(
+          py::init<double>(),
+          "ShuttlingRoutingMethod constructor.\n\n:param heating_weight: "
+          "Cost of each unit of heating relative to each unit of time.",
+          py::arg("heating_weight") = 1.);
+
   py::class_<
       AASRouteRoutingMethod, std::shared_ptr<AASRouteRoutingMethod>,
       RoutingMethod>(
@@ -151,8 +243,40 @@ PYBIND11_MODULE(mapping, m) {
           "subcircuits. In given order, each method is sequentially checked "
           "for viability, with the first viable method being used."
           "\n:param label_isolated_qubits: will not label qubits without gates "
+          "or only single qubit gates on them if this is set false"
+          "\n:param reuse_freed_qubits: if the circuit has more qubits than "
+          "the architecture, first reuse qubits freed by a measurement or "
+          "discard for qubits starting with a reset or creation",
+          py::arg("circuit"), py::arg("routing_methods"),
+          py::arg("label_isolated_qubits") = true,
+          py::arg("reuse_freed_qubits") = false)
+      .def(
+          "route_circuit_in_partitions",
+          [](const MappingManager &mm, Circuit &circuit,
+             const std::vector<RoutingMethodPtr> &routing_methods,
+             unsigned max_threads, bool label_isolated_qubits) {
+            py::gil_scoped_release release;
+            return mm.route_circuit_in_partitions(
+                circuit, routing_methods, std::make_shared<unit_bimaps_t>(),
+                max_threads, label_isolated_qubits);
+          },
+          "Maps from given logical circuit to physical circuit, routing parts "
+          "of the circuit with no gates between them concurrently. Each part "
+          "is placed on a disjoint connected part of the architecture and "
+          "routed separately, and the routed parts are combined in a fixed "
+          "order. The circuit is routed as a whole if it has only one part, "
+          "if it is already partially placed, or if the parts do not fit on "
+          "disjoint parts of the architecture."
+          "\n\n:param circuit: pytket circuit to be mapped"
+          "\n:param routing_methods: Ranked methods to use for routing "
+          "subcircuits. In given order, each method is sequentially checked "
+          "for viability, with the first viable method being used."
+          "\n:param max_threads: maximum number of threads to route with, or 0 "
+          "to use the number of concurrent threads supported by the hardware. "
+          "Unless SymEngine is built thread-safe, only one thread is used."
+          "\n:param label_isolated_qubits: will not label qubits without gates "
           "or only single qubit gates on them if this is set false",
           py::arg("circuit"), py::arg("routing_methods"),
-          py::arg("label_isolated_qubits") = true);
+          py::arg("max_threads") = 0, py::arg("label_isolated_qubits") = true);
 }
 }  // namespace tket
\ No newline at end of file
@@ -23,6 +23,7 @@
 #include "Predicates/PassLibrary.hpp"
 #include "Transformations/ContextualReduction.hpp"
 #include "Transformations/Decomposition.hpp"
+#include "Transformations/DynamicalDecoupling.hpp"
 #include "Transformations/PauliOptimisation.hpp"
 #include "Transformations/Transform.hpp"
 #include "Utils/Json.hpp"
@@ -454,6 +455,19 @@ PYBIND11_MODULE(passes, m) {
       "gates."
       "\n\n:param allow_swaps: whether to allow implicit wire swaps",
       py::arg("allow_swaps") = true);
+  m.def(
+      "ZXGraphlikeOptimisation", &ZXGraphlikeOptimisation,
+      "Resynthesises the circuit via ZX calculus. The circuit is converted to "
+      "a graph-like ZX diagram, Clifford spiders are removed, non-Clifford "
+      "phases are moved onto phase gadgets and gadgets acting on the same "
+      "spiders are merged, before a circuit is extracted from the diagram. "
+      "The circuit is only replaced if this reduces the number of "
+      "non-Clifford gates."
+      "\n\nThe circuit must have no classical bits and contain only H, X, Z, "
+      "Rx, Rz, CX, CZ and SWAP gates. A replacement contains H, Z, S, Sdg, "
+      "U1, CX and CZ gates, and implements the same unitary up to global "
+      "phase: the ZX diagram does not track the global phase, so the phase of "
+      "the result is arbitrary.");
   m.def("RebaseTket", &RebaseTket, "Converts all gates to CX and TK1.");
   m.def(
       "RemoveRedundancies", &RemoveRedundancies,
@@ -542,6 +556,27 @@ PYBIND11_MODULE(passes, m) {
       "\n:return: a pass that rebases to the given gate set",
       py::arg("gateset"), py::arg("cx_replacement"),
       py::arg("tk1_replacement"));
+  m.def(
+      "RebaseEdgesCustom", &gen_edge_rebase_pass,
+      "Construct a custom rebase pass for a device whose connections may "
+      "natively support different two-qubit gates. Two-qubit gates acting on "
+      "a connection of `architecture` with a gate set are rebased to a gate "
+      "of that set, choosing the first of CX, CZ, ECR, ISWAPMax, ZZMax, "
+      "XXPhase and TK2 which it contains. All other gates are rebased as by "
+      ":py:meth:`RebaseCustom`."
+      "\n\n:param architecture: Architecture providing the gate sets of its "
+      "connections."
+      "\n:param gateset: The allowed operations in the rebased circuit, other "
+      "than on connections with a gate set."
+      "\n:param cx_replacement: The equivalent circuit to replace a CX "
+      "gate not on a connection with a gate set, using two qubit gates from "
+      "`gateset` (can use any single qubit OpTypes)."
+      "\n:param tk1_replacement: A function which, given the parameters of "
+      "an Rz(a)Rx(b)Rz(c) triple, returns an equivalent circuit in the "
+      "desired basis."
+      "\n:return: a pass that rebases to the gate sets of the architecture",
+      py::arg("architecture"), py::arg("gateset"), py::arg("cx_replacement"),
+      py::arg("tk1_replacement"));
 
   m.def(
       "EulerAngleReduction", &gen_euler_pass,
@@ -566,6 +601,15 @@ PYBIND11_MODULE(passes, m) {
       "\n:return: a pass that routes to the given device architecture",
       py::arg("arc"), py::arg("config"));
 
+  m.def(
+      "ShuttlingRoutingPass", &gen_shuttling_routing_pass,
+      "Construct a pass to route to a :py:class:`ShuttlingArchitecture`, "
+      "e.g. with a :py:class:`ShuttlingRoutingMethod` moving qubits by "
+      "Transport operations. The routed circuit satisfies a "
+      ":py:class:`ZoneConstraintsPredicate`."
+      "\n:return: a pass that routes to the given shuttling architecture",
+      py::arg("arc"), py::arg("config"));
+
   m.def(
       "RoutingPass", &gen_default_routing_pass,
       "Construct a pass to route to the connectivity graph of an "
@@ -575,6 +619,79 @@ PYBIND11_MODULE(passes, m) {
       "\n:return: a pass that routes to the given device architecture",
       py::arg("arc"));
 
+  m.def(
+      "QubitReusePass", &gen_qubit_reuse_pass,
+      "Construct a pass to reduce the number of qubits in a circuit by "
+      "reusing qubits freed by a measurement or discard for qubits starting "
+      "with a reset or creation. A reset is inserted between the two where "
+      "needed, and classical wires are unchanged, so the circuit is "
+      "equivalent under classical control."
+      "\n:param max_qubits: stop once the circuit has at most this many "
+      "qubits, or 0 to reuse as many qubits as possible"
+      "\n:return: a pass to reuse qubits",
+      py::arg("max_qubits") = 0);
+
+  m.def(
+      "CrosstalkScheduling",
+      [](const crosstalk_errors_t &crosstalk_errors, gate_error_t threshold) {
+        DeviceCharacterisation characterisation(
+            avg_node_errors_t(), {}, {}, crosstalk_errors);
+        return gen_crosstalk_scheduling_pass(characterisation, threshold);
+      },
+      "Construct a pass to schedule two-qubit gates so that no pair of gates "
+      "with a crosstalk penalty above the threshold shares a slice of the "
+      "circuit. A Barrier is inserted after one gate of each such pair and "
+      "before the other, delaying the gate with fewer slices after it."
+      "\n\n:param crosstalk_errors: a dictionary mapping pairs of pairs of "
+      "nodes in the architecture to the penalty for running two-qubit gates "
+      "on both pairs at the same time"
+      "\n:param threshold: penalty above which a pair of gates must not "
+      "share a slice"
+      "\n:return: a pass to schedule gates avoiding crosstalk",
+      py::arg("crosstalk_errors"), py::arg("threshold") = 0.);
+
+  m.def(
+      "DynamicalDecoupling", &gen_dynamical_decoupling_pass,
+      "Construct a pass to fill the idle windows of a schedule of the "
+      "circuit with a dynamical decoupling sequence. The pulses are spread "
+      "evenly across each window long enough to hold them, padded with "
+      ":py:class:`DelayOp` operations so that the other operations keep "
+      "their start times, and the global phase is corrected so the unitary "
+      "is unchanged. Windows are bounded by every operation, including "
+      "conditional operations and barriers, and windows before the first "
+      "operation on a qubit or after a final measurement are left empty."
+      "\n\n:param durations: durations of the operations in the circuit and "
+      "sequence, as a :py:class:`GateDurations`"
+      "\n:param dd_sequence: single-qubit circuit of gates whose product is "
+      "the identity up to global phase"
+      "\n:param schedule_type: whether operations start as soon or as late "
+      "as possible"
+      "\n:param min_duration: shortest idle window to fill"
+      "\n:return: a pass to perform dynamical decoupling",
+      py::arg("durations"), py::arg("dd_sequence"),
+      py::arg("schedule_type") = ScheduleType::ASAP,
+      py::arg("min_duration") = 0.);
+  m.def(
+      "DynamicalDecoupling",
+      [](const GateDurations &durations, DDSequence dd_sequence,
+         ScheduleType schedule_type, double min_duration) {
+        return gen_dynamical_decoupling_pass(
+            durations, get_dd_sequence(dd_sequence), schedule_type,
+            min_duration);
+      },
+      "Construct a pass to fill the idle windows of a schedule of the "
+      "circuit with a standard dynamical decoupling sequence."
+      "\n\n:param durations: durations of the operations in the circuit and "
+      "sequence, as a :py:class:`GateDurations`"
+      "\n:param dd_sequence: a :py:class:`DDSequence`"
+      "\n:param schedule_type: whether operations start as soon or as late "
+      "as possible"
+      "\n:param min_duration: shortest idle window to fill"
+      "\n:return: a pass to perform dynamical decoupling",
+      py::arg("durations"), py::arg("dd_sequence") = DDSequence::XY4,
+      py::arg("schedule_type") = ScheduleType::ASAP,
+      py::arg("min_duration") = 0.);
+
   m.def(
       "PlacementPass", &gen_placement_pass,
       ":param placer: The Placement used for relabelling."
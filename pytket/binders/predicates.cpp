@@ -105,6 +105,23 @@ PYBIND11_MODULE(predicates, m) {
           py::init<const OpTypeSet &>(), "Construct from a set of gate types.",
           py::arg("allowed_types"))
       .def_property_readonly("gate_set", &GateSetPredicate::get_allowed_types);
+  py::class_<
+      EdgeGateSetPredicate, std::shared_ptr<EdgeGateSetPredicate>, Predicate>(
+      m, "EdgeGateSetPredicate",
+      "Predicate asserting that the circuit contains only gates native to "
+      "a device whose connections may support different two-qubit gates. "
+      "Two-qubit gates acting on a connection with a gate set must be in "
+      "that gate set; all other gates must be in a given set.")
+      .def(
+          py::init<const OpTypeSet &, const Architecture &>(),
+          "Construct from a set of gate types and an :py:class:`Architecture` "
+          "providing the gate sets of its connections."
+          "\n\n:param allowed_types: Gate types allowed other than on "
+          "connections with a gate set"
+          "\n:param architecture: Architecture with gate sets",
+          py::arg("allowed_types"), py::arg("architecture"))
+      .def_property_readonly(
+          "gate_set", &EdgeGateSetPredicate::get_allowed_types);
   py::class_<
       NoClassicalControlPredicate, std::shared_ptr<NoClassicalControlPredicate>,
       Predicate>(
@@ -144,6 +161,37 @@ PYBIND11_MODULE(predicates, m) {
           py::init<const Architecture &>(),
           "Construct from an :py:class:`Architecture`.",
           py::arg("architecture"));
+  py::class_<
+      ZoneConstraintsPredicate, std::shared_ptr<ZoneConstraintsPredicate>,
+      Predicate>(
+      m, "ZoneConstraintsPredicate",
+      "Predicate asserting that a circuit can run on a given "
+      ":py:class:`ShuttlingArchitecture`: every transport is along a "
+      "transport connection and every other multi-qubit gate acts on a "
+      "single interaction zone.")
+      .def(
+          py::init<const ShuttlingArchitecture &>(),
+          "Construct from a :py:class:`ShuttlingArchitecture`.",
+          py::arg("architecture"));
+  py::class_<
+      CrosstalkPredicate, std::shared_ptr<CrosstalkPredicate>, Predicate>(
+      m, "CrosstalkPredicate",
+      "Predicate asserting that no two two-qubit gates sharing a slice of "
+      "the circuit have a crosstalk penalty above a threshold.")
+      .def(
+          py::init([](const crosstalk_errors_t &crosstalk_errors,
+                      gate_error_t threshold) {
+            DeviceCharacterisation characterisation(
+                avg_node_errors_t(), {}, {}, crosstalk_errors);
+            return CrosstalkPredicate(characterisation, threshold);
+          }),
+          "Construct from crosstalk penalties of pairs of links."
+          "\n\n:param crosstalk_errors: a dictionary mapping pairs of pairs "
+          "of nodes in the architecture to the penalty for running two-qubit "
+          "gates on both pairs at the same time"
+          "\n:param threshold: penalty above which a pair of gates must not "
+          "share a slice",
+          py::arg("crosstalk_errors"), py::arg("threshold") = 0.);
   py::class_<
       DirectednessPredicate, std::shared_ptr<DirectednessPredicate>, Predicate>(
       m, "DirectednessPredicate",
@@ -25,10 +25,13 @@
 #include "Transformations/Combinator.hpp"
 #include "Transformations/ContextualReduction.hpp"
 #include "Transformations/ControlledGates.hpp"
+#include "Transformations/CrosstalkScheduling.hpp"
 #include "Transformations/Decomposition.hpp"
+#include "Transformations/DynamicalDecoupling.hpp"
 #include "Transformations/OptimisationPass.hpp"
 #include "Transformations/PauliOptimisation.hpp"
 #include "Transformations/Rebase.hpp"
+#include "Transformations/Schedule.hpp"
 #include "Transformations/ThreeQubitSquash.hpp"
 #include "typecast.hpp"
 
@@ -90,6 +93,64 @@ PYBIND11_MODULE(transform, m) {
           "Support for multi-qubit architectures, decomposing to 3-qubit "
           "XXPhase3 gates instead of CXs where possible.");
 
+  py::enum_<Transforms::CnXAncillaMode>(
+      m, "CnXAncillaMode",
+      "Enum for the qubits that may be used as ancillas when decomposing "
+      "multi-controlled X gates")
+      .value("NoAncillas", Transforms::CnXAncillaMode::None, "Use no ancillas")
+      .value(
+          "Clean", Transforms::CnXAncillaMode::Clean,
+          "Use qubits known to be in the zero state, i.e. qubits that have "
+          "been created or reset and not acted on since")
+      .value(
+          "Borrowed", Transforms::CnXAncillaMode::Borrowed,
+          "Use clean qubits where possible, otherwise borrow idle qubits in "
+          "an arbitrary state and return them to that state");
+
+  py::enum_<ScheduleType>(
+      m, "ScheduleType",
+      "Enum for when operations start in a schedule of a circuit")
+      .value(
+          "ASAP", ScheduleType::ASAP,
+          "Operations start as soon as their dependencies allow")
+      .value(
+          "ALAP", ScheduleType::ALAP,
+          "Operations start as late as their dependencies allow");
+
+  py::enum_<DDSequence>(
+      m, "DDSequence", "Enum for standard dynamical decoupling sequences")
+      .value("XY4", DDSequence::XY4, "X, Y, X, Y")
+      .value("CPMG", DDSequence::CPMG, "X, X");
+
+  py::class_<GateDurations>(
+      m, "GateDurations",
+      "Durations of operations, used to schedule a circuit in time.")
+      .def(
+          py::init<
+              const GateDurations::op_durations_t &,
+              const GateDurations::node_durations_t &>(),
+          "Construct gate durations."
+          "\n\n:param op_durations: a dictionary mapping each type of "
+          "operation to its duration"
+          "\n:param node_durations: a dictionary mapping pairs of a type of "
+          "operation and a tuple of nodes to the duration of the operation on "
+          "those nodes, taking precedence over `op_durations`",
+          py::arg("op_durations") = GateDurations::op_durations_t(),
+          py::arg("node_durations") = GateDurations::node_durations_t())
+      .def(
+          "get_duration",
+          [](const GateDurations &durations, const Op_ptr &op,
+             const qubit_vector_t &qubits) {
+            return durations.get_duration(op, qubits);
+          },
+          "Duration of an operation acting on some qubits. A "
+          ":py:class:`DelayOp` lasts for its own duration, and other meta "
+          "operations take no time."
+          "\n\n:param op: the operation"
+          "\n:param qubits: the qubits it acts on"
+          "\n:return: the duration of the operation",
+          py::arg("op"), py::arg("qubits"));
+
   py::class_<Transform>(
       m, "Transform", "An in-place transformation of a :py:class:`Circuit`.")
       .def(py::init<const Transform::SimpleTransformation &>())
@@ -175,6 +236,27 @@ PYBIND11_MODULE(transform, m) {
           "DecomposeControlledRys", &Transforms::decomp_controlled_Rys,
           "Decomposes all arbitrarily-quantum-controlled Rys into CX "
           "and Ry gates.")
+      .def_static(
+          "DecomposeArbitrarilyControlledGates",
+          [](Transforms::CnXAncillaMode mode, bool relative_phase) {
+            return Transforms::decomp_arbitrary_controlled_gates(
+                mode, relative_phase);
+          },
+          "Decomposes all CnX, CCX and CnRy gates into CX, H, T, Tdg, Ry "
+          "and Rz gates. A CnX with n >= 3 controls is decomposed with a "
+          "linear ladder of Toffolis if at least n-2 qubits allowed by "
+          "``mode`` are free at that point in the circuit."
+          "\n\n:param mode: qubits that may be used as ancillas"
+          "\n:param relative_phase: whether to implement compute-uncompute "
+          "pairs of identical CCX or CnX gates, between which their qubits "
+          "are only acted on as controls or diagonally, with relative-phase "
+          "Toffolis",
+          py::arg("mode"), py::arg("relative_phase") = false)
+      .def_static(
+          "DecomposeMultiplexors", &Transforms::decomp_multiplexors,
+          "Decomposes all MultiplexorBoxes and MultiplexedRotationBoxes. "
+          "Multiplexors of single-qubit operations are decomposed into CX, "
+          "Ry and Rz gates.")
       .def_static(
           "DecomposeSWAP", &Transforms::decompose_SWAP,
           "Decomposes all SWAP gates to provided replacement "
@@ -405,7 +487,34 @@ PYBIND11_MODULE(transform, m) {
           py::arg("cx_config") = CXConfigType::Snake)
       .def_static(
           "ZZPhaseToRz", &Transforms::ZZPhase_to_Rz,
-          "Fixes all ZZPhase gate angles to [-1, 1) half turns.");
+          "Fixes all ZZPhase gate angles to [-1, 1) half turns.")
+      .def_static(
+          "ScheduleCrosstalk",
+          [](const crosstalk_errors_t &crosstalk_errors,
+             gate_error_t threshold) {
+            DeviceCharacterisation characterisation(
+                avg_node_errors_t(), {}, {}, crosstalk_errors);
+            return Transforms::schedule_crosstalk(characterisation, threshold);
+          },
+          "Inserts Barriers so that no pair of two-qubit gates with a "
+          "crosstalk penalty above the threshold shares a slice of the "
+          "circuit."
+          "\n\n:param crosstalk_errors: a dictionary mapping pairs of pairs "
+          "of nodes to the penalty for running two-qubit gates on both pairs "
+          "at the same time"
+          "\n:param threshold: penalty above which a pair of gates must not "
+          "share a slice",
+          py::arg("crosstalk_errors"), py::arg("threshold") = 0.)
+      .def_static(
+          "InsertDelays", &Transforms::insert_delays,
+          "Fills the idle windows of a schedule of the circuit with "
+          ":py:class:`DelayOp` operations."
+          "\n\n:param durations: durations of the operations in the circuit"
+          "\n:param schedule_type: whether operations start as soon or as "
+          "late as possible"
+          "\n:param min_duration: shortest idle window to fill",
+          py::arg("durations"), py::arg("schedule_type") = ScheduleType::ASAP,
+          py::arg("min_duration") = 0.);
   m.def(
       "separate_classical", &Transforms::separate_classical,
       "Separate the input circuit into a 'main' circuit and a classical "
@@ -413,6 +522,87 @@ PYBIND11_MODULE(transform, m) {
       "when composed."
       "\n\n:param circ: circuit to be separated",
       py::arg("circ"));
+
+  py::class_<CrosstalkReport>(
+      m, "CrosstalkReport",
+      "Crosstalk between two-qubit gates sharing a slice of a circuit.")
+      .def_readonly(
+          "total_penalty", &CrosstalkReport::total_penalty,
+          "Sum of the penalties of all pairs of gates sharing a slice")
+      .def_readonly(
+          "max_penalty", &CrosstalkReport::max_penalty,
+          "Largest penalty of a pair of gates sharing a slice")
+      .def_readonly(
+          "n_conflicts", &CrosstalkReport::n_conflicts,
+          "Number of pairs of gates sharing a slice with penalty above the "
+          "threshold");
+  m.def(
+      "get_crosstalk_report",
+      [](const Circuit &circ, const crosstalk_errors_t &crosstalk_errors,
+         gate_error_t threshold) {
+        DeviceCharacterisation characterisation(
+            avg_node_errors_t(), {}, {}, crosstalk_errors);
+        return get_crosstalk_report(circ, characterisation, threshold);
+      },
+      "Report the crosstalk between two-qubit gates in each slice of a "
+      "circuit."
+      "\n\n:param circ: circuit whose qubits are nodes of the device"
+      "\n:param crosstalk_errors: a dictionary mapping pairs of pairs of "
+      "nodes to the penalty for running two-qubit gates on both pairs at the "
+      "same time"
+      "\n:param threshold: penalty above which a pair of gates is a conflict"
+      "\n:return: a :py:class:`CrosstalkReport`",
+      py::arg("circ"), py::arg("crosstalk_errors"), py::arg("threshold") = 0.);
+  m.def(
+      "get_dd_sequence", &get_dd_sequence,
+      "A standard dynamical decoupling sequence as a single-qubit circuit."
+      "\n\n:param dd_sequence: a :py:class:`DDSequence`"
+      "\n:return: a :py:class:`Circuit` with the pulses of the sequence",
+      py::arg("dd_sequence"));
+
+  py::class_<ScheduledCommand>(
+      m, "ScheduledCommand", "A command of a circuit with its start time.")
+      .def_readonly(
+          "command", &ScheduledCommand::command, "The scheduled command")
+      .def_readonly(
+          "start", &ScheduledCommand::start, "Time at which the command starts")
+      .def_readonly(
+          "duration", &ScheduledCommand::duration, "Duration of the command");
+  py::class_<IdleWindow>(
+      m, "IdleWindow", "A time during which a qubit is not acted on.")
+      .def_readonly("qubit", &IdleWindow::qubit, "The idle qubit")
+      .def_readonly("start", &IdleWindow::start, "Start of the window")
+      .def_readonly("end", &IdleWindow::end, "End of the window");
+  py::class_<Schedule>(
+      m, "Schedule",
+      "Start times of the operations of a circuit, given their durations. "
+      "An operation waits for all earlier operations on its qubits and bits, "
+      "and an operation writing to a bit also waits for earlier operations "
+      "conditioned on it.")
+      .def(
+          py::init<const Circuit &, const GateDurations &, ScheduleType>(),
+          "Schedule a circuit."
+          "\n\n:param circ: circuit to schedule"
+          "\n:param durations: durations of the operations in the circuit"
+          "\n:param schedule_type: whether operations start as soon or as "
+          "late as possible",
+          py::arg("circ"), py::arg("durations"),
+          py::arg("schedule_type") = ScheduleType::ASAP)
+      .def_property_readonly(
+          "total_duration", &Schedule::get_total_duration,
+          "Time at which the last operation ends")
+      .def(
+          "get_commands", &Schedule::get_commands,
+          ":return: the commands of the circuit in causal order, as "
+          ":py:class:`ScheduledCommand` objects")
+      .def(
+          "get_idle_windows", &Schedule::get_idle_windows,
+          "Times during which qubits are not acted on, between consecutive "
+          "operations or the start or end of the circuit."
+          "\n\n:param min_duration: shortest window to report"
+          "\n:return: a list of :py:class:`IdleWindow`, ordered by qubit and "
+          "then by time",
+          py::arg("min_duration") = 0.);
 }
 
 }  // namespace tket
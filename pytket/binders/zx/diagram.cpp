@@ -16,6 +16,7 @@
 #include "Converters/Converters.hpp"
 #include "Utils/GraphHeaders.hpp"
 #include "ZX/ZXDiagram.hpp"
+#include "binder_json.hpp"
 #include "typecast.hpp"
 
 namespace py = pybind11;
@@ -432,7 +433,10 @@ void ZXDiagramPybind::init_zxdiagram(py::module& m) {
           "to_circuit", &wrapped_zx_to_circuit,
           "Extracts a unitary diagram in MBQC form as a Circuit following the "
           "routine by Backens et al. (\"There and back again: A circuit "
-          "extraction tale\").\n\n"
+          "extraction tale\"). The diagram must have gflow, with vertices "
+          "measured in any of the XY, XZ and YZ planes (X and Y measurements "
+          "are treated as XY and Z measurements as YZ). If it has none, an "
+          "error lists the vertices that cannot be corrected.\n\n"
           ":return: A pair of the generated :py:class:`Circuit`, and a map "
           "from each boundary vertex in the :py:class:`ZXDiagram` to its "
           "corresponding :py:class:`UnitID` in the :py:class:`Circuit`.")
@@ -450,7 +454,39 @@ void ZXDiagramPybind::init_zxdiagram(py::module& m) {
       .def(
           "to_graphviz_str",
           [](ZXDiagram& diag) { return diag.to_graphviz_str(); },
-          "Returns a graphviz source string");
+          "Returns a graphviz source string")
+      .def(
+          "to_dict", [](const ZXDiagram& diag) { return nlohmann::json(diag); },
+          ":return: a JSON serializable dictionary representation of the "
+          ":py:class:`ZXDiagram`")
+      .def_static(
+          "from_dict",
+          [](const nlohmann::json& j) { return j.get<ZXDiagram>(); },
+          "Construct a :py:class:`ZXDiagram` from its JSON serializable "
+          "dictionary representation.")
+      .def(py::pickle(
+          [](py::object self) {  // __getstate__
+            return py::make_tuple(self.attr("to_dict")());
+          },
+          [](const py::tuple& t) {  // __setstate__
+            const nlohmann::json j = t[0].cast<nlohmann::json>();
+            return j.get<ZXDiagram>();
+          }))
+      .def(
+          "to_qgraph_str", &ZXDiagram::to_qgraph_str,
+          "Returns a string in the JSON-based `.qgraph` format used by PyZX. "
+          "Only supports diagrams of Quantum boundaries, ZSpiders, XSpiders, "
+          "and Hboxes (with unit-modulus parameters) connected by Basic and "
+          "Hadamard wires. Open boundaries are flagged as neither inputs nor "
+          "outputs and the global scalar is not included.")
+      .def_static(
+          "from_qgraph_str", &ZXDiagram::from_qgraph_str,
+          "Reads a :py:class:`ZXDiagram` from a string in the `.qgraph` "
+          "format used by PyZX. The boundary lists all inputs, then outputs, "
+          "then any other boundary vertices as Open. Hadamard nodes marked as "
+          "edges become Hadamard wires, and all other Hadamard nodes become "
+          "Hboxes.",
+          py::arg("qgraph"));
 }
 
 PYBIND11_MODULE(zx, m) {
@@ -109,7 +109,23 @@ void init_rewrite(py::module &m) {
           "Identifies adjacent Pauli spiders where one is adjacent to a "
           "boundary. This rule applies I/O extensions to push the match into "
           "the interior from which it can be handled by "
-          ":py:meth:`remove_interior_paulis`.");
+          ":py:meth:`remove_interior_paulis`.")
+      .def_static(
+          "from_rule", &Rewrite::from_rule,
+          "Builds a :py:class:`Rewrite` from a user-defined rule, replacing "
+          "every match of the interior of `lhs` by `rhs`. Both sides must "
+          "have the same number of boundaries, matched up by their order in "
+          "the boundary. Each wire at a matched vertex must either match a "
+          "wire of `lhs` exactly or be taken by a boundary of `lhs`. "
+          "Parameters on the left-hand side may be constants or single "
+          "variables, which bind to the parameters of each match and are "
+          "substituted into `rhs`. The global scalar is multiplied by the "
+          "ratio of the scalars of `rhs` and `lhs`.\n\n"
+          ":param lhs: The diagram to search for.\n"
+          ":param rhs: The diagram to replace each match with.\n"
+          ":return: A new :py:class:`Rewrite` applying the rule to all "
+          "non-overlapping matches.",
+          py::arg("lhs"), py::arg("rhs"));
 }
 
 }  // namespace zx
@@ -25,3 +25,12 @@ from .qasm import (
     circuit_to_qasm_io,
     circuit_from_qasm_wasm,
 )
+from .qasm3 import (
+    circuit_from_qasm3,
+    circuit_to_qasm3,
+    circuit_from_qasm3_str,
+    circuit_to_qasm3_str,
+    circuit_from_qasm3_io,
+    circuit_to_qasm3_io,
+    circuit_from_qasm3_wasm,
+)
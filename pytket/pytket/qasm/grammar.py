@@ -38,7 +38,11 @@ cop:   assign
 assign: margs "=" _exp ";"
 
 !cond: "(" (_arg|_iarg) ("<"|">"|"<="|">="|"!="|"==") pint ")"
+"""
 
+# classical and parameter expressions, arguments and terminals, shared between
+# OpenQASM 2 and OpenQASM 3
+_expressions = r"""
 _exp: b_or | _xor_exp
 b_or: _exp "|" _xor_exp
 
@@ -124,3 +128,60 @@ COMMENT: "//" /[^\n]/*
 %ignore WS
 %ignore COMMENT
 """
+
+grammar = grammar + _expressions
+
+grammar3 = (
+    r"""
+prog: oqasm? _stmt*
+
+_stmt: incl | qdecl | cdecl | qreg | creg | gdef | extern | sdef | reset | meas
+    | meas3 | barr | ifc | cop | mixedcall | modcall | ccall
+
+oqasm:  "OPENQASM" version ";"
+?version: DECIMAL | INT
+
+incl: "include" "\"" INAM "\"" ";"
+INAM: CNAME ("." CNAME)*
+
+qdecl: ("qubit" | QUBITS) ARG ";"
+cdecl: ("bit" | BITS) ARG ";"
+QUBITS.2: /qubit\s*\[\s*\d+\s*\]/
+BITS.2: /bit\s*\[\s*\d+\s*\]/
+qreg: "qreg" _iarg ";"
+creg: "creg" _iarg ";"
+
+mixedcall: _arg pars? margs ";"
+modcall: ctrlmod+ _arg pars? margs ";"
+ccall: _arg pars ";"
+ctrlmod: "ctrl" ("(" INT ")")? "@"
+gatecall: id pars? args? ";"
+gatemodcall: ctrlmod+ id pars? args ";"
+gdef:  "gate" id pars? args "{" (gatecall|gatemodcall)* "}"
+
+extern: "extern" ARG "(" (TYPE ("," TYPE)*)? ")" ("->" TYPE)? ";"
+sdef: "def" ARG "(" (sdefpar ("," sdefpar)*)? ")" ("->" TYPE)? DEFBODY
+sdefpar: TYPE ARG
+TYPE.2: /(bit|int|uint)(\s*\[\s*\d+\s*\])?/
+DEFBODY: /\{[^{}]*\}/
+
+reset: "reset" _marg ";"
+meas:  "measure" _marg "->" _marg ";"
+meas3: margs "=" "measure" _marg ";"
+barr:  "barrier" margs ";"
+ifc:   "if" "(" cond ")" block else_block?
+block: "{" _ifstmt* "}" | _ifstmt
+else_block: "else" block
+_ifstmt: mixedcall | modcall | ccall | meas | meas3 | reset | cop | barr
+cop:   assign
+assign: margs "=" _exp ";"
+
+!cond: (_arg|_iarg) ("<"|">"|"<="|">="|"!="|"==") pint
+    | _iarg
+    | "!" _iarg
+
+COMMENT_BLOCK: "/*" /(.|\n)*?/ "*/"
+%ignore COMMENT_BLOCK
+"""
+    + _expressions
+)
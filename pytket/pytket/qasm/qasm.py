@@ -237,6 +237,9 @@ class ParsMap:
 
 
 class CircuitTransformer(Transformer):
+    # names of the gates understood natively, mapped to OpType names
+    gate_names: Dict[str, str] = _all_string_maps
+
     def __init__(self, return_gate_dict: bool = False) -> None:
         super().__init__()
         self.q_registers: Dict[str, int] = {}
@@ -349,7 +352,7 @@ class CircuitTransformer(Transformer):
             params = []  # to stop duplication in to op
         else:
             try:
-                optype = _all_string_maps[opstr]
+                optype = self.gate_names[opstr]
             except KeyError as e:
                 raise QASMParseError(
                     "Cannot parse gate of type: {}".format(opstr), optoken.line
@@ -456,10 +459,13 @@ class CircuitTransformer(Transformer):
         return RegNeg(self._get_logic_args(tree)[0][0])
 
     def cond(self, tree: List[Token]) -> PredicateExp:
-        if tree[1].type == "IARG":
-            arg = Bit(*_extract_reg(tree[1]))
+        return self._predicate(tree[1], str(tree[2]), int(tree[3].value))
+
+    def _predicate(self, var: Token, comparator: str, value: int) -> PredicateExp:
+        if var.type == "IARG":
+            arg = Bit(*_extract_reg(var))
         else:
-            arg = BitRegister(tree[1].value, self.c_registers[tree[1].value])
+            arg = BitRegister(var.value, self.c_registers[var.value])
 
         op_enum = BitWiseOp if isinstance(arg, Bit) else RegWiseOp
         comp = cast(
@@ -467,11 +473,11 @@ class CircuitTransformer(Transformer):
             LogicExp.factory(
                 cast(
                     Union[BitWiseOp, RegWiseOp],
-                    op_enum(str(tree[2])),
+                    op_enum(comparator),
                 )
             ),
         )
-        return comp(arg, int(tree[3].value))
+        return comp(arg, value)
 
     def ifc(self, tree: Sequence) -> Iterable[CommandDict]:
         condition = cast(PredicateExp, tree[0])
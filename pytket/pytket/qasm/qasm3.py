@@ -0,0 +1,650 @@
+# Copyright 2019-2022 Cambridge Quantum Computing
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+"""OpenQASM 3 frontend and backend for tket Circuits"""
+
+import io
+import os
+import re
+from itertools import chain
+from typing import (
+    Any,
+    Dict,
+    Iterable,
+    Iterator,
+    List,
+    Sequence,
+    Set,
+    TextIO,
+    Tuple,
+    Type,
+    Union,
+    cast,
+)
+from sympy import pi  # type: ignore
+from lark import Discard, Lark, Token
+
+from pytket.circuit import (  # type: ignore
+    Bit,
+    BitRegister,
+    Circuit,
+    Command,
+    CustomGateDef,
+    Op,
+    OpType,
+    QubitRegister,
+    UnitID,
+)
+from pytket.circuit.logic_exp import (
+    BitWiseOp,
+    LogicExp,
+    PredicateExp,
+    RegWiseOp,
+)
+from pytket.qasm.grammar import grammar3
+from pytket.qasm.qasm import (
+    CircuitTransformer,
+    CommandDict,
+    ParsMap,
+    QASMParseError,
+    QASMUnsupportedError,
+    _classical_gatestr_map,
+    _get_optype_and_params,
+    _parse_range,
+    _retrieve_registers,
+)
+from pytket.wasm import WasmFileHandler
+
+
+NOPARAM_COMMANDS_3 = {
+    "CX": OpType.CX,  # built-in gate equivalent to "cx"
+    "cx": OpType.CX,
+    "x": OpType.X,
+    "y": OpType.Y,
+    "z": OpType.Z,
+    "h": OpType.H,
+    "s": OpType.S,
+    "sdg": OpType.Sdg,
+    "t": OpType.T,
+    "tdg": OpType.Tdg,
+    "sx": OpType.SX,
+    "sxdg": OpType.SXdg,
+    "cy": OpType.CY,
+    "cz": OpType.CZ,
+    "ch": OpType.CH,
+    "ccx": OpType.CCX,
+    "swap": OpType.SWAP,
+    "cswap": OpType.CSWAP,
+    "id": OpType.noop,
+}
+
+PARAM_COMMANDS_3 = {
+    "U": OpType.U3,  # built-in gate equivalent to "u3"
+    "u3": OpType.U3,
+    "u2": OpType.U2,
+    "u1": OpType.U1,
+    "p": OpType.U1,
+    "phase": OpType.U1,
+    "rx": OpType.Rx,
+    "ry": OpType.Ry,
+    "rz": OpType.Rz,
+    "cp": OpType.CU1,
+    "cphase": OpType.CU1,
+    "crx": OpType.CRx,
+    "cry": OpType.CRy,
+    "crz": OpType.CRz,
+    "cu": OpType.CU3,  # with an extra global phase parameter
+    "rxx": OpType.XXPhase,
+    "rzz": OpType.ZZPhase,
+}
+
+# Gates not in "stdgates.inc", written with these definitions when used. A
+# definition of one of these gates in an input file is ignored in favour of the
+# corresponding tket gate.
+_EXTRA_GATE_DEFS = {
+    "sxdg": "gate sxdg a { h a; sdg a; h a; }\n",
+    "rxx": "gate rxx(theta) a, b "
+    "{ h a; h b; cx a, b; rz(theta) b; cx a, b; h a; h b; }\n",
+    "rzz": "gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }\n",
+}
+
+# Gates that may be used with a single "ctrl @" modifier
+_CONTROLLED_COMMANDS = {
+    "x": OpType.CX,
+    "y": OpType.CY,
+    "z": OpType.CZ,
+    "h": OpType.CH,
+    "sx": OpType.CSX,
+    "rx": OpType.CRx,
+    "ry": OpType.CRy,
+    "rz": OpType.CRz,
+    "p": OpType.CU1,
+    "swap": OpType.CSWAP,
+}
+
+# Gates that may be used with any number of controls
+_MULTI_CONTROLLED_COMMANDS = {
+    "x": OpType.CnX,
+    "ry": OpType.CnRy,
+}
+
+_tk_to_qasm3_noparams = dict(
+    ((item[1], item[0]) for item in NOPARAM_COMMANDS_3.items())
+)
+_tk_to_qasm3_noparams[OpType.CX] = "cx"  # prefer "cx" to "CX"
+_tk_to_qasm3_params = dict(((item[1], item[0]) for item in PARAM_COMMANDS_3.items()))
+_tk_to_qasm3_params[OpType.U3] = "u3"  # prefer "u3" to "U"
+_tk_to_qasm3_params[OpType.U1] = "p"
+_tk_to_qasm3_params[OpType.CU1] = "cp"
+del _tk_to_qasm3_params[OpType.CU3]  # written as "cu" with an extra parameter
+
+_all_string_maps_3 = {
+    key: val.name
+    for key, val in chain(PARAM_COMMANDS_3.items(), NOPARAM_COMMANDS_3.items())
+}
+
+_size_regex = re.compile(r"\[\s*(\d+)\s*\]")
+
+_NEGATED_REGWISE = {
+    RegWiseOp.EQ: RegWiseOp.NEQ,
+    RegWiseOp.NEQ: RegWiseOp.EQ,
+    RegWiseOp.LT: RegWiseOp.GEQ,
+    RegWiseOp.GEQ: RegWiseOp.LT,
+    RegWiseOp.GT: RegWiseOp.LEQ,
+    RegWiseOp.LEQ: RegWiseOp.GT,
+}
+
+
+def _decl_size(tree: List[Token]) -> int:
+    if len(tree) == 1:
+        return 1
+    match = _size_regex.search(tree[0].value)
+    assert match is not None
+    return int(match.group(1))
+
+
+def _negate(condition: PredicateExp) -> PredicateExp:
+    var, val = condition.args
+    if isinstance(var, Bit):
+        return cast(PredicateExp, LogicExp.factory(BitWiseOp.EQ)(var, 1 - val))
+    return cast(
+        PredicateExp, LogicExp.factory(_NEGATED_REGWISE[condition.op])(var, val)
+    )
+
+
+def _writes_condition(condition: PredicateExp, block: List[CommandDict]) -> bool:
+    var = condition.args[0]
+    reg_name = var.reg_name if isinstance(var, Bit) else var.name
+    return any(
+        isinstance(arg, list) and arg[0] == reg_name
+        for com in block
+        for arg in com["args"]
+    )
+
+
+class Qasm3CircuitTransformer(CircuitTransformer):
+    gate_names = _all_string_maps_3
+
+    def __init__(self, return_gate_dict: bool = False) -> None:
+        super().__init__(return_gate_dict)
+        self.subroutines: Set[str] = set()
+
+    def _reset_context(self) -> None:
+        super()._reset_context()
+        self.subroutines = set()
+
+    def qdecl(self, tree: List[Token]) -> None:
+        self.q_registers[tree[-1].value] = _decl_size(tree)
+
+    def cdecl(self, tree: List[Token]) -> None:
+        self.c_registers[tree[-1].value] = _decl_size(tree)
+
+    def meas3(self, tree: List) -> Iterable[CommandDict]:
+        out_args = list(tree[0])
+        if len(out_args) != 1:
+            raise QASMParseError(
+                "Measurement results must be assigned to a single (qu)bit or "
+                "register.",
+                tree[1].line,
+            )
+        qargs = [self._get_arg(tree[1])]
+        for args in zip(*self.unroll_all_args(qargs + out_args)):
+            yield {"args": list(args), "op": {"type": "Measure"}}
+
+    def mixedcall(self, tree: List) -> Iterator[CommandDict]:
+        if tree[0].value != "cu":
+            return super().mixedcall(tree)
+        # cu(theta, phi, lambda, gamma) is CU3(theta, phi, lambda) with a phase
+        # gamma on the control
+        if len(tree) != 3:
+            raise QASMParseError("Gate cu requires 4 parameters.", tree[0].line)
+        pars = list(cast(ParsMap, tree[1]).pars)
+        if len(pars) != 4:
+            raise QASMParseError("Gate cu requires 4 parameters.", tree[0].line)
+        coms = list(super().mixedcall([tree[0], ParsMap(pars[:3]), tree[2]]))
+        gamma = f"({pars[3]})/pi"
+        phase_coms = [
+            {"args": [com["args"][0]], "op": {"type": "U1", "params": [gamma]}}
+            for com in coms
+        ]
+        return iter(coms + phase_coms)
+
+    def ctrlmod(self, tree: List[Token]) -> int:
+        return int(tree[0].value) if tree else 1
+
+    def modcall(self, tree: List) -> Iterator[CommandDict]:
+        n_controls = sum(child for child in tree if isinstance(child, int))
+        children = [child for child in tree if not isinstance(child, int)]
+        optoken = children[0]
+        opstr = optoken.value
+        if len(children) == 3:
+            pars = list(cast(ParsMap, children[1]).pars)
+            args = list(children[2])
+        else:
+            pars = []
+            args = list(children[1])
+        if n_controls == 1 and opstr in _CONTROLLED_COMMANDS:
+            optype = _CONTROLLED_COMMANDS[opstr].name
+        elif opstr in _MULTI_CONTROLLED_COMMANDS:
+            optype = _MULTI_CONTROLLED_COMMANDS[opstr].name
+        else:
+            raise QASMParseError(
+                f"Cannot parse gate of type {opstr} with {n_controls} controls",
+                optoken.line,
+            )
+        n_targets = 2 if opstr == "swap" else 1
+        if len(args) != n_controls + n_targets:
+            raise QASMParseError(
+                f"Gate {opstr} with {n_controls} controls requires "
+                f"{n_controls + n_targets} qubit arguments, but {len(args)} "
+                "were given",
+                optoken.line,
+            )
+        op: Dict[str, Any] = {"type": optype}
+        if pars:
+            op["params"] = [f"({par})/pi" for par in pars]
+        if optype.startswith("Cn"):
+            op["n_qb"] = len(args)
+        for arg in zip(*self.unroll_all_args(args)):
+            yield {"args": list(arg), "op": op}
+
+    gatemodcall = modcall
+
+    def gdef(self, tree: List) -> None:
+        if tree[0].value not in _EXTRA_GATE_DEFS:
+            super().gdef(tree)
+
+    def incl(self, tree: List[Token]) -> None:
+        if tree[0].value != "stdgates.inc":
+            raise QASMParseError(
+                f"Header {tree[0].value} is not known and cannot be loaded.",
+                tree[0].line,
+            )
+
+    def extern(self, tree: List[Token]) -> Type[Discard]:
+        self.subroutines.add(tree[0].value)
+        return Discard
+
+    def sdef(self, tree: List[Token]) -> Type[Discard]:
+        # the body of the subroutine is provided by the WASM module
+        self.subroutines.add(tree[0].value)
+        return Discard
+
+    def cce_call(self, tree: List) -> Iterable[CommandDict]:
+        if tree[0].value not in self.subroutines:
+            raise QASMParseError(
+                f"Call to undeclared subroutine {tree[0].value}.", tree[0].line
+            )
+        return super().cce_call(tree)
+
+    def cond(self, tree: List[Token]) -> PredicateExp:
+        if len(tree) == 1:
+            # a bit on its own is true if set
+            return self._predicate(tree[0], "==", 1)
+        if len(tree) == 2:
+            return self._predicate(tree[1], "==", 0)
+        comparator, value = str(tree[1]), int(tree[2].value)
+        if tree[0].type == "IARG" and comparator == "!=":
+            comparator, value = "==", 1 - value
+        return self._predicate(tree[0], comparator, value)
+
+    def block(self, tree: List) -> List[CommandDict]:
+        return list(
+            chain.from_iterable(
+                filter(lambda x: x is not None and x is not Discard, tree)
+            )
+        )
+
+    def else_block(self, tree: List) -> List[CommandDict]:
+        return cast(List[CommandDict], tree[0])
+
+    def _copy_condition(
+        self, condition: PredicateExp
+    ) -> Tuple[CommandDict, PredicateExp]:
+        # evaluate the condition into a scratch bit, so that it is not affected
+        # by writes to the condition bits within the block
+        var, val = condition.args
+        val = cast(int, val)
+        if isinstance(var, Bit):
+            cond_bits = [var.to_list()]
+        else:
+            cond_bits = next(self.unroll_all_args([cast(BitRegister, var).name]))
+        lower, upper = val, val
+        if condition.op == RegWiseOp.LT:
+            lower, upper = 0, val - 1
+        elif condition.op == RegWiseOp.LEQ:
+            lower = 0
+        elif condition.op == RegWiseOp.GT:
+            lower, upper = val + 1, (1 << 32) - 1
+        elif condition.op == RegWiseOp.GEQ:
+            upper = (1 << 32) - 1
+        scratch = self._fresh_temp_bit()
+        com = {
+            "args": cond_bits + [scratch],
+            "op": {
+                "classical": {"lower": lower, "n_i": len(cond_bits), "upper": upper},
+                "type": "RangePredicate",
+            },
+        }
+        value = int(condition.op not in (BitWiseOp.NEQ, RegWiseOp.NEQ))
+        copied = LogicExp.factory(BitWiseOp.EQ)(Bit(scratch[0], scratch[1]), value)
+        return com, cast(PredicateExp, copied)
+
+    def ifc(self, tree: Sequence) -> Iterable[CommandDict]:
+        condition = cast(PredicateExp, tree[0])
+        if len(tree) > 2 and _writes_condition(condition, tree[1]):
+            com, condition = self._copy_condition(condition)
+            yield com
+        for com in super().ifc([condition, tree[1]]):
+            yield com
+        if len(tree) > 2:
+            for com in super().ifc([_negate(condition), tree[2]]):
+                yield com
+
+
+parser3 = Lark(
+    grammar3,
+    start="prog",
+    debug=False,
+    parser="lalr",
+    cache=True,
+    transformer=Qasm3CircuitTransformer(),
+)
+
+
+def circuit_from_qasm3(
+    input_file: Union[str, "os.PathLike[Any]"], encoding: str = "utf-8"
+) -> Circuit:
+    """A method to generate a tket Circuit from an OpenQASM 3 file"""
+    ext = os.path.splitext(input_file)[-1]
+    if ext != ".qasm":
+        raise TypeError("Can only convert .qasm files")
+    with open(input_file, "r", encoding=encoding) as f:
+        try:
+            circ = circuit_from_qasm3_io(f)
+        except QASMParseError as e:
+            raise QASMParseError(e.msg, e.line, str(input_file))
+    return circ
+
+
+def circuit_from_qasm3_str(qasm_str: str) -> Circuit:
+    """A method to generate a tket Circuit from an OpenQASM 3 str"""
+    return Circuit.from_dict(parser3.parse(qasm_str))
+
+
+def circuit_from_qasm3_io(stream_in: TextIO) -> Circuit:
+    """A method to generate a tket Circuit from an OpenQASM 3 text stream"""
+    return circuit_from_qasm3_str(stream_in.read())
+
+
+def circuit_from_qasm3_wasm(
+    input_file: Union[str, "os.PathLike[Any]"],
+    wasm_file: Union[str, "os.PathLike[Any]"],
+    encoding: str = "utf-8",
+) -> Circuit:
+    """A method to generate a tket Circuit from an OpenQASM 3 file and external WASM
+    module. Subroutines declared with ``extern`` or ``def`` are called as functions
+    of the WASM module; the bodies of ``def`` declarations are ignored."""
+    wasm_module = WasmFileHandler(str(wasm_file))
+    cast(Qasm3CircuitTransformer, parser3.options.transformer).wasm = wasm_module
+    return circuit_from_qasm3(input_file, encoding=encoding)
+
+
+def circuit_to_qasm3(circ: Circuit, output_file: str) -> None:
+    """A method to generate an OpenQASM 3 file from a tket Circuit"""
+    with open(output_file, "w") as out:
+        circuit_to_qasm3_io(circ, out)
+
+
+def circuit_to_qasm3_str(circ: Circuit) -> str:
+    """A method to generate an OpenQASM 3 str from a tket Circuit"""
+    buffer = io.StringIO()
+    circuit_to_qasm3_io(circ, buffer)
+    return buffer.getvalue()
+
+
+def _params_str(params: Sequence[Any]) -> str:
+    strs = []
+    for param in params:
+        try:
+            strs.append("{}*pi".format(float(param)))
+        except TypeError:
+            strs.append("({})*pi".format(param))
+    return "({})".format(", ".join(strs))
+
+
+def _add_gate_def(gate: CustomGateDef, gate_defs: Dict[str, str]) -> None:
+    if gate.name in gate_defs:
+        return
+    def_circ = gate.definition.copy()
+    if def_circ.n_gates == 0:
+        raise QASMUnsupportedError(
+            f"CustomGate {gate.name} has empty definition."
+            " Empty CustomGates and opaque gates are not supported."
+        )
+    # parameters of the definition are in half-turns, those of the gate in radians
+    def_circ.symbol_substitution({sym: sym / pi for sym in gate.args})
+    names = {qb: f"q{i}" for i, qb in enumerate(def_circ.qubits)}
+    body = [
+        _gate_str(com.op, [names[qb] for qb in com.args], gate_defs)
+        for com in def_circ
+    ]
+    pars = "({})".format(", ".join(map(str, gate.args))) if gate.args else ""
+    gate_defs[gate.name] = "gate {}{} {} {{ {} }}\n".format(
+        gate.name, pars, ", ".join(names.values()), " ".join(body)
+    )
+
+
+def _gate_str(op: Op, args: List[str], gate_defs: Dict[str, str]) -> str:
+    optype, params = _get_optype_and_params(op)
+    if params is None and optype in _tk_to_qasm3_params:
+        params = op.params
+    if optype == OpType.CustomGate:
+        _add_gate_def(op.gate, gate_defs)
+        opstr = op.gate.name
+    elif optype == OpType.CnX:
+        opstr = f"ctrl({len(args) - 1}) @ x"
+    elif optype == OpType.CnRy:
+        opstr = f"ctrl({len(args) - 1}) @ ry"
+        params = op.params
+    elif optype == OpType.CSX:
+        opstr = "ctrl @ sx"
+    elif optype == OpType.CU3:
+        opstr = "cu"
+        params = op.params + [0]
+    elif optype == OpType.ZZMax:
+        opstr = "rzz"
+        params = [0.5]
+    elif optype in _tk_to_qasm3_noparams:
+        opstr = _tk_to_qasm3_noparams[optype]
+    elif optype in _tk_to_qasm3_params:
+        opstr = _tk_to_qasm3_params[optype]
+    else:
+        raise QASMUnsupportedError(
+            "Cannot print command of type: {}".format(op.get_name())
+        )
+    if opstr in _EXTRA_GATE_DEFS:
+        gate_defs[opstr] = _EXTRA_GATE_DEFS[opstr]
+    pars = _params_str(params) if params is not None else ""
+    return f"{opstr}{pars} {', '.join(args)};"
+
+
+def _whole_register(bits: List[UnitID], cregs: Dict[str, BitRegister]) -> bool:
+    return bits == list(cregs[bits[0].reg_name])
+
+
+def _condition_str(
+    op: Op,
+    args: List[UnitID],
+    range_preds: Dict[UnitID, Command],
+    cregs: Dict[str, BitRegister],
+) -> str:
+    bits = args[: op.width]
+    if bits[0] in range_preds:
+        range_com = range_preds[bits[0]]
+        comparator, value = _parse_range(range_com.op.lower, range_com.op.upper)
+        if op.value == 0:
+            if comparator != "==":
+                raise QASMUnsupportedError(
+                    "Range predicates can only be negated for equality."
+                )
+            comparator = "!="
+        bits = range_com.args[:-1]
+    else:
+        comparator, value = "==", op.value
+    if len(bits) == 1:
+        return f"{bits[0]} {comparator} {value}"
+    if not _whole_register(bits, cregs):
+        raise QASMUnsupportedError(
+            "OpenQASM conditions must be on a single bit or an entire classical "
+            "register"
+        )
+    return f"{bits[0].reg_name} {comparator} {value}"
+
+
+def _statements(
+    op: Op,
+    args: List[UnitID],
+    cregs: Dict[str, BitRegister],
+    gate_defs: Dict[str, str],
+    externs: Dict[str, str],
+) -> List[str]:
+    optype = op.type
+    if optype == OpType.Measure:
+        return [f"{args[1]} = measure {args[0]};"]
+    if optype == OpType.Reset:
+        return [f"reset {args[0]};"]
+    if optype == OpType.Barrier:
+        return [f"barrier {', '.join(map(str, args))};"]
+    if optype == OpType.SetBits:
+        creg_name = args[0].reg_name
+        bits, vals = zip(*sorted(zip(args, op.values)))
+        # check if whole register can be set at once
+        if list(bits) == list(cregs[creg_name]):
+            value = int("".join(map(str, map(int, vals[::-1]))), 2)
+            return [f"{creg_name} = {value};"]
+        return [f"{bit} = {int(value)};" for bit, value in zip(bits, vals)]
+    if optype == OpType.CopyBits:
+        l_args = args[op.n_inputs :]
+        r_args = args[: op.n_inputs]
+        if _whole_register(l_args, cregs) and _whole_register(r_args, cregs):
+            return [f"{l_args[0].reg_name} = {r_args[0].reg_name};"]
+        return [f"{bit_l} = {bit_r};" for bit_l, bit_r in zip(l_args, r_args)]
+    if optype == OpType.MultiBit:
+        basic_op = op.basic_op
+        if str(basic_op) not in _classical_gatestr_map:
+            raise QASMUnsupportedError(f"Classical gate {basic_op} not supported.")
+        registers = [arg.reg_name for arg in args[:2]]
+        if len(args) > 2 and args[2].reg_name not in registers:
+            # there is a distinct output register
+            registers.append(args[2].reg_name)
+        return [
+            f"{registers[-1]} = {registers[0]} "
+            f"{_classical_gatestr_map[str(basic_op)]} {registers[1]};"
+        ]
+    if optype in (OpType.ExplicitPredicate, OpType.ExplicitModifier):
+        # &, ^ and | gates
+        opstr = str(op)
+        if opstr not in _classical_gatestr_map:
+            raise QASMUnsupportedError(f"Classical gate {opstr} not supported.")
+        return [f"{args[-1]} = {args[0]} {_classical_gatestr_map[opstr]} {args[1]};"]
+    if optype == OpType.ClassicalExpBox:
+        out_args = args[op.get_n_i() :]
+        if out_args == list(cregs[out_args[0].reg_name])[: len(out_args)] and (
+            len(out_args) > 1 or len(cregs[out_args[0].reg_name]) == 1
+        ):
+            return [f"{out_args[0].reg_name} = {op.get_exp()};"]
+        if len(out_args) == 1:
+            return [f"{out_args[0]} = {op.get_exp()};"]
+        raise QASMUnsupportedError(
+            "ClassicalExpBox only supported for writing to a single bit or whole "
+            "registers."
+        )
+    if optype == OpType.WASM:
+        regs: List[List[str]] = [[], []]
+        for reglist, widths in zip(regs, [op.input_widths, op.output_widths]):
+            for width in widths:
+                bits, args = args[:width], args[width:]
+                if not _whole_register(bits, cregs):
+                    raise QASMUnsupportedError("WASM ops must act on entire registers.")
+                reglist.append(bits[0].reg_name)
+        inputs, outputs = regs
+        if len(outputs) > 1:
+            raise QASMUnsupportedError(
+                "OpenQASM 3 subroutines can only return a single register."
+            )
+        decl = "extern {}({})".format(
+            op.func_name, ", ".join(f"bit[{w}]" for w in op.input_widths)
+        )
+        if outputs:
+            decl += f" -> bit[{op.output_widths[0]}]"
+        externs[op.func_name] = decl + ";\n"
+        call = f"{op.func_name}({', '.join(inputs)});"
+        return [f"{outputs[0]} = {call}" if outputs else call]
+    return [_gate_str(op, list(map(str, args)), gate_defs)]
+
+
+def circuit_to_qasm3_io(circ: Circuit, stream_out: TextIO) -> None:
+    """A method to generate an OpenQASM 3 text stream from a tket Circuit"""
+    qregs = _retrieve_registers(circ.qubits, QubitRegister)
+    cregs = _retrieve_registers(circ.bits, BitRegister)
+    gate_defs: Dict[str, str] = {}
+    externs: Dict[str, str] = {}
+    range_preds: Dict[UnitID, Command] = {}
+    body: List[str] = []
+    for command in circ:
+        op = command.op
+        args = command.args
+        if op.type == OpType.RangePredicate:
+            # attach predicate to bit, subsequent conditional will handle it
+            range_preds[args[-1]] = command
+            continue
+        if op.type == OpType.Conditional:
+            condition = _condition_str(op, args, range_preds, cregs)
+            statements = _statements(
+                op.op, args[op.width :], cregs, gate_defs, externs
+            )
+            body.append(f"if ({condition}) {{ {' '.join(statements)} }}")
+        else:
+            body.extend(_statements(op, args, cregs, gate_defs, externs))
+
+    stream_out.write('OPENQASM 3.0;\ninclude "stdgates.inc";\n\n')
+    for decl in chain(externs.values(), gate_defs.values()):
+        stream_out.write(decl)
+    for qreg in qregs.values():
+        stream_out.write(f"qubit[{qreg.size}] {qreg.name};\n")
+    for creg in cregs.values():
+        stream_out.write(f"bit[{creg.size}] {creg.name};\n")
+    for line in body:
+        stream_out.write(line + "\n")
@@ -27,9 +27,16 @@ from pytket.circuit import (  # type: ignore
     Unitary1qBox,
     Unitary2qBox,
     Unitary3qBox,
+    UnitaryNqBox,
     ExpBox,
     PauliExpBox,
     QControlBox,
+    MultiplexorBox,
+    MultiplexedRotationBox,
+    StatePreparationBox,
+    DiagonalBox,
+    ToffoliBox,
+    ToffoliBoxSynthStrat,
     PhasePolyBox,
     CustomGateDef,
     CustomGate,
@@ -302,6 +309,108 @@ def test_8x8_matrix_to_circ() -> None:
     assert c.n_gates_of_type(OpType.CX) <= 10
 
 
+def test_16x16_matrix_to_circ() -> None:
+    rng = np.random.default_rng(7)
+    a = rng.normal(size=(16, 16)) + 1j * rng.normal(size=(16, 16))
+    u, _ = np.linalg.qr(a)
+    ubox = UnitaryNqBox(u)
+    assert ubox.type == OpType.UnitaryNqBox
+    assert np.allclose(ubox.get_matrix(), u)
+    c = Circuit(4)
+    c.add_unitarynqbox(ubox, [0, 1, 2, 3])
+    Transform.DecomposeBoxes().apply(c)
+    assert np.allclose(c.get_unitary(), u)
+    c1 = Circuit(4).add_unitarynqbox(ubox, [0, 1, 2, 3])
+    c2 = Circuit.from_dict(c1.to_dict())
+    assert np.allclose(c2.get_unitary(), u)
+
+
+def test_multiplexor_boxes() -> None:
+    op_map = {(False, True): Op.create(OpType.H), (True, True): Op.create(OpType.T)}
+    mbox = MultiplexorBox(op_map)
+    assert mbox.type == OpType.MultiplexorBox
+    assert mbox.get_n_controls() == 2
+    assert set(mbox.get_op_map().keys()) == set(op_map.keys())
+    c = Circuit(3).add_multiplexorbox(mbox, [0, 1, 2])
+    u = np.eye(8, dtype=complex)
+    u[2:4, 2:4] = Circuit(1).H(0).get_unitary()
+    u[6:8, 6:8] = Circuit(1).T(0).get_unitary()
+    assert np.allclose(c.get_unitary(), u)
+    rbox = MultiplexedRotationBox([0.2, 0.4], OpType.Ry)
+    assert rbox.get_axis() == OpType.Ry
+    assert np.allclose(rbox.get_angles(), [0.2, 0.4])
+    c.add_multiplexedrotationbox(rbox, [1, 0])
+    u1 = c.get_unitary()
+    Transform.DecomposeMultiplexors().apply(c)
+    assert c.n_gates_of_type(OpType.MultiplexorBox) == 0
+    assert c.n_gates_of_type(OpType.MultiplexedRotationBox) == 0
+    assert np.allclose(c.get_unitary(), u1)
+    c2 = Circuit.from_dict(Circuit(3).add_multiplexorbox(mbox, [0, 1, 2]).to_dict())
+    assert np.allclose(c2.get_unitary(), u)
+
+
+def test_state_preparation_box() -> None:
+    rng = np.random.default_rng(7)
+    state = rng.normal(size=8) + 1j * rng.normal(size=8)
+    state /= np.linalg.norm(state)
+    box = StatePreparationBox(state)
+    assert box.type == OpType.StatePreparationBox
+    assert np.allclose(box.get_statevector(), state)
+    assert not box.with_initial_reset()
+    c = Circuit(3).add_statepreparationbox(box, [0, 1, 2])
+    assert np.allclose(c.get_statevector(), state)
+    c2 = Circuit.from_dict(c.to_dict())
+    assert np.allclose(c2.get_statevector(), state)
+    rbox = StatePreparationBox(np.array([0, 1j]), with_initial_reset=True)
+    assert rbox.with_initial_reset()
+    assert rbox.get_circuit().n_gates_of_type(OpType.Reset) == 1
+    with pytest.raises(RuntimeError):
+        StatePreparationBox(np.array([1, 1]))
+
+
+def test_diagonal_box() -> None:
+    phases = [0.1, -0.3, 1.2, 0.0, 0.7, 0.25, -1.5, 0.9]
+    box = DiagonalBox(phases)
+    assert box.type == OpType.DiagonalBox
+    assert np.allclose([float(p) for p in box.get_phases()], phases)
+    c = Circuit(3).add_diagonalbox(box, [0, 1, 2])
+    u = np.diag(np.exp(1j * np.pi * np.array(phases)))
+    assert np.allclose(c.get_unitary(), u)
+    c1 = box.get_circuit()
+    assert all(cmd.op.type in [OpType.CX, OpType.Rz] for cmd in c1)
+    ppb = box.to_phase_poly_box()
+    c2 = Circuit(3).add_phasepolybox(ppb, [0, 1, 2])
+    c2.add_phase(box.get_global_phase())
+    assert np.allclose(c2.get_unitary(), u)
+    c3 = Circuit.from_dict(c.to_dict())
+    assert np.allclose(c3.get_unitary(), u)
+    a = Symbol("a")
+    sbox = DiagonalBox([0, a, 0, -a])
+    assert sbox.free_symbols() == {a}
+
+
+def test_toffoli_box() -> None:
+    # Cyclic increment on 3 qubits
+    states = [(bool(i & 4), bool(i & 2), bool(i & 1)) for i in range(8)]
+    perm = {states[i]: states[(i + 1) % 8] for i in range(8)}
+    for strat in [ToffoliBoxSynthStrat.Naive, ToffoliBoxSynthStrat.CostAware]:
+        box = ToffoliBox(perm, strat)
+        assert box.type == OpType.ToffoliBox
+        assert box.get_strat() == strat
+        assert box.get_permutation() == perm
+        c = Circuit(3).add_toffolibox(box, [0, 1, 2])
+        u = np.zeros((8, 8))
+        for i in range(8):
+            u[(i + 1) % 8, i] = 1
+        assert np.allclose(c.get_unitary(), u)
+        Transform.DecomposeBoxes().apply(c)
+        assert np.allclose(c.get_unitary(), u)
+    swap = ToffoliBox({(False, True): (True, False), (True, False): (False, True)})
+    c = Circuit(2).add_toffolibox(swap, [0, 1])
+    c2 = Circuit.from_dict(c.to_dict())
+    assert np.allclose(c2.get_unitary(), Circuit(2).SWAP(0, 1).get_unitary())
+
+
 def test_exp_to_circ() -> None:
     PI = float(pi.evalf())
     u = np.asarray([[1, 0, 0, 0], [0, -1, 0, 0], [0, 0, -1, 0], [0, 0, 0, 1]]) * -PI / 4
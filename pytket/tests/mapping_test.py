@@ -21,8 +21,18 @@ from pytket.mapping import (  # type: ignore
     AASLabellingMethod,
     MultiGateReorderRoutingMethod,
     BoxDecompositionRoutingMethod,
+    NoiseAwareRoutingMethod,
+    SabreRoutingMethod,
+    ExactRoutingMethod,
+    ShuttlingRoutingMethod,
 )
-from pytket.architecture import Architecture  # type: ignore
+from pytket.architecture import (  # type: ignore
+    Architecture,
+    ShuttlingArchitecture,
+    Zone,
+)
+from pytket.predicates import ZoneConstraintsPredicate  # type: ignore
+from pytket.passes import ShuttlingRoutingPass, RebaseTket  # type: ignore
 from pytket import Circuit, OpType
 from pytket.circuit import Node, PhasePolyBox, Qubit, CircBox  # type: ignore
 from pytket.placement import Placement  # type: ignore
@@ -425,6 +435,122 @@ def test_BoxDecompositionRoutingMethod() -> None:
     assert len(circ.get_commands()) == 4
 
 
+def test_NoiseAwareRoutingMethod() -> None:
+    # ring in which connections through nodes[1] are unreliable
+    nodes = [Node("test", i) for i in range(4)]
+    test_a = Architecture(
+        [
+            [nodes[0], nodes[1]],
+            [nodes[1], nodes[2]],
+            [nodes[2], nodes[3]],
+            [nodes[3], nodes[0]],
+        ]
+    )
+    link_errors = {
+        (nodes[0], nodes[1]): 0.1,
+        (nodes[1], nodes[2]): 0.1,
+        (nodes[2], nodes[3]): 0.01,
+        (nodes[3], nodes[0]): 0.01,
+    }
+    test_c = Circuit(4).CZ(0, 2).CZ(1, 3)
+    Placement(test_a).place_with_map(test_c, {Qubit(i): nodes[i] for i in range(4)})
+    MappingManager(test_a).route_circuit(
+        test_c, [NoiseAwareRoutingMethod(link_errors=link_errors)]
+    )
+    assert test_c.valid_connectivity(test_a, directed=False)
+    swaps = [cmd for cmd in test_c.get_commands() if cmd.op.type == OpType.SWAP]
+    assert len(swaps) == 1
+    assert nodes[3] in swaps[0].qubits
+    assert nodes[1] not in swaps[0].qubits
+
+
+def test_SabreRoutingMethod() -> None:
+    nodes = [Node("test", i) for i in range(5)]
+    test_a = Architecture([[nodes[i], nodes[i + 1]] for i in range(4)])
+    test_c = Circuit(5)
+    for i in range(4):
+        test_c.CX(i, i + 1)
+    greedy_c = test_c.copy()
+    MappingManager(test_a).route_circuit(
+        greedy_c, [SabreRoutingMethod(iterations=0, restarts=0)]
+    )
+    assert greedy_c.valid_connectivity(test_a, directed=False)
+    assert greedy_c.n_gates_of_type(OpType.SWAP) == 1
+    MappingManager(test_a).route_circuit(test_c, [SabreRoutingMethod(seed=3)])
+    assert test_c.valid_connectivity(test_a, directed=False)
+    assert test_c.n_gates_of_type(OpType.SWAP) == 0
+    assert all(qb in nodes for qb in test_c.qubits)
+
+
+def test_ExactRoutingMethod() -> None:
+    nodes = [Node("test", i) for i in range(4)]
+    test_a = Architecture([[nodes[i], nodes[i + 1]] for i in range(3)])
+    test_c = Circuit(4).CZ(0, 3).CZ(1, 2)
+    Placement(test_a).place_with_map(test_c, {Qubit(i): nodes[i] for i in range(4)})
+    MappingManager(test_a).route_circuit(test_c, [ExactRoutingMethod(timeout=5000)])
+    assert test_c.valid_connectivity(test_a, directed=False)
+    assert test_c.n_gates_of_type(OpType.SWAP) == 2
+
+
+def test_reuse_freed_qubits() -> None:
+    nodes = [Node("test", i) for i in range(2)]
+    test_a = Architecture([[nodes[0], nodes[1]]])
+    test_c = Circuit(3, 2).H(0).CX(0, 1).Measure(0, 0).CX(2, 1).Measure(2, 1)
+    test_c.qubit_create(Qubit(2))
+    test_mm = MappingManager(test_a)
+    test_mm.route_circuit(
+        test_c,
+        [LexiLabellingMethod(), LexiRouteRoutingMethod()],
+        reuse_freed_qubits=True,
+    )
+    assert test_c.n_qubits == 2
+    assert test_c.n_gates_of_type(OpType.Reset) == 1
+    assert test_c.valid_connectivity(test_a, directed=False)
+
+
+def test_ShuttlingRoutingMethod() -> None:
+    load = [Node("l", i) for i in range(2)]
+    gate = [Node("g", i) for i in range(2)]
+    shuttling_arc = ShuttlingArchitecture(
+        [Zone("load", load, False), Zone("gate", gate, True)],
+        [((load[i], gate[i]), 1.0, 0.5) for i in range(2)],
+    )
+    assert ShuttlingArchitecture.from_dict(shuttling_arc.to_dict()) == shuttling_arc
+    test_c = Circuit()
+    for node in load:
+        test_c.add_qubit(node)
+    test_c.CX(load[0], load[1])
+    test_mm = MappingManager(shuttling_arc)
+    test_mm.route_circuit(test_c, [ShuttlingRoutingMethod(heating_weight=2.0)])
+    assert test_c.n_gates_of_type(OpType.Transport) == 2
+    assert test_c.n_gates_of_type(OpType.SWAP) == 0
+    assert ZoneConstraintsPredicate(shuttling_arc).verify(test_c)
+
+    pass_c = Circuit()
+    for node in load:
+        pass_c.add_qubit(node)
+    pass_c.CX(load[0], load[1])
+    ShuttlingRoutingPass(shuttling_arc, [ShuttlingRoutingMethod()]).apply(pass_c)
+    assert pass_c.n_gates_of_type(OpType.Transport) == 2
+    assert ZoneConstraintsPredicate(shuttling_arc).verify(pass_c)
+    RebaseTket().apply(pass_c)
+    assert pass_c.n_gates_of_type(OpType.Transport) == 0
+
+
+def test_route_circuit_in_partitions() -> None:
+    nodes = [Node("test", i) for i in range(6)]
+    test_a = Architecture([[nodes[i], nodes[i + 1]] for i in range(5)])
+    test_c = Circuit(6)
+    for i in [0, 3]:
+        test_c.CX(i, i + 2).CX(i + 1, i + 2).CX(i, i + 1)
+    test_mm = MappingManager(test_a)
+    test_mm.route_circuit_in_partitions(
+        test_c, [LexiLabellingMethod(), LexiRouteRoutingMethod()], max_threads=2
+    )
+    assert test_c.valid_connectivity(test_a, directed=False)
+    assert all(qb in nodes for qb in test_c.qubits)
+
+
 if __name__ == "__main__":
     test_LexiRouteRoutingMethod()
     test_RoutingMethodCircuit_custom()
@@ -432,3 +558,9 @@ if __name__ == "__main__":
     test_basic_mapping()
     test_MultiGateReorderRoutingMethod()
     test_BoxDecompositionRoutingMethod()
+    test_NoiseAwareRoutingMethod()
+    test_SabreRoutingMethod()
+    test_ExactRoutingMethod()
+    test_reuse_freed_qubits()
+    test_ShuttlingRoutingMethod()
+    test_route_circuit_in_partitions()
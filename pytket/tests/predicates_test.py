@@ -28,12 +28,17 @@ from pytket.passes import (  # type: ignore
     SquashTK1,
     RepeatWithMetricPass,
     RebaseCustom,
+    RebaseEdgesCustom,
     EulerAngleReduction,
     RoutingPass,
     CXMappingPass,
     PlacementPass,
     NaivePlacementPass,
     RenameQubitsPass,
+    QubitReusePass,
+    CrosstalkScheduling,
+    DynamicalDecoupling,
+    ZXGraphlikeOptimisation,
     FullMappingPass,
     DefaultMappingPass,
     AASRouting,
@@ -64,6 +69,8 @@ from pytket.passes import (  # type: ignore
 )
 from pytket.predicates import (  # type: ignore
     GateSetPredicate,
+    EdgeGateSetPredicate,
+    CrosstalkPredicate,
     NoClassicalControlPredicate,
     DirectednessPredicate,
     NoFastFeedforwardPredicate,
@@ -90,6 +97,8 @@ from pytket.mapping import (  # type: ignore
 from pytket.architecture import Architecture  # type: ignore
 from pytket.placement import Placement, GraphPlacement  # type: ignore
 from pytket.transform import Transform, PauliSynthStrat, CXConfigType  # type: ignore
+from pytket.transform import get_crosstalk_report  # type: ignore
+from pytket.transform import DDSequence, GateDurations, Schedule  # type: ignore
 from pytket._tket.passes import SynthesiseOQC  # type: ignore
 import numpy as np
 
@@ -163,6 +172,37 @@ def test_rebase_pass_generation() -> None:
     assert str(coms) == "[TK1(0.5, 1, 0.5) q[0];, TK1(0.5, 1, 3.5) q[1];]"
 
 
+def test_edge_rebase_pass_generation() -> None:
+    n = [Node(i) for i in range(3)]
+    arc = Architecture(
+        [(n[0], n[1]), (n[1], n[2]), (n[0], n[2])],
+        {(n[0], n[1]): {OpType.CZ}, (n[1], n[2]): {OpType.ECR, OpType.ZZMax}},
+    )
+    assert arc.get_gate_set(n[1], n[0]) == {OpType.CZ}
+    assert arc.get_gate_set(n[0], n[2]) is None
+    assert Architecture.from_dict(arc.to_dict()).gate_sets == arc.gate_sets
+    cx = Circuit(2)
+    cx.CX(0, 1)
+    gateset = {OpType.CX, OpType.PhasedX, OpType.Rz}
+    rebase = RebaseEdgesCustom(arc, gateset, cx, tk1_to_phasedxrz)
+    circ = Circuit()
+    for node in n:
+        circ.add_qubit(node)
+    circ.H(n[0]).CX(n[0], n[1]).CX(n[2], n[1]).CX(n[0], n[2]).CZ(n[0], n[2])
+    u = circ.get_unitary()
+    pred = EdgeGateSetPredicate(gateset, arc)
+    assert not pred.verify(circ)
+    assert rebase.apply(circ)
+    assert pred.verify(circ)
+    assert circ.n_gates_of_type(OpType.CZ) == 1
+    assert circ.n_gates_of_type(OpType.ECR) == 1
+    assert circ.n_gates_of_type(OpType.ZZMax) == 0
+    assert circ.n_gates_of_type(OpType.CX) == 2
+    assert np.allclose(circ.get_unitary(), u)
+    assert rebase.to_dict()["StandardPass"]["name"] == "RebaseEdgesCustom"
+    assert EdgeGateSetPredicate.from_dict(pred.to_dict()).to_dict() == pred.to_dict()
+
+
 def test_custom_combinator_generation() -> None:
     def test_CX_size_threshold(circ: Circuit) -> bool:
         return bool(circ.n_gates_of_type(OpType.CX) == 0)
@@ -1056,6 +1096,95 @@ def test_three_qubit_squash() -> None:
     assert c.n_gates_of_type(OpType.CX) <= 18
 
 
+def test_qubit_reuse() -> None:
+    c = Circuit(3, 3).H(0).Measure(0, 0).X(1).CX(1, 2).Measure(1, 1)
+    c.H(2).Measure(2, 2)
+    c.qubit_create(Qubit(1))
+    c.qubit_create(Qubit(2))
+    assert QubitReusePass().apply(c)
+    assert c.n_qubits == 2
+    assert c.n_gates_of_type(OpType.Reset) == 1
+    p = QubitReusePass(max_qubits=2)
+    assert p.to_dict()["StandardPass"]["name"] == "QubitReusePass"
+    assert p.to_dict()["StandardPass"]["max_qubits"] == 2
+
+
+def test_crosstalk_scheduling() -> None:
+    n = [Node(i) for i in range(4)]
+    crosstalk = {((n[0], n[1]), (n[2], n[3])): 0.05}
+    c = Circuit()
+    for node in n:
+        c.add_qubit(node)
+    c.CX(n[0], n[1]).CX(n[2], n[3])
+    report = get_crosstalk_report(c, crosstalk, threshold=0.01)
+    assert report.max_penalty == 0.05
+    assert report.n_conflicts == 1
+    pred = CrosstalkPredicate(crosstalk, threshold=0.01)
+    assert not pred.verify(c)
+    p = CrosstalkScheduling(crosstalk, threshold=0.01)
+    assert p.apply(c)
+    assert pred.verify(c)
+    assert c.n_gates_of_type(OpType.Barrier) == 1
+    assert get_crosstalk_report(c, crosstalk).total_penalty == 0
+    assert p.to_dict()["StandardPass"]["name"] == "CrosstalkScheduling"
+    assert p.to_dict()["StandardPass"]["crosstalk_threshold"] == 0.01
+    assert CrosstalkPredicate.from_dict(pred.to_dict()).to_dict() == pred.to_dict()
+
+
+def test_dynamical_decoupling() -> None:
+    durations = GateDurations(
+        {OpType.H: 50, OpType.X: 20, OpType.Y: 20, OpType.CX: 300}
+    )
+    c = Circuit(3).H(0).H(1).CX(0, 1).H(2).CX(1, 2)
+    u = c.get_unitary()
+    p = DynamicalDecoupling(durations)
+    assert p.apply(c)
+    # qubit 2 idles between its H gate and the second CX, and qubit 0 after the
+    # first CX
+    assert c.n_gates_of_type(OpType.X) == 4
+    assert c.n_gates_of_type(OpType.Y) == 4
+    assert np.allclose(c.get_unitary(), u)
+    assert Schedule(c, durations).total_duration == 650
+    assert p.to_dict()["StandardPass"]["name"] == "DynamicalDecoupling"
+
+    c = Circuit(3).H(0).H(1).CX(0, 1).H(2).CX(1, 2)
+    assert not DynamicalDecoupling(
+        durations, DDSequence.CPMG, min_duration=310
+    ).apply(c)
+    assert DynamicalDecoupling(durations, Circuit(1).Y(0).Y(0)).apply(c)
+    assert c.n_gates_of_type(OpType.Y) == 4
+    assert np.allclose(c.get_unitary(), u)
+    with pytest.raises(ValueError):
+        DynamicalDecoupling(durations, Circuit(1).X(0))
+
+
+def test_zx_graphlike_optimisation() -> None:
+    c = Circuit(3).H(0).CX(0, 1).Rz(0.25, 1).CX(0, 1).H(2).CZ(1, 2)
+    c.CX(0, 1).Rz(0.25, 1).CX(0, 1).Rx(0.5, 2).SWAP(0, 2)
+    u = c.get_unitary()
+    p = ZXGraphlikeOptimisation()
+    assert p.apply(c)
+    n_non_clifford = 0
+    for cmd in c.get_commands():
+        if cmd.op.type == OpType.U1:
+            angle = 2 * float(cmd.op.params[0])
+            if not np.isclose(angle, round(angle)):
+                n_non_clifford += 1
+    assert n_non_clifford <= 2
+    assert c.n_qubits == 3
+    v = c.get_unitary()
+    phase = np.trace(v.conj().T @ u) / 8
+    assert np.isclose(abs(phase), 1)
+    assert np.allclose(v * phase, u)
+    assert p.to_dict()["StandardPass"]["name"] == "ZXGraphlikeOptimisation"
+    # the circuit is kept unless the number of non-Clifford gates is reduced
+    c1 = Circuit(2).H(0).Rz(0.25, 0).CX(0, 1)
+    assert not p.apply(c1)
+    assert c1 == Circuit(2).H(0).Rz(0.25, 0).CX(0, 1)
+    with pytest.raises(RuntimeError):
+        ZXGraphlikeOptimisation().apply(Circuit(2).CRz(0.3, 0, 1))
+
+
 def test_predicate_serialization() -> None:
     arc = Architecture([(0, 2), (1, 2)])
 
@@ -1134,6 +1263,7 @@ if __name__ == "__main__":
     test_compilation_unit_generation()
     test_compilerpass_seq()
     test_rebase_pass_generation()
+    test_edge_rebase_pass_generation()
     test_routing_and_placement_pass()
     test_default_mapping_pass()
     test_SynthesiseTket_creation()
@@ -1149,3 +1279,7 @@ if __name__ == "__main__":
     test_RebaseOQC_and_SynthesiseOQC()
     test_ZZPhaseToRz()
     test_predicate_serialization()
+    test_qubit_reuse()
+    test_crosstalk_scheduling()
+    test_dynamical_decoupling()
+    test_zx_graphlike_optimisation()
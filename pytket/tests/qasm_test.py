@@ -13,6 +13,7 @@
 # limitations under the License.
 
 from io import StringIO
+from math import pi
 import re
 from pathlib import Path
 
@@ -40,6 +41,12 @@ from pytket.qasm import (
     circuit_from_qasm_wasm,
 )
 from pytket.qasm.qasm import QASMParseError, QASMUnsupportedError
+from pytket.qasm.qasm3 import (
+    circuit_from_qasm3,
+    circuit_from_qasm3_str,
+    circuit_from_qasm3_wasm,
+    circuit_to_qasm3_str,
+)
 from pytket.qasm.includes.load_includes import (
     _get_declpath,
     _get_files,
@@ -465,6 +472,121 @@ def test_include_loading(fi: Path) -> None:
         assert bytes(defs_string, "utf-8") == bytes(fil_content, "utf-8")
 
 
+def test_qasm3_import() -> None:
+    fname = str(curr_file_path / "qasm_test_files/test19.qasm")
+    c = circuit_from_qasm3_wasm(fname, "testfile.wasm")
+    assert c.n_qubits == 4
+    assert c.n_bits == 8
+    for optype, count in [
+        (OpType.CnX, 1),
+        (OpType.CRz, 1),
+        (OpType.CU3, 1),
+        (OpType.U1, 1),
+        (OpType.CustomGate, 1),
+        (OpType.Reset, 1),
+        (OpType.Barrier, 1),
+        (OpType.Measure, 3),
+        (OpType.Conditional, 5),
+        (OpType.WASM, 1),
+    ]:
+        assert c.n_gates_of_type(optype) == count
+    cmds = c.get_commands()
+    cu3 = next(cmd for cmd in cmds if cmd.op.type == OpType.CU3)
+    assert cu3.op.params == pytest.approx([0.1 / pi, 0.2 / pi, 0.3 / pi])
+    u1 = next(cmd for cmd in cmds if cmd.op.type == OpType.U1)
+    assert u1.args == [Qubit("q", 1)]
+    assert u1.op.params == pytest.approx([0.4 / pi])
+    gate = next(cmd.op for cmd in cmds if cmd.op.type == OpType.CustomGate)
+    assert gate.gate.name == "mygate"
+    assert gate.params == [0.5]
+    conds = {
+        cmd.op.op.type: cmd.op.value
+        for cmd in cmds
+        if cmd.op.type == OpType.Conditional
+    }
+    # the else branch is conditioned on the negation of the if condition
+    assert conds[OpType.Y] == 1
+    assert conds[OpType.Z] == 0
+    with pytest.raises(QASMParseError):
+        circuit_from_qasm3(fname)
+
+
+def test_qasm3_if_else_overwriting_condition() -> None:
+    qasm = """OPENQASM 3.0;
+include "stdgates.inc";
+qubit[2] q;
+bit[2] c;
+if (c == 0) {
+    c = measure q;
+} else {
+    x q[0];
+}
+"""
+    c = circuit_from_qasm3_str(qasm)
+    cmds = c.get_commands()
+    assert cmds[0].op.type == OpType.RangePredicate
+    scratch = cmds[0].args[-1]
+    assert scratch.reg_name != "c"
+    conds = [cmd for cmd in cmds if cmd.op.type == OpType.Conditional]
+    assert len(conds) == 3
+    # both branches are conditioned on the condition as it was before the block
+    for cmd in conds:
+        assert cmd.op.width == 1
+        assert cmd.args[0] == scratch
+    assert {(cmd.op.op.type, cmd.op.value) for cmd in conds} == {
+        (OpType.Measure, 1),
+        (OpType.X, 0),
+    }
+
+
+def test_qasm3_roundtrip() -> None:
+    fname = str(curr_file_path / "qasm_test_files/test19.qasm")
+    c = circuit_from_qasm3_wasm(fname, "testfile.wasm")
+    qasm3 = circuit_to_qasm3_str(c)
+    assert qasm3.startswith('OPENQASM 3.0;\ninclude "stdgates.inc";\n')
+    assert "extern CCE2(bit[2], bit[3]) -> bit[2];" in qasm3
+    assert "gate mygate(theta) q0, q1 {" in qasm3
+    assert "ctrl(2) @ x q[0], q[1], q[2];" in qasm3
+    assert "if (c[0] == 0) { z q[1]; }" in qasm3
+    assert "a = CCE2(a, b);" in qasm3
+    with open(curr_file_path / "qasm_test_files/testout7.qasm", "w") as f:
+        f.write(qasm3)
+    c2 = circuit_from_qasm3_wasm(
+        curr_file_path / "qasm_test_files/testout7.qasm", "testfile.wasm"
+    )
+    assert circuit_to_qasm3_str(c2) == qasm3
+
+    circ = Circuit(3, 2)
+    circ.H(0).CX(0, 1).Rz(0.25, 2).CU1(0.5, 1, 2).SXdg(0).ZZPhase(0.3, 0, 2)
+    circ.CSX(0, 1).add_gate(OpType.CnRy, [0.2], [0, 1, 2])
+    circ.Measure(0, 0).Measure(1, 1).Reset(0)
+    circ.Z(2, condition_bits=[0, 1], condition_value=2)
+    circ.X(1, condition_bits=[1], condition_value=0)
+    qasm3 = circuit_to_qasm3_str(circ)
+    assert "gate sxdg a" in qasm3
+    assert "gate rzz(theta) a, b" in qasm3
+    assert "if (c == 2) { z q[2]; }" in qasm3
+    assert circuit_from_qasm3_str(qasm3) == circ
+
+
+def test_qasm3_errors() -> None:
+    header = 'OPENQASM 3.0;\ninclude "stdgates.inc";\nqubit[3] q;\n'
+    with pytest.raises(QASMParseError) as e:
+        circuit_from_qasm3_str(header + "ctrl(2) @ rz(0.1) q[0], q[1], q[2];")
+    assert "rz with 2 controls" in str(e.value)
+    with pytest.raises(QASMParseError) as e:
+        circuit_from_qasm3_str(header + "ctrl(3) @ x q[0], q[1];")
+    assert "requires 4 qubit arguments" in str(e.value)
+    with pytest.raises(QASMParseError) as e:
+        circuit_from_qasm3_str(header + "bit[2] c;\nc = f(c);")
+    assert "undeclared subroutine f" in str(e.value)
+    with pytest.raises(QASMParseError):
+        circuit_from_qasm3_str('OPENQASM 3.0;\ninclude "qelib1.inc";')
+    c = Circuit(2).add_gate(OpType.ISWAP, [0.5], [0, 1])
+    with pytest.raises(QASMUnsupportedError):
+        circuit_to_qasm3_str(c)
+
+
 if __name__ == "__main__":
     test_qasm_correct()
     test_qasm_qubit()
@@ -481,3 +603,6 @@ if __name__ == "__main__":
     test_builtin_gates()
     test_new_qelib1_aliases()
     test_h1_rzz()
+    test_qasm3_import()
+    test_qasm3_roundtrip()
+    test_qasm3_errors()
@@ -0,0 +1,36 @@
+OPENQASM 3.0;
+include "stdgates.inc";
+
+extern CCE2(bit[2], bit[3]) -> bit[2];
+
+/* a custom gate using a control modifier */
+gate mygate(theta) a, b {
+    cx a, b;
+    rz(theta) b;
+    ctrl @ x b, a;
+}
+
+qubit[3] q;
+qubit r;
+bit[3] c;
+bit[2] a;
+bit[3] b;
+
+h q[0];
+ctrl(2) @ x q[0], q[1], q[2];
+ctrl @ rz(pi/2) q[0], r;
+cu(0.1, 0.2, 0.3, 0.4) q[1], q[2];
+mygate(0.5*pi) q[0], q[1];
+reset r;
+barrier q, r;
+c = measure q;
+if (c[0]) {
+    x q[1];
+    y q[2];
+} else {
+    z q[1];
+}
+if (c == 3) h q[2];
+if (!c[1]) x r;
+c[2] = c[0] ^ c[1];
+a = CCE2(a, b);
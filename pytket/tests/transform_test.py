@@ -15,7 +15,7 @@
 import itertools
 from typing import List
 from pathlib import Path
-from pytket.circuit import Circuit, OpType, PauliExpBox, Node, Qubit  # type: ignore
+from pytket.circuit import Bit, Circuit, OpType, PauliExpBox, Node, Qubit  # type: ignore
 from pytket._tket.circuit import _library  # type: ignore
 from pytket.pauli import Pauli  # type: ignore
 from pytket.passes import (  # type: ignore
@@ -37,7 +37,8 @@ from pytket.passes import (  # type: ignore
 )
 from pytket.predicates import CompilationUnit, NoMidMeasurePredicate  # type: ignore
 from pytket.passes.auto_rebase import _CX_CIRCS, NoAutoRebase
-from pytket.transform import Transform, CXConfigType, PauliSynthStrat  # type: ignore
+from pytket.transform import Transform, CXConfigType, PauliSynthStrat, CnXAncillaMode  # type: ignore
+from pytket.transform import GateDurations, Schedule, ScheduleType  # type: ignore
 from pytket.qasm import circuit_from_qasm
 from pytket.architecture import Architecture  # type: ignore
 from pytket.mapping import MappingManager, LexiRouteRoutingMethod, LexiLabellingMethod  # type: ignore
@@ -1074,6 +1075,66 @@ def test_auto_squash() -> None:
     assert "TK1" in str(tk_err.value)
 
 
+def test_decompose_controlled_gates_with_ancillas() -> None:
+    c = Circuit(9)
+    c.H(5)
+    c.add_gate(OpType.CnX, [0, 1, 2, 3, 4, 8])
+    u = c.get_unitary()
+    c0 = c.copy()
+    assert Transform.DecomposeArbitrarilyControlledGates(
+        CnXAncillaMode.Borrowed
+    ).apply(c)
+    assert Transform.DecomposeArbitrarilyControlledGates(
+        CnXAncillaMode.NoAncillas
+    ).apply(c0)
+    assert c.n_gates_of_type(OpType.CX) < c0.n_gates_of_type(OpType.CX)
+    assert np.allclose(c.get_unitary(), u)
+    assert np.allclose(c0.get_unitary(), u)
+
+    c = Circuit(3).CCX(0, 1, 2).Rz(0.3, 2).CCX(0, 1, 2)
+    u = c.get_unitary()
+    assert Transform.DecomposeArbitrarilyControlledGates(
+        CnXAncillaMode.NoAncillas, relative_phase=True
+    ).apply(c)
+    assert c.n_gates_of_type(OpType.CX) == 6
+    assert np.allclose(c.get_unitary(), u)
+
+
+def test_schedule() -> None:
+    durations = GateDurations(
+        {OpType.H: 50, OpType.X: 20, OpType.CX: 300, OpType.Measure: 1000},
+        {(OpType.X, (Node(2),)): 40},
+    )
+    c = Circuit()
+    nodes = [Node(i) for i in range(3)]
+    for node in nodes:
+        c.add_qubit(node)
+    c.add_bit(Bit(0))
+    c.H(nodes[0]).CX(nodes[0], nodes[1]).X(nodes[2]).Measure(nodes[1], Bit(0))
+    c.X(nodes[2], condition_bits=[Bit(0)], condition_value=1)
+    schedule = Schedule(c, durations)
+    assert schedule.total_duration == 1390
+    starts = [com.start for com in schedule.get_commands()]
+    assert sorted(starts) == [0, 0, 50, 350, 1350]
+    windows = schedule.get_idle_windows()
+    assert [(w.qubit, w.start, w.end) for w in windows] == [
+        (Qubit("node", 0), 350, 1390),
+        (Qubit("node", 1), 0, 50),
+        (Qubit("node", 1), 1350, 1390),
+        (Qubit("node", 2), 40, 1350),
+    ]
+    assert len(schedule.get_idle_windows(min_duration=100)) == 2
+    late = Schedule(c, durations, ScheduleType.ALAP)
+    late_starts = [com.start for com in late.get_commands()]
+    assert sorted(late_starts) == [0, 50, 350, 1310, 1350]
+
+    assert Transform.InsertDelays(durations, min_duration=100).apply(c)
+    delays = [com.op for com in c.get_commands() if com.op.type == OpType.Delay]
+    assert sorted(d.duration for d in delays) == [1040, 1310]
+    assert Schedule(c, durations).total_duration == 1390
+    assert Circuit.from_dict(c.to_dict()) == c
+
+
 if __name__ == "__main__":
     test_remove_redundancies()
     test_reduce_singles()
@@ -1099,3 +1160,5 @@ if __name__ == "__main__":
     test_CXMappingPass_correctness()
     test_CXMappingPass_terminates()
     test_FullMappingPass()
+    test_decompose_controlled_gates_with_ancillas()
+    test_schedule()
@@ -12,9 +12,17 @@
 # See the License for the specific language governing permissions and
 # limitations under the License.
 
+from __future__ import annotations
+
+import json
+import pickle
 from math import pow, isclose
+from pathlib import Path
+from typing import Any
 import numpy as np
 import pytest  # type: ignore
+from jsonschema import validate  # type: ignore
+from sympy import Symbol  # type: ignore
 from pytket import Qubit, Circuit, OpType
 from pytket.pauli import Pauli, QubitPauliString  # type: ignore
 from pytket.utils.results import compare_unitaries
@@ -39,6 +47,11 @@ from zx_tensor import (  # type: ignore
     density_matrix_from_cptp_diagram,
 )
 
+curr_file_path = Path(__file__).resolve().parent
+
+with open(curr_file_path.parent.parent / "schemas/zx_diagram_v1.json", "r") as f:
+    schema = json.load(f)
+
 
 def test_generator_creation() -> None:
     diag = ZXDiagram(1, 0, 0, 0)
@@ -804,6 +817,153 @@ def test_XY_YZ_extraction() -> None:
     assert compare_unitaries(diag_u, circ_u)
 
 
+def test_XZ_PZ_extraction() -> None:
+    # Identical to the diagrams in test_ZXExtraction.cpp
+    diag = ZXDiagram(1, 1, 0, 0)
+    ins = diag.get_boundary(ZXType.Input)
+    outs = diag.get_boundary(ZXType.Output)
+    a = diag.add_vertex(ZXType.XY, 0.3)
+    c = diag.add_vertex(ZXType.XZ, 1.4)
+    d = diag.add_vertex(ZXType.YZ, 0.9)
+    o = diag.add_vertex(ZXType.PX)
+    diag.add_wire(ins[0], a)
+    diag.add_wire(a, c, ZXWireType.H)
+    diag.add_wire(a, d, ZXWireType.H)
+    diag.add_wire(a, o, ZXWireType.H)
+    diag.add_wire(c, o, ZXWireType.H)
+    diag.add_wire(o, outs[0])
+    circ, _ = diag.to_circuit()
+    assert circ.n_qubits == 1
+    Rewrite.rebase_to_zx().apply(diag)
+    diag.check_validity()
+    diag_u = unitary_from_quantum_diagram(diag)
+    circ_u = circ.get_unitary()
+    assert compare_unitaries(diag_u, circ_u)
+
+    diag = ZXDiagram(2, 2, 0, 0)
+    ins = diag.get_boundary(ZXType.Input)
+    outs = diag.get_boundary(ZXType.Output)
+    a0 = diag.add_vertex(ZXType.XY, 0.25)
+    a1 = diag.add_vertex(ZXType.XY)
+    z = diag.add_vertex(CliffordGen(ZXType.PZ, True, QuantumType.Quantum))
+    o0 = diag.add_vertex(ZXType.PX)
+    o1 = diag.add_vertex(ZXType.PX)
+    diag.add_wire(ins[0], a0)
+    diag.add_wire(ins[1], a1)
+    diag.add_wire(a0, o0, ZXWireType.H)
+    diag.add_wire(a1, o1, ZXWireType.H)
+    diag.add_wire(z, a0, ZXWireType.H)
+    diag.add_wire(z, a1, ZXWireType.H)
+    diag.add_wire(o0, outs[0])
+    diag.add_wire(o1, outs[1])
+    circ, _ = diag.to_circuit()
+    assert circ.n_qubits == 2
+    Rewrite.rebase_to_zx().apply(diag)
+    diag.check_validity()
+    diag_u = unitary_from_quantum_diagram(diag)
+    circ_u = circ.get_unitary()
+    assert compare_unitaries(diag_u, circ_u)
+
+    # A leaf attached to an input vertex cannot be corrected
+    diag = ZXDiagram(1, 1, 0, 0)
+    ins = diag.get_boundary(ZXType.Input)
+    outs = diag.get_boundary(ZXType.Output)
+    a = diag.add_vertex(ZXType.XY, 0.3)
+    b = diag.add_vertex(ZXType.XY, 0.7)
+    o = diag.add_vertex(ZXType.PX)
+    diag.add_wire(ins[0], a)
+    diag.add_wire(a, b, ZXWireType.H)
+    diag.add_wire(a, o, ZXWireType.H)
+    diag.add_wire(o, outs[0])
+    with pytest.raises(RuntimeError) as e:
+        diag.to_circuit()
+    assert "does not have gflow" in str(e.value)
+
+
+def test_serialisation() -> None:
+    c = Circuit(2)
+    c.H(0).CX(0, 1).Rz(0.3, 1).CX(0, 1).Rx(0.25, 0)
+    diag, _ = circuit_to_zx(c)
+    d = diag.to_dict()
+    validate(instance=d, schema=schema)
+    loaded = ZXDiagram.from_dict(d)
+    assert loaded.to_dict() == d
+    assert np.allclose(
+        unitary_from_quantum_diagram(loaded), unitary_from_quantum_diagram(diag)
+    )
+    copied = pickle.loads(pickle.dumps(diag))
+    assert copied.to_dict() == d
+
+    # Generators with parameters and boxes
+    box_diag = ZXDiagram(1, 1, 0, 0)
+    box_diag.add_wire(box_diag.get_boundary()[0], box_diag.get_boundary()[1])
+    mixed = ZXDiagram(1, 0, 0, 1)
+    ins = mixed.get_boundary()
+    box = mixed.add_zxbox(box_diag)
+    py = mixed.add_vertex(CliffordGen(ZXType.PY, True, QuantumType.Quantum))
+    m = mixed.add_vertex(ZXType.ZSpider, QuantumType.Classical)
+    mixed.add_wire(u=ins[0], v=box, v_port=0)
+    mixed.add_wire(u=box, v=py, u_port=1)
+    mixed.add_wire(py, m)
+    mixed.add_wire(m, ins[1], qtype=QuantumType.Classical)
+    d = mixed.to_dict()
+    validate(instance=d, schema=schema)
+    assert ZXDiagram.from_dict(d).to_dict() == d
+
+    # PyZX .qgraph only supports ZX spiders and Hboxes
+    Rewrite.rebase_to_zx().apply(diag)
+    qgraph = diag.to_qgraph_str()
+    assert len(json.loads(qgraph)["wire_vertices"]) == 4
+    from_qgraph = ZXDiagram.from_qgraph_str(qgraph)
+    assert compare_unitaries(
+        unitary_from_quantum_diagram(from_qgraph), unitary_from_quantum_diagram(diag)
+    )
+    with pytest.raises(RuntimeError) as e:
+        mixed.to_qgraph_str()
+    assert "Cannot represent" in str(e.value)
+
+
+def chain_diagram(
+    phases: list[tuple[ZXType, Any]], wire_type: ZXWireType = ZXWireType.Basic
+) -> ZXDiagram:
+    diag = ZXDiagram(1, 1, 0, 0)
+    prev = diag.get_boundary(ZXType.Input)[0]
+    for zxtype, param in phases:
+        v = diag.add_vertex(zxtype, param)
+        diag.add_wire(prev, v, wire_type)
+        prev = v
+    diag.add_wire(prev, diag.get_boundary(ZXType.Output)[0], wire_type)
+    return diag
+
+
+def test_rule_rewrites() -> None:
+    a = Symbol("a")
+    b = Symbol("b")
+    lhs = chain_diagram([(ZXType.ZSpider, a), (ZXType.ZSpider, b)])
+    rhs = chain_diagram([(ZXType.ZSpider, a + b)])
+    fusion = Rewrite.from_rule(lhs, rhs)
+    diag = chain_diagram(
+        [(ZXType.ZSpider, 0.1), (ZXType.ZSpider, 0.2), (ZXType.ZSpider, 0.3)]
+    )
+    original = unitary_from_quantum_diagram(diag)
+    assert Rewrite.repeat(fusion).apply(diag)
+    assert diag.n_vertices == 3
+    assert np.allclose(original, unitary_from_quantum_diagram(diag))
+
+    # Colour change, with Hadamard wires on the legs of the rule
+    lhs = chain_diagram([(ZXType.XSpider, a)])
+    rhs = chain_diagram([(ZXType.ZSpider, a)], ZXWireType.H)
+    diag = chain_diagram([(ZXType.XSpider, 0.3), (ZXType.ZSpider, 0.4)])
+    original = unitary_from_quantum_diagram(diag)
+    assert Rewrite.sequence([Rewrite.from_rule(lhs, rhs), fusion]).apply(diag)
+    assert diag.count_vertices(ZXType.XSpider) == 0
+    assert np.allclose(original, unitary_from_quantum_diagram(diag))
+
+    with pytest.raises(RuntimeError) as errorinfo:
+        Rewrite.from_rule(lhs, chain_diagram([(ZXType.ZSpider, b)]))
+    assert "not bound" in str(errorinfo.value)
+
+
 if __name__ == "__main__":
     test_generator_creation()
     test_diagram_creation()
@@ -816,3 +976,6 @@ if __name__ == "__main__":
     test_constructors()
     test_XY_extraction()
     test_XY_YZ_extraction()
+    test_XZ_PZ_extraction()
+    test_serialisation()
+    test_rule_rewrites()
@@ -59,9 +59,9 @@ class TketConan(ConanFile):
         "PauliGraph",
         "Circuit",
         "Architecture",
-        "Simulation",
         "Diagonalisation",
         "Characterisation",
+        "Simulation",
         "ZX",
         "Converters",
         "Placement",
@@ -24,6 +24,11 @@
 
 namespace tket {
 
+static Architecture::Connection ordered(const Node& n0, const Node& n1) {
+  return (n1 < n0) ? Architecture::Connection{n1, n0}
+                   : Architecture::Connection{n0, n1};
+}
+
 bool Architecture::valid_operation(const std::vector<Node>& uids) const {
   for (Node n : uids) {
     if (!this->node_exists(Node(n))) return false;
@@ -46,6 +51,11 @@ Architecture Architecture::create_subarch(
       subarc.add_connection(u1, u2);
     }
   }
+  for (const auto& [con, gate_set] : gate_sets_) {
+    if (subarc.node_exists(con.first) && subarc.node_exists(con.second)) {
+      subarc.gate_sets_.insert({con, gate_set});
+    }
+  }
   return subarc;
 }
 
@@ -191,6 +201,23 @@ MatrixXb Architecture::get_connectivity() const {
   return connectivity;
 }
 
+void Architecture::set_gate_set(
+    const Node& node_0, const Node& node_1, const OpTypeSet& gate_set) {
+  if (!edge_exists(node_0, node_1) && !edge_exists(node_1, node_0)) {
+    throw ArchitectureInvalidity(
+        "No connection between " + node_0.repr() + " and " + node_1.repr() +
+        ".");
+  }
+  gate_sets_[ordered(node_0, node_1)] = gate_set;
+}
+
+std::optional<OpTypeSet> Architecture::get_gate_set(
+    const Node& node_0, const Node& node_1) const {
+  auto it = gate_sets_.find(ordered(node_0, node_1));
+  if (it == gate_sets_.end()) return std::nullopt;
+  return it->second;
+}
+
 void to_json(nlohmann::json& j, const Architecture::Connection& link) {
   j.push_back(link.first);
   j.push_back(link.second);
@@ -215,6 +242,17 @@ void to_json(nlohmann::json& j, const Architecture& ar) {
     links.push_back(entry);
   }
   j["links"] = links;
+
+  if (!ar.get_gate_sets().empty()) {
+    nlohmann::json gate_sets = nlohmann::json::array();
+    for (const auto& [con, gate_set] : ar.get_gate_sets()) {
+      nlohmann::json entry;
+      entry["link"] = con;
+      entry["gate_set"] = gate_set;
+      gate_sets.push_back(entry);
+    }
+    j["gate_sets"] = gate_sets;
+  }
 }
 
 void from_json(const nlohmann::json& j, Architecture& ar) {
@@ -227,6 +265,14 @@ void from_json(const nlohmann::json& j, Architecture& ar) {
     unsigned w = j_entry.at("weight").get<unsigned>();
     ar.add_connection(l.first, l.second, w);
   }
+  if (j.contains("gate_sets")) {
+    for (const auto& j_entry : j.at("gate_sets")) {
+      Architecture::Connection l =
+          j_entry.at("link").get<Architecture::Connection>();
+      ar.set_gate_set(
+          l.first, l.second, j_entry.at("gate_set").get<OpTypeSet>());
+    }
+  }
 }
 
 void to_json(nlohmann::json& j, const FullyConnected& ar) {
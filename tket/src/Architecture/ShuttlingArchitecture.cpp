@@ -0,0 +1,143 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Architecture/ShuttlingArchitecture.hpp"
+
+#include "Utils/Json.hpp"
+
+namespace tket {
+
+static Architecture::Connection ordered(const Node& n0, const Node& n1) {
+  return (n1 < n0) ? Architecture::Connection{n1, n0}
+                   : Architecture::Connection{n0, n1};
+}
+
+ShuttlingArchitecture::ShuttlingArchitecture(
+    const std::vector<Zone>& zones, const std::vector<Transport>& transports)
+    : Architecture(), zones_(zones) {
+  for (unsigned i = 0; i < zones_.size(); i++) {
+    const Zone& zone = zones_[i];
+    for (const Node& site : zone.sites) {
+      if (!zone_index_.insert({site, i}).second) {
+        throw ArchitectureInvalidity(
+            "Site " + site.repr() + " is in more than one zone.");
+      }
+      add_node(site);
+    }
+    if (zone.interaction) {
+      for (unsigned j = 0; j < zone.sites.size(); j++) {
+        for (unsigned k = j + 1; k < zone.sites.size(); k++) {
+          add_connection(zone.sites[j], zone.sites[k]);
+        }
+      }
+    }
+  }
+  for (const Transport& transport : transports) {
+    const Node& n0 = transport.first.first;
+    const Node& n1 = transport.first.second;
+    if (!node_exists(n0) || !node_exists(n1)) {
+      throw ArchitectureInvalidity(
+          "Transport between " + n0.repr() + " and " + n1.repr() +
+          " is not between sites of any zone.");
+    }
+    if (transports_.insert({ordered(n0, n1), transport.second}).second) {
+      transport_neighbours_[n0].push_back(n1);
+      transport_neighbours_[n1].push_back(n0);
+    }
+  }
+}
+
+unsigned ShuttlingArchitecture::get_zone_index(const Node& site) const {
+  auto it = zone_index_.find(site);
+  if (it == zone_index_.end()) {
+    throw ArchitectureInvalidity(site.repr() + " is not in any zone.");
+  }
+  return it->second;
+}
+
+bool ShuttlingArchitecture::transport_exists(
+    const Node& site_0, const Node& site_1) const {
+  return transports_.find(ordered(site_0, site_1)) != transports_.end();
+}
+
+TransportCost ShuttlingArchitecture::get_transport_cost(
+    const Node& site_0, const Node& site_1) const {
+  auto it = transports_.find(ordered(site_0, site_1));
+  if (it == transports_.end()) {
+    throw ArchitectureInvalidity(
+        "No transport between " + site_0.repr() + " and " + site_1.repr() +
+        ".");
+  }
+  return it->second;
+}
+
+std::vector<Node> ShuttlingArchitecture::get_transport_neighbours(
+    const Node& site) const {
+  auto it = transport_neighbours_.find(site);
+  if (it == transport_neighbours_.end()) return {};
+  return it->second;
+}
+
+std::vector<ShuttlingArchitecture::Transport>
+ShuttlingArchitecture::get_transports() const {
+  return {transports_.begin(), transports_.end()};
+}
+
+bool ShuttlingArchitecture::operator==(
+    const ShuttlingArchitecture& other) const {
+  return zones_ == other.zones_ && transports_ == other.transports_;
+}
+
+void to_json(nlohmann::json& j, const ShuttlingArchitecture& ar) {
+  nlohmann::json zones = nlohmann::json::array();
+  for (const Zone& zone : ar.get_zones()) {
+    nlohmann::json entry;
+    entry["name"] = zone.name;
+    entry["sites"] = zone.sites;
+    entry["interaction"] = zone.interaction;
+    zones.push_back(entry);
+  }
+  j["zones"] = zones;
+
+  nlohmann::json transports = nlohmann::json::array();
+  for (const ShuttlingArchitecture::Transport& transport :
+       ar.get_transports()) {
+    nlohmann::json entry;
+    entry["link"] = transport.first;
+    entry["time"] = transport.second.time;
+    entry["heating"] = transport.second.heating;
+    transports.push_back(entry);
+  }
+  j["transports"] = transports;
+}
+
+void from_json(const nlohmann::json& j, ShuttlingArchitecture& ar) {
+  std::vector<Zone> zones;
+  for (const auto& j_entry : j.at("zones")) {
+    zones.push_back(
+        {j_entry.at("name").get<std::string>(),
+         j_entry.at("sites").get<node_vector_t>(),
+         j_entry.at("interaction").get<bool>()});
+  }
+  std::vector<ShuttlingArchitecture::Transport> transports;
+  for (const auto& j_entry : j.at("transports")) {
+    transports.push_back(
+        {j_entry.at("link").get<Architecture::Connection>(),
+         {j_entry.at("time").get<double>(),
+          j_entry.at("heating").get<double>()}});
+  }
+  ar = ShuttlingArchitecture(zones, transports);
+}
+
+}  // namespace tket
@@ -15,7 +15,9 @@
 #pragma once
 
 #include <iostream>
+#include <map>
 #include <numeric>
+#include <optional>
 #include <set>
 #include <string>
 #include <tklog/TketLog.hpp>
@@ -24,6 +26,7 @@
 
 #include "Graphs/CompleteGraph.hpp"
 #include "Graphs/DirectedGraph.hpp"
+#include "OpType/OpTypeFunctions.hpp"
 #include "Utils/BiMapHeaders.hpp"
 #include "Utils/EigenConfig.hpp"
 #include "Utils/Json.hpp"
@@ -129,9 +132,39 @@ class Architecture : public ArchitectureBase<graphs::DirectedGraph<Node>> {
    */
   MatrixXb get_connectivity() const;
 
+  /**
+   * Set the two-qubit gates natively supported by the coupler between two
+   * nodes, in either direction.
+   *
+   * @param node_0 First node of the connection
+   * @param node_1 Second node of the connection
+   * @param gate_set Native two-qubit gate types of the connection
+   * @throws ArchitectureInvalidity if the nodes are not connected
+   */
+  void set_gate_set(
+      const Node &node_0, const Node &node_1, const OpTypeSet &gate_set);
+
+  /**
+   * @return Native two-qubit gate types of the connection between two nodes,
+   * if any have been set
+   */
+  std::optional<OpTypeSet> get_gate_set(
+      const Node &node_0, const Node &node_1) const;
+
+  /**
+   * @return Native two-qubit gate types of all connections which have them,
+   * keyed by connections with the lesser node first
+   */
+  const std::map<Connection, OpTypeSet> &get_gate_sets() const {
+    return gate_sets_;
+  }
+
  protected:
   // Returns node with least connectivity given some distance matrix.
   std::optional<Node> find_worst_node(const Architecture &orig_g);
+
+ private:
+  std::map<Connection, OpTypeSet> gate_sets_;
 };
 
 JSON_DECL(Architecture::Connection)
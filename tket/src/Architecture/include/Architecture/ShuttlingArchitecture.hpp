@@ -0,0 +1,129 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <map>
+#include <string>
+#include <vector>
+
+#include "Architecture/Architecture.hpp"
+
+namespace tket {
+
+/**
+ * A group of sites which each hold at most one qubit, such as a trap of an
+ * ion-trap device or a region of an atom array.
+ */
+struct Zone {
+  std::string name;
+  std::vector<Node> sites;
+  /** Whether multi-qubit gates can act on qubits held in this zone */
+  bool interaction;
+
+  bool operator==(const Zone &other) const {
+    return name == other.name && sites == other.sites &&
+           interaction == other.interaction;
+  }
+};
+
+/** Cost of moving a qubit between two sites */
+struct TransportCost {
+  /** Duration of the move */
+  double time;
+  /** Motional heating caused by the move */
+  double heating;
+
+  bool operator==(const TransportCost &other) const {
+    return time == other.time && heating == other.heating;
+  }
+};
+
+/**
+ * Architecture of a device whose qubits are moved between sites, such as a
+ * QCCD ion-trap device or a neutral-atom device with atom shuttling.
+ *
+ * The nodes of the Architecture are the sites. Multi-qubit gates can act on
+ * any qubits held in the same interaction zone, so the connectivity graph
+ * joins every pair of sites in each interaction zone. Qubits are moved
+ * between sites by \ref OpType::Transport operations, along the given
+ * transport connections, each of which can be used in either direction.
+ */
+class ShuttlingArchitecture : public Architecture {
+ public:
+  typedef std::pair<Connection, TransportCost> Transport;
+
+  /** Construct an empty ShuttlingArchitecture. */
+  ShuttlingArchitecture() : Architecture() {}
+
+  /**
+   * Construct a ShuttlingArchitecture from zones and transport connections.
+   *
+   * @param zones Zones of the device, with disjoint sets of sites
+   * @param transports Pairs of sites between which qubits can be moved, with
+   * the cost of moving a qubit between them
+   * @throws ArchitectureInvalidity if a site is in more than one zone, or a
+   * transport connection is between sites which are not in any zone
+   */
+  ShuttlingArchitecture(
+      const std::vector<Zone> &zones, const std::vector<Transport> &transports);
+
+  /**
+   * @return Zones of the device
+   */
+  const std::vector<Zone> &get_zones() const { return zones_; }
+
+  /**
+   * @param site Site of the device
+   * @return Index of the zone holding the site
+   * @throws ArchitectureInvalidity if the site is not in any zone
+   */
+  unsigned get_zone_index(const Node &site) const;
+
+  /**
+   * @return Whether a qubit can be moved between two sites
+   */
+  bool transport_exists(const Node &site_0, const Node &site_1) const;
+
+  /**
+   * @return Cost of moving a qubit between two sites
+   * @throws ArchitectureInvalidity if there is no transport connection between
+   * the sites
+   */
+  TransportCost get_transport_cost(
+      const Node &site_0, const Node &site_1) const;
+
+  /**
+   * @return Sites a qubit can be moved to from the given site
+   */
+  std::vector<Node> get_transport_neighbours(const Node &site) const;
+
+  /**
+   * @return All transport connections, with their costs
+   */
+  std::vector<Transport> get_transports() const;
+
+  bool operator==(const ShuttlingArchitecture &other) const;
+
+ private:
+  std::vector<Zone> zones_;
+  std::map<Node, unsigned> zone_index_;
+  // costs, keyed by connections with the lesser site first
+  std::map<Connection, TransportCost> transports_;
+  std::map<Node, std::vector<Node>> transport_neighbours_;
+};
+
+JSON_DECL(ShuttlingArchitecture)
+
+}  // namespace tket
@@ -67,13 +67,33 @@ readout_error_t DeviceCharacterisation::get_readout_error(const Node& n) const {
   return maybe_err ? *maybe_err : 0.;
 }
 
+gate_error_t DeviceCharacterisation::get_crosstalk_error(
+    const Architecture::Connection& link_0,
+    const Architecture::Connection& link_1) const {
+  Architecture::Connection rev_0 = {link_0.second, link_0.first};
+  Architecture::Connection rev_1 = {link_1.second, link_1.first};
+  for (const Architecture::Connection& l0 : {link_0, rev_0}) {
+    for (const Architecture::Connection& l1 : {link_1, rev_1}) {
+      for (const auto& key : {std::make_pair(l0, l1), std::make_pair(l1, l0)}) {
+        std::optional<gate_error_t> maybe_err =
+            maybe_get(crosstalk_errors_, key);
+        if (maybe_err) {
+          return *maybe_err;
+        }
+      }
+    }
+  }
+  return 0.;
+}
+
 bool DeviceCharacterisation::operator==(
     const DeviceCharacterisation& other) const {
   return (this->default_node_errors_ == other.default_node_errors_) &&
          (this->default_link_errors_ == other.default_link_errors_) &&
          (this->default_readout_errors_ == other.default_readout_errors_) &&
          (this->op_node_errors_ == other.op_node_errors_) &&
-         (this->op_link_errors_ == other.op_link_errors_);
+         (this->op_link_errors_ == other.op_link_errors_) &&
+         (this->crosstalk_errors_ == other.crosstalk_errors_);
 }
 
 void to_json(nlohmann::json& j, const DeviceCharacterisation& dc) {
@@ -82,6 +102,9 @@ void to_json(nlohmann::json& j, const DeviceCharacterisation& dc) {
   j["readouts"] = dc.default_readout_errors_;
   j["op_node_errors"] = dc.op_node_errors_;
   j["op_link_errors"] = dc.op_link_errors_;
+  if (!dc.crosstalk_errors_.empty()) {
+    j["crosstalk_errors"] = dc.crosstalk_errors_;
+  }
 }
 
 void from_json(const nlohmann::json& j, DeviceCharacterisation& dc) {
@@ -90,6 +113,11 @@ void from_json(const nlohmann::json& j, DeviceCharacterisation& dc) {
   dc.default_readout_errors_ = j.at("readouts").get<avg_readout_errors_t>();
   dc.op_node_errors_ = j.at("op_node_errors").get<op_node_errors_t>();
   dc.op_link_errors_ = j.at("op_link_errors").get<op_link_errors_t>();
+  if (j.contains("crosstalk_errors")) {
+    dc.crosstalk_errors_ = j.at("crosstalk_errors").get<crosstalk_errors_t>();
+  } else {
+    dc.crosstalk_errors_.clear();
+  }
 }
 
 }  // namespace tket
@@ -27,7 +27,9 @@
  * commute_SQ_gates_through_SWAPS as a simple device noise model.
  * This is just a container of errors.
  *
- * This supports single-qubit errors, two-qubit errors and readout errors.
+ * This supports single-qubit errors, two-qubit errors and readout errors, as
+ * well as crosstalk penalties for pairs of two-qubit gates running at the same
+ * time.
  * Errors can either be OpType-specific, or a default value (average over all
  * possible OpTypes) If an OpType-specific value is provided, this will be used.
  * If not it will fallback to the default value for the given Node or Node pair,
@@ -40,20 +42,24 @@ class DeviceCharacterisation {
  public:
   DeviceCharacterisation(
       avg_node_errors_t _node_errors = {}, avg_link_errors_t _link_errors = {},
-      avg_readout_errors_t _readout_errors = {})
+      avg_readout_errors_t _readout_errors = {},
+      crosstalk_errors_t _crosstalk_errors = {})
       : default_node_errors_(_node_errors),
         default_link_errors_(_link_errors),
         default_readout_errors_(_readout_errors),
         op_node_errors_(),
-        op_link_errors_() {}
+        op_link_errors_(),
+        crosstalk_errors_(_crosstalk_errors) {}
   explicit DeviceCharacterisation(
       op_node_errors_t _node_errors, op_link_errors_t _link_errors = {},
-      avg_readout_errors_t _readout_errors = {})
+      avg_readout_errors_t _readout_errors = {},
+      crosstalk_errors_t _crosstalk_errors = {})
       : default_node_errors_(),
         default_link_errors_(),
         default_readout_errors_(_readout_errors),
         op_node_errors_(_node_errors),
-        op_link_errors_(_link_errors) {}
+        op_link_errors_(_link_errors),
+        crosstalk_errors_(_crosstalk_errors) {}
 
   // get device gate errors, preferring OpType-specific over default values over
   // 0. error
@@ -66,6 +72,14 @@ class DeviceCharacterisation {
       const Architecture::Connection& link, const OpType& op) const;
   // readout errors
   readout_error_t get_readout_error(const Node& n) const;
+  // crosstalk penalty for two-qubit gates on two links running at the same
+  // time, in either order and either direction of each link, falling back to 0.
+  gate_error_t get_crosstalk_error(
+      const Architecture::Connection& link_0,
+      const Architecture::Connection& link_1) const;
+  const crosstalk_errors_t& get_crosstalk_errors() const {
+    return crosstalk_errors_;
+  }
 
   bool operator==(const DeviceCharacterisation& other) const;
 
@@ -81,6 +95,9 @@ class DeviceCharacterisation {
   // OpType-specific errors per Node
   op_node_errors_t op_node_errors_;
   op_link_errors_t op_link_errors_;
+
+  // penalties per pair of links
+  crosstalk_errors_t crosstalk_errors_;
 };
 
 JSON_DECL(DeviceCharacterisation)
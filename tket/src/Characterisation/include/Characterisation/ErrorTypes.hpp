@@ -34,4 +34,9 @@ typedef std::map<OpType, gate_error_t> op_errors_t;
 typedef std::map<Node, op_errors_t> op_node_errors_t;
 typedef std::map<std::pair<Node, Node>, op_errors_t> op_link_errors_t;
 
+// penalties for two-qubit gates on a pair of links running at the same time
+typedef std::map<
+    std::pair<std::pair<Node, Node>, std::pair<Node, Node>>, gate_error_t>
+    crosstalk_errors_t;
+
 }  // namespace tket
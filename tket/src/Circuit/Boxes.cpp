@@ -14,18 +14,25 @@
 
 #include "Boxes.hpp"
 
+#include <algorithm>
+#include <cmath>
+#include <complex>
 #include <memory>
 #include <numeric>
+#include <set>
 #include <tkassert/Assert.hpp>
 
 #include "CircUtils.hpp"
 #include "Circuit/AssertionSynthesis.hpp"
 #include "Command.hpp"
+#include "Gate/OpPtrFunctions.hpp"
 #include "Gate/Rotation.hpp"
 #include "OpType/OpTypeInfo.hpp"
 #include "Ops/OpJsonFactory.hpp"
 #include "Ops/OpPtr.hpp"
+#include "ShannonDecomposition.hpp"
 #include "ThreeQubitConversion.hpp"
+#include "Utils/Constants.hpp"
 #include "Utils/EigenConfig.hpp"
 #include "Utils/Expression.hpp"
 #include "Utils/Json.hpp"
@@ -186,6 +193,45 @@ void Unitary3qBox::generate_circuit() const {
   circ_ = std::make_shared<Circuit>(three_qubit_tk_synthesis(m_));
 }
 
+// Number of qubits acted on by a 2^n x 2^n matrix.
+static unsigned unitary_n_qubits(const Eigen::MatrixXcd &m) {
+  const unsigned dim = m.rows();
+  unsigned n = 0;
+  while ((1u << n) < dim) n++;
+  if (dim != (1u << n) || m.cols() != m.rows()) {
+    throw CircuitInvalidity(
+        "Matrix for UnitaryNqBox must be square with dimension a power of 2");
+  }
+  return n;
+}
+
+UnitaryNqBox::UnitaryNqBox(const Eigen::MatrixXcd &m, BasisOrder basis)
+    : Box(OpType::UnitaryNqBox,
+          op_signature_t(unitary_n_qubits(m), EdgeType::Quantum)),
+      m_(basis == BasisOrder::ilo ? m : reverse_indexing(m)) {
+  if (!is_unitary(m)) {
+    throw CircuitInvalidity("Matrix for UnitaryNqBox must be unitary");
+  }
+}
+
+UnitaryNqBox::UnitaryNqBox(const UnitaryNqBox &other)
+    : Box(other), m_(other.m_) {}
+
+UnitaryNqBox::UnitaryNqBox()
+    : UnitaryNqBox(Eigen::MatrixXcd::Identity(2, 2)) {}
+
+Op_ptr UnitaryNqBox::dagger() const {
+  return std::make_shared<UnitaryNqBox>(m_.adjoint());
+}
+
+Op_ptr UnitaryNqBox::transpose() const {
+  return std::make_shared<UnitaryNqBox>(m_.transpose());
+}
+
+void UnitaryNqBox::generate_circuit() const {
+  circ_ = std::make_shared<Circuit>(quantum_shannon_synthesis(m_));
+}
+
 ExpBox::ExpBox(const Eigen::Matrix4cd &A, double t, BasisOrder basis)
     : Box(OpType::ExpBox),
       A_(basis == BasisOrder::ilo ? A : reverse_indexing(A)),
@@ -408,6 +454,404 @@ Op_ptr QControlBox::transpose() const {
   return std::make_shared<QControlBox>(inner_transpose, n_controls_);
 }
 
+MultiplexorBox::MultiplexorBox(const ctrl_op_map_t &op_map)
+    : Box(OpType::MultiplexorBox), op_map_(op_map) {
+  if (op_map_.empty()) {
+    throw CircuitInvalidity("MultiplexorBox requires at least one op");
+  }
+  n_controls_ = op_map_.begin()->first.size();
+  n_targets_ = op_map_.begin()->second->get_signature().size();
+  for (const auto &[state, op] : op_map_) {
+    if (state.size() != n_controls_) {
+      throw CircuitInvalidity(
+          "Control states of a MultiplexorBox must all have the same size");
+    }
+    op_signature_t sig = op->get_signature();
+    if (sig.size() != n_targets_ ||
+        static_cast<unsigned>(std::count(
+            sig.begin(), sig.end(), EdgeType::Quantum)) != n_targets_) {
+      throw CircuitInvalidity(
+          "Ops in a MultiplexorBox must all act on the same number of qubits "
+          "and no classical wires");
+    }
+  }
+  signature_ = op_signature_t(n_controls_ + n_targets_, EdgeType::Quantum);
+}
+
+MultiplexorBox::MultiplexorBox(const MultiplexorBox &other)
+    : Box(other),
+      op_map_(other.op_map_),
+      n_controls_(other.n_controls_),
+      n_targets_(other.n_targets_) {}
+
+Op_ptr MultiplexorBox::symbol_substitution(
+    const SymEngine::map_basic_basic &sub_map) const {
+  ctrl_op_map_t new_map;
+  for (const auto &[state, op] : op_map_) {
+    Op_ptr new_op = op->symbol_substitution(sub_map);
+    new_map.insert({state, new_op ? new_op : op});
+  }
+  return std::make_shared<MultiplexorBox>(new_map);
+}
+
+SymSet MultiplexorBox::free_symbols() const {
+  SymSet symbols;
+  for (const auto &[state, op] : op_map_) {
+    SymSet op_symbols = op->free_symbols();
+    symbols.insert(op_symbols.begin(), op_symbols.end());
+  }
+  return symbols;
+}
+
+Op_ptr MultiplexorBox::dagger() const {
+  ctrl_op_map_t new_map;
+  for (const auto &[state, op] : op_map_) {
+    new_map.insert({state, op->dagger()});
+  }
+  return std::make_shared<MultiplexorBox>(new_map);
+}
+
+Op_ptr MultiplexorBox::transpose() const {
+  ctrl_op_map_t new_map;
+  for (const auto &[state, op] : op_map_) {
+    new_map.insert({state, op->transpose()});
+  }
+  return std::make_shared<MultiplexorBox>(new_map);
+}
+
+void MultiplexorBox::generate_circuit() const {
+  Circuit circ(n_controls_ + n_targets_);
+  std::vector<unsigned> targets(n_targets_);
+  std::iota(targets.begin(), targets.end(), 0);
+  // Controls that should be |0> for the current op are conjugated with X.
+  // Only the X gates that change between consecutive ops are added.
+  std::vector<bool> flipped(n_controls_, false);
+  for (const auto &[state, op] : op_map_) {
+    for (unsigned i = 0; i < n_controls_; i++) {
+      if (flipped[i] == state[i]) {
+        circ.add_op<unsigned>(OpType::X, {i});
+        flipped[i] = !flipped[i];
+      }
+    }
+    Circuit c(n_targets_);
+    c.add_op(op, targets);
+    c.decompose_boxes_recursively();
+    circ.append(with_controls(c, n_controls_));
+  }
+  for (unsigned i = 0; i < n_controls_; i++) {
+    if (flipped[i]) circ.add_op<unsigned>(OpType::X, {i});
+  }
+  circ_ = std::make_shared<Circuit>(circ);
+}
+
+MultiplexedRotationBox::MultiplexedRotationBox(
+    const std::vector<double> &angles, OpType axis)
+    : Box(OpType::MultiplexedRotationBox), angles_(angles), axis_(axis) {
+  if (axis_ != OpType::Rx && axis_ != OpType::Ry && axis_ != OpType::Rz) {
+    throw CircuitInvalidity(
+        "The axis of a MultiplexedRotationBox must be Rx, Ry or Rz");
+  }
+  n_controls_ = 0;
+  while ((1u << n_controls_) < angles_.size()) n_controls_++;
+  if (angles_.size() != (1u << n_controls_)) {
+    throw CircuitInvalidity(
+        "Number of angles for a MultiplexedRotationBox must be a power of 2");
+  }
+  signature_ = op_signature_t(n_controls_ + 1, EdgeType::Quantum);
+}
+
+MultiplexedRotationBox::MultiplexedRotationBox(
+    const MultiplexedRotationBox &other)
+    : Box(other),
+      angles_(other.angles_),
+      axis_(other.axis_),
+      n_controls_(other.n_controls_) {}
+
+Eigen::MatrixXcd MultiplexedRotationBox::get_unitary() const {
+  const unsigned N = angles_.size();
+  Eigen::MatrixXcd U = Eigen::MatrixXcd::Zero(2 * N, 2 * N);
+  for (unsigned j = 0; j < N; j++) {
+    U.block(2 * j, 2 * j, 2, 2) = get_op_ptr(axis_, angles_[j])->get_unitary();
+  }
+  return U;
+}
+
+Op_ptr MultiplexedRotationBox::dagger() const {
+  std::vector<double> new_angles;
+  for (double a : angles_) new_angles.push_back(-a);
+  return std::make_shared<MultiplexedRotationBox>(new_angles, axis_);
+}
+
+Op_ptr MultiplexedRotationBox::transpose() const {
+  // Rx and Rz matrices are symmetric; Ry matrices are antisymmetric apart
+  // from the diagonal.
+  if (axis_ != OpType::Ry) {
+    return std::make_shared<MultiplexedRotationBox>(*this);
+  }
+  return dagger();
+}
+
+void MultiplexedRotationBox::generate_circuit() const {
+  // multiplexed_rotation() puts the target first, followed by the controls.
+  unit_map_t qm{{Qubit(0), Qubit(n_controls_)}};
+  for (unsigned i = 0; i < n_controls_; i++) {
+    qm.insert({Qubit(i + 1), Qubit(i)});
+  }
+  Circuit circ(n_controls_ + 1);
+  if (axis_ == OpType::Rx) {
+    // Conjugating a multiplexed Rz by H on the target gives a multiplexed Rx.
+    circ.add_op<unsigned>(OpType::H, {n_controls_});
+    circ.append_with_map(multiplexed_rotation(OpType::Rz, angles_), qm);
+    circ.add_op<unsigned>(OpType::H, {n_controls_});
+  } else {
+    circ.append_with_map(multiplexed_rotation(axis_, angles_), qm);
+  }
+  circ_ = std::make_shared<Circuit>(circ);
+}
+
+StatePreparationBox::StatePreparationBox(
+    const Eigen::VectorXcd &statevector, bool with_initial_reset)
+    : Box(OpType::StatePreparationBox),
+      statevector_(statevector),
+      with_initial_reset_(with_initial_reset) {
+  const unsigned dim = statevector_.size();
+  n_qubits_ = 0;
+  while ((1u << n_qubits_) < dim) n_qubits_++;
+  if (dim != (1u << n_qubits_) || n_qubits_ == 0) {
+    throw CircuitInvalidity(
+        "Statevector for StatePreparationBox must have size a power of 2 "
+        "(at least 2)");
+  }
+  if (std::abs(statevector_.norm() - 1) >= EPS) {
+    throw CircuitInvalidity(
+        "Statevector for StatePreparationBox is not normalised");
+  }
+  signature_ = op_signature_t(n_qubits_, EdgeType::Quantum);
+}
+
+StatePreparationBox::StatePreparationBox(const StatePreparationBox &other)
+    : Box(other),
+      statevector_(other.statevector_),
+      with_initial_reset_(other.with_initial_reset_),
+      n_qubits_(other.n_qubits_) {}
+
+void StatePreparationBox::generate_circuit() const {
+  // We compute the circuit that maps the state to (a multiple of) |0...0>,
+  // disentangling one qubit at a time starting from the last, and then
+  // invert it. To disentangle qubit t we pair up the amplitudes of the
+  // basis states that differ only in qubit t, and for each pair (a0, a1)
+  // apply an Rz to equalise their phases followed by an Ry to zero a1,
+  // multiplexed over the basis states of qubits 0, ..., t-1.
+  std::vector<std::vector<double>> ry_angles(n_qubits_);
+  std::vector<std::vector<double>> rz_angles(n_qubits_);
+  Eigen::VectorXcd v = statevector_;
+  for (unsigned t = n_qubits_; t-- > 0;) {
+    const unsigned N = 1u << t;
+    Eigen::VectorXcd w(N);
+    for (unsigned m = 0; m < N; m++) {
+      const Complex a0 = v(2 * m), a1 = v(2 * m + 1);
+      const double r0 = std::abs(a0), r1 = std::abs(a1);
+      const double phi0 = std::arg(a0), phi1 = std::arg(a1);
+      rz_angles[t].push_back((phi0 - phi1) / PI);
+      ry_angles[t].push_back(-2 / PI * std::atan2(r1, r0));
+      w(m) = std::polar(std::hypot(r0, r1), (phi0 + phi1) / 2);
+    }
+    v = w;
+  }
+
+  Circuit circ(n_qubits_);
+  if (with_initial_reset_) {
+    for (unsigned q = 0; q < n_qubits_; q++) {
+      circ.add_op<unsigned>(OpType::Reset, {q});
+    }
+  }
+  for (unsigned t = 0; t < n_qubits_; t++) {
+    // multiplexed_rotation() puts the target first, followed by the controls.
+    unit_map_t qm{{Qubit(0), Qubit(t)}};
+    for (unsigned i = 0; i < t; i++) {
+      qm.insert({Qubit(i + 1), Qubit(i)});
+    }
+    for (double &a : ry_angles[t]) a = -a;
+    for (double &a : rz_angles[t]) a = -a;
+    circ.append_with_map(multiplexed_rotation(OpType::Ry, ry_angles[t]), qm);
+    circ.append_with_map(multiplexed_rotation(OpType::Rz, rz_angles[t]), qm);
+  }
+  circ.add_phase(std::arg(v(0)) / PI);
+  circ_ = std::make_shared<Circuit>(circ);
+}
+
+ToffoliBox::ToffoliBox(
+    const state_perm_t &permutation, ToffoliBoxSynthStrat strat)
+    : Box(OpType::ToffoliBox), permutation_(permutation), strat_(strat) {
+  if (permutation_.empty()) {
+    throw CircuitInvalidity("ToffoliBox requires a non-empty permutation");
+  }
+  n_qubits_ = permutation_.begin()->first.size();
+  if (n_qubits_ == 0) {
+    throw CircuitInvalidity("ToffoliBox must act on at least one qubit");
+  }
+  std::set<std::vector<bool>> images;
+  for (const auto &[state, image] : permutation_) {
+    if (state.size() != n_qubits_ || image.size() != n_qubits_) {
+      throw CircuitInvalidity(
+          "Basis states in a ToffoliBox must all have the same size");
+    }
+    images.insert(image);
+  }
+  if (images.size() != permutation_.size() ||
+      !std::all_of(
+          images.begin(), images.end(),
+          [this](const std::vector<bool> &image) {
+            return permutation_.count(image) != 0;
+          })) {
+    throw CircuitInvalidity(
+        "The images of the basis states in a ToffoliBox must be a "
+        "rearrangement of the basis states");
+  }
+  signature_ = op_signature_t(n_qubits_, EdgeType::Quantum);
+}
+
+ToffoliBox::ToffoliBox(const ToffoliBox &other)
+    : Box(other),
+      permutation_(other.permutation_),
+      strat_(other.strat_),
+      n_qubits_(other.n_qubits_) {}
+
+// Index of a basis state in BasisOrder::ilo.
+static unsigned basis_index(const std::vector<bool> &state) {
+  unsigned i = 0;
+  for (bool b : state) i = 2 * i + b;
+  return i;
+}
+
+Eigen::MatrixXcd ToffoliBox::get_unitary() const {
+  const unsigned dim = 1u << n_qubits_;
+  Eigen::MatrixXcd U = Eigen::MatrixXcd::Identity(dim, dim);
+  for (const auto &[state, image] : permutation_) {
+    U.col(basis_index(state)) = Eigen::VectorXcd::Unit(dim, basis_index(image));
+  }
+  return U;
+}
+
+Op_ptr ToffoliBox::dagger() const {
+  state_perm_t inverse;
+  for (const auto &[state, image] : permutation_) {
+    inverse.insert({image, state});
+  }
+  return std::make_shared<ToffoliBox>(inverse, strat_);
+}
+
+Op_ptr ToffoliBox::transpose() const {
+  // Permutation matrices are orthogonal.
+  return dagger();
+}
+
+static unsigned hamming_distance(
+    const std::vector<bool> &a, const std::vector<bool> &b) {
+  unsigned d = 0;
+  for (unsigned i = 0; i < a.size(); i++) {
+    if (a[i] != b[i]) d++;
+  }
+  return d;
+}
+
+// Add a multi-controlled X with the given target, controlled on the other
+// qubits being in the given state. Controls that should be |0> are
+// conjugated with X; the X gates are tracked in `flipped` so that only those
+// that change between consecutive gates are added.
+static void add_toffoli(
+    Circuit &circ, const std::vector<bool> &state, unsigned target,
+    std::vector<bool> &flipped) {
+  const unsigned n = state.size();
+  std::vector<unsigned> args;
+  for (unsigned q = 0; q < n; q++) {
+    if (q == target) continue;
+    if (flipped[q] == state[q]) {
+      circ.add_op<unsigned>(OpType::X, {q});
+      flipped[q] = !flipped[q];
+    }
+    args.push_back(q);
+  }
+  args.push_back(target);
+  switch (n) {
+    case 1:
+      circ.add_op<unsigned>(OpType::X, args);
+      break;
+    case 2:
+      circ.add_op<unsigned>(OpType::CX, args);
+      break;
+    case 3:
+      circ.add_op<unsigned>(OpType::CCX, args);
+      break;
+    default:
+      circ.add_op<unsigned>(OpType::CnX, args);
+  }
+}
+
+// Add the transposition of basis states a and b. Writing a = g_0, g_1, ...,
+// g_d = b for a path in which consecutive states differ in one bit, the
+// transposition is the product of the transpositions (g_i g_{i+1}) for
+// i = 0, ..., d-1, d-2, ..., 0, each of which is a multi-controlled X.
+static void add_transposition(
+    Circuit &circ, const std::vector<bool> &a, const std::vector<bool> &b,
+    std::vector<bool> &flipped) {
+  std::vector<unsigned> diff;
+  for (unsigned q = 0; q < a.size(); q++) {
+    if (a[q] != b[q]) diff.push_back(q);
+  }
+  const unsigned d = diff.size();
+  std::vector<std::vector<bool>> path{a};
+  for (unsigned i = 0; i + 1 < d; i++) {
+    std::vector<bool> g = path.back();
+    g[diff[i]] = !g[diff[i]];
+    path.push_back(g);
+  }
+  for (unsigned i = 0; i < d; i++) {
+    add_toffoli(circ, path[i], diff[i], flipped);
+  }
+  for (unsigned i = d - 1; i-- > 0;) {
+    add_toffoli(circ, path[i], diff[i], flipped);
+  }
+}
+
+void ToffoliBox::generate_circuit() const {
+  Circuit circ(n_qubits_);
+  std::vector<bool> flipped(n_qubits_, false);
+  std::set<std::vector<bool>> done;
+  for (const auto &[start, start_image] : permutation_) {
+    if (done.count(start) != 0 || start == start_image) continue;
+    std::vector<std::vector<bool>> cycle;
+    std::vector<bool> x = start;
+    do {
+      cycle.push_back(x);
+      done.insert(x);
+      x = permutation_.at(x);
+    } while (x != start);
+    const unsigned k = cycle.size();
+    if (strat_ == ToffoliBoxSynthStrat::CostAware) {
+      // The cycle (x_0 x_1 ... x_{k-1}) is the product of the transpositions
+      // (x_i x_{i+1}) for i < k-1, so rotate it to leave out the most
+      // expensive pair.
+      unsigned best = k - 1, best_d = 0;
+      for (unsigned i = 0; i < k; i++) {
+        unsigned d = hamming_distance(cycle[i], cycle[(i + 1) % k]);
+        if (d > best_d) {
+          best = i;
+          best_d = d;
+        }
+      }
+      std::rotate(cycle.begin(), cycle.begin() + (best + 1) % k, cycle.end());
+    }
+    for (unsigned i = k - 1; i-- > 0;) {
+      add_transposition(circ, cycle[i], cycle[i + 1], flipped);
+    }
+  }
+  for (unsigned q = 0; q < n_qubits_; q++) {
+    if (flipped[q]) circ.add_op<unsigned>(OpType::X, {q});
+  }
+  circ_ = std::make_shared<Circuit>(circ);
+}
+
 ProjectorAssertionBox::ProjectorAssertionBox(
     const Eigen::MatrixXcd &m, BasisOrder basis)
     : Box(OpType::ProjectorAssertionBox),
@@ -556,6 +1000,20 @@ Op_ptr Unitary3qBox::from_json(const nlohmann::json &j) {
       boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
 }
 
+nlohmann::json UnitaryNqBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const UnitaryNqBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  j["matrix"] = box.get_matrix();
+  return j;
+}
+
+Op_ptr UnitaryNqBox::from_json(const nlohmann::json &j) {
+  UnitaryNqBox box = UnitaryNqBox(j.at("matrix").get<Eigen::MatrixXcd>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
 nlohmann::json ExpBox::to_json(const Op_ptr &op) {
   const auto &box = static_cast<const ExpBox &>(*op);
   nlohmann::json j = core_box_json(box);
@@ -634,6 +1092,72 @@ Op_ptr QControlBox::from_json(const nlohmann::json &j) {
       boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
 }
 
+nlohmann::json MultiplexorBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const MultiplexorBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  j["op_map"] = box.get_op_map();
+  return j;
+}
+
+Op_ptr MultiplexorBox::from_json(const nlohmann::json &j) {
+  MultiplexorBox box = MultiplexorBox(j.at("op_map").get<ctrl_op_map_t>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
+nlohmann::json MultiplexedRotationBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const MultiplexedRotationBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  j["angles"] = box.get_angles();
+  j["axis"] = box.get_axis();
+  return j;
+}
+
+Op_ptr MultiplexedRotationBox::from_json(const nlohmann::json &j) {
+  MultiplexedRotationBox box = MultiplexedRotationBox(
+      j.at("angles").get<std::vector<double>>(), j.at("axis").get<OpType>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
+nlohmann::json StatePreparationBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const StatePreparationBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  const Eigen::VectorXcd sv = box.get_statevector();
+  j["statevector"] = std::vector<Complex>(sv.data(), sv.data() + sv.size());
+  j["with_initial_reset"] = box.with_initial_reset();
+  return j;
+}
+
+Op_ptr StatePreparationBox::from_json(const nlohmann::json &j) {
+  std::vector<Complex> sv = j.at("statevector").get<std::vector<Complex>>();
+  StatePreparationBox box = StatePreparationBox(
+      Eigen::Map<Eigen::VectorXcd>(sv.data(), sv.size()),
+      j.at("with_initial_reset").get<bool>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
+nlohmann::json ToffoliBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const ToffoliBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  j["permutation"] = box.get_permutation();
+  j["strat"] = box.get_strat();
+  return j;
+}
+
+Op_ptr ToffoliBox::from_json(const nlohmann::json &j) {
+  ToffoliBox box = ToffoliBox(
+      j.at("permutation").get<state_perm_t>(),
+      j.at("strat").get<ToffoliBoxSynthStrat>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
 nlohmann::json ProjectorAssertionBox::to_json(const Op_ptr &op) {
   const auto &box = static_cast<const ProjectorAssertionBox &>(*op);
   nlohmann::json j = core_box_json(box);
@@ -669,10 +1193,15 @@ REGISTER_OPFACTORY(CircBox, CircBox)
 REGISTER_OPFACTORY(Unitary1qBox, Unitary1qBox)
 REGISTER_OPFACTORY(Unitary2qBox, Unitary2qBox)
 REGISTER_OPFACTORY(Unitary3qBox, Unitary3qBox)
+REGISTER_OPFACTORY(UnitaryNqBox, UnitaryNqBox)
 REGISTER_OPFACTORY(ExpBox, ExpBox)
 REGISTER_OPFACTORY(PauliExpBox, PauliExpBox)
 REGISTER_OPFACTORY(CustomGate, CustomGate)
 REGISTER_OPFACTORY(QControlBox, QControlBox)
+REGISTER_OPFACTORY(MultiplexorBox, MultiplexorBox)
+REGISTER_OPFACTORY(MultiplexedRotationBox, MultiplexedRotationBox)
+REGISTER_OPFACTORY(StatePreparationBox, StatePreparationBox)
+REGISTER_OPFACTORY(ToffoliBox, ToffoliBox)
 REGISTER_OPFACTORY(ProjectorAssertionBox, ProjectorAssertionBox)
 REGISTER_OPFACTORY(StabiliserAssertionBox, StabiliserAssertionBox)
 }  // namespace tket
@@ -129,6 +129,22 @@ const Circuit &CX_using_XXPhase_1() {
   return *C;
 }
 
+const Circuit &CX_using_ISWAPMax() {
+  static std::unique_ptr<const Circuit> C = std::make_unique<Circuit>([]() {
+    Circuit c(2);
+    c.add_op<unsigned>(OpType::Rz, -0.5, {0});
+    c.add_op<unsigned>(OpType::Rx, 0.5, {1});
+    c.add_op<unsigned>(OpType::Rz, 0.5, {1});
+    c.add_op<unsigned>(OpType::ISWAPMax, {0, 1});
+    c.add_op<unsigned>(OpType::Rx, 0.5, {0});
+    c.add_op<unsigned>(OpType::ISWAPMax, {0, 1});
+    c.add_op<unsigned>(OpType::Rz, 0.5, {1});
+    c.add_phase(0.25);
+    return c;
+  }());
+  return *C;
+}
+
 const Circuit &CX_VS_CX_reduced() {
   static std::unique_ptr<const Circuit> C = std::make_unique<Circuit>([]() {
     Circuit c(2);
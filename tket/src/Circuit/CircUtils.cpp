@@ -534,6 +534,7 @@ Circuit with_CX(Gate_ptr op) {
     case OpType::PhaseGadget:
       return phase_gadget(n, params[0], CXConfigType::Snake);
     case OpType::SWAP:
+    case OpType::Transport:
       return CircPool::SWAP_using_CX_0();
     case OpType::CSWAP:
       return CircPool::CSWAP_using_CX();
@@ -26,7 +26,9 @@ namespace tket {
 
 void from_json(const nlohmann::json& j, Op_ptr& op) {
   OpType optype = j.at("type").get<OpType>();
-  if (is_metaop_type(optype)) {
+  if (optype == OpType::Delay) {
+    op = DelayOp::deserialize(j);
+  } else if (is_metaop_type(optype)) {
     op = MetaOp::deserialize(j);
   } else if (is_box_type(optype)) {
     op = Box::deserialize(j);
@@ -0,0 +1,245 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "ShannonDecomposition.hpp"
+
+#include <cmath>
+#include <complex>
+#include <optional>
+#include <stdexcept>
+
+#include "CircUtils.hpp"
+#include "Circuit.hpp"
+#include "Gate/Rotation.hpp"
+#include "OpType/OpType.hpp"
+#include "ThreeQubitConversion.hpp"
+#include "Utils/Constants.hpp"
+#include "Utils/CosSinDecomposition.hpp"
+#include "Utils/EigenConfig.hpp"
+#include "Utils/MatrixAnalysis.hpp"
+#include "Utils/UnitID.hpp"
+
+namespace tket {
+
+// The number n such that dim = 2^n, if there is one.
+static std::optional<unsigned> log2_dim(std::size_t dim) {
+  unsigned n = 0;
+  while (dim > 1 && dim % 2 == 0) {
+    dim /= 2;
+    n++;
+  }
+  if (dim != 1) return std::nullopt;
+  return n;
+}
+
+static bool approx_equal(const Eigen::MatrixXcd &A, const Eigen::MatrixXcd &B) {
+  return (A - B).cwiseAbs().maxCoeff() < EPS;
+}
+
+static bool all_equal(const std::vector<double> &angles) {
+  for (double a : angles) {
+    if (std::abs(a - angles[0]) >= EPS) return false;
+  }
+  return true;
+}
+
+static unsigned gray_code(unsigned i) { return i ^ (i >> 1); }
+
+static bool parity(unsigned x) {
+  bool p = false;
+  for (; x != 0; x &= x - 1) p = !p;
+  return p;
+}
+
+// The control qubit of the CX following rotation i in a multiplexor with k
+// controls. The Gray codes of i and i+1 (mod 2^k) differ in a single bit, and
+// bit b of a basis state of qubits 1, ..., k corresponds to qubit k-b.
+static unsigned mux_control(unsigned i, unsigned k) {
+  unsigned diff = gray_code(i) ^ gray_code((i + 1) % (1u << k));
+  unsigned b = 0;
+  while (diff >>= 1) b++;
+  return k - b;
+}
+
+// Angles for the rotations in a multiplexor, which alternate with CX gates
+// whose controls follow the Gray code. When qubits 1, ..., k are in state |j>,
+// the sign of rotation i is flipped by the preceding CX gates iff
+// j & gray_code(i) has odd parity; the net rotation must be by angles[j].
+static std::vector<double> mux_angles(const std::vector<double> &angles) {
+  const unsigned N = angles.size();
+  std::vector<double> alphas(N, 0.);
+  for (unsigned i = 0; i < N; i++) {
+    unsigned g = gray_code(i);
+    for (unsigned j = 0; j < N; j++) {
+      alphas[i] += parity(j & g) ? -angles[j] : angles[j];
+    }
+    alphas[i] /= N;
+  }
+  return alphas;
+}
+
+static void add_rotation(Circuit &circ, OpType type, double angle) {
+  if (std::abs(angle) >= EPS) {
+    circ.add_op<unsigned>(type, angle, {0});
+  }
+}
+
+Circuit multiplexed_rotation(OpType type, const std::vector<double> &angles) {
+  if (type != OpType::Ry && type != OpType::Rz) {
+    throw std::invalid_argument(
+        "Multiplexed rotations must be of type Ry or Rz");
+  }
+  std::optional<unsigned> k = log2_dim(angles.size());
+  if (!k) {
+    throw std::invalid_argument(
+        "Number of angles for a multiplexed rotation must be a power of 2");
+  }
+  Circuit circ(*k + 1);
+  if (all_equal(angles)) {
+    add_rotation(circ, type, angles[0]);
+    return circ;
+  }
+  std::vector<double> alphas = mux_angles(angles);
+  for (unsigned i = 0; i < alphas.size(); i++) {
+    add_rotation(circ, type, alphas[i]);
+    circ.add_op<unsigned>(OpType::CX, {mux_control(i, *k), 0});
+  }
+  return circ;
+}
+
+// Return a (k+1)-qubit circuit implementing the multiplexed Ry rotation with
+// the given angles followed by a CZ on qubits 0 and 1, using 2^k - 1 CX gates.
+//
+// The multiplexor works equally well with CZ in place of CX, and its final
+// CZ (on qubits 0 and 1) then cancels. We convert the remaining CZ operations
+// to CX by adding Hadamards and simplifying H Ry(t) H to Ry(-t).
+static Circuit ry_multiplexor_cz(
+    const std::vector<double> &angles, unsigned k) {
+  std::vector<double> alphas = mux_angles(angles);
+  Circuit circ(k + 1);
+  add_rotation(circ, OpType::Ry, alphas[0]);
+  circ.add_op<unsigned>(OpType::H, {0});
+  for (unsigned i = 1; i < alphas.size(); i++) {
+    circ.add_op<unsigned>(OpType::CX, {mux_control(i - 1, k), 0});
+    add_rotation(circ, OpType::Ry, -alphas[i]);
+  }
+  circ.add_op<unsigned>(OpType::H, {0});
+  return circ;
+}
+
+static Circuit shannon_synth(const Eigen::MatrixXcd &U);
+
+// Return an n-qubit circuit implementing the unitary
+//     [ U0    ]
+//     [    U1 ]
+// where U0 and U1 are unitaries on n-1 qubits.
+//
+// We write U0 = V D W and U1 = V D* W where D is diagonal, so that the
+// circuit consists of W and V on qubits 1, ..., n-1 separated by a
+// multiplexed Rz on qubit 0.
+static Circuit demultiplex(
+    const Eigen::MatrixXcd &U0, const Eigen::MatrixXcd &U1, unsigned n) {
+  Circuit circ(n);
+  unit_map_t qm;
+  for (unsigned q = 0; q + 1 < n; q++) {
+    qm.insert({Qubit(q), Qubit(q + 1)});
+  }
+  if (approx_equal(U0, U1)) {
+    circ.append_with_map(shannon_synth(U1), qm);
+    return circ;
+  }
+  // 1. Decompose U0 U1* as V T V* where V and T are unitary and T is diagonal.
+  Eigen::ComplexSchur<Eigen::MatrixXcd> schur(U0 * U1.adjoint());
+  Eigen::MatrixXcd V = schur.matrixU();
+  // By construction T is unitary and upper-triangular, hence diagonal.
+  // 2. Let D = sqrt(T)
+  Eigen::VectorXcd D = schur.matrixT().diagonal().cwiseSqrt();
+  // 3. Compute W such that U0 = V D W and U1 = V D* W.
+  Eigen::MatrixXcd W = D.asDiagonal() * V.adjoint() * U1;
+  // 4. Compute angles a_j such that D_jj = e^{-i pi/2 a_j}.
+  static const double f = -2 / PI;
+  std::vector<double> angles;
+  for (unsigned j = 0; j < D.size(); j++) {
+    angles.push_back(f * std::arg(D(j)));
+  }
+  // 5. Construct the circuit.
+  circ.append_with_map(shannon_synth(W), qm);
+  circ.append(multiplexed_rotation(OpType::Rz, angles));
+  circ.append_with_map(shannon_synth(V), qm);
+  return circ;
+}
+
+static Circuit shannon_synth(const Eigen::MatrixXcd &U) {
+  const unsigned dim = U.rows();
+  const unsigned n = *log2_dim(dim);
+
+  // A multiple of the identity needs no gates.
+  if (approx_equal(U, U(0, 0) * Eigen::MatrixXcd::Identity(dim, dim))) {
+    Circuit circ(n);
+    circ.add_phase(std::arg(U(0, 0)) / PI);
+    return circ;
+  }
+
+  if (n == 1) {
+    Circuit circ(1);
+    std::vector<double> angles = tk1_angles_from_unitary(U);
+    circ.add_op<unsigned>(OpType::TK1, {angles.begin(), angles.end() - 1}, {0});
+    circ.add_phase(angles[3]);
+    return circ;
+  }
+  if (n == 2) return two_qubit_canonical(U);
+  if (n == 3) return three_qubit_synthesis(U);
+
+  // A block-diagonal unitary only needs demultiplexing.
+  const unsigned N = dim / 2;
+  if (U.topRightCorner(N, N).cwiseAbs().maxCoeff() < EPS &&
+      U.bottomLeftCorner(N, N).cwiseAbs().maxCoeff() < EPS) {
+    return demultiplex(U.topLeftCorner(N, N), U.bottomRightCorner(N, N), n);
+  }
+
+  auto [l0, l1, r0, r1, c, s] = CS_decomp(U);
+  // Compute angles a_j such that c_jj = cos(pi/2 a_j) and s_jj = sin(pi/2 a_j).
+  static const double f = 2 / PI;
+  std::vector<double> angles;
+  for (unsigned j = 0; j < N; j++) {
+    angles.push_back(f * atan2(s(j, j), c(j, j)));
+  }
+  Circuit circ = demultiplex(r0, r1, n);
+  if (all_equal(angles)) {
+    circ.append(multiplexed_rotation(OpType::Ry, angles));
+  } else {
+    circ.append(ry_multiplexor_cz(angles, n - 1));
+    // We chopped off the last CZ (on qubits 0 and 1) from the circuit
+    // implementing the CS decomposition. Account for this by changing the
+    // signs of the columns of l1 where qubit 1 is set.
+    l1.rightCols(N / 2) *= -1;
+  }
+  circ.append(demultiplex(l0, l1, n));
+  return circ;
+}
+
+Circuit quantum_shannon_synthesis(const Eigen::MatrixXcd &U) {
+  if (U.rows() != U.cols() || !log2_dim(U.rows())) {
+    throw std::invalid_argument(
+        "Matrix for Shannon synthesis must be square with dimension a power "
+        "of 2");
+  }
+  if (!is_unitary(U)) {
+    throw std::invalid_argument(
+        "Non-unitary matrix passed to quantum_shannon_synthesis");
+  }
+  return shannon_synth(U);
+}
+
+}  // namespace tket
@@ -17,7 +17,9 @@
 #include <boost/uuid/uuid.hpp>
 #include <boost/uuid/uuid_generators.hpp>
 #include <boost/uuid/uuid_io.hpp>
+#include <map>
 #include <memory>
+#include <vector>
 
 #include "OpType/OpTypeInfo.hpp"
 #include "Ops/Op.hpp"
@@ -339,6 +341,71 @@ class Unitary3qBox : public Box {
   const Matrix8cd m_;
 };
 
+/**
+ * Operation defined as an arbitrary unitary on any number of qubits.
+ *
+ * The circuit is synthesised using the quantum Shannon decomposition (see
+ * \ref quantum_shannon_synthesis). Use \ref Unitary1qBox, \ref Unitary2qBox
+ * or \ref Unitary3qBox for unitaries on up to three qubits.
+ */
+class UnitaryNqBox : public Box {
+ public:
+  /**
+   * Construct from a given 2^n x 2^n unitary matrix
+   *
+   * @param m unitary matrix
+   * @param basis basis order convention for matrix
+   */
+  explicit UnitaryNqBox(
+      const Eigen::MatrixXcd &m, BasisOrder basis = BasisOrder::ilo);
+
+  /**
+   * Construct from the 1-qubit identity matrix
+   */
+  UnitaryNqBox();
+
+  /**
+   * Copy constructor
+   */
+  UnitaryNqBox(const UnitaryNqBox &other);
+
+  ~UnitaryNqBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &) const override {
+    return Op_ptr();
+  }
+
+  SymSet free_symbols() const override { return {}; }
+
+  /**
+   * Equality check between two UnitaryNqBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const UnitaryNqBox &other = dynamic_cast<const UnitaryNqBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  /** Get the unitary matrix correspnding to this operation */
+  Eigen::MatrixXcd get_matrix() const { return m_; }
+
+  Eigen::MatrixXcd get_unitary() const override { return m_; }
+
+  Op_ptr dagger() const override;
+
+  Op_ptr transpose() const override;
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  void generate_circuit() const override;
+
+ private:
+  const Eigen::MatrixXcd m_;
+};
+
 /**
  * Two-qubit operation defined in terms of a hermitian matrix and a phase.
  *
@@ -593,6 +660,291 @@ class QControlBox : public Box {
   unsigned n_inner_qubits_;
 };
 
+/**
+ * Map from basis states of the control qubits to operations
+ */
+typedef std::map<std::vector<bool>, Op_ptr> ctrl_op_map_t;
+
+/**
+ * Multiplexed operation: for each basis state of the control qubits in the
+ * map, the corresponding op is applied to the target qubits. Basis states not
+ * in the map act as the identity.
+ *
+ * The controls occupy the low-index ports, in the order of the entries of the
+ * basis states, and the targets the remaining ports.
+ */
+class MultiplexorBox : public Box {
+ public:
+  /**
+   * Construct from a map from basis states of the controls to ops
+   *
+   * @param op_map map from basis states to ops, which must all act on the
+   * same number of qubits and no classical wires
+   */
+  explicit MultiplexorBox(const ctrl_op_map_t &op_map);
+
+  /**
+   * Copy constructor
+   */
+  MultiplexorBox(const MultiplexorBox &other);
+
+  ~MultiplexorBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &sub_map) const override;
+
+  SymSet free_symbols() const override;
+
+  /**
+   * Equality check between two MultiplexorBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const MultiplexorBox &other =
+        dynamic_cast<const MultiplexorBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  Op_ptr dagger() const override;
+
+  Op_ptr transpose() const override;
+
+  ctrl_op_map_t get_op_map() const { return op_map_; }
+  unsigned get_n_controls() const { return n_controls_; }
+  unsigned get_n_targets() const { return n_targets_; }
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  void generate_circuit() const override;
+
+ private:
+  const ctrl_op_map_t op_map_;
+  unsigned n_controls_;
+  unsigned n_targets_;
+};
+
+/**
+ * Multiplexed (uniformly-controlled) rotation: for each basis state |j> of
+ * the control qubits, the target qubit is rotated about the given axis by the
+ * j-th angle.
+ *
+ * The controls occupy the low-index ports and the target the last port.
+ */
+class MultiplexedRotationBox : public Box {
+ public:
+  /**
+   * Construct from a list of angles and an axis
+   *
+   * @param angles rotation angles in half-turns, one for each basis state of
+   * the controls in \ref BasisOrder::ilo, so of size 2^n for n controls
+   * @param axis OpType::Rx, OpType::Ry or OpType::Rz
+   */
+  MultiplexedRotationBox(const std::vector<double> &angles, OpType axis);
+
+  /**
+   * Copy constructor
+   */
+  MultiplexedRotationBox(const MultiplexedRotationBox &other);
+
+  ~MultiplexedRotationBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &) const override {
+    return Op_ptr();
+  }
+
+  SymSet free_symbols() const override { return {}; }
+
+  /**
+   * Equality check between two MultiplexedRotationBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const MultiplexedRotationBox &other =
+        dynamic_cast<const MultiplexedRotationBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  Eigen::MatrixXcd get_unitary() const override;
+
+  Op_ptr dagger() const override;
+
+  Op_ptr transpose() const override;
+
+  std::vector<double> get_angles() const { return angles_; }
+  OpType get_axis() const { return axis_; }
+  unsigned get_n_controls() const { return n_controls_; }
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  void generate_circuit() const override;
+
+ private:
+  const std::vector<double> angles_;
+  const OpType axis_;
+  unsigned n_controls_;
+};
+
+/**
+ * Box to prepare a given quantum state from the all-zero state
+ */
+class StatePreparationBox : public Box {
+ public:
+  /**
+   * Construct from a normalised statevector
+   *
+   * Unless \p with_initial_reset is set, the box only has the intended effect
+   * when its qubits are in the all-zero state.
+   *
+   * @param statevector normalised statevector of size 2^n, in
+   * \ref BasisOrder::ilo
+   * @param with_initial_reset whether to reset all qubits before preparing
+   * the state
+   */
+  explicit StatePreparationBox(
+      const Eigen::VectorXcd &statevector, bool with_initial_reset = false);
+
+  /**
+   * Copy constructor
+   */
+  StatePreparationBox(const StatePreparationBox &other);
+
+  ~StatePreparationBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &) const override {
+    return Op_ptr();
+  }
+
+  SymSet free_symbols() const override { return {}; }
+
+  /**
+   * Equality check between two StatePreparationBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const StatePreparationBox &other =
+        dynamic_cast<const StatePreparationBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  Eigen::VectorXcd get_statevector() const { return statevector_; }
+  bool with_initial_reset() const { return with_initial_reset_; }
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  /**
+   * Construct the circuit using uniformly-controlled rotations, following
+   * Shende, Bullock and Markov, https://arxiv.org/abs/quant-ph/0406176
+   */
+  void generate_circuit() const override;
+
+ private:
+  const Eigen::VectorXcd statevector_;
+  const bool with_initial_reset_;
+  unsigned n_qubits_;
+};
+
+/**
+ * Map from basis states to basis states, defining a permutation
+ */
+typedef std::map<std::vector<bool>, std::vector<bool>> state_perm_t;
+
+/**
+ * Strategy for synthesising a \ref ToffoliBox
+ */
+enum class ToffoliBoxSynthStrat {
+  /**
+   * Decompose each cycle of the permutation into transpositions in the order
+   * given, starting from its least basis state
+   */
+  Naive,
+  /**
+   * Decompose each cycle of the permutation into transpositions so as to
+   * avoid the transposition of greatest Hamming distance, minimising the
+   * number of multi-controlled X gates
+   */
+  CostAware
+};
+
+NLOHMANN_JSON_SERIALIZE_ENUM(
+    ToffoliBoxSynthStrat, {{ToffoliBoxSynthStrat::Naive, "Naive"},
+                           {ToffoliBoxSynthStrat::CostAware, "CostAware"}});
+
+/**
+ * Permutation of computational basis states, synthesised as a network of
+ * multi-controlled X gates
+ *
+ * Each transposition of basis states at Hamming distance d is implemented
+ * with 2d-1 multi-controlled X gates, following a Gray-code path between
+ * them. Controls that must be |0> are conjugated with X gates, omitting
+ * those that cancel between consecutive gates.
+ */
+class ToffoliBox : public Box {
+ public:
+  /**
+   * Construct from a permutation of basis states
+   *
+   * Basis states not in the map are left unchanged.
+   *
+   * @param permutation map from basis states to their images, all of the
+   * same size; the images must be a rearrangement of the keys
+   * @param strat synthesis strategy
+   */
+  explicit ToffoliBox(
+      const state_perm_t &permutation,
+      ToffoliBoxSynthStrat strat = ToffoliBoxSynthStrat::CostAware);
+
+  /**
+   * Copy constructor
+   */
+  ToffoliBox(const ToffoliBox &other);
+
+  ~ToffoliBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &) const override {
+    return Op_ptr();
+  }
+
+  SymSet free_symbols() const override { return {}; }
+
+  /**
+   * Equality check between two ToffoliBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const ToffoliBox &other = dynamic_cast<const ToffoliBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  Eigen::MatrixXcd get_unitary() const override;
+
+  Op_ptr dagger() const override;
+
+  Op_ptr transpose() const override;
+
+  state_perm_t get_permutation() const { return permutation_; }
+  ToffoliBoxSynthStrat get_strat() const { return strat_; }
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  void generate_circuit() const override;
+
+ private:
+  const state_perm_t permutation_;
+  const ToffoliBoxSynthStrat strat_;
+  unsigned n_qubits_;
+};
+
 class ProjectorAssertionBox : public Box {
  public:
   /**
@@ -45,6 +45,9 @@ const Circuit &CX_using_XXPhase_0();
 /** Equivalent to CX, using only XXPhase, Rx and Rz gates */
 const Circuit &CX_using_XXPhase_1();
 
+/** Equivalent to CX, using only ISWAPMax, Rx and Rz gates */
+const Circuit &CX_using_ISWAPMax();
+
 /**
  * CX-reduced form of CX/V,S/CX
  *
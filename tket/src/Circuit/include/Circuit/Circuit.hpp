@@ -1555,8 +1555,9 @@ class Circuit {
   /**
    * Evaluate a classical circuit on given inputs.
    *
-   * The circuit must have only ClassicalTransform and SetBits operations. The
-   * keys of the input map must correspond to the bits of the circuit.
+   * The circuit must have only ClassicalEvalOp operations, such as
+   * ClassicalTransform, SetBits, CopyBits, predicates and MultiBitOp. The keys
+   * of the input map must correspond to the bits of the circuit.
    *
    * @param values input values
    * @return output values
@@ -0,0 +1,64 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <vector>
+
+#include "Circuit.hpp"
+#include "Utils/EigenConfig.hpp"
+
+namespace tket {
+
+/**
+ * Construct a multiplexed (uniformly-controlled) rotation.
+ *
+ * For a vector of 2^k angles, the returned circuit acts on k+1 qubits. For
+ * each basis state |j> of qubits 1, ..., k (in \ref BasisOrder::ilo) it
+ * applies a rotation of type \p type by angle `angles[j]` to qubit 0.
+ *
+ * The circuit consists of rotations on qubit 0 and at most 2^k CX gates with
+ * target qubit 0. Rotations with zero angle are omitted, and if all the
+ * angles are equal no CX gates are used.
+ *
+ * @param type OpType::Ry or OpType::Rz
+ * @param angles rotation angles in half-turns
+ *
+ * @return circuit implementing the multiplexed rotation
+ */
+Circuit multiplexed_rotation(OpType type, const std::vector<double> &angles);
+
+/**
+ * Synthesise a circuit from an arbitrary 2^n x 2^n unitary, using the
+ * quantum Shannon decomposition of Shende, Bullock and Markov,
+ * https://arxiv.org/abs/quant-ph/0406176
+ *
+ * The unitary is split recursively by cosine-sine decomposition and
+ * demultiplexing into four unitaries on n-1 qubits and three multiplexed
+ * rotations, which together use 3*2^(n-1) - 1 CX gates. Unitaries on three
+ * qubits are synthesised with \ref three_qubit_synthesis and unitaries on two
+ * qubits with \ref two_qubit_canonical. Multiplexors that turn out to be
+ * trivial (such as block-diagonal factors or uncontrolled rotations) are
+ * detected and synthesised without CX gates.
+ *
+ * The returned circuit consists of CX, TK2 and 1-qubit gates only. The TK2
+ * gates may be decomposed further using `decompose_TK2`.
+ *
+ * @param U unitary matrix in \ref BasisOrder::ilo
+ *
+ * @return circuit implementing the unitary
+ */
+Circuit quantum_shannon_synthesis(const Eigen::MatrixXcd &U);
+
+}  // namespace tket
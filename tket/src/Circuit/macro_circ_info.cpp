@@ -624,7 +624,8 @@ SliceVec Circuit::get_reverse_slices() const {
 unsigned Circuit::depth() const {
   unsigned count = 0;
   std::function<bool(Op_ptr)> skip_func = [&](Op_ptr op) {
-    return (op->get_type() == OpType::Barrier);
+    return (
+        op->get_type() == OpType::Barrier || op->get_type() == OpType::Delay);
   };
   Circuit::SliceIterator slice_iter(*this, skip_func);
   if (!(*slice_iter).empty()) count++;
@@ -677,33 +677,35 @@ std::map<Bit, bool> Circuit::classical_eval(
     if (!is_classical_type(optype)) {
       throw CircuitInvalidity("Non-classical operation");
     }
+    unit_vector_t args = it->get_args();
+    // a MultiBitOp applies its op to consecutive blocks of arguments
+    unsigned n_blocks = 1;
+    if (optype == OpType::MultiBit) {
+      const MultiBitOp& multi_op = static_cast<const MultiBitOp&>(*op);
+      n_blocks = multi_op.get_n();
+      op = multi_op.get_op();
+    }
     std::shared_ptr<const ClassicalEvalOp> cop =
         std::dynamic_pointer_cast<const ClassicalEvalOp>(op);
-    unit_vector_t args = it->get_args();
-    unsigned n_args = args.size();
-    switch (optype) {
-      case OpType::ClassicalTransform: {
-        std::vector<bool> input(n_args);
-        for (unsigned i = 0; i < n_args; i++) {
-          input[i] = v[Bit(args[i])];
-        }
-        std::vector<bool> output = cop->eval(input);
-        TKET_ASSERT(output.size() == n_args);
-        for (unsigned i = 0; i < n_args; i++) {
-          v[Bit(args[i])] = output[i];
-        }
-        break;
+    if (!cop) {
+      throw CircuitInvalidity("Unexpected operation in circuit");
+    }
+    // arguments are ordered as input-only, input/output, output-only
+    unsigned n_i = cop->get_n_i();
+    unsigned n_inputs = n_i + cop->get_n_io();
+    unsigned block_size = n_inputs + cop->get_n_o();
+    TKET_ASSERT(args.size() == n_blocks * block_size);
+    for (unsigned b = 0; b < n_blocks; b++) {
+      unsigned offset = b * block_size;
+      std::vector<bool> input(n_inputs);
+      for (unsigned i = 0; i < n_inputs; i++) {
+        input[i] = v[Bit(args[offset + i])];
       }
-      case OpType::SetBits: {
-        std::vector<bool> output = cop->eval({});
-        TKET_ASSERT(output.size() == n_args);
-        for (unsigned i = 0; i < n_args; i++) {
-          v[Bit(args[i])] = output[i];
-        }
-        break;
+      std::vector<bool> output = cop->eval(input);
+      TKET_ASSERT(output.size() == block_size - n_i);
+      for (unsigned i = 0; i < output.size(); i++) {
+        v[Bit(args[offset + n_i + i])] = output[i];
       }
-      default:
-        throw CircuitInvalidity("Unexpected operation in circuit");
     }
   }
   return v;
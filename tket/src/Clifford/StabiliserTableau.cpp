@@ -0,0 +1,107 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "StabiliserTableau.hpp"
+
+#include "tkassert/Assert.hpp"
+
+namespace tket {
+
+StabiliserTableau::StabiliserTableau(unsigned n) : tab_({}), n_qubits_(n) {
+  MatrixXb xmat(2 * n, n);
+  xmat << MatrixXb::Identity(n, n), MatrixXb::Zero(n, n);
+  MatrixXb zmat(2 * n, n);
+  zmat << MatrixXb::Zero(n, n), MatrixXb::Identity(n, n);
+  tab_ = SymplecticTableau(xmat, zmat, VectorXb::Zero(2 * n));
+}
+
+unsigned StabiliserTableau::get_n_qubits() const { return n_qubits_; }
+
+PauliStabiliserList StabiliserTableau::get_stabilisers() const {
+  PauliStabiliserList stabs;
+  for (unsigned i = 0; i < n_qubits_; ++i) {
+    stabs.push_back(tab_.get_pauli(n_qubits_ + i));
+  }
+  return stabs;
+}
+
+void StabiliserTableau::apply_gate(
+    OpType type, const std::vector<unsigned> &qbs) {
+  tab_.apply_gate(type, qbs);
+}
+
+std::optional<bool> StabiliserTableau::get_deterministic_outcome(
+    unsigned qb) const {
+  for (unsigned i = n_qubits_; i < 2 * n_qubits_; ++i) {
+    // A stabilizer anticommutes with Z on qb
+    if (tab_.xmat_(i, qb)) return std::nullopt;
+  }
+  /*
+   * Z on qb commutes with every stabilizer, so it is (up to sign) the product
+   * of the stabilizers whose destabilizers anticommute with it. Accumulate
+   * this product to read off the sign.
+   */
+  MatrixXb xrow = MatrixXb::Zero(1, n_qubits_);
+  MatrixXb zrow = MatrixXb::Zero(1, n_qubits_);
+  Complex phase = 1.;
+  for (unsigned i = 0; i < n_qubits_; ++i) {
+    if (!tab_.xmat_(i, qb)) continue;
+    unsigned r = n_qubits_ + i;
+    if (tab_.phase_(r)) phase *= -1.;
+    for (unsigned q = 0; q < n_qubits_; ++q) {
+      std::pair<BoolPauli, Complex> res = BoolPauli::mult_lut.at(
+          {{xrow(0, q), zrow(0, q)}, {tab_.xmat_(r, q), tab_.zmat_(r, q)}});
+      xrow(0, q) = res.first.x;
+      zrow(0, q) = res.first.z;
+      phase *= res.second;
+    }
+  }
+  TKET_ASSERT(phase == 1. || phase == -1.);
+  return phase == -1.;
+}
+
+bool StabiliserTableau::measure(unsigned qb, bool random_outcome) {
+  unsigned p = n_qubits_;
+  while (p < 2 * n_qubits_ && !tab_.xmat_(p, qb)) ++p;
+  if (p == 2 * n_qubits_) {
+    return get_deterministic_outcome(qb).value();
+  }
+  /*
+   * Stabilizer p anticommutes with Z on qb. Multiply it into every other row
+   * that anticommutes with Z on qb, then replace its destabilizer by it and
+   * replace it by +-Z on qb.
+   */
+  for (unsigned i = 0; i < 2 * n_qubits_; ++i) {
+    if (i != p && tab_.xmat_(i, qb)) {
+      tab_.row_mult(p, i);
+    }
+  }
+  unsigned d = p - n_qubits_;
+  tab_.xmat_.row(d) = tab_.xmat_.row(p);
+  tab_.zmat_.row(d) = tab_.zmat_.row(p);
+  tab_.phase_(d) = tab_.phase_(p);
+  tab_.xmat_.row(p).setZero();
+  tab_.zmat_.row(p).setZero();
+  tab_.zmat_(p, qb) = true;
+  tab_.phase_(p) = random_outcome;
+  return random_outcome;
+}
+
+void StabiliserTableau::reset(unsigned qb, bool random_outcome) {
+  if (measure(qb, random_outcome)) {
+    tab_.apply_gate(OpType::X, {qb});
+  }
+}
+
+}  // namespace tket
@@ -0,0 +1,100 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <optional>
+
+#include "SymplecticTableau.hpp"
+
+namespace tket {
+
+class StabiliserTableau {
+  /**
+   * The stabilizer-destabilizer tableau of a stabilizer state, following
+   * Aaronson & Gottesman, "Improved Simulation of Stabilizer Circuits",
+   * https://arxiv.org/pdf/quant-ph/0406196.pdf
+   *
+   * The layout matches UnitaryTableau: rows 0-(n-1) are the destabilizers
+   * and rows n-(2n-1) are the stabilizers. Unlike UnitaryTableau, the state
+   * may be measured, which projects it onto the observed outcome. Phases of
+   * the destabilizer rows carry no information and are not maintained.
+   *
+   * Qubits are indexed by unsigneds.
+   */
+ public:
+  /**
+   * Construct the tableau for the state |0>^n.
+   */
+  explicit StabiliserTableau(unsigned n);
+
+  /**
+   * Other required constructors
+   */
+  StabiliserTableau(const StabiliserTableau &other) = default;
+  StabiliserTableau(StabiliserTableau &&other) = default;
+  StabiliserTableau &operator=(const StabiliserTableau &other) = default;
+  StabiliserTableau &operator=(StabiliserTableau &&other) = default;
+
+  unsigned get_n_qubits() const;
+
+  /**
+   * Read off the generators of the stabilizer group of the state
+   */
+  PauliStabiliserList get_stabilisers() const;
+
+  /**
+   * Applies a Clifford gate to the state. Accepts the same gates as
+   * SymplecticTableau::apply_gate.
+   */
+  void apply_gate(OpType type, const std::vector<unsigned> &qbs);
+
+  /**
+   * The outcome of measuring the qubit in the Z basis, if it is determined
+   * by the state; std::nullopt if both outcomes have probability 1/2.
+   * Takes time O(N^2) for N qubits.
+   */
+  std::optional<bool> get_deterministic_outcome(unsigned qb) const;
+
+  /**
+   * Measure the qubit in the Z basis, projecting the state onto the
+   * outcome.
+   *
+   * @param qb The qubit to measure
+   * @param random_outcome The outcome to project onto if the outcome is not
+   * determined by the state
+   *
+   * @return The outcome of the measurement
+   */
+  bool measure(unsigned qb, bool random_outcome);
+
+  /**
+   * Measure the qubit as with measure() and then flip it to |0>.
+   */
+  void reset(unsigned qb, bool random_outcome);
+
+ private:
+  /**
+   * The actual binary tableau.
+   * Rows 0-(n-1) are the destabilizers, n-(2n-1) are the stabilizers.
+   */
+  SymplecticTableau tab_;
+
+  /**
+   * Number of qubits
+   */
+  unsigned n_qubits_;
+};
+
+}  // namespace tket
@@ -22,6 +22,7 @@ namespace tket {
 
 // Forward declare friend UnitaryTableau and Circuit for converters
 class UnitaryTableau;
+class StabiliserTableau;
 class Circuit;
 
 /**
@@ -188,6 +189,7 @@ class SymplecticTableau {
       MatrixXb::ColXpr &w, VectorXb &pw);
 
   friend class UnitaryTableau;
+  friend class StabiliserTableau;
   friend Circuit unitary_tableau_to_circuit(const UnitaryTableau &tab);
   friend std::ostream &operator<<(std::ostream &os, const UnitaryTableau &tab);
 
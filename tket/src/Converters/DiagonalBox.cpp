@@ -0,0 +1,146 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "DiagonalBox.hpp"
+
+#include <boost/bimap.hpp>
+#include <cmath>
+#include <list>
+#include <optional>
+
+#include "Circuit/Circuit.hpp"
+#include "Ops/OpJsonFactory.hpp"
+#include "Utils/Constants.hpp"
+#include "Utils/Json.hpp"
+
+namespace tket {
+
+DiagonalBox::DiagonalBox(const std::vector<Expr> &phases)
+    : Box(OpType::DiagonalBox), phases_(phases) {
+  n_qubits_ = 0;
+  while ((1u << n_qubits_) < phases_.size()) n_qubits_++;
+  if (phases_.size() != (1u << n_qubits_) || n_qubits_ == 0) {
+    throw CircuitInvalidity(
+        "Number of phases for a DiagonalBox must be a power of 2 (at least "
+        "2)");
+  }
+  signature_ = op_signature_t(n_qubits_, EdgeType::Quantum);
+}
+
+DiagonalBox::DiagonalBox(const DiagonalBox &other)
+    : Box(other), phases_(other.phases_), n_qubits_(other.n_qubits_) {}
+
+Op_ptr DiagonalBox::symbol_substitution(
+    const SymEngine::map_basic_basic &sub_map) const {
+  std::vector<Expr> new_phases;
+  for (const Expr &p : phases_) new_phases.push_back(p.subs(sub_map));
+  return std::make_shared<DiagonalBox>(new_phases);
+}
+
+SymSet DiagonalBox::free_symbols() const { return expr_free_symbols(phases_); }
+
+Eigen::MatrixXcd DiagonalBox::get_unitary() const {
+  Eigen::VectorXcd diag(phases_.size());
+  for (unsigned j = 0; j < phases_.size(); j++) {
+    std::optional<double> p = eval_expr(phases_[j]);
+    if (!p) throw SymbolsNotSupported();
+    diag(j) = std::exp(i_ * PI * p.value());
+  }
+  return diag.asDiagonal();
+}
+
+Op_ptr DiagonalBox::dagger() const {
+  std::vector<Expr> new_phases;
+  for (const Expr &p : phases_) new_phases.push_back(-p);
+  return std::make_shared<DiagonalBox>(new_phases);
+}
+
+Op_ptr DiagonalBox::transpose() const {
+  return std::make_shared<DiagonalBox>(*this);
+}
+
+std::vector<Expr> DiagonalBox::walsh_coefficients() const {
+  const unsigned N = phases_.size();
+  std::vector<Expr> coeffs = phases_;
+  for (unsigned h = 1; h < N; h *= 2) {
+    for (unsigned i = 0; i < N; i += 2 * h) {
+      for (unsigned j = i; j < i + h; j++) {
+        Expr a = coeffs[j], b = coeffs[j + h];
+        coeffs[j] = a + b;
+        coeffs[j + h] = a - b;
+      }
+    }
+  }
+  for (Expr &c : coeffs) c = c / N;
+  return coeffs;
+}
+
+PhasePolynomial DiagonalBox::get_phase_polynomial() const {
+  // With coefficients c_k from the Walsh-Hadamard transform, the phase on
+  // basis state |j> is the sum over k of c_k (-1)^{j.k}. For k > 0 this is
+  // implemented by Rz(-2 c_k) on the parity of the qubits in k.
+  const std::vector<Expr> coeffs = walsh_coefficients();
+  PhasePolynomial poly;
+  for (unsigned k = 1; k < coeffs.size(); k++) {
+    Expr angle = -2 * coeffs[k];
+    if (approx_0(angle)) continue;
+    std::vector<bool> parity(n_qubits_);
+    for (unsigned q = 0; q < n_qubits_; q++) {
+      parity[q] = (k >> (n_qubits_ - 1 - q)) & 1;
+    }
+    poly.insert({parity, angle});
+  }
+  return poly;
+}
+
+Expr DiagonalBox::get_global_phase() const {
+  return walsh_coefficients()[0];
+}
+
+PhasePolyBox DiagonalBox::to_phase_poly_box() const {
+  boost::bimap<Qubit, unsigned> qubit_indices;
+  for (unsigned q = 0; q < n_qubits_; q++) {
+    qubit_indices.insert({Qubit(q), q});
+  }
+  return PhasePolyBox(
+      n_qubits_, qubit_indices, get_phase_polynomial(),
+      MatrixXb::Identity(n_qubits_, n_qubits_));
+}
+
+void DiagonalBox::generate_circuit() const {
+  const PhasePolynomial poly = get_phase_polynomial();
+  std::list<phase_term_t> terms(poly.begin(), poly.end());
+  Circuit circ =
+      gray_synth(n_qubits_, terms, MatrixXb::Identity(n_qubits_, n_qubits_));
+  circ.add_phase(get_global_phase());
+  circ_ = std::make_shared<Circuit>(circ);
+}
+
+nlohmann::json DiagonalBox::to_json(const Op_ptr &op) {
+  const auto &box = static_cast<const DiagonalBox &>(*op);
+  nlohmann::json j = core_box_json(box);
+  j["phases"] = box.get_phases();
+  return j;
+}
+
+Op_ptr DiagonalBox::from_json(const nlohmann::json &j) {
+  DiagonalBox box = DiagonalBox(j.at("phases").get<std::vector<Expr>>());
+  return set_box_id(
+      box,
+      boost::lexical_cast<boost::uuids::uuid>(j.at("id").get<std::string>()));
+}
+
+REGISTER_OPFACTORY(DiagonalBox, DiagonalBox)
+
+}  // namespace tket
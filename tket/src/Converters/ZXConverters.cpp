@@ -28,8 +28,8 @@ typedef std::vector<ZXVertPort> ZXVertPortVec;
 typedef boost::bimap<ZXVert, Vertex> BoundaryVertMap;
 
 bool is_spiderless_optype(const OpType& optype) {
-  return optype == OpType::Barrier || optype == OpType::SWAP ||
-         optype == OpType::noop;
+  return optype == OpType::Barrier || optype == OpType::Delay ||
+         optype == OpType::SWAP || optype == OpType::noop;
 }
 
 // Add a swicth with the on-state controlled by the on_value.
@@ -158,6 +158,7 @@ BoundaryVertMap circuit_to_zx_recursive(
       }
       // Spiderless ops are handled during vertex wiring
       case OpType::Barrier:
+      case OpType::Delay:
       case OpType::noop:
       case OpType::SWAP: {
         continue;
@@ -686,6 +687,73 @@ static void bipartite_complementation(
   }
 }
 
+// Local complementation about an XZ vertex, moving it into the XY plane. The
+// neighbours pick up a Z rotation by -pi/2, which exchanges the XZ and YZ
+// planes and the X and Y axes of their measurements.
+static void xz_local_complementation(ZXDiagram& diag, const ZXVert& v) {
+  ZXVertVec ns;
+  for (const ZXVert& n : diag.neighbours(v)) {
+    if (!is_boundary_type(diag.get_zxtype(n))) ns.push_back(n);
+  }
+  for (ZXVertVec::iterator it = ns.begin(); it != ns.end(); ++it) {
+    for (ZXVertVec::iterator jt = std::next(it); jt != ns.end(); ++jt) {
+      std::optional<Wire> wire = diag.wire_between(*it, *jt);
+      if (wire)
+        diag.remove_wire(*wire);
+      else
+        diag.add_wire(*it, *jt, ZXWireType::H);
+    }
+  }
+  const PhasedGen& v_gen = diag.get_vertex_ZXGen<PhasedGen>(v);
+  diag.set_vertex_ZXGen_ptr(
+      v, ZXGen::create_gen(
+             ZXType::XY, v_gen.get_param() - 0.5, QuantumType::Quantum));
+  for (const ZXVert& n : ns) {
+    ZXGen_ptr new_gen;
+    switch (diag.get_zxtype(n)) {
+      case ZXType::XY: {
+        const PhasedGen& ph_gen = diag.get_vertex_ZXGen<PhasedGen>(n);
+        new_gen = ZXGen::create_gen(
+            ZXType::XY, ph_gen.get_param() - 0.5, QuantumType::Quantum);
+        break;
+      }
+      case ZXType::XZ: {
+        const PhasedGen& ph_gen = diag.get_vertex_ZXGen<PhasedGen>(n);
+        new_gen = ZXGen::create_gen(
+            ZXType::YZ, -ph_gen.get_param(), QuantumType::Quantum);
+        break;
+      }
+      case ZXType::YZ: {
+        const PhasedGen& ph_gen = diag.get_vertex_ZXGen<PhasedGen>(n);
+        new_gen = ZXGen::create_gen(
+            ZXType::XZ, ph_gen.get_param(), QuantumType::Quantum);
+        break;
+      }
+      case ZXType::PX: {
+        const CliffordGen& cl_gen = diag.get_vertex_ZXGen<CliffordGen>(n);
+        new_gen = ZXGen::create_gen(
+            ZXType::PY, !cl_gen.get_param(), QuantumType::Quantum);
+        break;
+      }
+      case ZXType::PY: {
+        const CliffordGen& cl_gen = diag.get_vertex_ZXGen<CliffordGen>(n);
+        new_gen = ZXGen::create_gen(
+            ZXType::PX, cl_gen.get_param(), QuantumType::Quantum);
+        break;
+      }
+      case ZXType::PZ: {
+        new_gen = diag.get_vertex_ZXGen_ptr(n);
+        break;
+      }
+      default:
+        throw ZXError(
+            "Error during extraction from ZX diagram: unexpected ZXType "
+            "during local complementation");
+    }
+    diag.set_vertex_ZXGen_ptr(n, new_gen);
+  }
+}
+
 void extend_if_input(
     ZXDiagram& diag, const ZXVert& v, std::map<ZXVert, ZXVert>& input_qubits) {
   std::map<ZXVert, ZXVert>::iterator found = input_qubits.find(v);
@@ -722,7 +790,22 @@ bool remove_all_gadgets(
           "adjacent to an output");
     ZXVert o = *found_output;
     for (const ZXVert& n : f_ns) {
-      if (diag.get_zxtype(n) == ZXType::YZ) {
+      ZXType n_type = diag.get_zxtype(n);
+      if (n_type == ZXType::XZ) {
+        // Move n into the XY plane, after which it is extracted as usual
+        extend_if_input(diag, n, input_qubits);
+        xz_local_complementation(diag, n);
+        removed_gadget = true;
+        break;
+      }
+      if (n_type == ZXType::YZ || n_type == ZXType::PZ) {
+        Expr n_angle;
+        if (n_type == ZXType::PZ) {
+          // A Z measurement is a YZ measurement with angle 0 or pi
+          n_angle = diag.get_vertex_ZXGen<CliffordGen>(n).get_param() ? 1. : 0.;
+        } else {
+          n_angle = diag.get_vertex_ZXGen<PhasedGen>(n).get_param();
+        }
         // Pivot
         // Identify three subsets of neighbours
         ZXVertSeqSet excl_f;
@@ -761,10 +844,8 @@ bool remove_all_gadgets(
             ow, (diag.get_wire_type(ow) == ZXWireType::Basic)
                     ? ZXWireType::H
                     : ZXWireType::Basic);
-        const PhasedGen& yz_gen = diag.get_vertex_ZXGen<PhasedGen>(n);
         diag.set_vertex_ZXGen_ptr(
-            n, ZXGen::create_gen(
-                   ZXType::XY, -yz_gen.get_param(), QuantumType::Quantum));
+            n, ZXGen::create_gen(ZXType::XY, -n_angle, QuantumType::Quantum));
         for (const ZXVert& nn : joint.get<TagSeq>()) {
           ZXGen_ptr new_gen;
           switch (diag.get_zxtype(nn)) {
@@ -816,6 +897,9 @@ Circuit zx_to_circuit(const ZXDiagram& d) {
   ZXVertVec outs = diag.get_boundary(ZXType::Output);
   if (ins.size() != outs.size())
     throw ZXError("Can only extract a circuit from a unitary ZX diagram");
+  // Fail early with the vertices that cannot be corrected, rather than part
+  // way through extraction
+  Flow::identify_gflow(diag);
 
   Circuit circ(ins.size());
 
@@ -835,9 +919,12 @@ Circuit zx_to_circuit(const ZXDiagram& d) {
 
   clean_frontier(diag, frontier, circ, qubit_map);
   while (!frontier.empty()) {
-    if (remove_all_gadgets(diag, frontier, input_qubits)) {
+    // Each pivot or local complementation moves a vertex into the XY plane,
+    // so this terminates
+    while (remove_all_gadgets(diag, frontier, input_qubits)) {
       clean_frontier(diag, frontier, circ, qubit_map);
     }
+    if (frontier.empty()) break;
     ZXVertSeqSet neighbours = neighbours_of_frontier(diag, frontier);
     boost::bimap<ZXVert, unsigned> correctors, preserve, ys;
     ZXVertVec to_solve;
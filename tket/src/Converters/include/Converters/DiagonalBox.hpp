@@ -0,0 +1,115 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <vector>
+
+#include "Circuit/Boxes.hpp"
+#include "PhasePoly.hpp"
+#include "Utils/EigenConfig.hpp"
+#include "Utils/Expression.hpp"
+
+namespace tket {
+
+/**
+ * A diagonal unitary, specified by its phase on each computational basis
+ * state.
+ *
+ * The phases are converted to a \ref PhasePolynomial using the
+ * Walsh-Hadamard transform, which is then synthesised using \ref gray_synth.
+ * The box may instead be lowered to a \ref PhasePolyBox, for example to make
+ * use of architecture-aware synthesis.
+ */
+class DiagonalBox : public Box {
+ public:
+  /**
+   * Construct from a list of phases
+   *
+   * The box maps each basis state |j> to e^{i pi phases[j]} |j>.
+   *
+   * @param phases phases in half-turns, one for each basis state in
+   * \ref BasisOrder::ilo, so of size 2^n for n qubits
+   */
+  explicit DiagonalBox(const std::vector<Expr> &phases);
+
+  /**
+   * Copy constructor
+   */
+  DiagonalBox(const DiagonalBox &other);
+
+  ~DiagonalBox() override {}
+
+  Op_ptr symbol_substitution(
+      const SymEngine::map_basic_basic &sub_map) const override;
+
+  SymSet free_symbols() const override;
+
+  /**
+   * Equality check between two DiagonalBox instances
+   */
+  bool is_equal(const Op &op_other) const override {
+    const DiagonalBox &other = dynamic_cast<const DiagonalBox &>(op_other);
+    return id_ == other.get_id();
+  }
+
+  /**
+   * @throws SymbolsNotSupported if any of the phases are symbolic
+   */
+  Eigen::MatrixXcd get_unitary() const override;
+
+  Op_ptr dagger() const override;
+
+  Op_ptr transpose() const override;
+
+  const std::vector<Expr> &get_phases() const { return phases_; }
+
+  /**
+   * The phase polynomial implementing the box, up to global phase
+   *
+   * Each qubit is indexed by its position in the box.
+   */
+  PhasePolynomial get_phase_polynomial() const;
+
+  /**
+   * The global phase (in half-turns) not accounted for by
+   * \ref get_phase_polynomial
+   */
+  Expr get_global_phase() const;
+
+  /**
+   * Lower the box to a \ref PhasePolyBox, which is equal to it up to the
+   * global phase given by \ref get_global_phase
+   */
+  PhasePolyBox to_phase_poly_box() const;
+
+  static Op_ptr from_json(const nlohmann::json &j);
+
+  static nlohmann::json to_json(const Op_ptr &op);
+
+ protected:
+  void generate_circuit() const override;
+
+ private:
+  /**
+   * Walsh-Hadamard transform of the phases, scaled so that the phase on
+   * basis state |j> is the sum over k of (-1)^{j.k} times entry k
+   */
+  std::vector<Expr> walsh_coefficients() const;
+
+  const std::vector<Expr> phases_;
+  unsigned n_qubits_;
+};
+
+}  // namespace tket
@@ -48,7 +48,8 @@ Op_ptr Gate::dagger() const {
     case OpType::noop:
     case OpType::CSWAP:
     case OpType::ECR:
-    case OpType::BRIDGE: {
+    case OpType::BRIDGE:
+    case OpType::Transport: {
       return get_op_ptr(optype);
     }
     case OpType::CnX: {
@@ -184,6 +185,7 @@ Op_ptr Gate::transpose() const {
     case OpType::noop:
     case OpType::CSWAP:
     case OpType::BRIDGE:
+    case OpType::Transport:
     case OpType::S:
     case OpType::Sdg:
     case OpType::T:
@@ -115,6 +115,11 @@ static Eigen::MatrixXcd get_unitary_or_throw(
     CASE_RETURN_0P(Sycamore)
     CASE_RETURN_0P(ISWAPMax)
 #undef CASE_RETURN_0P
+    case OpType::Transport:
+      // acts on the two sites as a SWAP
+      GateUnitaryMatrixUtils::check_and_throw_upon_wrong_number_of_parameters(
+          op_type, number_of_qubits, parameters, 0);
+      return GateUnitaryMatrixImplementations::SWAP();
     CASE_RETURN_1P(Rx)
     CASE_RETURN_1P(Ry)
     CASE_RETURN_1P(Rz)
@@ -0,0 +1,334 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/ExactRoutingMethod.hpp"
+
+#include <algorithm>
+#include <chrono>
+#include <functional>
+#include <limits>
+#include <optional>
+#include <queue>
+
+#include "Architecture/ArchitectureMapping.hpp"
+#include "Architecture/DistancesFromArchitecture.hpp"
+#include "Circuit/Conditional.hpp"
+#include "Mapping/LexiRouteRoutingMethod.hpp"
+
+namespace tket {
+
+static const unsigned UNSET = std::numeric_limits<unsigned>::max();
+
+namespace {
+
+// gate acting on two or more qubits, indexed from 0
+struct ExactGate {
+  std::vector<unsigned> qubits;
+  // false for barriers, which can be passed wherever the qubits are
+  bool adjacent;
+};
+
+// The vertex of each qubit, followed by the number of gates passed on each
+// qubit. Gates are passed as soon as they can be, which never needs more
+// SWAP gates later, so these determine the rest of the search.
+typedef std::vector<unsigned> SearchState;
+
+struct SearchNode {
+  SearchState state;
+  unsigned n_swaps;
+  // index of the node this was reached from, UNSET for the root
+  unsigned parent;
+  std::pair<unsigned, unsigned> swap;
+};
+
+// A* search for the fewest SWAP gates routing a sequence of gates
+class ExactSearch {
+ public:
+  ExactSearch(
+      const std::vector<ExactGate>& gates, unsigned n_qubits,
+      const ArchitectureMapping& arch_mapping)
+      : gates_(gates), n_qubits_(n_qubits), qubit_gates_(n_qubits) {
+    for (unsigned g = 0; g < gates.size(); g++) {
+      for (unsigned q : gates[g].qubits) {
+        qubit_gates_[q].push_back(g);
+      }
+    }
+    unsigned n_vertices = arch_mapping.number_of_vertices();
+    DistancesFromArchitecture distances(arch_mapping);
+    distances_.assign(n_vertices, std::vector<unsigned>(n_vertices, 0));
+    for (unsigned v0 = 0; v0 < n_vertices; v0++) {
+      for (unsigned v1 = v0 + 1; v1 < n_vertices; v1++) {
+        distances_[v0][v1] = distances_[v1][v0] = distances(v0, v1);
+      }
+    }
+    std::set<std::pair<unsigned, unsigned>> edges;
+    for (const Swap& edge : arch_mapping.get_edges()) {
+      edges.insert(
+          {std::min(edge.first, edge.second),
+           std::max(edge.first, edge.second)});
+    }
+    edges_.assign(edges.begin(), edges.end());
+  }
+
+  /**
+   * SWAP gates between vertices routing the gates from the given vertices of
+   * the qubits, or nullopt if the search takes longer than the given time.
+   */
+  std::optional<std::vector<std::pair<unsigned, unsigned>>> solve(
+      const std::vector<unsigned>& initial_vertices,
+      std::chrono::milliseconds timeout) {
+    auto start = std::chrono::steady_clock::now();
+    SearchState root = initial_vertices;
+    root.resize(2 * n_qubits_, 0);
+    pass_gates(root);
+
+    // nodes are taken in order of estimated total cost, then heuristic
+    typedef std::tuple<unsigned, unsigned, unsigned> Entry;
+    std::priority_queue<Entry, std::vector<Entry>, std::greater<Entry>> queue;
+    std::vector<SearchNode> nodes = {{root, 0, UNSET, {UNSET, UNSET}}};
+    std::map<SearchState, unsigned> fewest_swaps = {{root, 0}};
+    unsigned root_h = heuristic(root);
+    queue.push({root_h, root_h, 0});
+
+    while (!queue.empty()) {
+      if (std::chrono::steady_clock::now() - start >= timeout) {
+        return std::nullopt;
+      }
+      unsigned index = std::get<2>(queue.top());
+      queue.pop();
+      SearchNode node = nodes[index];
+      if (fewest_swaps[node.state] < node.n_swaps) {
+        continue;
+      }
+      if (finished(node.state)) {
+        std::vector<std::pair<unsigned, unsigned>> swaps;
+        for (unsigned i = index; nodes[i].parent != UNSET;
+             i = nodes[i].parent) {
+          swaps.push_back(nodes[i].swap);
+        }
+        std::reverse(swaps.begin(), swaps.end());
+        return swaps;
+      }
+
+      std::vector<unsigned> occupant(distances_.size(), UNSET);
+      for (unsigned q = 0; q < n_qubits_; q++) {
+        occupant[node.state[q]] = q;
+      }
+      for (const std::pair<unsigned, unsigned>& edge : edges_) {
+        unsigned q0 = occupant[edge.first];
+        unsigned q1 = occupant[edge.second];
+        if (q0 == UNSET && q1 == UNSET) continue;
+        SearchState state = node.state;
+        if (q0 != UNSET) state[q0] = edge.second;
+        if (q1 != UNSET) state[q1] = edge.first;
+        pass_gates(state);
+        unsigned n_swaps = node.n_swaps + 1;
+        auto it = fewest_swaps.find(state);
+        if (it != fewest_swaps.end() && it->second <= n_swaps) continue;
+        fewest_swaps[state] = n_swaps;
+        unsigned h = heuristic(state);
+        queue.push({n_swaps + h, h, unsigned(nodes.size())});
+        nodes.push_back({state, n_swaps, index, edge});
+      }
+    }
+    // only reached if some gate is between disconnected vertices
+    return std::nullopt;
+  }
+
+ private:
+  // next gate to pass on a qubit, or UNSET if all have been passed
+  unsigned next_gate(const SearchState& state, unsigned q) const {
+    unsigned n_passed = state[n_qubits_ + q];
+    return n_passed < qubit_gates_[q].size() ? qubit_gates_[q][n_passed]
+                                             : UNSET;
+  }
+
+  // whether a gate is next on all of its qubits
+  bool in_front(const SearchState& state, unsigned g) const {
+    for (unsigned q : gates_[g].qubits) {
+      if (next_gate(state, q) != g) return false;
+    }
+    return true;
+  }
+
+  void pass_gates(SearchState& state) const {
+    bool progress = true;
+    while (progress) {
+      progress = false;
+      for (unsigned q = 0; q < n_qubits_; q++) {
+        unsigned g = next_gate(state, q);
+        if (g == UNSET || !in_front(state, g)) continue;
+        const ExactGate& gate = gates_[g];
+        if (gate.adjacent &&
+            distances_[state[gate.qubits[0]]][state[gate.qubits[1]]] != 1) {
+          continue;
+        }
+        for (unsigned p : gate.qubits) {
+          state[n_qubits_ + p]++;
+        }
+        progress = true;
+      }
+    }
+  }
+
+  bool finished(const SearchState& state) const {
+    for (unsigned q = 0; q < n_qubits_; q++) {
+      if (next_gate(state, q) != UNSET) return false;
+    }
+    return true;
+  }
+
+  // Each SWAP gate moves two qubits, so brings the qubits of at most two
+  // gates in the front layer one step closer together.
+  unsigned heuristic(const SearchState& state) const {
+    unsigned total = 0, largest = 0;
+    for (unsigned q = 0; q < n_qubits_; q++) {
+      unsigned g = next_gate(state, q);
+      if (g == UNSET || gates_[g].qubits[0] != q || !gates_[g].adjacent ||
+          !in_front(state, g)) {
+        continue;
+      }
+      unsigned d =
+          distances_[state[gates_[g].qubits[0]]][state[gates_[g].qubits[1]]] -
+          1;
+      total += d;
+      largest = std::max(largest, d);
+    }
+    return std::max(largest, (total + 1) / 2);
+  }
+
+  const std::vector<ExactGate>& gates_;
+  unsigned n_qubits_;
+  // gates on each qubit, in order
+  std::vector<std::vector<unsigned>> qubit_gates_;
+  std::vector<std::vector<unsigned>> distances_;
+  std::vector<std::pair<unsigned, unsigned>> edges_;
+};
+
+}  // namespace
+
+static bool routable(const Op_ptr& op) {
+  OpType ot = op->get_type();
+  if (ot == OpType::Conditional) {
+    return routable(static_cast<const Conditional&>(*op).get_op());
+  }
+  return !is_box_type(ot);
+}
+
+ExactRoutingMethod::ExactRoutingMethod(
+    unsigned _timeout, unsigned _max_depth, unsigned _max_size,
+    unsigned _lookahead)
+    : timeout_(_timeout),
+      max_depth_(_max_depth),
+      max_size_(_max_size),
+      lookahead_(_lookahead) {}
+
+std::pair<bool, unit_map_t> ExactRoutingMethod::routing_method(
+    MappingFrontier_ptr& mapping_frontier,
+    const ArchitecturePtr& architecture) const {
+  Subcircuit frontier_subcircuit = mapping_frontier->get_frontier_subcircuit(
+      this->max_depth_, this->max_size_);
+  Circuit frontier_circuit =
+      mapping_frontier->circuit_.subcircuit(frontier_subcircuit);
+  frontier_circuit.rename_units(
+      mapping_frontier->get_default_to_linear_boundary_unit_map());
+
+  ArchitectureMapping arch_mapping(*architecture);
+  std::map<UnitID, unsigned> qubit_index;
+  std::vector<unsigned> initial_vertices;
+  std::vector<ExactGate> gates;
+  // qubits of gates which can't be routed, and of any later gates
+  std::set<UnitID> blocked;
+  bool all_placed = true;
+  for (const Command& com : frontier_circuit.get_commands()) {
+    qubit_vector_t args = com.get_qubits();
+    if (args.size() < 2) continue;
+    Op_ptr op = com.get_op_ptr();
+    bool is_barrier = op->get_type() == OpType::Barrier;
+    bool is_blocked = !routable(op) || (args.size() > 2 && !is_barrier);
+    for (const Qubit& qb : args) {
+      is_blocked |= blocked.find(qb) != blocked.end();
+    }
+    if (is_blocked) {
+      blocked.insert(args.begin(), args.end());
+      continue;
+    }
+    ExactGate gate{{}, !is_barrier};
+    for (const Qubit& qb : args) {
+      auto it = qubit_index.find(qb);
+      if (it == qubit_index.end()) {
+        if (!architecture->node_exists(Node(qb))) {
+          all_placed = false;
+          break;
+        }
+        it = qubit_index.insert({qb, initial_vertices.size()}).first;
+        initial_vertices.push_back(arch_mapping.get_vertex(Node(qb)));
+      }
+      gate.qubits.push_back(it->second);
+    }
+    if (!all_placed) break;
+    gates.push_back(gate);
+  }
+
+  std::optional<std::vector<std::pair<unsigned, unsigned>>> swaps;
+  if (all_placed && !gates.empty()) {
+    ExactSearch search(gates, initial_vertices.size(), arch_mapping);
+    swaps = search.solve(
+        initial_vertices, std::chrono::milliseconds(this->timeout_));
+  }
+  if (!swaps) {
+    return LexiRouteRoutingMethod(this->lookahead_)
+        .routing_method(mapping_frontier, architecture);
+  }
+
+  // the frontier passes gates as soon as they can be, as in the search, so
+  // the SWAP gates are added in between as they were found
+  bool modified = false;
+  for (const std::pair<unsigned, unsigned>& swap : *swaps) {
+    mapping_frontier->advance_frontier_boundary(architecture);
+    if (!mapping_frontier->add_swap(
+            arch_mapping.get_node(swap.first),
+            arch_mapping.get_node(swap.second))) {
+      break;
+    }
+    modified = true;
+  }
+  return {modified, {}};
+}
+
+unsigned ExactRoutingMethod::get_timeout() const { return this->timeout_; }
+
+unsigned ExactRoutingMethod::get_max_depth() const { return this->max_depth_; }
+
+unsigned ExactRoutingMethod::get_max_size() const { return this->max_size_; }
+
+unsigned ExactRoutingMethod::get_lookahead() const { return this->lookahead_; }
+
+nlohmann::json ExactRoutingMethod::serialize() const {
+  nlohmann::json j;
+  j["timeout"] = this->get_timeout();
+  j["depth"] = this->get_max_depth();
+  j["size"] = this->get_max_size();
+  j["lookahead"] = this->get_lookahead();
+  j["name"] = "ExactRoutingMethod";
+  return j;
+}
+
+ExactRoutingMethod ExactRoutingMethod::deserialize(const nlohmann::json& j) {
+  return ExactRoutingMethod(
+      j.at("timeout").get<unsigned>(), j.at("depth").get<unsigned>(),
+      j.at("size").get<unsigned>(), j.at("lookahead").get<unsigned>());
+}
+
+}  // namespace tket
@@ -466,14 +466,21 @@ void MappingFrontier::update_bimaps(UnitID qubit, UnitID node) {
   auto init_it = this->bimaps_->initial.left.find(qubit);
   if (init_it == this->bimaps_->initial.left.end())
     throw MappingFrontierError("Qubit not found in initial map.");
+  UnitID uid = init_it->second;
   this->bimaps_->initial.left.erase(init_it);
   this->bimaps_->initial.left.insert({qubit, node});
-  // Update final map
-  auto final_it = this->bimaps_->final.left.find(qubit);
-  if (final_it == this->bimaps_->final.left.end())
-    throw MappingFrontierError("Qubit not found in final map.");
-  this->bimaps_->final.left.erase(final_it);
-  this->bimaps_->final.left.insert({qubit, node});
+  // Update final map, in which the wire of a reused qubit ends with the
+  // qubit reusing it
+  UnitID final_qubit = qubit;
+  if (this->bimaps_->final.left.find(qubit) ==
+      this->bimaps_->final.left.end()) {
+    auto reused_it = this->bimaps_->final.right.find(uid);
+    if (reused_it == this->bimaps_->final.right.end())
+      throw MappingFrontierError("Qubit not found in final map.");
+    final_qubit = reused_it->second;
+  }
+  this->bimaps_->final.left.erase(final_qubit);
+  this->bimaps_->final.left.insert({final_qubit, node});
 }
 
 void MappingFrontier::update_linear_boundary_uids(
@@ -567,7 +574,8 @@ void MappingFrontier::set_linear_boundary(
  * linear_boundary This directly modifies circuit_ Updates linear_boundary to
  * reflect new edges
  */
-bool MappingFrontier::add_swap(const UnitID& uid_0, const UnitID& uid_1) {
+bool MappingFrontier::add_swap(
+    const UnitID& uid_0, const UnitID& uid_1, OpType swap_type) {
   // get iterators to linear_boundary uids
   auto uid0_in_it = this->linear_boundary->find(uid_0);
   auto uid1_in_it = this->linear_boundary->find(uid_1);
@@ -611,16 +619,18 @@ bool MappingFrontier::add_swap(const UnitID& uid_0, const UnitID& uid_1) {
   // implies a rut has been hit In which case return false that SWAP can't be
   // added Can safely do this check here after relabelling as adding/updating
   // ancillas => fresh SWAP
+  // Other gate types are inserted to follow a planned path, which may
+  // legitimately move a qubit back, so are not checked
   Vertex source0 = this->circuit_.source(predecessors[0]);
   Vertex source1 = this->circuit_.source(predecessors[1]);
-  if (source0 == source1) {
+  if (swap_type == OpType::SWAP && source0 == source1) {
     if (this->circuit_.get_OpType_from_Vertex(source0) == OpType::SWAP) {
       return false;
     }
   }
 
   // add SWAP vertex to circuit_ and rewire into predecessor
-  Vertex swap_v = this->circuit_.add_vertex(OpType::SWAP);
+  Vertex swap_v = this->circuit_.add_vertex(swap_type);
   this->circuit_.rewire(
       swap_v, predecessors, {EdgeType::Quantum, EdgeType::Quantum});
 
@@ -14,7 +14,16 @@
 
 #include "Mapping/MappingManager.hpp"
 
+#include <algorithm>
+#include <atomic>
+#include <exception>
+#include <symengine/symengine_config.h>
+#include <thread>
+
 #include "Architecture/BestTsaWithArch.hpp"
+#include "Architecture/ShuttlingArchitecture.hpp"
+#include "Mapping/QubitReuse.hpp"
+#include "Mapping/RoutingPartitions.hpp"
 
 namespace tket {
 
@@ -23,15 +32,22 @@ MappingManager::MappingManager(const ArchitecturePtr& _architecture)
 
 bool MappingManager::route_circuit(
     Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
-    bool label_isolated_qubits) const {
+    bool label_isolated_qubits, bool reuse_freed_qubits) const {
   return this->route_circuit_with_maps(
       circuit, routing_methods, std::make_shared<unit_bimaps_t>(),
-      label_isolated_qubits);
+      label_isolated_qubits, reuse_freed_qubits);
 }
 
 bool MappingManager::route_circuit_with_maps(
     Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
-    std::shared_ptr<unit_bimaps_t> maps, bool label_isolated_qubits) const {
+    std::shared_ptr<unit_bimaps_t> maps, bool label_isolated_qubits,
+    bool reuse_freed_qubits) const {
+  bool qubits_reused = false;
+  if (reuse_freed_qubits &&
+      circuit.n_qubits() > this->architecture_->n_nodes()) {
+    qubits_reused =
+        !reuse_qubits(circuit, this->architecture_->n_nodes(), maps).empty();
+  }
   if (circuit.n_qubits() > this->architecture_->n_nodes()) {
     std::string error_string =
         "Circuit has" + std::to_string(circuit.n_qubits()) +
@@ -68,7 +84,7 @@ bool MappingManager::route_circuit_with_maps(
     return true;
   };
 
-  bool circuit_modified = !check_finish();
+  bool circuit_modified = qubits_reused || !check_finish();
   while (!check_finish()) {
     // The order methods are passed in std::vector<RoutingMethod> is
     // the order they are run
@@ -81,11 +97,14 @@ bool MappingManager::route_circuit_with_maps(
           rm->routing_method(mapping_frontier, this->architecture_);
       if (bool_map.first) {
         valid_methods = true;
-        if (bool_map.second.size() > 0) {
-          std::map<Node, Node> node_map;
-          for (const auto& x : bool_map.second) {
+        std::map<Node, Node> node_map;
+        for (const auto& x : bool_map.second) {
+          // qubits already on their nodes need no permutation
+          if (x.first != x.second) {
             node_map.insert({Node(x.first), Node(x.second)});
           }
+        }
+        if (node_map.size() > 0) {
           for (const std::pair<Node, Node>& swap :
                BestTsaWithArch::get_swaps(*this->architecture_, node_map)) {
             mapping_frontier->add_swap(swap.first, swap.second);
@@ -159,4 +178,116 @@ bool MappingManager::route_circuit_with_maps(
 
   return circuit_modified;
 }
-}  // namespace tket
\ No newline at end of file
+
+bool MappingManager::route_circuit_in_partitions(
+    Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
+    std::shared_ptr<unit_bimaps_t> maps, unsigned max_threads,
+    bool label_isolated_qubits) const {
+  auto route_whole = [&]() {
+    return this->route_circuit_with_maps(
+        circuit, routing_methods, maps, label_isolated_qubits);
+  };
+  // routing on a ShuttlingArchitecture needs its zones
+  if (std::dynamic_pointer_cast<ShuttlingArchitecture>(this->architecture_)) {
+    return route_whole();
+  }
+  for (const Qubit& qb : circuit.all_qubits()) {
+    if (this->architecture_->node_exists(Node(qb))) return route_whole();
+  }
+  std::vector<unit_vector_t> components = get_interaction_components(circuit);
+  std::vector<unsigned> sizes;
+  for (const unit_vector_t& units : components) {
+    sizes.push_back(std::count_if(
+        units.begin(), units.end(),
+        [](const UnitID& unit) { return unit.type() == UnitType::Qubit; }));
+  }
+  if (std::count_if(sizes.begin(), sizes.end(), [](unsigned size) {
+        return size > 0;
+      }) < 2) {
+    return route_whole();
+  }
+  std::optional<std::vector<node_vector_t>> subarchitectures =
+      get_disjoint_subarchitectures(*this->architecture_, sizes);
+  if (!subarchitectures) return route_whole();
+
+  // split the circuit and the maps of its qubits between the groups
+  unsigned n_components = components.size();
+  std::vector<Circuit> subcircuits;
+  std::vector<std::shared_ptr<unit_bimaps_t>> submaps;
+  for (const unit_vector_t& units : components) {
+    subcircuits.push_back(get_interaction_subcircuit(circuit, units));
+    std::shared_ptr<unit_bimaps_t> sub = std::make_shared<unit_bimaps_t>();
+    for (const UnitID& unit : units) {
+      auto init_it = maps->initial.right.find(unit);
+      if (init_it != maps->initial.right.end()) {
+        sub->initial.insert({init_it->second, unit});
+      }
+      auto final_it = maps->final.right.find(unit);
+      if (final_it != maps->final.right.end()) {
+        sub->final.insert({final_it->second, unit});
+      }
+    }
+    submaps.push_back(sub);
+  }
+
+  // each thread routes the next unrouted subcircuit until none are left
+  std::vector<unsigned> modified(n_components, 0);
+  std::vector<std::exception_ptr> errors(n_components);
+  std::atomic<unsigned> next_component = 0;
+  auto route_subcircuits = [&]() {
+    for (unsigned i = next_component++; i < n_components;
+         i = next_component++) {
+      if (sizes[i] == 0) continue;
+      try {
+        MappingManager mm(std::make_shared<Architecture>(
+            this->architecture_->create_subarch((*subarchitectures)[i])));
+        modified[i] = mm.route_circuit_with_maps(
+            subcircuits[i], routing_methods, submaps[i],
+            label_isolated_qubits);
+      } catch (...) {
+        errors[i] = std::current_exception();
+      }
+    }
+  };
+  unsigned n_threads = (max_threads == 0)
+                           ? std::thread::hardware_concurrency()
+                           : max_threads;
+  n_threads = std::clamp(n_threads, 1u, n_components);
+#ifndef WITH_SYMENGINE_THREAD_SAFE
+  // the operations of the subcircuits share the SymEngine expressions of
+  // their parameters, whose reference counts are only atomic if SymEngine is
+  // built thread-safe
+  n_threads = 1;
+#endif
+  std::vector<std::thread> threads;
+  for (unsigned t = 1; t < n_threads; t++) {
+    threads.emplace_back(route_subcircuits);
+  }
+  route_subcircuits();
+  for (std::thread& thread : threads) thread.join();
+  for (const std::exception_ptr& error : errors) {
+    if (error) std::rethrow_exception(error);
+  }
+
+  // combine the routed subcircuits and their maps in order
+  Circuit routed;
+  std::optional<std::string> name = circuit.get_name();
+  if (name) routed.set_name(*name);
+  routed.add_phase(circuit.get_phase());
+  for (unsigned i = 0; i < n_components; i++) {
+    routed.append(subcircuits[i]);
+    for (const auto& pair : submaps[i]->initial) {
+      maps->initial.left.erase(pair.left);
+      maps->initial.insert({pair.left, pair.right});
+    }
+    for (const auto& pair : submaps[i]->final) {
+      maps->final.left.erase(pair.left);
+      maps->final.insert({pair.left, pair.right});
+    }
+  }
+  circuit = routed;
+  return std::any_of(
+      modified.begin(), modified.end(), [](unsigned m) { return m != 0; });
+}
+
+}  // namespace tket
@@ -0,0 +1,411 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/NoiseAwareRoutingMethod.hpp"
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <memory>
+#include <optional>
+#include <set>
+#include <tuple>
+
+#include "Circuit/Conditional.hpp"
+#include "OpType/OpTypeFunctions.hpp"
+
+namespace tket {
+
+// errors are capped below 1 so that costs are finite
+static const double MAX_ERROR = 1. - 1e-12;
+// added to the cost of each SWAP so that, among equally reliable paths, the
+// shortest is preferred, and so that distances are still used when no errors
+// are given
+static const double SWAP_PENALTY = 1e-6;
+// weight of each layer of two-qubit gates relative to the previous one
+static const double LOOKAHEAD_DECAY = 0.5;
+
+static double error_cost(double error) {
+  return -std::log1p(-std::min(error, MAX_ERROR));
+}
+
+NoiseAwareCosts::NoiseAwareCosts(
+    const Architecture& architecture,
+    const DeviceCharacterisation& characterisation)
+    : nodes_(architecture.get_all_nodes_vec()) {
+  const unsigned n = nodes_.size();
+  const double inf = std::numeric_limits<double>::infinity();
+  std::vector<double> node_costs(n);
+  for (unsigned i = 0; i < n; i++) {
+    index_.insert({nodes_[i], i});
+    node_costs[i] = error_cost(characterisation.get_error(nodes_[i]));
+    readout_costs_.push_back(
+        error_cost(characterisation.get_readout_error(nodes_[i])));
+  }
+
+  // a two-qubit gate may be performed in either direction along a connection,
+  // using single-qubit gates on both nodes
+  gate_costs_.assign(n, std::vector<double>(n, inf));
+  std::vector<std::pair<unsigned, unsigned>> edges;
+  for (const Architecture::Connection& link :
+       architecture.get_all_edges_vec()) {
+    unsigned i = index_.at(link.first);
+    unsigned j = index_.at(link.second);
+    double cost = error_cost(characterisation.get_error(link)) +
+                  node_costs[i] + node_costs[j];
+    if (gate_costs_[i][j] == inf) {
+      edges.push_back({i, j});
+      edges.push_back({j, i});
+    }
+    gate_costs_[i][j] = gate_costs_[j][i] = std::min(gate_costs_[i][j], cost);
+  }
+
+  // a SWAP is implemented as three two-qubit gates
+  swap_costs_.assign(n, std::vector<double>(n, inf));
+  path_costs_.assign(n, std::vector<double>(n, inf));
+  next_.assign(n, std::vector<unsigned>(n, n));
+  for (unsigned i = 0; i < n; i++) {
+    path_costs_[i][i] = 0.;
+    next_[i][i] = i;
+  }
+  for (const std::pair<unsigned, unsigned>& e : edges) {
+    swap_costs_[e.first][e.second] =
+        3 * gate_costs_[e.first][e.second] + SWAP_PENALTY;
+    path_costs_[e.first][e.second] = swap_costs_[e.first][e.second];
+    next_[e.first][e.second] = e.second;
+  }
+  // Floyd-Warshall
+  for (unsigned k = 0; k < n; k++) {
+    for (unsigned i = 0; i < n; i++) {
+      for (unsigned j = 0; j < n; j++) {
+        double via_k = path_costs_[i][k] + path_costs_[k][j];
+        if (via_k < path_costs_[i][j]) {
+          path_costs_[i][j] = via_k;
+          next_[i][j] = next_[i][k];
+        }
+      }
+    }
+  }
+
+  // both qubits may be moved before interacting them on some connection
+  interaction_costs_.assign(n, std::vector<double>(n, inf));
+  for (unsigned i = 0; i < n; i++) {
+    interaction_costs_[i][i] = 0.;
+    for (unsigned j = i + 1; j < n; j++) {
+      double best = inf;
+      for (const std::pair<unsigned, unsigned>& e : edges) {
+        best = std::min(
+            best, path_costs_[i][e.first] + path_costs_[j][e.second] +
+                      gate_costs_[e.first][e.second]);
+      }
+      interaction_costs_[i][j] = interaction_costs_[j][i] = best;
+    }
+  }
+}
+
+double NoiseAwareCosts::swap_cost(const Node& n0, const Node& n1) const {
+  return swap_costs_[index_.at(n0)][index_.at(n1)];
+}
+
+double NoiseAwareCosts::gate_cost(const Node& n0, const Node& n1) const {
+  return gate_costs_[index_.at(n0)][index_.at(n1)];
+}
+
+double NoiseAwareCosts::interaction_cost(const Node& n0, const Node& n1) const {
+  return interaction_costs_[index_.at(n0)][index_.at(n1)];
+}
+
+double NoiseAwareCosts::readout_cost(const Node& n) const {
+  return readout_costs_[index_.at(n)];
+}
+
+Node NoiseAwareCosts::next_step(const Node& from, const Node& to) const {
+  unsigned next = next_[index_.at(from)][index_.at(to)];
+  TKET_ASSERT(next < nodes_.size());
+  return nodes_[next];
+}
+
+NoiseAwareRoutingMethod::NoiseAwareRoutingMethod(
+    const DeviceCharacterisation& characterisation, unsigned max_depth)
+    : characterisation_(characterisation), max_depth_(max_depth) {}
+
+std::shared_ptr<const NoiseAwareCosts> NoiseAwareRoutingMethod::get_costs(
+    const ArchitecturePtr& architecture) const {
+  std::shared_ptr<const cache_t> cache = std::atomic_load(&this->cache_);
+  if (!cache || cache->first != architecture) {
+    cache = std::make_shared<const cache_t>(
+        architecture, std::make_shared<const NoiseAwareCosts>(
+                          *architecture, this->characterisation_));
+    std::atomic_store(&this->cache_, cache);
+  }
+  return cache->second;
+}
+
+namespace {
+struct Interaction {
+  Node first;
+  Node second;
+  Vertex vertex;
+};
+}  // namespace
+
+static bool is_cx_vertex(const Circuit& circ, const Vertex& v) {
+  Op_ptr op = circ.get_Op_ptr_from_Vertex(v);
+  if (op->get_type() == OpType::Conditional) {
+    op = static_cast<const Conditional&>(*op).get_op();
+  }
+  return op->get_type() == OpType::CX;
+}
+
+/**
+ * Two-qubit gates immediately after the boundary of the mapping frontier.
+ * If placed_only is false, returns nullopt if some gate is a box or acts on a
+ * qubit not placed on the architecture; otherwise such gates are ignored.
+ */
+static std::optional<std::vector<Interaction>> boundary_interactions(
+    const MappingFrontier& mapping_frontier, const Architecture& architecture,
+    bool placed_only) {
+  const Circuit& circ = mapping_frontier.circuit_;
+  std::vector<std::pair<Vertex, std::vector<UnitID>>> gates;
+  for (const std::pair<UnitID, VertPort>& pair :
+       mapping_frontier.linear_boundary->get<TagKey>()) {
+    Vertex v = circ.target(
+        circ.get_nth_out_edge(pair.second.first, pair.second.second));
+    if (circ.get_OpType_from_Vertex(v) == OpType::Barrier ||
+        circ.n_in_edges_of_type(v, EdgeType::Quantum) != 2) {
+      continue;
+    }
+    auto it = std::find_if(
+        gates.begin(), gates.end(),
+        [&v](const std::pair<Vertex, std::vector<UnitID>>& gate) {
+          return gate.first == v;
+        });
+    if (it == gates.end()) {
+      gates.push_back({v, {pair.first}});
+    } else {
+      it->second.push_back(pair.first);
+    }
+  }
+  std::vector<Interaction> interactions;
+  for (const std::pair<Vertex, std::vector<UnitID>>& gate : gates) {
+    if (gate.second.size() != 2) continue;
+    Node n0(gate.second[0]), n1(gate.second[1]);
+    if (!architecture.node_exists(n0) || !architecture.node_exists(n1) ||
+        circ.get_Op_ptr_from_Vertex(gate.first)->get_desc().is_box()) {
+      if (placed_only) continue;
+      return std::nullopt;
+    }
+    interactions.push_back({n0, n1, gate.first});
+  }
+  return interactions;
+}
+
+std::pair<bool, unit_map_t> NoiseAwareRoutingMethod::routing_method(
+    MappingFrontier_ptr& mapping_frontier,
+    const ArchitecturePtr& architecture) const {
+  std::optional<std::vector<Interaction>> first_layer =
+      boundary_interactions(*mapping_frontier, *architecture, false);
+  if (!first_layer || first_layer->empty()) {
+    return {false, {}};
+  }
+  std::shared_ptr<const NoiseAwareCosts> cached_costs =
+      this->get_costs(architecture);
+  const NoiseAwareCosts& costs = *cached_costs;
+  const Circuit& circ = mapping_frontier->circuit_;
+
+  // find the following layers of two-qubit gates, then restore the boundary
+  unit_vertport_frontier_t copy;
+  for (const std::pair<UnitID, VertPort>& pair :
+       mapping_frontier->linear_boundary->get<TagKey>()) {
+    copy.insert({pair.first, pair.second});
+  }
+  std::vector<std::vector<std::pair<Node, Node>>> layers(1);
+  for (const Interaction& interaction : *first_layer) {
+    layers[0].push_back({interaction.first, interaction.second});
+  }
+  for (unsigned depth = 1; depth < this->max_depth_; depth++) {
+    mapping_frontier->advance_next_2qb_slice(this->max_depth_);
+    std::vector<Interaction> interactions =
+        *boundary_interactions(*mapping_frontier, *architecture, true);
+    std::vector<std::pair<Node, Node>> layer;
+    for (const Interaction& interaction : interactions) {
+      layer.push_back({interaction.first, interaction.second});
+    }
+    if (layer.empty() || layer == layers.back()) break;
+    layers.push_back(layer);
+  }
+  mapping_frontier->set_linear_boundary(copy);
+
+  // qubits to be measured within the lookahead, on their current nodes
+  std::vector<Node> measured;
+  for (const std::pair<UnitID, VertPort>& pair :
+       mapping_frontier->linear_boundary->get<TagKey>()) {
+    Node node(pair.first);
+    if (!architecture->node_exists(node)) continue;
+    Edge e = circ.get_nth_out_edge(pair.second.first, pair.second.second);
+    for (unsigned depth = 0; depth < this->max_depth_; depth++) {
+      Vertex v = circ.target(e);
+      OpType ot = circ.get_OpType_from_Vertex(v);
+      if (ot == OpType::Measure) {
+        measured.push_back(node);
+        break;
+      }
+      if (is_final_q_type(ot)) break;
+      e = circ.get_next_edge(v, e);
+    }
+  }
+
+  // costs after a SWAP, given as a pair of equal nodes for no SWAP
+  auto permute = [](const Node& n, const std::pair<Node, Node>& swap) {
+    if (n == swap.first) return swap.second;
+    if (n == swap.second) return swap.first;
+    return n;
+  };
+  auto layer_cost = [&](const std::vector<std::pair<Node, Node>>& layer,
+                        const std::pair<Node, Node>& swap) {
+    double cost = 0.;
+    for (const std::pair<Node, Node>& p : layer) {
+      cost += costs.interaction_cost(
+          permute(p.first, swap), permute(p.second, swap));
+    }
+    return cost;
+  };
+  auto total_cost = [&](const std::pair<Node, Node>& swap) {
+    double cost = 0., weight = 1.;
+    for (const std::vector<std::pair<Node, Node>>& layer : layers) {
+      cost += weight * layer_cost(layer, swap);
+      weight *= LOOKAHEAD_DECAY;
+    }
+    for (const Node& node : measured) {
+      cost += costs.readout_cost(permute(node, swap));
+    }
+    return cost;
+  };
+
+  // candidate SWAPs move a qubit of the first layer; only those making the
+  // first layer cheaper are considered, so that routing cannot cycle
+  const std::pair<Node, Node> no_swap = {
+      first_layer->front().first, first_layer->front().first};
+  const double current_first_cost = layer_cost(layers[0], no_swap);
+  std::set<std::pair<Node, Node>> candidate_swaps;
+  for (const Interaction& interaction : *first_layer) {
+    for (const Node& node : {interaction.first, interaction.second}) {
+      for (const Node& neighbour : architecture->nodes_at_distance(node, 1)) {
+        if (neighbour == interaction.first || neighbour == interaction.second) {
+          continue;
+        }
+        candidate_swaps.insert(
+            {std::min(node, neighbour), std::max(node, neighbour)});
+      }
+    }
+  }
+  std::optional<std::pair<Node, Node>> best_swap;
+  double best_cost = std::numeric_limits<double>::infinity();
+  for (const std::pair<Node, Node>& swap : candidate_swaps) {
+    if (!(layer_cost(layers[0], swap) < current_first_cost)) continue;
+    double cost = costs.swap_cost(swap.first, swap.second) + total_cost(swap);
+    if (cost < best_cost) {
+      best_cost = cost;
+      best_swap = swap;
+    }
+  }
+
+  // a CX between nodes at distance 2 may instead be replaced by a BRIDGE
+  std::optional<std::tuple<Node, Node, Node>> best_bridge;
+  const double current_cost = total_cost(no_swap);
+  for (const Interaction& interaction : *first_layer) {
+    if (architecture->get_distance(interaction.first, interaction.second) !=
+            2 ||
+        !is_cx_vertex(circ, interaction.vertex)) {
+      continue;
+    }
+    for (const Node& central :
+         architecture->nodes_at_distance(interaction.first, 1)) {
+      if (architecture->get_distance(central, interaction.second) != 1) {
+        continue;
+      }
+      double cost =
+          2 * costs.gate_cost(interaction.first, central) +
+          2 * costs.gate_cost(central, interaction.second) + current_cost -
+          costs.interaction_cost(interaction.first, interaction.second);
+      if (cost < best_cost) {
+        best_cost = cost;
+        best_bridge =
+            std::make_tuple(interaction.first, central, interaction.second);
+      }
+    }
+  }
+
+  if (best_bridge) {
+    const auto& [n0, central, n1] = *best_bridge;
+    // the control of the BRIDGE is the qubit on the lower port of the CX
+    auto target_port = [&](const Node& n) {
+      VertPort vp = mapping_frontier->linear_boundary->find(n)->second;
+      return circ.get_target_port(circ.get_nth_out_edge(vp.first, vp.second));
+    };
+    if (target_port(n0) < target_port(n1)) {
+      mapping_frontier->add_bridge(n0, central, n1);
+    } else {
+      mapping_frontier->add_bridge(n1, central, n0);
+    }
+    return {true, {}};
+  }
+  if (best_swap && mapping_frontier->add_swap(
+                       best_swap->first, best_swap->second)) {
+    return {true, {}};
+  }
+
+  // no single SWAP improves the first layer, so move the qubits of its
+  // cheapest interaction together along the most reliable path
+  auto cheapest = std::min_element(
+      first_layer->begin(), first_layer->end(),
+      [&costs](const Interaction& a, const Interaction& b) {
+        return costs.interaction_cost(a.first, a.second) <
+               costs.interaction_cost(b.first, b.second);
+      });
+  Node from = cheapest->first;
+  const Node& to = cheapest->second;
+  while (architecture->get_distance(from, to) > 1) {
+    Node next = costs.next_step(from, to);
+    mapping_frontier->add_swap(from, next);
+    from = next;
+  }
+  return {true, {}};
+}
+
+const DeviceCharacterisation& NoiseAwareRoutingMethod::get_characterisation()
+    const {
+  return this->characterisation_;
+}
+
+unsigned NoiseAwareRoutingMethod::get_max_depth() const {
+  return this->max_depth_;
+}
+
+nlohmann::json NoiseAwareRoutingMethod::serialize() const {
+  nlohmann::json j;
+  j["characterisation"] = this->get_characterisation();
+  j["depth"] = this->get_max_depth();
+  j["name"] = "NoiseAwareRoutingMethod";
+  return j;
+}
+
+NoiseAwareRoutingMethod NoiseAwareRoutingMethod::deserialize(
+    const nlohmann::json& j) {
+  return NoiseAwareRoutingMethod(
+      j.at("characterisation").get<DeviceCharacterisation>(),
+      j.at("depth").get<unsigned>());
+}
+
+}  // namespace tket
@@ -0,0 +1,156 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/QubitReuse.hpp"
+
+#include <optional>
+
+namespace tket {
+
+// vertex of the last operation on a qubit
+static Vertex last_vertex(const Circuit& circ, const Qubit& qb) {
+  return circ.source(circ.get_nth_in_edge(circ.get_out(qb), 0));
+}
+
+// vertex of the first operation on a qubit
+static Vertex first_vertex(const Circuit& circ, const Qubit& qb) {
+  return circ.target(circ.get_nth_out_edge(circ.get_in(qb), 0));
+}
+
+static bool is_freed(const Circuit& circ, const Qubit& qb) {
+  return circ.is_discarded(qb) ||
+         circ.get_OpType_from_Vertex(last_vertex(circ, qb)) == OpType::Measure;
+}
+
+static bool is_fresh(const Circuit& circ, const Qubit& qb) {
+  return circ.is_created(qb) ||
+         circ.get_OpType_from_Vertex(first_vertex(circ, qb)) == OpType::Reset;
+}
+
+// whether there is a path in the DAG from one vertex to the other
+static bool reachable(
+    const Circuit& circ, const Vertex& from, const Vertex& to) {
+  std::set<Vertex> visited = {from};
+  std::vector<Vertex> to_visit = {from};
+  while (!to_visit.empty()) {
+    Vertex v = to_visit.back();
+    to_visit.pop_back();
+    if (v == to) return true;
+    for (const Vertex& succ : circ.get_successors(v)) {
+      if (visited.insert(succ).second) {
+        to_visit.push_back(succ);
+      }
+    }
+  }
+  return false;
+}
+
+// append the wire of a fresh qubit to the wire of a freed qubit
+static void merge_wires(Circuit& circ, const Qubit& freed, const Qubit& fresh) {
+  Vertex freed_in = circ.get_in(freed);
+  Vertex freed_out = circ.get_out(freed);
+  Vertex fresh_in = circ.get_in(fresh);
+  Vertex fresh_out = circ.get_out(fresh);
+
+  Edge freed_edge = circ.get_nth_in_edge(freed_out, 0);
+  Vertex freed_last = circ.source(freed_edge);
+  port_t freed_port = circ.get_source_port(freed_edge);
+  Edge fresh_edge = circ.get_nth_out_edge(fresh_in, 0);
+  Vertex fresh_first = circ.target(fresh_edge);
+  port_t fresh_port = circ.get_target_port(fresh_edge);
+
+  circ.remove_edge(freed_edge);
+  circ.remove_edge(fresh_edge);
+  if (circ.get_OpType_from_Vertex(fresh_first) == OpType::Reset) {
+    circ.add_edge(
+        {freed_last, freed_port}, {fresh_first, fresh_port},
+        EdgeType::Quantum);
+  } else {
+    Vertex reset = circ.add_vertex(OpType::Reset);
+    circ.add_edge({freed_last, freed_port}, {reset, 0}, EdgeType::Quantum);
+    circ.add_edge({reset, 0}, {fresh_first, fresh_port}, EdgeType::Quantum);
+  }
+
+  // the merged wire keeps the input of the freed qubit and the output of the
+  // fresh one
+  circ.boundary.get<TagID>().erase(freed);
+  circ.boundary.get<TagID>().erase(fresh);
+  circ.boundary.get<TagID>().insert({freed, freed_in, fresh_out});
+  circ.dag[freed_out].op = get_op_ptr(OpType::noop);
+  circ.dag[fresh_in].op = get_op_ptr(OpType::noop);
+  circ.remove_vertex(
+      freed_out, Circuit::GraphRewiring::No, Circuit::VertexDeletion::Yes);
+  circ.remove_vertex(
+      fresh_in, Circuit::GraphRewiring::No, Circuit::VertexDeletion::Yes);
+}
+
+unit_map_t reuse_qubits(
+    Circuit& circ, unsigned max_qubits, std::shared_ptr<unit_bimaps_t> maps) {
+  unit_map_t merged;
+  while (circ.n_qubits() > max_qubits) {
+    // longest path from the inputs to each vertex
+    std::map<Vertex, int> depth;
+    for (const Vertex& v : circ.vertices_in_order()) {
+      int d = 0;
+      for (const Vertex& pred : circ.get_predecessors(v)) {
+        d = std::max(d, depth[pred] + 1);
+      }
+      depth[v] = d;
+    }
+
+    std::vector<Qubit> freed, fresh;
+    for (const Qubit& qb : circ.all_qubits()) {
+      if (is_freed(circ, qb)) freed.push_back(qb);
+      if (is_fresh(circ, qb)) fresh.push_back(qb);
+    }
+    std::optional<std::pair<Qubit, Qubit>> best;
+    int best_slack = 0;
+    for (const Qubit& a : freed) {
+      Vertex a_last = last_vertex(circ, a);
+      for (const Qubit& b : fresh) {
+        if (a == b) continue;
+        Vertex b_first = first_vertex(circ, b);
+        int slack = depth[b_first] - depth[a_last];
+        if (best && slack <= best_slack) continue;
+        // b can't start after a ends if something on b leads to a
+        if (reachable(circ, b_first, a_last)) continue;
+        best = {a, b};
+        best_slack = slack;
+      }
+    }
+    if (!best) break;
+
+    const Qubit& a = best->first;
+    const Qubit& b = best->second;
+    merge_wires(circ, a, b);
+    for (std::pair<const UnitID, UnitID>& pair : merged) {
+      if (pair.second == b) pair.second = a;
+    }
+    merged.insert({b, a});
+    if (maps) {
+      auto b_it = maps->initial.right.find(b);
+      if (b_it != maps->initial.right.end()) {
+        UnitID b_q = b_it->second;
+        maps->initial.right.erase(b_it);
+        // b now ends where the wire of a does, and a no longer ends anywhere
+        maps->final.left.erase(b_q);
+        maps->final.right.erase(a);
+        maps->final.insert({b_q, a});
+      }
+    }
+  }
+  return merged;
+}
+
+}  // namespace tket
@@ -50,6 +50,18 @@ void from_json(const nlohmann::json& j, std::vector<RoutingMethodPtr>& rmp_v) {
     } else if (name == "MultiGateReorderRoutingMethod") {
       rmp_v.push_back(std::make_shared<MultiGateReorderRoutingMethod>(
           MultiGateReorderRoutingMethod::deserialize(c)));
+    } else if (name == "NoiseAwareRoutingMethod") {
+      rmp_v.push_back(std::make_shared<NoiseAwareRoutingMethod>(
+          NoiseAwareRoutingMethod::deserialize(c)));
+    } else if (name == "SabreRoutingMethod") {
+      rmp_v.push_back(std::make_shared<SabreRoutingMethod>(
+          SabreRoutingMethod::deserialize(c)));
+    } else if (name == "ShuttlingRoutingMethod") {
+      rmp_v.push_back(std::make_shared<ShuttlingRoutingMethod>(
+          ShuttlingRoutingMethod::deserialize(c)));
+    } else if (name == "ExactRoutingMethod") {
+      rmp_v.push_back(std::make_shared<ExactRoutingMethod>(
+          ExactRoutingMethod::deserialize(c)));
     } else if (name == "BoxDecompositionRoutingMethod") {
       rmp_v.push_back(std::make_shared<BoxDecompositionRoutingMethod>(
           BoxDecompositionRoutingMethod::deserialize(c)));
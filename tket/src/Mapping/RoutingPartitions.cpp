@@ -0,0 +1,169 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/RoutingPartitions.hpp"
+
+#include <algorithm>
+#include <map>
+#include <numeric>
+#include <set>
+
+#include "Graphs/AdjacencyData.hpp"
+#include "Graphs/GraphRoutines.hpp"
+
+namespace tket {
+
+std::vector<unit_vector_t> get_interaction_components(const Circuit& circ) {
+  unit_vector_t units;
+  for (const Qubit& qb : circ.all_qubits()) units.push_back(qb);
+  for (const Bit& bit : circ.all_bits()) units.push_back(bit);
+  std::sort(units.begin(), units.end());
+  std::map<UnitID, std::size_t> index;
+  for (std::size_t i = 0; i < units.size(); i++) index.insert({units[i], i});
+
+  // join the first argument of each operation to all its other arguments
+  std::map<std::size_t, std::vector<std::size_t>> raw_data;
+  for (const Command& com : circ) {
+    unit_vector_t args = com.get_args();
+    std::size_t first = index.at(args[0]);
+    for (unsigned i = 1; i < args.size(); i++) {
+      std::size_t other = index.at(args[i]);
+      if (other != first) raw_data[first].push_back(other);
+    }
+  }
+  graphs::AdjacencyData adjacency(raw_data, units.size());
+
+  std::vector<unit_vector_t> components;
+  for (const std::set<std::size_t>& component :
+       graphs::GraphRoutines::get_connected_components(adjacency)) {
+    unit_vector_t group;
+    for (std::size_t i : component) group.push_back(units[i]);
+    components.push_back(group);
+  }
+  std::sort(
+      components.begin(), components.end(),
+      [](const unit_vector_t& a, const unit_vector_t& b) {
+        return a.front() < b.front();
+      });
+  return components;
+}
+
+Circuit get_interaction_subcircuit(
+    const Circuit& circ, const unit_vector_t& units) {
+  Circuit sub;
+  std::set<UnitID> unit_set(units.begin(), units.end());
+  for (const UnitID& unit : units) {
+    if (unit.type() == UnitType::Qubit) {
+      Qubit qb(unit);
+      sub.add_qubit(qb);
+      if (circ.is_created(qb)) sub.qubit_create(qb);
+      if (circ.is_discarded(qb)) sub.qubit_discard(qb);
+    } else {
+      sub.add_bit(Bit(unit));
+    }
+  }
+  for (const Command& com : circ) {
+    unit_vector_t args = com.get_args();
+    if (unit_set.find(args[0]) == unit_set.end()) continue;
+    sub.add_op<UnitID>(com.get_op_ptr(), args, com.get_opgroup());
+  }
+  return sub;
+}
+
+// connected components of the subgraph of an Architecture on some nodes
+static std::vector<std::set<Node>> connected_regions(
+    const Architecture& architecture, const std::set<Node>& nodes) {
+  std::vector<Node> node_list(nodes.begin(), nodes.end());
+  std::map<Node, std::size_t> index;
+  for (std::size_t i = 0; i < node_list.size(); i++) {
+    index.insert({node_list[i], i});
+  }
+  std::map<std::size_t, std::vector<std::size_t>> raw_data;
+  for (std::size_t i = 0; i < node_list.size(); i++) {
+    for (const Node& n : architecture.get_neighbour_nodes(node_list[i])) {
+      auto it = index.find(n);
+      if (it != index.end() && it->second > i) {
+        raw_data[i].push_back(it->second);
+      }
+    }
+  }
+  graphs::AdjacencyData adjacency(raw_data, node_list.size());
+  std::vector<std::set<Node>> regions;
+  for (const std::set<std::size_t>& component :
+       graphs::GraphRoutines::get_connected_components(adjacency)) {
+    std::set<Node> region;
+    for (std::size_t i : component) region.insert(node_list[i]);
+    regions.push_back(region);
+  }
+  return regions;
+}
+
+std::optional<std::vector<node_vector_t>> get_disjoint_subarchitectures(
+    const Architecture& architecture, const std::vector<unsigned>& sizes) {
+  std::vector<unsigned> order(sizes.size());
+  std::iota(order.begin(), order.end(), 0);
+  std::stable_sort(
+      order.begin(), order.end(),
+      [&sizes](unsigned a, unsigned b) { return sizes[a] > sizes[b]; });
+
+  node_vector_t all_nodes = architecture.get_all_nodes_vec();
+  std::set<Node> free(all_nodes.begin(), all_nodes.end());
+  std::vector<node_vector_t> subarchitectures(sizes.size());
+  for (unsigned i : order) {
+    unsigned size = sizes[i];
+    if (size == 0) continue;
+    // the smallest region of free nodes which is large enough
+    std::optional<std::set<Node>> best;
+    for (const std::set<Node>& region : connected_regions(architecture, free)) {
+      if (region.size() >= size && (!best || region.size() < best->size())) {
+        best = region;
+      }
+    }
+    if (!best) return std::nullopt;
+
+    std::set<Node> remaining = *best;
+    node_vector_t& chosen = subarchitectures[i];
+    while (chosen.size() < size) {
+      std::set<Node> aps =
+          architecture
+              .create_subarch({remaining.begin(), remaining.end()})
+              .get_articulation_points();
+      // prefer keeping the remaining region connected, then nodes with fewest
+      // remaining neighbours
+      std::optional<std::pair<std::pair<bool, unsigned>, Node>> next;
+      for (const Node& n : remaining) {
+        std::set<Node> neighbours = architecture.get_neighbour_nodes(n);
+        if (!chosen.empty() &&
+            std::none_of(chosen.begin(), chosen.end(), [&](const Node& c) {
+              return neighbours.find(c) != neighbours.end();
+            })) {
+          continue;
+        }
+        unsigned n_remaining = std::count_if(
+            neighbours.begin(), neighbours.end(), [&](const Node& m) {
+              return remaining.find(m) != remaining.end();
+            });
+        std::pair<bool, unsigned> key = {
+            aps.find(n) != aps.end(), n_remaining};
+        if (!next || key < next->first) next = {key, n};
+      }
+      chosen.push_back(next->second);
+      remaining.erase(next->second);
+      free.erase(next->second);
+    }
+  }
+  return subarchitectures;
+}
+
+}  // namespace tket
@@ -0,0 +1,573 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/SabreRoutingMethod.hpp"
+
+#include <limits>
+#include <optional>
+#include <tkrng/RNG.hpp>
+
+#include "Circuit/Conditional.hpp"
+
+namespace tket {
+
+// weight of the extended set relative to the front layer
+static const double EXTENDED_SET_WEIGHT = 0.5;
+// max number of gates following the front layer in the extended set
+static const unsigned EXTENDED_SET_SIZE = 20;
+// increase in the decay of a node each time it is swapped
+static const double DECAY_DELTA = 0.001;
+// number of SWAP gates after which decays are reset
+static const unsigned DECAY_RESET = 5;
+static const unsigned UNSET = std::numeric_limits<unsigned>::max();
+
+namespace {
+
+// gate acting on two or more qubits, indexed from 0
+struct SabreGate {
+  std::vector<unsigned> qubits;
+  // false for barriers, which can be passed wherever the qubits are
+  bool adjacent;
+};
+
+// connectivity of an Architecture, with nodes indexed from 0
+struct SabreGraph {
+  std::vector<Node> nodes;
+  std::vector<std::vector<unsigned>> neighbours;
+  // unconnected nodes are at distance nodes.size()
+  std::vector<std::vector<unsigned>> distances;
+};
+
+struct SabrePass {
+  // node of each qubit at the end of the pass
+  std::vector<unsigned> final_layout;
+  std::vector<std::pair<unsigned, unsigned>> swaps;
+  // false if some gate could not be routed
+  bool complete;
+};
+
+}  // namespace
+
+static SabreGraph make_graph(const Architecture& architecture) {
+  SabreGraph graph;
+  graph.nodes = architecture.get_all_nodes_vec();
+  unsigned n_nodes = graph.nodes.size();
+  std::map<Node, unsigned> index;
+  for (unsigned i = 0; i < n_nodes; i++) {
+    index.insert({graph.nodes[i], i});
+  }
+  graph.neighbours.resize(n_nodes);
+  for (const Architecture::Connection& link :
+       architecture.get_all_edges_vec()) {
+    unsigned i = index.at(link.first);
+    unsigned j = index.at(link.second);
+    std::vector<unsigned>& i_neighbours = graph.neighbours[i];
+    if (i != j && std::find(i_neighbours.begin(), i_neighbours.end(), j) ==
+                      i_neighbours.end()) {
+      i_neighbours.push_back(j);
+      graph.neighbours[j].push_back(i);
+    }
+  }
+  graph.distances.assign(n_nodes, std::vector<unsigned>(n_nodes, n_nodes));
+  for (unsigned root = 0; root < n_nodes; root++) {
+    std::vector<unsigned>& dists = graph.distances[root];
+    dists[root] = 0;
+    std::vector<unsigned> queue = {root};
+    for (unsigned i = 0; i < queue.size(); i++) {
+      unsigned n = queue[i];
+      for (unsigned m : graph.neighbours[n]) {
+        if (dists[m] == n_nodes) {
+          dists[m] = dists[n] + 1;
+          queue.push_back(m);
+        }
+      }
+    }
+  }
+  return graph;
+}
+
+// Route the gates greedily from the given layout, choosing each SWAP gate by
+// the distances between qubits of the front layer and the following gates.
+static SabrePass route_gates(
+    const std::vector<SabreGate>& gates, const SabreGraph& graph,
+    std::vector<unsigned> layout) {
+  const unsigned n_nodes = graph.nodes.size();
+  std::vector<unsigned> occupant(n_nodes, UNSET);
+  for (unsigned q = 0; q < layout.size(); q++) {
+    if (layout[q] != UNSET) {
+      occupant[layout[q]] = q;
+    }
+  }
+
+  std::vector<unsigned> n_predecessors(gates.size(), 0);
+  std::vector<std::vector<unsigned>> successors(gates.size());
+  std::vector<unsigned> last_gate(layout.size(), UNSET);
+  for (unsigned g = 0; g < gates.size(); g++) {
+    for (unsigned q : gates[g].qubits) {
+      if (last_gate[q] != UNSET) {
+        successors[last_gate[q]].push_back(g);
+        n_predecessors[g]++;
+      }
+      last_gate[q] = g;
+    }
+  }
+  std::vector<unsigned> front;
+  std::vector<bool> done(gates.size(), false), in_front(gates.size(), false);
+  for (unsigned g = 0; g < gates.size(); g++) {
+    if (n_predecessors[g] == 0) {
+      front.push_back(g);
+      in_front[g] = true;
+    }
+  }
+
+  SabrePass pass;
+  pass.complete = true;
+  std::vector<double> decay(n_nodes, 1.);
+  // index of the last SWAP on each node, or UNSET if a gate has followed it;
+  // a SWAP is never immediately repeated
+  std::vector<unsigned> last_swap(n_nodes, UNSET);
+  unsigned swaps_since_progress = 0;
+
+  auto distance = [&](const std::vector<unsigned>& qubits,
+                      const std::pair<unsigned, unsigned>& swap) {
+    auto node_of = [&](unsigned q) {
+      unsigned n = layout[q];
+      if (n == swap.first) return swap.second;
+      if (n == swap.second) return swap.first;
+      return n;
+    };
+    return double(graph.distances[node_of(qubits[0])][node_of(qubits[1])]);
+  };
+  auto add_swap = [&](unsigned n0, unsigned n1) {
+    std::swap(occupant[n0], occupant[n1]);
+    if (occupant[n0] != UNSET) layout[occupant[n0]] = n0;
+    if (occupant[n1] != UNSET) layout[occupant[n1]] = n1;
+    last_swap[n0] = last_swap[n1] = pass.swaps.size();
+    pass.swaps.push_back({n0, n1});
+    swaps_since_progress++;
+  };
+  auto repeats_swap = [&](unsigned n0, unsigned n1) {
+    return last_swap[n0] != UNSET && last_swap[n0] == last_swap[n1];
+  };
+
+  while (!front.empty()) {
+    // pass every gate in the front layer that can be executed
+    bool progress = true;
+    while (progress) {
+      progress = false;
+      std::vector<unsigned> blocked;
+      for (unsigned g : front) {
+        const SabreGate& gate = gates[g];
+        if (gate.adjacent &&
+            graph.distances[layout[gate.qubits[0]]]
+                           [layout[gate.qubits[1]]] != 1) {
+          blocked.push_back(g);
+          continue;
+        }
+        progress = true;
+        done[g] = true;
+        in_front[g] = false;
+        for (unsigned q : gate.qubits) {
+          last_swap[layout[q]] = UNSET;
+        }
+        for (unsigned s : successors[g]) {
+          if (--n_predecessors[s] == 0) {
+            blocked.push_back(s);
+            in_front[s] = true;
+          }
+        }
+      }
+      if (progress) {
+        swaps_since_progress = 0;
+        std::fill(decay.begin(), decay.end(), 1.);
+      }
+      front = blocked;
+    }
+    if (front.empty()) {
+      break;
+    }
+
+    std::set<std::pair<unsigned, unsigned>> candidates;
+    if (swaps_since_progress < n_nodes) {
+      for (unsigned g : front) {
+        for (unsigned q : gates[g].qubits) {
+          unsigned n = layout[q];
+          for (unsigned m : graph.neighbours[n]) {
+            if (!repeats_swap(n, m)) {
+              candidates.insert({std::min(n, m), std::max(n, m)});
+            }
+          }
+        }
+      }
+    }
+    if (candidates.empty()) {
+      // the heuristic is stuck, so move the qubits of the closest gate in
+      // the front layer together along a shortest path
+      unsigned closest = front[0];
+      for (unsigned g : front) {
+        if (distance(gates[g].qubits, {UNSET, UNSET}) <
+            distance(gates[closest].qubits, {UNSET, UNSET})) {
+          closest = g;
+        }
+      }
+      unsigned n0 = layout[gates[closest].qubits[0]];
+      unsigned n1 = layout[gates[closest].qubits[1]];
+      if (graph.distances[n0][n1] == n_nodes) {
+        pass.complete = false;
+        break;
+      }
+      while (graph.distances[n0][n1] > 1) {
+        // every step brings the qubits closer, so a repeated SWAP is only
+        // avoided when there is another neighbour on a shortest path
+        unsigned next = UNSET;
+        for (unsigned m : graph.neighbours[n0]) {
+          if (graph.distances[m][n1] < graph.distances[n0][n1] &&
+              (next == UNSET || repeats_swap(n0, next))) {
+            next = m;
+          }
+        }
+        add_swap(n0, next);
+        n0 = next;
+      }
+      continue;
+    }
+
+    std::vector<unsigned> extended;
+    for (unsigned g = 0;
+         g < gates.size() && extended.size() < EXTENDED_SET_SIZE; g++) {
+      if (!done[g] && !in_front[g] && gates[g].adjacent) {
+        extended.push_back(g);
+      }
+    }
+    double best_cost = std::numeric_limits<double>::infinity();
+    std::pair<unsigned, unsigned> best_swap = *candidates.begin();
+    for (const std::pair<unsigned, unsigned>& swap : candidates) {
+      double front_cost = 0., extended_cost = 0.;
+      for (unsigned g : front) {
+        front_cost += distance(gates[g].qubits, swap);
+      }
+      for (unsigned g : extended) {
+        extended_cost += distance(gates[g].qubits, swap);
+      }
+      double cost = front_cost / front.size();
+      if (!extended.empty()) {
+        cost += EXTENDED_SET_WEIGHT * extended_cost / extended.size();
+      }
+      cost *= std::max(decay[swap.first], decay[swap.second]);
+      if (cost < best_cost) {
+        best_cost = cost;
+        best_swap = swap;
+      }
+    }
+    add_swap(best_swap.first, best_swap.second);
+    if (pass.swaps.size() % DECAY_RESET == 0) {
+      std::fill(decay.begin(), decay.end(), 1.);
+    } else {
+      decay[best_swap.first] += DECAY_DELTA;
+      decay[best_swap.second] += DECAY_DELTA;
+    }
+  }
+  pass.final_layout = layout;
+  return pass;
+}
+
+// Free node closest to the given nodes, or of largest degree if none given.
+static unsigned closest_free_node(
+    const SabreGraph& graph, const std::vector<bool>& occupied,
+    const std::vector<unsigned>& targets) {
+  unsigned best = UNSET;
+  unsigned best_score = std::numeric_limits<unsigned>::max();
+  for (unsigned n = 0; n < graph.nodes.size(); n++) {
+    if (occupied[n]) continue;
+    unsigned score = 0;
+    if (targets.empty()) {
+      score = graph.nodes.size() - graph.neighbours[n].size();
+    }
+    for (unsigned t : targets) {
+      score += graph.distances[n][t];
+    }
+    if (score < best_score) {
+      best = n;
+      best_score = score;
+    }
+  }
+  return best;
+}
+
+// Place the unplaced qubits in order of their first gate, each next to the
+// qubits it interacts with.
+static std::vector<unsigned> greedy_layout(
+    const std::vector<SabreGate>& gates, const SabreGraph& graph,
+    std::vector<unsigned> layout) {
+  std::vector<bool> occupied(graph.nodes.size(), false);
+  std::vector<unsigned> occupied_nodes;
+  for (unsigned n : layout) {
+    if (n != UNSET) {
+      occupied[n] = true;
+      occupied_nodes.push_back(n);
+    }
+  }
+  for (const SabreGate& gate : gates) {
+    for (unsigned q : gate.qubits) {
+      if (layout[q] != UNSET) continue;
+      std::vector<unsigned> partners;
+      for (unsigned p : gate.qubits) {
+        if (layout[p] != UNSET) partners.push_back(layout[p]);
+      }
+      unsigned n;
+      if (!partners.empty() || occupied_nodes.empty()) {
+        n = closest_free_node(graph, occupied, partners);
+      } else {
+        // keep the placed qubits together
+        n = UNSET;
+        unsigned best_distance = std::numeric_limits<unsigned>::max();
+        for (unsigned m : occupied_nodes) {
+          unsigned c = closest_free_node(graph, occupied, {m});
+          if (graph.distances[m][c] < best_distance) {
+            n = c;
+            best_distance = graph.distances[m][c];
+          }
+        }
+      }
+      layout[q] = n;
+      occupied[n] = true;
+      occupied_nodes.push_back(n);
+    }
+  }
+  return layout;
+}
+
+// Place the unplaced qubits on random free nodes.
+static std::vector<unsigned> random_layout(
+    const SabreGraph& graph, std::vector<unsigned> layout, RNG& rng) {
+  std::vector<bool> occupied(graph.nodes.size(), false);
+  for (unsigned n : layout) {
+    if (n != UNSET) occupied[n] = true;
+  }
+  std::vector<unsigned> free_nodes;
+  for (unsigned n = 0; n < graph.nodes.size(); n++) {
+    if (!occupied[n]) free_nodes.push_back(n);
+  }
+  rng.do_shuffle(free_nodes);
+  unsigned i = 0;
+  for (unsigned& n : layout) {
+    if (n == UNSET) n = free_nodes[i++];
+  }
+  return layout;
+}
+
+// Return the placed qubits to their original nodes, moving the unplaced
+// qubits as little as possible from the refined layout.
+static std::vector<unsigned> pin_layout(
+    const SabreGraph& graph, const std::vector<unsigned>& refined,
+    std::vector<unsigned> layout) {
+  std::vector<bool> occupied(graph.nodes.size(), false);
+  std::vector<unsigned> unplaced;
+  for (unsigned n : layout) {
+    if (n != UNSET) occupied[n] = true;
+  }
+  for (unsigned q = 0; q < layout.size(); q++) {
+    if (layout[q] == UNSET) {
+      if (occupied[refined[q]]) {
+        unplaced.push_back(q);
+      } else {
+        layout[q] = refined[q];
+        occupied[refined[q]] = true;
+      }
+    }
+  }
+  for (unsigned q : unplaced) {
+    layout[q] = closest_free_node(graph, occupied, {refined[q]});
+    occupied[layout[q]] = true;
+  }
+  return layout;
+}
+
+static bool routable(const Op_ptr& op) {
+  OpType ot = op->get_type();
+  if (ot == OpType::Conditional) {
+    return routable(static_cast<const Conditional&>(*op).get_op());
+  }
+  return !is_box_type(ot);
+}
+
+SabreRoutingMethod::SabreRoutingMethod(
+    unsigned _max_depth, unsigned _max_size, unsigned _iterations,
+    unsigned _restarts, unsigned _seed)
+    : max_depth_(_max_depth),
+      max_size_(_max_size),
+      iterations_(_iterations),
+      restarts_(_restarts),
+      seed_(_seed) {}
+
+std::pair<bool, unit_map_t> SabreRoutingMethod::routing_method(
+    MappingFrontier_ptr& mapping_frontier,
+    const ArchitecturePtr& architecture) const {
+  Subcircuit frontier_subcircuit = mapping_frontier->get_frontier_subcircuit(
+      this->max_depth_, this->max_size_);
+  Circuit frontier_circuit =
+      mapping_frontier->circuit_.subcircuit(frontier_subcircuit);
+  frontier_circuit.rename_units(
+      mapping_frontier->get_default_to_linear_boundary_unit_map());
+
+  SabreGraph graph = make_graph(*architecture);
+  std::map<Node, unsigned> node_index;
+  for (unsigned i = 0; i < graph.nodes.size(); i++) {
+    node_index.insert({graph.nodes[i], i});
+  }
+
+  // placed qubits, and unplaced qubits with gates in the subcircuit
+  std::vector<UnitID> qubits;
+  std::map<UnitID, unsigned> qubit_index;
+  std::vector<unsigned> placed_layout;
+  for (const std::pair<UnitID, VertPort>& pair :
+       mapping_frontier->linear_boundary->get<TagKey>()) {
+    auto it = node_index.find(Node(pair.first));
+    if (it != node_index.end()) {
+      qubit_index.insert({pair.first, qubits.size()});
+      qubits.push_back(pair.first);
+      placed_layout.push_back(it->second);
+    }
+  }
+  std::vector<SabreGate> gates;
+  // qubits of gates which can't be routed, and of any later gates
+  std::set<UnitID> blocked;
+  for (const Command& com : frontier_circuit.get_commands()) {
+    qubit_vector_t args = com.get_qubits();
+    if (args.size() < 2) continue;
+    Op_ptr op = com.get_op_ptr();
+    bool is_barrier = op->get_type() == OpType::Barrier;
+    bool is_blocked = !routable(op) || (args.size() > 2 && !is_barrier);
+    for (const Qubit& qb : args) {
+      is_blocked |= blocked.find(qb) != blocked.end();
+    }
+    if (is_blocked) {
+      blocked.insert(args.begin(), args.end());
+      continue;
+    }
+    SabreGate gate{{}, !is_barrier};
+    for (const Qubit& qb : args) {
+      auto it = qubit_index.find(qb);
+      if (it == qubit_index.end()) {
+        it = qubit_index.insert({qb, qubits.size()}).first;
+        qubits.push_back(qb);
+        placed_layout.push_back(UNSET);
+      }
+      gate.qubits.push_back(it->second);
+    }
+    gates.push_back(gate);
+  }
+  if (gates.empty()) {
+    return {false, {}};
+  }
+  std::vector<SabreGate> reversed_gates(gates.rbegin(), gates.rend());
+
+  // candidate starting layouts
+  std::vector<std::vector<unsigned>> layouts = {
+      greedy_layout(gates, graph, placed_layout)};
+  bool all_placed =
+      std::find(placed_layout.begin(), placed_layout.end(), UNSET) ==
+      placed_layout.end();
+  if (!all_placed) {
+    RNG rng;
+    rng.set_seed(this->seed_);
+    for (unsigned i = 0; i < this->restarts_; i++) {
+      layouts.push_back(random_layout(graph, placed_layout, rng));
+    }
+  }
+
+  std::optional<SabrePass> best_pass;
+  std::vector<unsigned> best_layout;
+  for (std::vector<unsigned>& layout : layouts) {
+    for (unsigned i = 0; i < this->iterations_ && !all_placed; i++) {
+      SabrePass forward = route_gates(gates, graph, layout);
+      SabrePass backward =
+          route_gates(reversed_gates, graph, forward.final_layout);
+      layout = pin_layout(graph, backward.final_layout, placed_layout);
+    }
+    SabrePass pass = route_gates(gates, graph, layout);
+    if (pass.complete &&
+        (!best_pass || pass.swaps.size() < best_pass->swaps.size())) {
+      best_pass = pass;
+      best_layout = layout;
+    }
+  }
+  if (!best_pass) {
+    return {false, {}};
+  }
+
+  bool modified = false;
+  for (unsigned q = 0; q < qubits.size(); q++) {
+    if (placed_layout[q] == UNSET) {
+      const Node& node = graph.nodes[best_layout[q]];
+      mapping_frontier->update_bimaps(
+          mapping_frontier->get_qubit_from_circuit_uid(qubits[q]), node);
+      mapping_frontier->update_linear_boundary_uids({{qubits[q], node}});
+      modified = true;
+    }
+  }
+  // the frontier passes gates in the same way as the routed passes, so the
+  // SWAP gates are added in between as they were chosen
+  bool all_swaps_added = true;
+  for (const std::pair<unsigned, unsigned>& swap : best_pass->swaps) {
+    mapping_frontier->advance_frontier_boundary(architecture);
+    if (!mapping_frontier->add_swap(
+            graph.nodes[swap.first], graph.nodes[swap.second])) {
+      all_swaps_added = false;
+      break;
+    }
+    modified = true;
+  }
+  // the SWAP gates have moved each qubit to its final node, where the
+  // boundary now labels it, so no further permutation is needed
+  unit_map_t final_map;
+  if (all_swaps_added) {
+    for (unsigned n : best_pass->final_layout) {
+      final_map.insert({graph.nodes[n], graph.nodes[n]});
+    }
+  }
+  return {modified, final_map};
+}
+
+unsigned SabreRoutingMethod::get_max_depth() const { return this->max_depth_; }
+
+unsigned SabreRoutingMethod::get_max_size() const { return this->max_size_; }
+
+unsigned SabreRoutingMethod::get_iterations() const {
+  return this->iterations_;
+}
+
+unsigned SabreRoutingMethod::get_restarts() const { return this->restarts_; }
+
+unsigned SabreRoutingMethod::get_seed() const { return this->seed_; }
+
+nlohmann::json SabreRoutingMethod::serialize() const {
+  nlohmann::json j;
+  j["depth"] = this->get_max_depth();
+  j["size"] = this->get_max_size();
+  j["iterations"] = this->get_iterations();
+  j["restarts"] = this->get_restarts();
+  j["seed"] = this->get_seed();
+  j["name"] = "SabreRoutingMethod";
+  return j;
+}
+
+SabreRoutingMethod SabreRoutingMethod::deserialize(const nlohmann::json& j) {
+  return SabreRoutingMethod(
+      j.at("depth").get<unsigned>(), j.at("size").get<unsigned>(),
+      j.at("iterations").get<unsigned>(), j.at("restarts").get<unsigned>(),
+      j.at("seed").get<unsigned>());
+}
+
+}  // namespace tket
@@ -0,0 +1,278 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Mapping/ShuttlingRoutingMethod.hpp"
+
+#include <algorithm>
+#include <functional>
+#include <optional>
+#include <queue>
+#include <set>
+
+#include "Circuit/Conditional.hpp"
+
+namespace tket {
+
+ShuttlingRoutingMethod::ShuttlingRoutingMethod(double heating_weight)
+    : heating_weight_(heating_weight) {}
+
+namespace {
+// transports moving qubits into a zone for a gate, after placing unplaced
+// qubits on the given sites
+struct ZonePlan {
+  double cost = 0.;
+  unit_map_t placement;
+  std::vector<std::pair<Node, Node>> transports;
+};
+}  // namespace
+
+/**
+ * Cheapest path of transports from a site to a free site satisfying
+ * is_target, moving through free sites only. The path excludes the source.
+ */
+static std::optional<std::pair<double, std::vector<Node>>> cheapest_path(
+    const ShuttlingArchitecture& architecture, double heating_weight,
+    const Node& source, const std::set<Node>& occupied,
+    const std::function<bool(const Node&)>& is_target) {
+  typedef std::pair<double, Node> entry_t;
+  std::priority_queue<entry_t, std::vector<entry_t>, std::greater<entry_t>>
+      queue;
+  std::map<Node, double> dist = {{source, 0.}};
+  std::map<Node, Node> prev;
+  queue.push({0., source});
+  while (!queue.empty()) {
+    auto [d, site] = queue.top();
+    queue.pop();
+    if (d > dist.at(site)) continue;
+    if (site != source && is_target(site)) {
+      std::vector<Node> path = {site};
+      while (prev.at(path.back()) != source) {
+        path.push_back(prev.at(path.back()));
+      }
+      std::reverse(path.begin(), path.end());
+      return std::make_pair(d, path);
+    }
+    for (const Node& next : architecture.get_transport_neighbours(site)) {
+      if (occupied.find(next) != occupied.end()) continue;
+      TransportCost cost = architecture.get_transport_cost(site, next);
+      double next_d = d + cost.time + heating_weight * cost.heating;
+      auto it = dist.find(next);
+      if (it == dist.end() || next_d < it->second) {
+        dist[next] = next_d;
+        prev[next] = site;
+        queue.push({next_d, next});
+      }
+    }
+  }
+  return std::nullopt;
+}
+
+/**
+ * Plan bringing the qubits of a gate into an interaction zone. Sites are
+ * occupied if they hold a qubit of the circuit, and used if they have a wire
+ * in the circuit at all, so that unplaced qubits are only placed on unused
+ * sites.
+ */
+static std::optional<ZonePlan> plan_zone(
+    const ShuttlingArchitecture& architecture, double heating_weight,
+    const std::vector<UnitID>& qubits, unsigned zone_index,
+    std::set<Node> occupied, std::set<Node> used) {
+  const Zone& zone = architecture.get_zones()[zone_index];
+  auto in_zone = [&](const Node& site) {
+    return architecture.get_zone_index(site) == zone_index;
+  };
+  ZonePlan plan;
+  auto apply_path = [&](const Node& from, double cost,
+                        const std::vector<Node>& path) {
+    Node site = from;
+    for (const Node& next : path) {
+      plan.transports.push_back({site, next});
+      used.insert(next);
+      site = next;
+    }
+    occupied.erase(from);
+    occupied.insert(site);
+    plan.cost += cost;
+  };
+
+  std::set<Node> staying;
+  std::vector<UnitID> incoming;
+  for (const UnitID& qb : qubits) {
+    Node site(qb);
+    if (architecture.node_exists(site) && in_zone(site)) {
+      staying.insert(site);
+    } else {
+      incoming.push_back(qb);
+    }
+  }
+
+  // move other qubits out of the zone until there is room
+  unsigned n_free = 0;
+  for (const Node& site : zone.sites) {
+    if (occupied.find(site) == occupied.end()) ++n_free;
+  }
+  while (n_free < incoming.size()) {
+    std::optional<std::pair<Node, std::pair<double, std::vector<Node>>>> best;
+    for (const Node& site : zone.sites) {
+      if (occupied.find(site) == occupied.end() ||
+          staying.find(site) != staying.end()) {
+        continue;
+      }
+      auto path = cheapest_path(
+          architecture, heating_weight, site, occupied,
+          [&](const Node& n) { return !in_zone(n); });
+      if (path && (!best || path->first < best->second.first)) {
+        best = std::make_pair(site, *path);
+      }
+    }
+    if (!best) return std::nullopt;
+    apply_path(best->first, best->second.first, best->second.second);
+    ++n_free;
+  }
+
+  for (const UnitID& qb : incoming) {
+    Node site(qb);
+    if (!architecture.node_exists(site)) {
+      // place the qubit on an unused site of the zone, or else the unused
+      // site from which it can be moved into the zone most cheaply
+      std::optional<std::pair<Node, std::pair<double, std::vector<Node>>>>
+          best;
+      for (const Node& s : zone.sites) {
+        if (used.find(s) == used.end()) {
+          best = std::make_pair(s, std::make_pair(0., std::vector<Node>()));
+          break;
+        }
+      }
+      if (!best) {
+        for (const Node& s : architecture.get_all_nodes_vec()) {
+          if (used.find(s) != used.end()) continue;
+          occupied.insert(s);
+          auto path = cheapest_path(
+              architecture, heating_weight, s, occupied, in_zone);
+          occupied.erase(s);
+          if (path && (!best || path->first < best->second.first)) {
+            best = std::make_pair(s, *path);
+          }
+        }
+      }
+      if (!best) return std::nullopt;
+      plan.placement.insert({qb, best->first});
+      occupied.insert(best->first);
+      used.insert(best->first);
+      apply_path(best->first, best->second.first, best->second.second);
+    } else {
+      auto path = cheapest_path(
+          architecture, heating_weight, site, occupied, in_zone);
+      if (!path) return std::nullopt;
+      apply_path(site, path->first, path->second);
+    }
+  }
+  return plan;
+}
+
+std::pair<bool, unit_map_t> ShuttlingRoutingMethod::routing_method(
+    MappingFrontier_ptr& mapping_frontier,
+    const ArchitecturePtr& architecture) const {
+  std::shared_ptr<ShuttlingArchitecture> shuttling =
+      std::dynamic_pointer_cast<ShuttlingArchitecture>(architecture);
+  if (!shuttling) {
+    return {false, {}};
+  }
+  const Circuit& circ = mapping_frontier->circuit_;
+
+  // two-qubit gates immediately after the boundary, and the sites in use
+  std::vector<std::pair<Vertex, std::vector<UnitID>>> gates;
+  std::set<Node> occupied, used;
+  for (const std::pair<UnitID, VertPort>& pair :
+       mapping_frontier->linear_boundary->get<TagKey>()) {
+    Node site(pair.first);
+    if (shuttling->node_exists(site)) {
+      used.insert(site);
+      if (mapping_frontier->ancilla_nodes_.find(site) ==
+          mapping_frontier->ancilla_nodes_.end()) {
+        occupied.insert(site);
+      }
+    }
+    Vertex v = circ.target(
+        circ.get_nth_out_edge(pair.second.first, pair.second.second));
+    Op_ptr op = circ.get_Op_ptr_from_Vertex(v);
+    if (op->get_type() == OpType::Conditional) {
+      op = static_cast<const Conditional&>(*op).get_op();
+    }
+    if (op->get_type() == OpType::Barrier || op->get_desc().is_box() ||
+        circ.n_in_edges_of_type(v, EdgeType::Quantum) != 2) {
+      continue;
+    }
+    auto it = std::find_if(
+        gates.begin(), gates.end(),
+        [&v](const std::pair<Vertex, std::vector<UnitID>>& gate) {
+          return gate.first == v;
+        });
+    if (it == gates.end()) {
+      gates.push_back({v, {pair.first}});
+    } else {
+      it->second.push_back(pair.first);
+    }
+  }
+
+  // route the gate which can be brought into some zone most cheaply
+  std::optional<ZonePlan> best;
+  const std::vector<Zone>& zones = shuttling->get_zones();
+  for (const std::pair<Vertex, std::vector<UnitID>>& gate : gates) {
+    if (gate.second.size() != 2) continue;
+    for (unsigned z = 0; z < zones.size(); z++) {
+      if (!zones[z].interaction) continue;
+      std::optional<ZonePlan> plan = plan_zone(
+          *shuttling, this->heating_weight_, gate.second, z, occupied, used);
+      if (plan && (!best || plan->cost < best->cost)) {
+        best = plan;
+      }
+    }
+  }
+  if (!best) {
+    return {false, {}};
+  }
+
+  if (!best->placement.empty()) {
+    for (const std::pair<const UnitID, UnitID>& pair : best->placement) {
+      mapping_frontier->update_bimaps(
+          mapping_frontier->get_qubit_from_circuit_uid(pair.first),
+          pair.second);
+    }
+    mapping_frontier->update_linear_boundary_uids(best->placement);
+  }
+  for (const std::pair<Node, Node>& transport : best->transports) {
+    mapping_frontier->add_swap(
+        transport.first, transport.second, OpType::Transport);
+  }
+  return {true, {}};
+}
+
+double ShuttlingRoutingMethod::get_heating_weight() const {
+  return this->heating_weight_;
+}
+
+nlohmann::json ShuttlingRoutingMethod::serialize() const {
+  nlohmann::json j;
+  j["heating_weight"] = this->get_heating_weight();
+  j["name"] = "ShuttlingRoutingMethod";
+  return j;
+}
+
+ShuttlingRoutingMethod ShuttlingRoutingMethod::deserialize(
+    const nlohmann::json& j) {
+  return ShuttlingRoutingMethod(j.at("heating_weight").get<double>());
+}
+
+}  // namespace tket
@@ -0,0 +1,91 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Mapping/RoutingMethod.hpp"
+
+namespace tket {
+
+class ExactRoutingMethod : public RoutingMethod {
+ public:
+  /**
+   * Routing method finding the fewest SWAP gates needed to route the
+   * subcircuit at the frontier, by an A* search over the positions of its
+   * qubits, using distances in the Architecture as an admissible heuristic.
+   * If the whole circuit fits in the subcircuit, the number of SWAP gates
+   * added is minimal for the placement of its qubits. Only placed qubits are
+   * routed, so this should be ranked after a labelling method.
+   *
+   * The search is only practical for small circuits and architectures, so
+   * if it takes longer than the given time the subcircuit is routed by
+   * LexiRouteRoutingMethod instead.
+   *
+   * @param _timeout Time budget for each search, in milliseconds
+   * @param _max_depth Max depth of the subcircuit routed at once
+   * @param _max_size Max number of gates in the subcircuit routed at once
+   * @param _lookahead Max depth used by LexiRouteRoutingMethod when the time
+   * budget runs out
+   */
+  ExactRoutingMethod(
+      unsigned _timeout = 1000, unsigned _max_depth = 100,
+      unsigned _max_size = 100, unsigned _lookahead = 10);
+
+  /**
+   * @param mapping_frontier Contains boundary of routed/unrouted circuit for
+   * modifying
+   * @param architecture Architecture providing physical constraints
+   *
+   * @return True if modification made, map between relabelled Qubit, always
+   * empty.
+   */
+  std::pair<bool, unit_map_t> routing_method(
+      MappingFrontier_ptr& mapping_frontier,
+      const ArchitecturePtr& architecture) const override;
+
+  /**
+   * @return Time budget for each search, in milliseconds
+   */
+  unsigned get_timeout() const;
+
+  /**
+   * @return Max depth of the subcircuit routed at once
+   */
+  unsigned get_max_depth() const;
+
+  /**
+   * @return Max number of gates in the subcircuit routed at once
+   */
+  unsigned get_max_size() const;
+
+  /**
+   * @return Max depth used by LexiRouteRoutingMethod when the time budget
+   * runs out
+   */
+  unsigned get_lookahead() const;
+
+  nlohmann::json serialize() const override;
+
+  static ExactRoutingMethod deserialize(const nlohmann::json& j);
+
+ private:
+  unsigned timeout_;
+  unsigned max_depth_;
+  unsigned max_size_;
+  unsigned lookahead_;
+};
+
+JSON_DECL(ExactRoutingMethod);
+
+}  // namespace tket
@@ -141,9 +141,14 @@ struct MappingFrontier {
    *
    * @param uid_0 First Node in SWAP
    * @param uid_1 Second Node in SWAP
+   * @param swap_type Type of the inserted gate, which must act as a SWAP,
+   * e.g. OpType::Transport to move a qubit between sites. Only SWAP gates
+   * are checked for undoing the previous SWAP.
    * @return true if SWAP added
    */
-  bool add_swap(const UnitID& uid_0, const UnitID& uid_1);
+  bool add_swap(
+      const UnitID& uid_0, const UnitID& uid_1,
+      OpType swap_type = OpType::SWAP);
 
   /**
    * add_bridge
@@ -193,7 +198,9 @@ struct MappingFrontier {
       const std::vector<Node>& uids) const;
 
   /**
-   * Update a qubit mapping in both the initial map and the final map
+   * Update a qubit mapping in both the initial map and the final map.
+   * If the qubit's wire has been reused, the final map is updated for the
+   * qubit ending on that wire.
    * @param qubit the qubit mapping to be updated
    * @param node the new node to be mapped
    */
@@ -47,11 +47,14 @@ class MappingManager {
    * segments.
    * @param label_isolated_qubits will not label qubits without gates or only
    * single qubit gates on them if this is set false
+   * @param reuse_freed_qubits if the Circuit has more logical qubits than the
+   * Architecture has physical qubits, first reuse qubits freed by a Measure
+   * or Discard for qubits starting with a Reset or Create, see reuse_qubits
    * @return True if circuit is modified
    */
   bool route_circuit(
       Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
-      bool label_isolated_qubits = true) const;
+      bool label_isolated_qubits = true, bool reuse_freed_qubits = false) const;
 
   /**
    * route_circuit_maps
@@ -67,12 +70,53 @@ class MappingManager {
    * @param maps For tracking placed and permuted qubits during Compilation
    * @param label_isolated_qubits will not label qubits without gates or only
    * single qubit gates on them if this is set false
+   * @param reuse_freed_qubits if the Circuit has more logical qubits than the
+   * Architecture has physical qubits, first reuse qubits freed by a Measure
+   * or Discard for qubits starting with a Reset or Create, see reuse_qubits
    *
    * @return True if circuit is modified
    */
   bool route_circuit_with_maps(
       Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
-      std::shared_ptr<unit_bimaps_t> maps,
+      std::shared_ptr<unit_bimaps_t> maps, bool label_isolated_qubits = true,
+      bool reuse_freed_qubits = false) const;
+
+  /**
+   * route_circuit_in_partitions
+   * Referenced Circuit modified such that all multi-qubit gates are permitted
+   * by this->architecture_, routing independent parts of the Circuit
+   * concurrently.
+   *
+   * The units of the Circuit are split into groups which no operation acts
+   * across (see get_interaction_components). Each group with qubits is given
+   * a disjoint connected set of nodes of the Architecture (see
+   * get_disjoint_subarchitectures), and its subcircuit is routed on the
+   * sub-architecture of those nodes by route_circuit_with_maps, using up to
+   * max_threads threads. The routed subcircuits are then combined in the
+   * order of their groups, so the result does not depend on the number of
+   * threads. Unless SymEngine is built thread-safe, the subcircuits are routed
+   * on a single thread, as they share the expressions of their parameters.
+   *
+   * The Circuit is routed as a whole, as by route_circuit_with_maps, if it has
+   * fewer than two groups with qubits, if any of its qubits is already a node
+   * of the Architecture, if no disjoint sets of nodes are found, or if the
+   * Architecture is a ShuttlingArchitecture.
+   *
+   * @param circuit Circuit to be routed
+   * @param routing_methods Ranked RoutingMethod objects to use for routing
+   * segments. These are shared between threads, so must not be modified by
+   * routing.
+   * @param maps For tracking placed and permuted qubits during Compilation
+   * @param max_threads Maximum number of threads to route with, or 0 to use
+   * the number of concurrent threads supported by the hardware
+   * @param label_isolated_qubits will not label qubits without gates or only
+   * single qubit gates on them if this is set false
+   *
+   * @return True if circuit is modified
+   */
+  bool route_circuit_in_partitions(
+      Circuit& circuit, const std::vector<RoutingMethodPtr>& routing_methods,
+      std::shared_ptr<unit_bimaps_t> maps, unsigned max_threads = 0,
       bool label_isolated_qubits = true) const;
 
  private:
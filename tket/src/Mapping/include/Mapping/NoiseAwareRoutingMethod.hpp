@@ -0,0 +1,132 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Characterisation/DeviceCharacterisation.hpp"
+#include "Mapping/RoutingMethod.hpp"
+
+namespace tket {
+
+/**
+ * Estimated costs, as negative log fidelities, of moving logical qubits
+ * around an Architecture and interacting them, derived from a
+ * DeviceCharacterisation.
+ */
+class NoiseAwareCosts {
+ public:
+  /**
+   * @param architecture Architecture to find costs for
+   * @param characterisation Errors of the device
+   */
+  NoiseAwareCosts(
+      const Architecture& architecture,
+      const DeviceCharacterisation& characterisation);
+
+  /**
+   * Cost of a SWAP gate between adjacent nodes.
+   */
+  double swap_cost(const Node& n0, const Node& n1) const;
+
+  /**
+   * Cost of a two-qubit gate between adjacent nodes.
+   */
+  double gate_cost(const Node& n0, const Node& n1) const;
+
+  /**
+   * Cost of moving qubits on the given nodes together with SWAP gates and
+   * then interacting them with a two-qubit gate. Zero if the nodes are equal.
+   */
+  double interaction_cost(const Node& n0, const Node& n1) const;
+
+  /**
+   * Cost of measuring a qubit on the given node.
+   */
+  double readout_cost(const Node& n) const;
+
+  /**
+   * Next node on the cheapest path of SWAP gates from one node to another.
+   */
+  Node next_step(const Node& from, const Node& to) const;
+
+ private:
+  std::map<Node, unsigned> index_;
+  std::vector<Node> nodes_;
+  // gate and swap costs, indexed by node indices; infinite if not adjacent
+  std::vector<std::vector<double>> gate_costs_;
+  std::vector<std::vector<double>> swap_costs_;
+  // cheapest SWAP paths between nodes and the first step on each
+  std::vector<std::vector<double>> path_costs_;
+  std::vector<std::vector<unsigned>> next_;
+  std::vector<std::vector<double>> interaction_costs_;
+  std::vector<double> readout_costs_;
+};
+
+class NoiseAwareRoutingMethod : public RoutingMethod {
+ public:
+  /**
+   * Routing method inserting SWAP or BRIDGE gates chosen to minimise the
+   * estimated infidelity of the following layers of two-qubit gates and
+   * measurements, using the gate and readout errors of the device rather than
+   * distances on its connectivity graph alone.
+   *
+   * @param characterisation Errors of the device
+   * @param max_depth Number of layers of two-qubit gates considered
+   */
+  NoiseAwareRoutingMethod(
+      const DeviceCharacterisation& characterisation, unsigned max_depth = 10);
+
+  /**
+   * @param mapping_frontier Contains boundary of routed/unrouted circuit for
+   * modifying
+   * @param architecture Architecture providing physical constraints
+   *
+   * @return True if modification made, map between relabelled Qubit, always
+   * empty.
+   */
+  std::pair<bool, unit_map_t> routing_method(
+      MappingFrontier_ptr& mapping_frontier,
+      const ArchitecturePtr& architecture) const override;
+
+  /**
+   * @return Errors of the device
+   */
+  const DeviceCharacterisation& get_characterisation() const;
+
+  /**
+   * @return Max depth used in lookahead
+   */
+  unsigned get_max_depth() const;
+
+  nlohmann::json serialize() const override;
+
+  static NoiseAwareRoutingMethod deserialize(const nlohmann::json& j);
+
+ private:
+  std::shared_ptr<const NoiseAwareCosts> get_costs(
+      const ArchitecturePtr& architecture) const;
+
+  DeviceCharacterisation characterisation_;
+  unsigned max_depth_;
+  // costs for the last Architecture routed to, as these are reused between
+  // calls for the same circuit; only accessed atomically, as circuits may be
+  // routed concurrently
+  typedef std::pair<ArchitecturePtr, std::shared_ptr<const NoiseAwareCosts>>
+      cache_t;
+  mutable std::shared_ptr<const cache_t> cache_;
+};
+
+JSON_DECL(NoiseAwareRoutingMethod);
+
+}  // namespace tket
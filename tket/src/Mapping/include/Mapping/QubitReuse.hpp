@@ -0,0 +1,49 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Circuit/Circuit.hpp"
+#include "Utils/UnitID.hpp"
+
+namespace tket {
+
+/**
+ * Reduce the number of qubits in a circuit by reusing qubits which are no
+ * longer needed for qubits which are not needed yet.
+ *
+ * A qubit is freed once it is discarded or its final operation is a
+ * Measure, and a qubit is fresh if it is created or its first operation is a
+ * Reset. The wire of a fresh qubit is appended to the wire of a freed qubit,
+ * with a Reset in between unless the fresh qubit starts with one, so the
+ * state it starts in is unchanged. Classical wires are not touched, so
+ * measurement results, and any operations conditioned on them, are
+ * unchanged. Pairs are only merged if no operation on the fresh qubit must
+ * happen before the last operation on the freed one. Of the pairs that can
+ * be merged, the one leaving the most time between the two wires is merged
+ * first.
+ *
+ * @param circ Circuit to modify
+ * @param max_qubits Stop once the circuit has at most this many qubits, or 0
+ * to merge as many qubits as possible
+ * @param maps If given, merged qubits are removed from the initial map and
+ * mapped to the qubit whose wire they follow in the final map
+ *
+ * @return Map from each merged qubit to the qubit whose wire it now follows
+ */
+unit_map_t reuse_qubits(
+    Circuit& circ, unsigned max_qubits = 0,
+    std::shared_ptr<unit_bimaps_t> maps = nullptr);
+
+}  // namespace tket
@@ -17,9 +17,13 @@
 #include "Mapping/AASLabelling.hpp"
 #include "Mapping/AASRoute.hpp"
 #include "Mapping/BoxDecomposition.hpp"
+#include "Mapping/ExactRoutingMethod.hpp"
 #include "Mapping/LexiLabelling.hpp"
 #include "Mapping/LexiRouteRoutingMethod.hpp"
 #include "Mapping/MultiGateReorder.hpp"
+#include "Mapping/NoiseAwareRoutingMethod.hpp"
+#include "Mapping/SabreRoutingMethod.hpp"
+#include "Mapping/ShuttlingRoutingMethod.hpp"
 #include "Mapping/RoutingMethod.hpp"
 #include "Utils/Json.hpp"
 
@@ -0,0 +1,67 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <optional>
+#include <vector>
+
+#include "Architecture/Architecture.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Utils/UnitID.hpp"
+
+namespace tket {
+
+/**
+ * Split the units of a circuit into groups which no operation acts across,
+ * so that the subcircuits acting on each group can be routed independently.
+ *
+ * Units are in the same group if some operation acts on both, including
+ * classical bits read by conditional operations. Units without operations
+ * are in groups of their own.
+ *
+ * @param circ Circuit to split
+ * @return Groups of units, each sorted, in order of their least unit
+ */
+std::vector<unit_vector_t> get_interaction_components(const Circuit& circ);
+
+/**
+ * The subcircuit of a circuit acting on a group of its units, as given by
+ * get_interaction_components.
+ *
+ * @param circ Circuit to take the subcircuit of
+ * @param units Units of the circuit no operation acts across
+ * @return Subcircuit with the given units and the operations acting on them
+ */
+Circuit get_interaction_subcircuit(
+    const Circuit& circ, const unit_vector_t& units);
+
+/**
+ * Find disjoint connected sets of nodes of an Architecture with the given
+ * sizes, on which independent subcircuits can be placed.
+ *
+ * Sets are chosen from the largest to the smallest, each in the smallest
+ * connected region of unused nodes which is large enough. Each set is grown
+ * from a node at the edge of the region, preferring nodes whose removal does
+ * not split the remaining region, so that room is left for later sets.
+ *
+ * @param architecture Architecture to take nodes from
+ * @param sizes Number of nodes required for each set
+ * @return Sets of nodes in the order of sizes, or std::nullopt if no such
+ * sets are found
+ */
+std::optional<std::vector<node_vector_t>> get_disjoint_subarchitectures(
+    const Architecture& architecture, const std::vector<unsigned>& sizes);
+
+}  // namespace tket
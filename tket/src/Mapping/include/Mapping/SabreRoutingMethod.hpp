@@ -0,0 +1,100 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Mapping/RoutingMethod.hpp"
+
+namespace tket {
+
+class SabreRoutingMethod : public RoutingMethod {
+ public:
+  /**
+   * Routing method in the style of SABRE, choosing the placement of unplaced
+   * qubits and the SWAP gates routing the subcircuit at the frontier together.
+   *
+   * Starting from some layout of the qubits, the subcircuit is routed forward
+   * and then backward, and the layout reached at the end of the backward pass
+   * is used as the next starting layout. After the given number of iterations
+   * the subcircuit is routed forward from the refined layout. Qubits which
+   * are already placed keep their nodes, so only the placement of unplaced
+   * qubits is refined. The first starting layout is built greedily and each
+   * restart begins from a random layout, generated from the given seed. The
+   * layout and SWAP gates giving the fewest SWAP gates are then applied.
+   *
+   * @param _max_depth Max depth of the subcircuit routed at once
+   * @param _max_size Max number of gates in the subcircuit routed at once
+   * @param _iterations Number of forward and backward passes refining each
+   * starting layout
+   * @param _restarts Number of random starting layouts tried in addition to
+   * the greedy one
+   * @param _seed Seed for the random starting layouts
+   */
+  SabreRoutingMethod(
+      unsigned _max_depth = 100, unsigned _max_size = 100,
+      unsigned _iterations = 3, unsigned _restarts = 4, unsigned _seed = 0);
+
+  /**
+   * @param mapping_frontier Contains boundary of routed/unrouted circuit for
+   * modifying
+   * @param architecture Architecture providing physical constraints
+   *
+   * @return True if modification made, and the final mapping of the qubits
+   * of the subcircuit, from each qubit as labelled at the boundary to its
+   * node. The SWAP gates are already added, so this needs no permutation.
+   */
+  std::pair<bool, unit_map_t> routing_method(
+      MappingFrontier_ptr& mapping_frontier,
+      const ArchitecturePtr& architecture) const override;
+
+  /**
+   * @return Max depth of the subcircuit routed at once
+   */
+  unsigned get_max_depth() const;
+
+  /**
+   * @return Max number of gates in the subcircuit routed at once
+   */
+  unsigned get_max_size() const;
+
+  /**
+   * @return Number of forward and backward passes for each starting layout
+   */
+  unsigned get_iterations() const;
+
+  /**
+   * @return Number of random starting layouts
+   */
+  unsigned get_restarts() const;
+
+  /**
+   * @return Seed for the random starting layouts
+   */
+  unsigned get_seed() const;
+
+  nlohmann::json serialize() const override;
+
+  static SabreRoutingMethod deserialize(const nlohmann::json& j);
+
+ private:
+  unsigned max_depth_;
+  unsigned max_size_;
+  unsigned iterations_;
+  unsigned restarts_;
+  unsigned seed_;
+};
+
+JSON_DECL(SabreRoutingMethod);
+
+}  // namespace tket
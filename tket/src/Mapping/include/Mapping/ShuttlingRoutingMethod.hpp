@@ -0,0 +1,70 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Architecture/ShuttlingArchitecture.hpp"
+#include "Mapping/RoutingMethod.hpp"
+
+namespace tket {
+
+class ShuttlingRoutingMethod : public RoutingMethod {
+ public:
+  /**
+   * Routing method for a ShuttlingArchitecture, inserting OpType::Transport
+   * operations moving qubits into a shared interaction zone rather than SWAP
+   * gates.
+   *
+   * For a two-qubit gate at the frontier, the interaction zone the qubits can
+   * be brought into most cheaply is chosen. Qubits held in the zone which the
+   * gate does not act on are moved out to make room if needed, and unplaced
+   * qubits are placed on unused sites, in the zone if possible. Each qubit is
+   * moved along the cheapest path of free sites, where moving between two
+   * sites costs its time plus the given multiple of its heating. Qubits are
+   * only moved onto free sites, so a path may be blocked by other qubits.
+   * Not used for other architectures.
+   *
+   * @param heating_weight Cost of each unit of heating relative to each unit
+   * of time
+   */
+  ShuttlingRoutingMethod(double heating_weight = 1.);
+
+  /**
+   * @param mapping_frontier Contains boundary of routed/unrouted circuit for
+   * modifying
+   * @param architecture Architecture providing physical constraints
+   *
+   * @return True if modification made, map between relabelled Qubit, always
+   * empty.
+   */
+  std::pair<bool, unit_map_t> routing_method(
+      MappingFrontier_ptr& mapping_frontier,
+      const ArchitecturePtr& architecture) const override;
+
+  /**
+   * @return Cost of each unit of heating relative to each unit of time
+   */
+  double get_heating_weight() const;
+
+  nlohmann::json serialize() const override;
+
+  static ShuttlingRoutingMethod deserialize(const nlohmann::json& j);
+
+ private:
+  double heating_weight_;
+};
+
+JSON_DECL(ShuttlingRoutingMethod);
+
+}  // namespace tket
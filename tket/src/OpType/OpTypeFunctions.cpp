@@ -45,7 +45,7 @@ const OpTypeSet& all_gate_types() {
       OpType::BRIDGE,      OpType::Collapse, OpType::ESWAP,
       OpType::FSim,        OpType::Sycamore, OpType::ISWAPMax,
       OpType::PhasedISWAP, OpType::XXPhase3, OpType::NPhasedX,
-      OpType::TK2};
+      OpType::TK2,         OpType::Transport};
   static std::unique_ptr<const OpTypeSet> gates =
       std::make_unique<const OpTypeSet>(optypes);
   return *gates;
@@ -64,7 +64,7 @@ const OpTypeSet& all_multi_qubit_types() {
       OpType::CnX,         OpType::BRIDGE,      OpType::ESWAP,
       OpType::FSim,        OpType::Sycamore,    OpType::ISWAPMax,
       OpType::PhasedISWAP, OpType::XXPhase3,    OpType::NPhasedX,
-      OpType::TK2};
+      OpType::TK2,         OpType::Transport};
   static std::unique_ptr<const OpTypeSet> gates =
       std::make_unique<const OpTypeSet>(optypes);
   return *gates;
@@ -107,7 +107,7 @@ const OpTypeSet& all_projective_types() {
 bool is_metaop_type(OpType optype) {
   static const OpTypeSet metaops = {
       OpType::Input,   OpType::Output, OpType::ClInput, OpType::ClOutput,
-      OpType::Barrier, OpType::Create, OpType::Discard};
+      OpType::Barrier, OpType::Delay,  OpType::Create,  OpType::Discard};
   return find_in_set(optype, metaops);
 }
 
@@ -137,12 +137,18 @@ bool is_box_type(OpType optype) {
       OpType::Unitary1qBox,
       OpType::Unitary2qBox,
       OpType::Unitary3qBox,
+      OpType::UnitaryNqBox,
       OpType::ExpBox,
       OpType::PauliExpBox,
       OpType::CustomGate,
       OpType::CliffBox,
       OpType::PhasePolyBox,
       OpType::QControlBox,
+      OpType::MultiplexorBox,
+      OpType::MultiplexedRotationBox,
+      OpType::StatePreparationBox,
+      OpType::DiagonalBox,
+      OpType::ToffoliBox,
       OpType::ClassicalExpBox,
       OpType::ProjectorAssertionBox,
       OpType::StabiliserAssertionBox,
@@ -192,7 +198,8 @@ bool is_oneway_type(OpType optype) {
       OpType::Input,        OpType::Output,   OpType::Measure,
       OpType::ClInput,      OpType::ClOutput, OpType::Barrier,
       OpType::Reset,        OpType::Collapse, OpType::CustomGate,
-      OpType::PhasePolyBox, OpType::Create,   OpType::Discard};
+      OpType::PhasePolyBox, OpType::Create,   OpType::Discard,
+      OpType::Delay};
   return find_in_set(optype, no_defined_inverse);
 }
 
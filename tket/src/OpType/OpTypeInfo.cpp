@@ -85,10 +85,13 @@ const std::map<OpType, OpTypeInfo>& optypeinfo() {
       {OpType::Unitary1qBox, {"Unitary1qBox", "Unitary1qBox", {}, singleq}},
       {OpType::Unitary2qBox, {"Unitary2qBox", "Unitary2qBox", {}, doubleq}},
       {OpType::Unitary3qBox, {"Unitary3qBox", "Unitary3qBox", {}, tripleq}},
+      {OpType::UnitaryNqBox,
+       {"UnitaryNqBox", "UnitaryNqBox", {}, std::nullopt}},
       {OpType::ExpBox, {"ExpBox", "ExpBox", {}, doubleq}},
       {OpType::PauliExpBox, {"PauliExpBox", "PauliExpBox", {}, std::nullopt}},
       {OpType::CustomGate, {"CustomGate", "CustomGate", {}, std::nullopt}},
       {OpType::Barrier, {"Barrier", "Barrier", {}, std::nullopt}},
+      {OpType::Delay, {"Delay", "Delay", {}, std::nullopt}},
       {OpType::Measure,
        {"Measure",
         "Measure",
@@ -108,6 +111,7 @@ const std::map<OpType, OpTypeInfo>& optypeinfo() {
        {"XXPhase3", "$R_{X_0X_1}R_{X_0X_2}R_{X_1X_2}$", {4}, tripleq}},
       {OpType::CnRy, {"CnRy", "CnRy", {4}, std::nullopt}},
       {OpType::CnX, {"CnX", "CnX", {}, std::nullopt}},
+      {OpType::Transport, {"Transport", "Transport", {}, doubleq}},
       {OpType::TK1, {"TK1", "TK1", {4, 4, 4}, singleq}},
       {OpType::TK2, {"TK2", "TK2", {4, 4, 4}, doubleq}},
       {OpType::ESWAP, {"ESWAP", "$\\mathrm{eSWAP}$", {4}, doubleq}},
@@ -119,6 +123,14 @@ const std::map<OpType, OpTypeInfo>& optypeinfo() {
       {OpType::PhasePolyBox,
        {"PhasePolyBox", "PhasePolyBox", {}, std::nullopt}},
       {OpType::QControlBox, {"QControlBox", "Ctrl", {}, std::nullopt}},
+      {OpType::MultiplexorBox,
+       {"MultiplexorBox", "Multiplexor", {}, std::nullopt}},
+      {OpType::MultiplexedRotationBox,
+       {"MultiplexedRotationBox", "MultiplexedRotation", {}, std::nullopt}},
+      {OpType::StatePreparationBox,
+       {"StatePreparationBox", "StatePreparation", {}, std::nullopt}},
+      {OpType::DiagonalBox, {"DiagonalBox", "Diagonal", {}, std::nullopt}},
+      {OpType::ToffoliBox, {"ToffoliBox", "Toffoli", {}, std::nullopt}},
       {OpType::Conditional, {"Conditional", "If", {}, std::nullopt}},
       {OpType::ProjectorAssertionBox,
        {"ProjectorAssertionBox", "ProjectorAssertionBox", {}, std::nullopt}},
@@ -613,7 +613,52 @@ enum class OpType {
   /**
    * See \ref UnitaryTableauBox
    */
-  UnitaryTableauBox
+  UnitaryTableauBox,
+
+  /**
+   * Move a qubit between two sites of a \ref ShuttlingArchitecture
+   *
+   * Acts on the qubits held at the two sites as \ref OpType::SWAP, so the
+   * qubit at the source site ends up at the target site. Rebases to gate sets
+   * without it decompose it as a SWAP.
+   */
+  Transport,
+
+  /**
+   * Wait on the given qubits for a fixed duration, e.g. filling an idle window
+   * of a schedule (see \ref DelayOp)
+   */
+  Delay,
+
+  /**
+   * See \ref UnitaryNqBox
+   */
+  UnitaryNqBox,
+
+  /**
+   * See \ref MultiplexorBox
+   */
+  MultiplexorBox,
+
+  /**
+   * See \ref MultiplexedRotationBox
+   */
+  MultiplexedRotationBox,
+
+  /**
+   * See \ref StatePreparationBox
+   */
+  StatePreparationBox,
+
+  /**
+   * See \ref DiagonalBox
+   */
+  DiagonalBox,
+
+  /**
+   * See \ref ToffoliBox
+   */
+  ToffoliBox
 };
 
 JSON_DECL(OpType)
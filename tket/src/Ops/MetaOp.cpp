@@ -14,6 +14,8 @@
 
 #include "MetaOp.hpp"
 
+#include <sstream>
+#include <stdexcept>
 #include <typeinfo>
 
 #include "OpType/EdgeType.hpp"
@@ -81,4 +83,37 @@ bool MetaOp::is_equal(const Op& op_other) const {
 
 MetaOp::MetaOp() : Op(OpType::Barrier) {}
 
+DelayOp::DelayOp(double duration, unsigned n_qubits)
+    : MetaOp(OpType::Delay, op_signature_t(n_qubits, EdgeType::Quantum)),
+      duration_(duration) {
+  if (duration < 0) {
+    throw std::invalid_argument("Delay duration must be non-negative");
+  }
+}
+
+std::string DelayOp::get_name(bool latex) const {
+  std::stringstream name;
+  name << MetaOp::get_name(latex) << "(" << duration_ << ")";
+  return name.str();
+}
+
+bool DelayOp::is_equal(const Op& op_other) const {
+  const DelayOp* other = dynamic_cast<const DelayOp*>(&op_other);
+  if (!other) return false;
+  return get_signature() == other->get_signature() &&
+         duration_ == other->duration_;
+}
+
+nlohmann::json DelayOp::serialize() const {
+  nlohmann::json j = MetaOp::serialize();
+  j["duration"] = duration_;
+  return j;
+}
+
+Op_ptr DelayOp::deserialize(const nlohmann::json& j) {
+  op_signature_t sig = j.at("signature").get<op_signature_t>();
+  return std::make_shared<DelayOp>(
+      j.at("duration").get<double>(), (unsigned)sig.size());
+}
+
 }  // namespace tket
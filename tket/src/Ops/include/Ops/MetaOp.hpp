@@ -61,4 +61,37 @@ class MetaOp : public Op {
   const std::string data_;
 };
 
+/**
+ * Wait on some qubits for a fixed duration
+ *
+ * The duration is in the same units as those of the gate durations used to
+ * schedule the circuit.
+ */
+class DelayOp : public MetaOp {
+ public:
+  /**
+   * Construct a delay on some qubits
+   *
+   * @param duration length of the delay, which must be non-negative
+   * @param n_qubits number of qubits on which to wait
+   */
+  explicit DelayOp(double duration, unsigned n_qubits = 1);
+
+  std::string get_name(bool latex = false) const override;
+
+  double get_duration() const { return duration_; }
+
+  /**
+   * Equality check between two DelayOp instances
+   */
+  bool is_equal(const Op &other) const override;
+
+  nlohmann::json serialize() const override;
+
+  static Op_ptr deserialize(const nlohmann::json &j);
+
+ private:
+  const double duration_;
+};
+
 }  // namespace tket
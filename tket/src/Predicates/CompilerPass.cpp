@@ -387,6 +387,8 @@ void from_json(const nlohmann::json& j, PassPtr& pp) {
       pp = PeepholeOptimise2Q();
     } else if (passname == "FullPeepholeOptimise") {
       pp = FullPeepholeOptimise();
+    } else if (passname == "ZXGraphlikeOptimisation") {
+      pp = ZXGraphlikeOptimisation();
     } else if (passname == "RebaseTket") {
       pp = RebaseTket();
     } else if (passname == "RebaseUFR") {
@@ -423,6 +425,8 @@ void from_json(const nlohmann::json& j, PassPtr& pp) {
       pp = ComposePhasePolyBoxes(content.at("min_size").get<unsigned>());
     } else if (passname == "RebaseCustom") {
       throw PassNotSerializable(passname);
+    } else if (passname == "RebaseEdgesCustom") {
+      throw PassNotSerializable(passname);
     } else if (passname == "EulerAngleReduction") {
       OpType p = content.at("euler_p").get<OpType>();
       OpType q = content.at("euler_q").get<OpType>();
@@ -432,7 +436,24 @@ void from_json(const nlohmann::json& j, PassPtr& pp) {
       Architecture arc = content.at("architecture").get<Architecture>();
       std::vector<RoutingMethodPtr> con = content.at("routing_config");
       pp = gen_routing_pass(arc, con);
-
+    } else if (passname == "ShuttlingRoutingPass") {
+      ShuttlingArchitecture arc =
+          content.at("shuttling_architecture").get<ShuttlingArchitecture>();
+      std::vector<RoutingMethodPtr> con = content.at("routing_config");
+      pp = gen_shuttling_routing_pass(arc, con);
+
+    } else if (passname == "QubitReusePass") {
+      pp = gen_qubit_reuse_pass(content.at("max_qubits").get<unsigned>());
+    } else if (passname == "CrosstalkScheduling") {
+      pp = gen_crosstalk_scheduling_pass(
+          content.at("characterisation").get<DeviceCharacterisation>(),
+          content.at("crosstalk_threshold").get<gate_error_t>());
+    } else if (passname == "DynamicalDecoupling") {
+      pp = gen_dynamical_decoupling_pass(
+          content.at("durations").get<GateDurations>(),
+          content.at("dd_sequence").get<Circuit>(),
+          content.at("schedule_type").get<ScheduleType>(),
+          content.at("min_duration").get<double>());
     } else if (passname == "PlacementPass") {
       pp = gen_placement_pass(content.at("placement").get<PlacementPtr>());
     } else if (passname == "NaivePlacementPass") {
@@ -20,10 +20,12 @@
 #include "ArchAwareSynth/SteinerForest.hpp"
 #include "Circuit/CircPool.hpp"
 #include "Circuit/Circuit.hpp"
+#include "Converters/Converters.hpp"
 #include "Converters/PhasePoly.hpp"
 #include "Mapping/LexiLabelling.hpp"
 #include "Mapping/LexiRoute.hpp"
 #include "Mapping/MappingManager.hpp"
+#include "Mapping/QubitReuse.hpp"
 #include "Placement/Placement.hpp"
 #include "Predicates/CompilationUnit.hpp"
 #include "Predicates/CompilerPass.hpp"
@@ -31,13 +33,16 @@
 #include "Predicates/Predicates.hpp"
 #include "Transformations/BasicOptimisation.hpp"
 #include "Transformations/ContextualReduction.hpp"
+#include "Transformations/CrosstalkScheduling.hpp"
 #include "Transformations/Decomposition.hpp"
+#include "Transformations/DynamicalDecoupling.hpp"
 #include "Transformations/OptimisationPass.hpp"
 #include "Transformations/PauliOptimisation.hpp"
 #include "Transformations/Rebase.hpp"
 #include "Transformations/ThreeQubitSquash.hpp"
 #include "Transformations/Transform.hpp"
 #include "Utils/Json.hpp"
+#include "ZX/Rewrite.hpp"
 
 namespace tket {
 
@@ -70,6 +75,40 @@ PassPtr gen_rebase_pass(
   return std::make_shared<StandardPass>(precons, t, pc, j);
 }
 
+PassPtr gen_edge_rebase_pass(
+    const Architecture& arc, const OpTypeSet& allowed_gates,
+    const Circuit& cx_replacement,
+    const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
+        tk1_replacement) {
+  Transform t = Transforms::rebase_edges_factory(
+      arc, allowed_gates, cx_replacement, tk1_replacement);
+
+  PredicatePtrMap precons;
+  OpTypeSet all_types(allowed_gates);
+  all_types.insert(OpType::Measure);
+  all_types.insert(OpType::Collapse);
+  all_types.insert(OpType::Reset);
+  PredicatePtr postcon1 =
+      std::make_shared<EdgeGateSetPredicate>(all_types, arc);
+  PredicatePtr postcon2 = std::make_shared<MaxTwoQubitGatesPredicate>();
+  std::pair<const std::type_index, PredicatePtr> pair1 =
+      CompilationUnit::make_type_pair(postcon1);
+  PredicatePtrMap s_postcons{pair1, CompilationUnit::make_type_pair(postcon2)};
+  PredicateClassGuarantees g_postcons{
+      {pair1.first, Guarantee::Clear},
+      {typeid(GateSetPredicate), Guarantee::Clear}};
+  PostConditions pc = {s_postcons, g_postcons, Guarantee::Preserve};
+  // record pass config
+  nlohmann::json j;
+  j["name"] = "RebaseEdgesCustom";
+  j["architecture"] = arc;
+  j["basis_allowed"] = allowed_gates;
+  j["basis_cx_replacement"] = cx_replacement;
+  j["basis_tk1_replacement"] =
+      "SERIALIZATION OF FUNCTIONS IS NOT YET SUPPORTED";
+  return std::make_shared<StandardPass>(precons, t, pc, j);
+}
+
 PassPtr gen_squash_pass(
     const OpTypeSet& singleqs,
     const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
@@ -281,6 +320,123 @@ PassPtr gen_routing_pass(
   return std::make_shared<StandardPass>(precons, t, pc, j);
 }
 
+PassPtr gen_shuttling_routing_pass(
+    const ShuttlingArchitecture& arc,
+    const std::vector<RoutingMethodPtr>& config) {
+  Transform::Transformation trans = [=](Circuit& circ,
+                                        std::shared_ptr<unit_bimaps_t> maps) {
+    // routing methods need the zones, so keep the full architecture
+    MappingManager mm(std::make_shared<ShuttlingArchitecture>(arc));
+    return mm.route_circuit_with_maps(circ, config, maps);
+  };
+  Transform t = Transform(trans);
+
+  PredicatePtr twoqbpred = std::make_shared<MaxTwoQubitGatesPredicate>();
+  PredicatePtr n_qubit_pred =
+      std::make_shared<MaxNQubitsPredicate>(arc.n_nodes());
+  PredicatePtrMap precons{
+      CompilationUnit::make_type_pair(twoqbpred),
+      CompilationUnit::make_type_pair(n_qubit_pred)};
+
+  PredicatePtr postcon1 = std::make_shared<ZoneConstraintsPredicate>(arc);
+  std::pair<const std::type_index, PredicatePtr> pair1 =
+      CompilationUnit::make_type_pair(postcon1);
+  PredicatePtr postcon2 = std::make_shared<NoWireSwapsPredicate>();
+  PredicatePtrMap s_postcons{pair1, CompilationUnit::make_type_pair(postcon2)};
+  std::type_index gateset_ti = typeid(GateSetPredicate);
+  // clears Connectivity predicates (Transports act on unconnected sites)
+  // clears all GateSet predicates (inserts Transports)
+  PredicateClassGuarantees g_postcons{
+      {pair1.first, Guarantee::Clear},
+      {typeid(ConnectivityPredicate), Guarantee::Clear},
+      {gateset_ti, Guarantee::Clear},
+      {typeid(MaxTwoQubitGatesPredicate), Guarantee::Clear}};
+  PostConditions pc{s_postcons, g_postcons, Guarantee::Preserve};
+
+  // record pass config
+  nlohmann::json j;
+  j["name"] = "ShuttlingRoutingPass";
+  j["routing_config"] = config;
+  j["shuttling_architecture"] = arc;
+
+  return std::make_shared<StandardPass>(precons, t, pc, j);
+}
+
+PassPtr gen_qubit_reuse_pass(unsigned max_qubits) {
+  Transform::Transformation trans = [=](Circuit& circ,
+                                        std::shared_ptr<unit_bimaps_t> maps) {
+    return !reuse_qubits(circ, max_qubits, maps).empty();
+  };
+  Transform t = Transform(trans);
+
+  PredicatePtr wireswaps_pred = std::make_shared<NoWireSwapsPredicate>();
+  PredicatePtrMap precons{CompilationUnit::make_type_pair(wireswaps_pred)};
+
+  // merged wires move operations onto other qubits, add Reset gates, and
+  // leave Measure gates in the middle of wires
+  PredicateClassGuarantees g_postcons{
+      {typeid(ConnectivityPredicate), Guarantee::Clear},
+      {typeid(DirectednessPredicate), Guarantee::Clear},
+      {typeid(GateSetPredicate), Guarantee::Clear},
+      {typeid(NoMidMeasurePredicate), Guarantee::Clear},
+      {typeid(CliffordCircuitPredicate), Guarantee::Clear}};
+  PostConditions pc{{}, g_postcons, Guarantee::Preserve};
+
+  // record pass config
+  nlohmann::json j;
+  j["name"] = "QubitReusePass";
+  j["max_qubits"] = max_qubits;
+
+  return std::make_shared<StandardPass>(precons, t, pc, j);
+}
+
+PassPtr gen_crosstalk_scheduling_pass(
+    const DeviceCharacterisation& characterisation, gate_error_t threshold) {
+  Transform t = Transforms::schedule_crosstalk(characterisation, threshold);
+  PredicatePtrMap precons;
+  PredicatePtr crosstalk_pred =
+      std::make_shared<CrosstalkPredicate>(characterisation, threshold);
+  PredicatePtrMap s_postcons{CompilationUnit::make_type_pair(crosstalk_pred)};
+  // only Barriers are added, between gates on different qubits
+  PredicateClassGuarantees g_postcons{
+      {typeid(NoBarriersPredicate), Guarantee::Clear}};
+  PostConditions pc{s_postcons, g_postcons, Guarantee::Preserve};
+
+  // record pass config
+  nlohmann::json j;
+  j["name"] = "CrosstalkScheduling";
+  j["characterisation"] = characterisation;
+  j["crosstalk_threshold"] = threshold;
+
+  return std::make_shared<StandardPass>(precons, t, pc, j);
+}
+
+PassPtr gen_dynamical_decoupling_pass(
+    const GateDurations& durations, const Circuit& sequence, ScheduleType type,
+    double min_duration) {
+  Transform t =
+      Transforms::dynamical_decoupling(durations, sequence, type, min_duration);
+  PredicatePtrMap precons;
+  // single-qubit gates of the sequence are added between operations, but
+  // never after a final measurement
+  PredicateClassGuarantees g_postcons{
+      {typeid(GateSetPredicate), Guarantee::Clear},
+      {typeid(EdgeGateSetPredicate), Guarantee::Clear},
+      {typeid(CliffordCircuitPredicate), Guarantee::Clear},
+      {typeid(GlobalPhasedXPredicate), Guarantee::Clear}};
+  PostConditions pc{{}, g_postcons, Guarantee::Preserve};
+
+  // record pass config
+  nlohmann::json j;
+  j["name"] = "DynamicalDecoupling";
+  j["durations"] = durations;
+  j["dd_sequence"] = sequence;
+  j["schedule_type"] = type;
+  j["min_duration"] = min_duration;
+
+  return std::make_shared<StandardPass>(precons, t, pc, j);
+}
+
 PassPtr gen_placement_pass_phase_poly(const Architecture& arc) {
   Transform::Transformation trans = [=](Circuit& circ,
                                         std::shared_ptr<unit_bimaps_t> maps) {
@@ -751,4 +907,65 @@ PassPtr GlobalisePhasedX(bool squash) {
   return std::make_shared<StandardPass>(precons, t, postcon, j);
 }
 
+static unsigned n_non_clifford_gates(const Circuit& circ) {
+  unsigned count = 0;
+  for (const Command& com : circ) {
+    if (!com.get_op_ptr()->is_clifford()) ++count;
+  }
+  return count;
+}
+
+PassPtr ZXGraphlikeOptimisation() {
+  Transform t = Transform([](Circuit& circ) {
+    zx::ZXDiagram diag;
+    boost::bimap<zx::ZXVert, Vertex> bmap;
+    std::tie(diag, bmap) = circuit_to_zx(circ);
+    zx::Rewrite::to_graphlike_form().apply(diag);
+    zx::Rewrite::reduce_graphlike_form().apply(diag);
+    zx::Rewrite::to_MBQC_diag().apply(diag);
+    Circuit extracted = zx_to_circuit(diag);
+    // Qubit i of the extracted circuit runs from the i-th input of the
+    // diagram to its i-th output, which are mapped back to the original units
+    zx::ZXVertVec ins = diag.get_boundary(zx::ZXType::Input);
+    zx::ZXVertVec outs = diag.get_boundary(zx::ZXType::Output);
+    std::map<Qubit, Qubit> in_map;
+    qubit_map_t out_map;
+    for (unsigned i = 0; i < ins.size(); ++i) {
+      Qubit in_q(circ.get_id_from_in(bmap.left.at(ins.at(i))));
+      Qubit out_q(circ.get_id_from_out(bmap.left.at(outs.at(i))));
+      in_map.insert({Qubit(i), in_q});
+      out_map.insert({in_q, out_q});
+    }
+    extracted.rename_units(in_map);
+    extracted.permute_boundary_output(out_map);
+    // the extraction is not guaranteed to improve on the original circuit
+    if (n_non_clifford_gates(extracted) >= n_non_clifford_gates(circ)) {
+      return false;
+    }
+    circ = extracted;
+    return true;
+  });
+  OpTypeSet in_set = {OpType::H,  OpType::X,    OpType::Z,
+                      OpType::Rx, OpType::Rz,   OpType::CX,
+                      OpType::CZ, OpType::SWAP, OpType::noop};
+  PredicatePtr in_gateset = std::make_shared<GateSetPredicate>(in_set);
+  PredicatePtr nobits = std::make_shared<NoClassicalBitsPredicate>();
+  PredicatePtrMap precons = {
+      CompilationUnit::make_type_pair(in_gateset),
+      CompilationUnit::make_type_pair(nobits)};
+  // the original circuit is kept if the extracted one is no better
+  OpTypeSet out_set = in_set;
+  out_set.insert({OpType::S, OpType::Sdg, OpType::U1});
+  PredicatePtr out_gateset = std::make_shared<GateSetPredicate>(out_set);
+  PredicatePtrMap s_postcons = {CompilationUnit::make_type_pair(out_gateset)};
+  PredicateClassGuarantees g_postcons = {
+      {typeid(ConnectivityPredicate), Guarantee::Clear},
+      {typeid(DirectednessPredicate), Guarantee::Clear},
+      {typeid(CliffordCircuitPredicate), Guarantee::Clear}};
+  PostConditions postcon{s_postcons, g_postcons, Guarantee::Preserve};
+  nlohmann::json j;
+  j["name"] = "ZXGraphlikeOptimisation";
+  return std::make_shared<StandardPass>(precons, t, postcon, j);
+}
+
 }  // namespace tket
@@ -18,6 +18,7 @@
 #include "Mapping/Verification.hpp"
 #include "OpType/OpTypeFunctions.hpp"
 #include "Placement/Placement.hpp"
+#include "Transformations/CrosstalkScheduling.hpp"
 #include "Utils/MatrixAnalysis.hpp"
 #include "Utils/UnitID.hpp"
 
@@ -56,8 +57,10 @@ const std::string& predicate_name(std::type_index idx) {
 #define SET_PRED_NAME(a) {typeid(a), #a}
       SET_PRED_NAME(CliffordCircuitPredicate),
       SET_PRED_NAME(ConnectivityPredicate),
+      SET_PRED_NAME(CrosstalkPredicate),
       SET_PRED_NAME(DefaultRegisterPredicate),
       SET_PRED_NAME(DirectednessPredicate),
+      SET_PRED_NAME(EdgeGateSetPredicate),
       SET_PRED_NAME(GateSetPredicate),
       SET_PRED_NAME(MaxNQubitsPredicate),
       SET_PRED_NAME(MaxTwoQubitGatesPredicate),
@@ -71,7 +74,8 @@ const std::string& predicate_name(std::type_index idx) {
       SET_PRED_NAME(NormalisedTK2Predicate),
       SET_PRED_NAME(NoWireSwapsPredicate),
       SET_PRED_NAME(PlacementPredicate),
-      SET_PRED_NAME(UserDefinedPredicate)};
+      SET_PRED_NAME(UserDefinedPredicate),
+      SET_PRED_NAME(ZoneConstraintsPredicate)};
 #undef SET_PRED_NAME
   return predicate_names.at(idx);
 }
@@ -137,6 +141,108 @@ std::string GateSetPredicate::to_string() const {
   return str;
 }
 
+OpTypeSet EdgeGateSetPredicate::get_allowed_two_qubit_types(
+    const Node& node_0, const Node& node_1) const {
+  auto it = gate_sets_.find(
+      (node_1 < node_0) ? Architecture::Connection{node_1, node_0}
+                        : Architecture::Connection{node_0, node_1});
+  if (it != gate_sets_.end()) return it->second;
+  OpTypeSet two_qubit_types;
+  for (const OpType& ot : allowed_types_) {
+    if (find_in_set(ot, all_multi_qubit_types())) two_qubit_types.insert(ot);
+  }
+  return two_qubit_types;
+}
+
+bool EdgeGateSetPredicate::verify(const Circuit& circ) const {
+  for (const Command& com : circ) {
+    Op_ptr op = com.get_op_ptr();
+    if (op->get_desc().is_meta()) continue;
+    OpType type = op->get_type();
+    if (type == OpType::Conditional) {
+      const Conditional& cond = static_cast<const Conditional&>(*op);
+      type = cond.get_op()->get_type();
+    }
+    qubit_vector_t qbs = com.get_qubits();
+    if (qbs.size() == 2) {
+      Node n0(qbs[0]), n1(qbs[1]);
+      auto it = gate_sets_.find(
+          (n1 < n0) ? Architecture::Connection{n1, n0}
+                    : Architecture::Connection{n0, n1});
+      if (it != gate_sets_.end()) {
+        if (!find_in_set(type, it->second)) return false;
+        continue;
+      }
+    }
+    if (!find_in_set(type, allowed_types_)) return false;
+  }
+  return true;
+}
+
+bool EdgeGateSetPredicate::implies(const Predicate& other) const {
+  try {
+    const EdgeGateSetPredicate& other_p =
+        dynamic_cast<const EdgeGateSetPredicate&>(other);
+    for (const OpType& ot : allowed_types_) {
+      if (!find_in_set(ot, other_p.allowed_types_)) return false;
+    }
+    std::set<Architecture::Connection> edges;
+    for (const auto& [con, gate_set] : gate_sets_) edges.insert(con);
+    for (const auto& [con, gate_set] : other_p.gate_sets_) edges.insert(con);
+    for (const Architecture::Connection& con : edges) {
+      OpTypeSet other_types =
+          other_p.get_allowed_two_qubit_types(con.first, con.second);
+      for (const OpType& ot :
+           get_allowed_two_qubit_types(con.first, con.second)) {
+        if (!find_in_set(ot, other_types)) return false;
+      }
+    }
+    return true;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+PredicatePtr EdgeGateSetPredicate::meet(const Predicate& other) const {
+  try {
+    const EdgeGateSetPredicate& other_p =
+        dynamic_cast<const EdgeGateSetPredicate&>(other);
+    OpTypeSet new_set;
+    for (const OpType& ot : allowed_types_) {
+      if (find_in_set(ot, other_p.allowed_types_)) new_set.insert(ot);
+    }
+    std::set<Architecture::Connection> edges;
+    for (const auto& [con, gate_set] : gate_sets_) edges.insert(con);
+    for (const auto& [con, gate_set] : other_p.gate_sets_) edges.insert(con);
+    edge_gate_sets_t new_gate_sets;
+    for (const Architecture::Connection& con : edges) {
+      OpTypeSet other_types =
+          other_p.get_allowed_two_qubit_types(con.first, con.second);
+      OpTypeSet& new_types = new_gate_sets[con];
+      for (const OpType& ot :
+           get_allowed_two_qubit_types(con.first, con.second)) {
+        if (find_in_set(ot, other_types)) new_types.insert(ot);
+      }
+    }
+    PredicatePtr pp =
+        std::make_shared<EdgeGateSetPredicate>(new_set, new_gate_sets);
+    return pp;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+std::string EdgeGateSetPredicate::to_string() const {
+  std::string str = auto_name(*this) + ":{ ";
+  for (const OpType& ot : allowed_types_) {
+    str += (optypeinfo().find(ot)->second.name + " ");
+  }
+  str += ("Gate sets: " + std::to_string(gate_sets_.size())) += " }";
+  return str;
+}
+
 bool NoClassicalControlPredicate::verify(const Circuit& circ) const {
   BGL_FORALL_VERTICES(v, circ.dag, DAG) {
     Op_ptr op = circ.get_Op_ptr_from_Vertex(v);
@@ -689,12 +795,130 @@ std::string NormalisedTK2Predicate::to_string() const {
   return auto_name(*this);
 }
 
+bool ZoneConstraintsPredicate::verify(const Circuit& circ) const {
+  for (const Qubit& qb : circ.all_qubits()) {
+    if (!arch_.node_exists(Node(qb))) return false;
+  }
+  for (const Command& com : circ) {
+    Op_ptr op = com.get_op_ptr();
+    if (op->get_type() == OpType::Conditional) {
+      op = static_cast<const Conditional&>(*op).get_op();
+    }
+    if (op->get_type() == OpType::Barrier) continue;
+    qubit_vector_t qbs = com.get_qubits();
+    if (op->get_type() == OpType::Transport) {
+      if (!arch_.transport_exists(Node(qbs[0]), Node(qbs[1]))) return false;
+    } else if (qbs.size() > 1) {
+      unsigned zone = arch_.get_zone_index(Node(qbs[0]));
+      if (!arch_.get_zones()[zone].interaction) return false;
+      for (const Qubit& qb : qbs) {
+        if (arch_.get_zone_index(Node(qb)) != zone) return false;
+      }
+    }
+  }
+  return true;
+}
+
+bool ZoneConstraintsPredicate::implies(const Predicate& other) const {
+  try {
+    const ZoneConstraintsPredicate& other_c =
+        dynamic_cast<const ZoneConstraintsPredicate&>(other);
+    return arch_ == other_c.arch_;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+PredicatePtr ZoneConstraintsPredicate::meet(const Predicate& other) const {
+  try {
+    const ZoneConstraintsPredicate& other_c =
+        dynamic_cast<const ZoneConstraintsPredicate&>(other);
+    // zone constraints of different devices have no useful common strengthening
+    if (!(arch_ == other_c.arch_)) {
+      throw IncorrectPredicate(
+          "Cannot meet zone constraints of different architectures");
+    }
+    PredicatePtr pp = std::make_shared<ZoneConstraintsPredicate>(arch_);
+    return pp;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+std::string ZoneConstraintsPredicate::to_string() const {
+  std::string str = auto_name(*this) + ":{ ";
+  str +=
+      ("Zones: " + std::to_string(arch_.get_zones().size()) +
+       ", Sites: " + std::to_string(arch_.n_nodes())) += " }";
+  return str;
+}
+
+bool CrosstalkPredicate::verify(const Circuit& circ) const {
+  return get_crosstalk_report(circ, characterisation_, threshold_)
+             .n_conflicts == 0;
+}
+
+bool CrosstalkPredicate::implies(const Predicate& other) const {
+  try {
+    const CrosstalkPredicate& other_c =
+        dynamic_cast<const CrosstalkPredicate&>(other);
+    return characterisation_ == other_c.characterisation_ &&
+           threshold_ <= other_c.threshold_;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+PredicatePtr CrosstalkPredicate::meet(const Predicate& other) const {
+  try {
+    const CrosstalkPredicate& other_c =
+        dynamic_cast<const CrosstalkPredicate&>(other);
+    if (!(characterisation_ == other_c.characterisation_)) {
+      throw IncorrectPredicate(
+          "Cannot meet crosstalk constraints of different characterisations");
+    }
+    PredicatePtr pp = std::make_shared<CrosstalkPredicate>(
+        characterisation_, std::min(threshold_, other_c.threshold_));
+    return pp;
+  } catch (const std::bad_cast&) {
+    throw IncorrectPredicate(
+        "Cannot compare predicates of different subclasses");
+  }
+}
+
+std::string CrosstalkPredicate::to_string() const {
+  std::string str = auto_name(*this) + ":{ ";
+  str +=
+      ("Link pairs: " +
+       std::to_string(characterisation_.get_crosstalk_errors().size()) +
+       ", Threshold: " + std::to_string(threshold_)) += " }";
+  return str;
+}
+
 void to_json(nlohmann::json& j, const PredicatePtr& pred_ptr) {
   if (std::shared_ptr<GateSetPredicate> cast_pred =
           std::dynamic_pointer_cast<GateSetPredicate>(pred_ptr)) {
     j["type"] = "GateSetPredicate";
     j["allowed_types"] = cast_pred->get_allowed_types();
     std::sort(j["allowed_types"].begin(), j["allowed_types"].end());
+  } else if (
+      std::shared_ptr<EdgeGateSetPredicate> cast_pred =
+          std::dynamic_pointer_cast<EdgeGateSetPredicate>(pred_ptr)) {
+    j["type"] = "EdgeGateSetPredicate";
+    j["allowed_types"] = cast_pred->get_allowed_types();
+    std::sort(j["allowed_types"].begin(), j["allowed_types"].end());
+    nlohmann::json gate_sets = nlohmann::json::array();
+    for (const auto& [con, gate_set] : cast_pred->get_gate_sets()) {
+      nlohmann::json entry;
+      entry["link"] = con;
+      entry["gate_set"] = gate_set;
+      std::sort(entry["gate_set"].begin(), entry["gate_set"].end());
+      gate_sets.push_back(entry);
+    }
+    j["gate_sets"] = gate_sets;
   } else if (
       std::shared_ptr<NoClassicalControlPredicate> cast_pred =
           std::dynamic_pointer_cast<NoClassicalControlPredicate>(pred_ptr)) {
@@ -768,6 +992,17 @@ void to_json(nlohmann::json& j, const PredicatePtr& pred_ptr) {
       std::shared_ptr<NormalisedTK2Predicate> cast_pred =
           std::dynamic_pointer_cast<NormalisedTK2Predicate>(pred_ptr)) {
     j["type"] = "NormalisedTK2Predicate";
+  } else if (
+      std::shared_ptr<ZoneConstraintsPredicate> cast_pred =
+          std::dynamic_pointer_cast<ZoneConstraintsPredicate>(pred_ptr)) {
+    j["type"] = "ZoneConstraintsPredicate";
+    j["shuttling_architecture"] = cast_pred->get_arch();
+  } else if (
+      std::shared_ptr<CrosstalkPredicate> cast_pred =
+          std::dynamic_pointer_cast<CrosstalkPredicate>(pred_ptr)) {
+    j["type"] = "CrosstalkPredicate";
+    j["characterisation"] = cast_pred->get_characterisation();
+    j["threshold"] = cast_pred->get_threshold();
   } else {
     throw JsonError("Cannot serialize PredicatePtr of unknown type.");
   }
@@ -778,6 +1013,15 @@ void from_json(const nlohmann::json& j, PredicatePtr& pred_ptr) {
   if (classname == "GateSetPredicate") {
     OpTypeSet allowed_types = j.at("allowed_types").get<OpTypeSet>();
     pred_ptr = std::make_shared<GateSetPredicate>(allowed_types);
+  } else if (classname == "EdgeGateSetPredicate") {
+    OpTypeSet allowed_types = j.at("allowed_types").get<OpTypeSet>();
+    EdgeGateSetPredicate::edge_gate_sets_t gate_sets;
+    for (const auto& j_entry : j.at("gate_sets")) {
+      gate_sets.insert(
+          {j_entry.at("link").get<Architecture::Connection>(),
+           j_entry.at("gate_set").get<OpTypeSet>()});
+    }
+    pred_ptr = std::make_shared<EdgeGateSetPredicate>(allowed_types, gate_sets);
   } else if (classname == "NoClassicalControlPredicate") {
     pred_ptr = std::make_shared<NoClassicalControlPredicate>();
   } else if (classname == "NoFastFeedforwardPredicate") {
@@ -816,6 +1060,14 @@ void from_json(const nlohmann::json& j, PredicatePtr& pred_ptr) {
     pred_ptr = std::make_shared<GlobalPhasedXPredicate>();
   } else if (classname == "NormalisedTK2Predicate") {
     pred_ptr = std::make_shared<NormalisedTK2Predicate>();
+  } else if (classname == "ZoneConstraintsPredicate") {
+    ShuttlingArchitecture arch =
+        j.at("shuttling_architecture").get<ShuttlingArchitecture>();
+    pred_ptr = std::make_shared<ZoneConstraintsPredicate>(arch);
+  } else if (classname == "CrosstalkPredicate") {
+    pred_ptr = std::make_shared<CrosstalkPredicate>(
+        j.at("characterisation").get<DeviceCharacterisation>(),
+        j.at("threshold").get<gate_error_t>());
   } else {
     throw JsonError("Cannot load PredicatePtr of unknown type.");
   }
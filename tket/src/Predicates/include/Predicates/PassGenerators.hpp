@@ -15,12 +15,14 @@
 #pragma once
 
 #include "ArchAwareSynth/SteinerForest.hpp"
+#include "Characterisation/DeviceCharacterisation.hpp"
 #include "CompilerPass.hpp"
 #include "Mapping/LexiRoute.hpp"
 #include "Mapping/RoutingMethod.hpp"
 #include "Transformations/ContextualReduction.hpp"
 #include "Transformations/Decomposition.hpp"
 #include "Transformations/PauliOptimisation.hpp"
+#include "Transformations/Schedule.hpp"
 
 namespace tket {
 
@@ -30,6 +32,13 @@ PassPtr gen_rebase_pass(
     const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
         tk1_replacement);
 
+/* a wrapper method for the rebase_edges_factory in Transforms */
+PassPtr gen_edge_rebase_pass(
+    const Architecture& arc, const OpTypeSet& allowed_gates,
+    const Circuit& cx_replacement,
+    const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
+        tk1_replacement);
+
 /* a wrapper method for the squash_factory in Transforms */
 PassPtr gen_squash_pass(
     const OpTypeSet& singleqs,
@@ -61,9 +70,63 @@ PassPtr gen_cx_mapping_pass(
     bool delay_measures);
 PassPtr gen_routing_pass(
     const Architecture& arc, const std::vector<RoutingMethodPtr>& config);
+
+/**
+ * Pass to route a circuit to a ShuttlingArchitecture, e.g. with a
+ * ShuttlingRoutingMethod inserting OpType::Transport operations.
+ *
+ * @param arc Shuttling architecture to route to
+ * @param config Routing methods to use, in order of preference
+ * @return passpointer to perform routing, guaranteeing a
+ * ZoneConstraintsPredicate
+ */
+PassPtr gen_shuttling_routing_pass(
+    const ShuttlingArchitecture& arc,
+    const std::vector<RoutingMethodPtr>& config);
 PassPtr gen_directed_cx_routing_pass(
     const Architecture& arc, const std::vector<RoutingMethodPtr>& config);
 
+/**
+ * Pass to reduce the number of qubits in a circuit by reusing qubits freed by
+ * a Measure or Discard for qubits starting with a Reset or Create, inserting
+ * a Reset between them where needed. Classical wires are untouched, so the
+ * circuit is equivalent under classical control. See reuse_qubits.
+ *
+ * @param max_qubits Stop once the circuit has at most this many qubits, or 0
+ * to merge as many qubits as possible
+ * @return passpointer to perform qubit reuse
+ */
+PassPtr gen_qubit_reuse_pass(unsigned max_qubits = 0);
+
+/**
+ * Pass to schedule two-qubit gates so that no pair of gates with a crosstalk
+ * penalty above the threshold shares a slice, by inserting Barriers. See
+ * Transforms::schedule_crosstalk.
+ *
+ * @param characterisation Device characterisation with crosstalk penalties
+ * @param threshold Penalty above which a pair of gates must not share a slice
+ * @return passpointer to perform crosstalk-aware scheduling
+ */
+PassPtr gen_crosstalk_scheduling_pass(
+    const DeviceCharacterisation& characterisation,
+    gate_error_t threshold = 0.);
+
+/**
+ * Pass to fill the idle windows of a schedule of the circuit with a dynamical
+ * decoupling sequence, keeping the unitary of the circuit. See
+ * Transforms::dynamical_decoupling and get_dd_sequence.
+ *
+ * @param durations durations of the operations in the circuit and sequence
+ * @param sequence single-qubit circuit of gates whose product is the identity
+ *  up to global phase
+ * @param type whether operations start as soon or as late as possible
+ * @param min_duration shortest idle window to fill
+ * @return passpointer to perform dynamical decoupling
+ */
+PassPtr gen_dynamical_decoupling_pass(
+    const GateDurations& durations, const Circuit& sequence,
+    ScheduleType type = ScheduleType::ASAP, double min_duration = 0.);
+
 /**
  * execute architecture aware synthesis on a given architecture for an allready
  * place circuit, only for circuit which contains Cx+Rz+H gates
@@ -269,4 +332,25 @@ PassPtr PauliSquash(Transforms::PauliSynthStrat strat, CXConfigType cx_config);
  */
 PassPtr GlobalisePhasedX(bool squash = true);
 
+/**
+ * Resynthesises the circuit by optimising it as a ZX diagram
+ *
+ * The circuit is converted into a graph-like ZX diagram, which is reduced by
+ * removing interior Clifford spiders by local complementation and pivoting
+ * (including pivots next to the boundary), moving the remaining non-Clifford
+ * phases onto phase gadgets and merging gadgets on the same spiders for as
+ * long as this reduces the number of non-Clifford spiders. A circuit is then
+ * extracted from the diagram via its gflow. The original circuit is kept
+ * unless the extracted one has fewer non-Clifford gates.
+ *
+ * The circuit must be unitary, without classical bits, and use only the gates
+ * supported by the conversion to ZX (H, X, Z, Rx, Rz, CX, CZ and SWAP). As a
+ * resynthesis, this ignores any optimisation performed beforehand. The ZX
+ * diagram does not track the global phase, so the unitary is only preserved
+ * up to global phase, and the phase of the extracted circuit is arbitrary.
+ *
+ * @return pass to optimise a circuit via ZX calculus
+ */
+PassPtr ZXGraphlikeOptimisation();
+
 }  // namespace tket
@@ -17,6 +17,8 @@
 #include <typeindex>
 
 #include "Architecture/Architecture.hpp"
+#include "Architecture/ShuttlingArchitecture.hpp"
+#include "Characterisation/DeviceCharacterisation.hpp"
 #include "Transformations/Transform.hpp"
 
 namespace tket {
@@ -79,6 +81,52 @@ class GateSetPredicate : public Predicate {
   const OpTypeSet allowed_types_;
 };
 
+/**
+ * Asserts that all gates of the circuit are native to the device, for a device
+ * whose connections may natively support different two-qubit gates.
+ *
+ * Two-qubit gates acting on a connection with a gate set must be in that gate
+ * set. All other gates must be in the given set of allowed types.
+ */
+class EdgeGateSetPredicate : public Predicate {
+ public:
+  typedef std::map<Architecture::Connection, OpTypeSet> edge_gate_sets_t;
+
+  /**
+   * @param allowed_types Gate types allowed other than on connections with a
+   * gate set
+   * @param gate_sets Gate sets of connections, keyed by connections with the
+   * lesser node first
+   */
+  EdgeGateSetPredicate(
+      const OpTypeSet& allowed_types, const edge_gate_sets_t& gate_sets)
+      : allowed_types_(allowed_types), gate_sets_(gate_sets) {}
+  /**
+   * @param allowed_types Gate types allowed other than on connections with a
+   * gate set
+   * @param arch Architecture providing the gate sets of its connections
+   */
+  EdgeGateSetPredicate(const OpTypeSet& allowed_types, const Architecture& arch)
+      : EdgeGateSetPredicate(allowed_types, arch.get_gate_sets()) {}
+  bool verify(const Circuit& circ) const override;
+  bool implies(const Predicate& other) const override;
+  PredicatePtr meet(const Predicate& other) const override;
+
+  std::string to_string() const override;
+  const OpTypeSet& get_allowed_types() const { return allowed_types_; }
+  const edge_gate_sets_t& get_gate_sets() const { return gate_sets_; }
+
+  /**
+   * @return Two-qubit gate types allowed on the connection between two nodes
+   */
+  OpTypeSet get_allowed_two_qubit_types(
+      const Node& node_0, const Node& node_1) const;
+
+ private:
+  const OpTypeSet allowed_types_;
+  const edge_gate_sets_t gate_sets_;
+};
+
 /**
  * Asserts that there are no conditional gates in the circuit.
  */
@@ -281,4 +329,52 @@ class NormalisedTK2Predicate : public Predicate {
   std::string to_string() const override;
 };
 
+/**
+ * Asserts that the circuit can run on a ShuttlingArchitecture
+ *
+ * All qubits must be sites of the architecture, every \ref OpType::Transport
+ * must be along a transport connection, and every other multi-qubit gate
+ * must act on sites of a single interaction zone.
+ */
+class ZoneConstraintsPredicate : public Predicate {
+ public:
+  explicit ZoneConstraintsPredicate(const ShuttlingArchitecture& arch)
+      : arch_(arch) {}
+  bool verify(const Circuit& circ) const override;
+  bool implies(const Predicate& other) const override;
+  PredicatePtr meet(const Predicate& other) const override;
+  std::string to_string() const override;
+  const ShuttlingArchitecture& get_arch() const { return arch_; }
+
+ private:
+  const ShuttlingArchitecture arch_;
+};
+
+/**
+ * Asserts that no two two-qubit gates sharing a slice of the circuit have a
+ * crosstalk penalty above a threshold
+ *
+ * Penalties are those of the links the gates act on in a
+ * DeviceCharacterisation, and slices are as given by Circuit::get_slices.
+ */
+class CrosstalkPredicate : public Predicate {
+ public:
+  explicit CrosstalkPredicate(
+      const DeviceCharacterisation& characterisation,
+      gate_error_t threshold = 0.)
+      : characterisation_(characterisation), threshold_(threshold) {}
+  bool verify(const Circuit& circ) const override;
+  bool implies(const Predicate& other) const override;
+  PredicatePtr meet(const Predicate& other) const override;
+  std::string to_string() const override;
+  const DeviceCharacterisation& get_characterisation() const {
+    return characterisation_;
+  }
+  gate_error_t get_threshold() const { return threshold_; }
+
+ private:
+  const DeviceCharacterisation characterisation_;
+  const gate_error_t threshold_;
+};
+
 }  // namespace tket
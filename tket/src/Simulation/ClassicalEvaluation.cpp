@@ -0,0 +1,62 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "ClassicalEvaluation.hpp"
+
+#include <map>
+#include <numeric>
+#include <tkassert/Assert.hpp>
+
+#include "Circuit/Circuit.hpp"
+#include "Circuit/Conditional.hpp"
+
+namespace tket {
+namespace tket_sim {
+namespace internal {
+
+void apply_classical_op(
+    const Op_ptr& op, const std::vector<unsigned>& bit_indices,
+    std::vector<bool>& bits) {
+  // Evaluate the op as a circuit on its own arguments.
+  const unsigned n_args = bit_indices.size();
+  Circuit circ(0, n_args);
+  std::vector<unsigned> args(n_args);
+  std::iota(args.begin(), args.end(), 0);
+  circ.add_op<unsigned>(op, args);
+  std::map<Bit, bool> values;
+  for (unsigned i = 0; i < n_args; ++i) {
+    values[Bit(i)] = bits[bit_indices[i]];
+  }
+  for (const auto& [bit, value] : circ.classical_eval(values)) {
+    bits[bit_indices[bit.index().at(0)]] = value;
+  }
+}
+
+bool condition_holds(
+    const Conditional& cond, const std::vector<unsigned>& bit_indices,
+    const std::vector<bool>& bits) {
+  TKET_ASSERT(bit_indices.size() >= cond.get_width());
+  for (unsigned i = 0; i < cond.get_width(); ++i) {
+    // The condition value is little-endian.
+    const bool required = (cond.get_value() >> i) & 1u;
+    if (bits[bit_indices[i]] != required) {
+      return false;
+    }
+  }
+  return true;
+}
+
+}  // namespace internal
+}  // namespace tket_sim
+}  // namespace tket
@@ -0,0 +1,52 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <vector>
+
+#include "Ops/OpPtr.hpp"
+
+namespace tket {
+class Conditional;
+
+namespace tket_sim {
+namespace internal {
+
+/** Evaluate a purely classical operation, updating the bit values in place.
+ *  The operation is evaluated by Circuit::classical_eval, so anything it
+ *  does not support, e.g. WASMOp, throws a CircuitInvalidity.
+ *
+ *  @param op The classical operation.
+ *  @param bit_indices The indices within "bits" of the operation arguments,
+ *      in the order given by the op signature.
+ *  @param bits The current values of all bits in the simulated circuit.
+ */
+void apply_classical_op(
+    const Op_ptr& op, const std::vector<unsigned>& bit_indices,
+    std::vector<bool>& bits);
+
+/** Whether the condition of a Conditional op is satisfied.
+ *  @param cond The conditional operation.
+ *  @param bit_indices The indices within "bits" of the operation arguments;
+ *      only the first cond.get_width() are used.
+ *  @param bits The current values of all bits in the simulated circuit.
+ */
+bool condition_holds(
+    const Conditional& cond, const std::vector<unsigned>& bit_indices,
+    const std::vector<bool>& bits);
+
+}  // namespace internal
+}  // namespace tket_sim
+}  // namespace tket
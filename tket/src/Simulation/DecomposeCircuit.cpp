@@ -14,11 +14,13 @@
 
 #include "DecomposeCircuit.hpp"
 
+#include <numeric>
 #include <sstream>
 #include <tkassert/Assert.hpp>
 
 #include "Circuit/Boxes.hpp"
 #include "Circuit/Circuit.hpp"
+#include "CircuitSimulator.hpp"
 #include "Gate/Gate.hpp"
 #include "Gate/GateUnitaryMatrix.hpp"
 #include "GateNodesBuffer.hpp"
@@ -45,6 +47,49 @@ static QMap get_qmap_no_checks(
   return qmap;
 }
 
+// The block-diagonal unitary of a multiplexor, with the unitary of each op
+// computed by simulating it.
+static std::vector<TripletCd> get_multiplexor_triplets(
+    const MultiplexorBox& box, double abs_epsilon) {
+  const unsigned n_targets = box.get_n_targets();
+  const unsigned dim = 1u << n_targets;
+  const unsigned n_blocks = 1u << box.get_n_controls();
+  std::vector<unsigned> targets(n_targets);
+  std::iota(targets.begin(), targets.end(), 0);
+  Eigen::MatrixXcd matr =
+      Eigen::MatrixXcd::Identity(n_blocks * dim, n_blocks * dim);
+  for (const auto& [state, op] : box.get_op_map()) {
+    unsigned block = 0;
+    for (bool b : state) block = 2 * block + b;
+    Circuit circ(n_targets);
+    circ.add_op(op, targets);
+    matr.block(block * dim, block * dim, dim, dim) =
+        tket_sim::get_unitary(circ, abs_epsilon);
+  }
+  return tket::get_triplets(matr, abs_epsilon);
+}
+
+// The permutation matrix of a ToffoliBox.
+static std::vector<TripletCd> get_toffoli_triplets(const ToffoliBox& box) {
+  const unsigned n_qubits = box.n_qubits();
+  const state_perm_t permutation = box.get_permutation();
+  std::vector<TripletCd> triplets;
+  for (unsigned col = 0; col < (1u << n_qubits); ++col) {
+    std::vector<bool> state(n_qubits);
+    for (unsigned q = 0; q < n_qubits; ++q) {
+      state[q] = (col >> (n_qubits - 1 - q)) & 1;
+    }
+    const auto it = permutation.find(state);
+    unsigned row = col;
+    if (it != permutation.end()) {
+      row = 0;
+      for (bool b : it->second) row = 2 * row + b;
+    }
+    triplets.emplace_back(row, col, 1.);
+  }
+  return triplets;
+}
+
 // Already known to be a box, with a nonempty Op ptr.
 // If possible (if the op is able to calculate its own unitary matrix),
 // fill the node triplets with the raw unitary matrix represented by this box.
@@ -71,6 +116,31 @@ static bool fill_triplets_directly_from_box(
       node.triplets = tket::get_triplets(u3q_ptr->get_matrix(), abs_epsilon);
       return true;
     }
+    case OpType::UnitaryNqBox: {
+      auto unq_ptr = dynamic_cast<const UnitaryNqBox*>(box_ptr.get());
+      TKET_ASSERT(unq_ptr);
+      node.triplets = tket::get_triplets(unq_ptr->get_matrix(), abs_epsilon);
+      return true;
+    }
+    case OpType::MultiplexorBox: {
+      auto mplex_ptr = dynamic_cast<const MultiplexorBox*>(box_ptr.get());
+      TKET_ASSERT(mplex_ptr);
+      node.triplets = get_multiplexor_triplets(*mplex_ptr, abs_epsilon);
+      return true;
+    }
+    case OpType::MultiplexedRotationBox: {
+      auto mrot_ptr =
+          dynamic_cast<const MultiplexedRotationBox*>(box_ptr.get());
+      TKET_ASSERT(mrot_ptr);
+      node.triplets = tket::get_triplets(mrot_ptr->get_unitary(), abs_epsilon);
+      return true;
+    }
+    case OpType::ToffoliBox: {
+      auto toffoli_ptr = dynamic_cast<const ToffoliBox*>(box_ptr.get());
+      TKET_ASSERT(toffoli_ptr);
+      node.triplets = get_toffoli_triplets(*toffoli_ptr);
+      return true;
+    }
     case OpType::PauliExpBox: {
       auto pauli_box_ptr = dynamic_cast<const PauliExpBox*>(box_ptr.get());
       TKET_ASSERT(pauli_box_ptr);
@@ -134,7 +204,7 @@ static void decompose_circuit_recursive(
     {
       const auto current_type = current_op->get_type();
       if (current_type == OpType::noop || current_type == OpType::Barrier ||
-          current_type == OpType::Measure) {
+          current_type == OpType::Delay || current_type == OpType::Measure) {
         // Really, ignoring OpType::Measure is the wrong thing to do,
         // since get_unitary is completely meaningless for such circuits.
         // But this was the old behaviour in pytket tests with other
@@ -201,6 +271,24 @@ void decompose_circuit(
   buffer.flush();
 }
 
+std::vector<TripletCd> get_op_triplets(const Op_ptr& op, double abs_epsilon) {
+  if (op->get_desc().is_gate()) {
+    const Gate* gate = dynamic_cast<const Gate*>(op.get());
+    TKET_ASSERT(gate);
+    return GateUnitaryMatrix::get_unitary_triplets(*gate, abs_epsilon);
+  }
+  const unsigned n_qubits = op->n_qubits();
+  Circuit circ(n_qubits);
+  std::vector<unsigned> args(n_qubits);
+  std::iota(args.begin(), args.end(), 0);
+  circ.add_op<unsigned>(op, args);
+  const auto matr_size = get_matrix_size(n_qubits);
+  Eigen::MatrixXcd matr = Eigen::MatrixXcd::Identity(matr_size, matr_size);
+  GateNodesBuffer buffer(matr, abs_epsilon);
+  decompose_circuit(circ, buffer, abs_epsilon);
+  return get_triplets(matr, abs_epsilon);
+}
+
 }  // namespace internal
 }  // namespace tket_sim
 }  // namespace tket
@@ -14,6 +14,7 @@
 
 #pragma once
 
+#include "Ops/OpPtr.hpp"
 #include "Utils/MatrixAnalysis.hpp"
 
 namespace tket {
@@ -30,6 +31,11 @@ class GateNodesBuffer;
 void decompose_circuit(
     const Circuit& circ, GateNodesBuffer& buffer, double abs_epsilon);
 
+/** The unitary matrix of a single gate or purely quantum box, acting on
+ *  qubits [0,1,2,...,k-1], as triplets (using ILO-BE convention).
+ */
+std::vector<TripletCd> get_op_triplets(const Op_ptr& op, double abs_epsilon);
+
 }  // namespace internal
 }  // namespace tket_sim
 }  // namespace tket
@@ -0,0 +1,448 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "DensityMatrixSimulator.hpp"
+
+#include <array>
+#include <numeric>
+#include <tkassert/Assert.hpp>
+
+#include "BitOperations.hpp"
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Circuit/Conditional.hpp"
+#include "ClassicalEvaluation.hpp"
+#include "DecomposeCircuit.hpp"
+#include "Gate/GateUnitaryMatrixError.hpp"
+#include "GateNode.hpp"
+#include "OpType/OpTypeFunctions.hpp"
+
+namespace tket {
+namespace tket_sim {
+
+namespace {
+
+/** Evolves a ClassicalQuantumState through the commands of a circuit
+ *  (and, recursively, through the circuits of boxes with classical wires).
+ */
+class DensityMatrixEvolution {
+ public:
+  DensityMatrixEvolution(
+      const Circuit& circ, const NoiseModel& noise, double abs_epsilon);
+
+  /** The state |00...0>, with all bits 0. */
+  ClassicalQuantumState get_initial_state() const;
+
+  /** Process every command of a circuit.
+   *  @param state The state to update.
+   *  @param circ The (sub)circuit.
+   *  @param qubit_indices The top-level indices of circ.all_qubits().
+   *  @param bit_indices The top-level indices of circ.all_bits().
+   *  @param top_level Whether this is the circuit passed in by the user,
+   *      in which case vertices may carry their own noise channels.
+   */
+  void run(
+      ClassicalQuantumState& state, const Circuit& circ,
+      const std::vector<unsigned>& qubit_indices,
+      const std::vector<unsigned>& bit_indices, bool top_level) const;
+
+ private:
+  const NoiseModel& noise_;
+  const double abs_epsilon_;
+  const unsigned n_qubits_;
+  const unsigned n_bits_;
+  // The top-level qubits, used to look up noise channels.
+  const qubit_vector_t all_qubits_;
+
+  void apply_op(
+      ClassicalQuantumState& state, const Op_ptr& op,
+      const std::vector<unsigned>& qubits, const std::vector<unsigned>& bits,
+      const Vertex& vertex) const;
+
+  // rho -> sum_i K_i rho K_i^dagger on every branch, where the K_i act on
+  // the given top-level qubits.
+  void apply_kraus(
+      ClassicalQuantumState& state, const std::vector<Eigen::MatrixXcd>& ops,
+      const std::vector<unsigned>& qubits) const;
+
+  void apply_noise(
+      ClassicalQuantumState& state, const Vertex& vertex, OpType type,
+      const std::vector<unsigned>& qubits) const;
+
+  void apply_measure(
+      ClassicalQuantumState& state, unsigned qubit, unsigned bit) const;
+
+  void apply_reset(ClassicalQuantumState& state, unsigned qubit) const;
+
+  void apply_collapse(ClassicalQuantumState& state, unsigned qubit) const;
+
+  void apply_classical(
+      ClassicalQuantumState& state, const Op_ptr& op,
+      const std::vector<unsigned>& bits) const;
+
+  void apply_permutation(
+      ClassicalQuantumState& state,
+      const std::map<unsigned, unsigned>& permutation) const;
+
+  // The mask of the qubit within a basis state index.
+  internal::SimUInt qubit_mask(unsigned qubit) const;
+};
+
+// Add the branch into the state, merging with an existing branch with the
+// same classical values.
+static void add_branch(
+    ClassicalQuantumState& state, const std::vector<bool>& key,
+    const DensityMatrix& rho, double abs_epsilon) {
+  if (std::abs(rho.trace()) <= abs_epsilon) {
+    return;
+  }
+  const auto it = state.find(key);
+  if (it == state.end()) {
+    state.emplace(key, rho);
+  } else {
+    it->second += rho;
+  }
+}
+
+DensityMatrixEvolution::DensityMatrixEvolution(
+    const Circuit& circ, const NoiseModel& noise, double abs_epsilon)
+    : noise_(noise),
+      abs_epsilon_(abs_epsilon),
+      n_qubits_(circ.n_qubits()),
+      n_bits_(circ.n_bits()),
+      all_qubits_(circ.all_qubits()) {}
+
+ClassicalQuantumState DensityMatrixEvolution::get_initial_state() const {
+  const auto size = get_matrix_size(n_qubits_);
+  DensityMatrix rho = DensityMatrix::Zero(size, size);
+  rho(0, 0) = 1.;
+  ClassicalQuantumState state;
+  state.emplace(std::vector<bool>(n_bits_, false), rho);
+  return state;
+}
+
+internal::SimUInt DensityMatrixEvolution::qubit_mask(unsigned qubit) const {
+  // ILO-BE: qubit 0 is the most significant bit.
+  return internal::SimUInt(1) << (n_qubits_ - 1 - qubit);
+}
+
+void DensityMatrixEvolution::run(
+    ClassicalQuantumState& state, const Circuit& circ,
+    const std::vector<unsigned>& qubit_indices,
+    const std::vector<unsigned>& bit_indices, bool top_level) const {
+  std::map<UnitID, unsigned> qmap;
+  std::map<UnitID, unsigned> bmap;
+  {
+    const qubit_vector_t qubits = circ.all_qubits();
+    TKET_ASSERT(qubits.size() == qubit_indices.size());
+    for (unsigned i = 0; i < qubits.size(); ++i) {
+      qmap[qubits[i]] = qubit_indices[i];
+    }
+    const bit_vector_t bits = circ.all_bits();
+    TKET_ASSERT(bits.size() == bit_indices.size());
+    for (unsigned i = 0; i < bits.size(); ++i) {
+      bmap[bits[i]] = bit_indices[i];
+    }
+  }
+  std::vector<unsigned> op_qubits;
+  std::vector<unsigned> op_bits;
+  for (const Command& command : circ) {
+    op_qubits.clear();
+    op_bits.clear();
+    for (const UnitID& unit : command.get_args()) {
+      if (unit.type() == UnitType::Qubit) {
+        op_qubits.push_back(qmap.at(unit));
+      } else {
+        op_bits.push_back(bmap.at(unit));
+      }
+    }
+    apply_op(
+        state, command.get_op_ptr(), op_qubits, op_bits,
+        top_level ? command.get_vertex()
+                  : boost::graph_traits<DAG>::null_vertex());
+  }
+  std::map<unsigned, unsigned> permutation;
+  for (const auto& [in, out] : circ.implicit_qubit_permutation()) {
+    if (in != out) {
+      permutation[qmap.at(in)] = qmap.at(out);
+    }
+  }
+  if (!permutation.empty()) {
+    apply_permutation(state, permutation);
+  }
+}
+
+// Whether every wire of the op is quantum.
+static bool is_purely_quantum(const Op_ptr& op) {
+  for (const EdgeType& edge : op->get_signature()) {
+    if (edge != EdgeType::Quantum) {
+      return false;
+    }
+  }
+  return true;
+}
+
+void DensityMatrixEvolution::apply_op(
+    ClassicalQuantumState& state, const Op_ptr& op,
+    const std::vector<unsigned>& qubits, const std::vector<unsigned>& bits,
+    const Vertex& vertex) const {
+  const OpType type = op->get_type();
+  switch (type) {
+    case OpType::noop:
+    case OpType::Barrier:
+    case OpType::Delay:
+      return;
+    case OpType::Measure:
+      apply_measure(state, qubits.at(0), bits.at(0));
+      break;
+    case OpType::Reset:
+      apply_reset(state, qubits.at(0));
+      break;
+    case OpType::Collapse:
+      apply_collapse(state, qubits.at(0));
+      break;
+    case OpType::Conditional: {
+      const Conditional& cond = static_cast<const Conditional&>(*op);
+      ClassicalQuantumState active;
+      for (auto it = state.begin(); it != state.end();) {
+        if (internal::condition_holds(cond, bits, it->first)) {
+          active.insert(state.extract(it++));
+        } else {
+          ++it;
+        }
+      }
+      if (active.empty()) {
+        return;
+      }
+      const std::vector<unsigned> inner_bits(
+          bits.begin() + cond.get_width(), bits.end());
+      apply_op(active, cond.get_op(), qubits, inner_bits, vertex);
+      for (const auto& [key, rho] : active) {
+        add_branch(state, key, rho, abs_epsilon_);
+      }
+      // The inner op has already had its noise applied.
+      return;
+    }
+    default: {
+      if (is_classical_type(type)) {
+        apply_classical(state, op, bits);
+        return;
+      }
+      if (qubits.empty() && bits.empty()) {
+        // E.g. OpType::Phase, which has no effect on a density matrix.
+        return;
+      }
+      if (is_purely_quantum(op)) {
+        const internal::GateNode node{
+            internal::get_op_triplets(op, abs_epsilon_), qubits};
+        for (auto& [key, rho] : state) {
+          // U rho U^dagger = U (U rho)^dagger, since rho is self-adjoint.
+          node.apply_full_unitary(rho, n_qubits_);
+          rho.adjointInPlace();
+          node.apply_full_unitary(rho, n_qubits_);
+        }
+        break;
+      }
+      if (!op->get_desc().is_box()) {
+        throw CircuitInvalidity(
+            "Cannot simulate operation " + op->get_name() +
+            " as a density matrix");
+      }
+      const Box& box = static_cast<const Box&>(*op);
+      const std::shared_ptr<Circuit> box_circ = box.to_circuit();
+      if (!box_circ) {
+        throw CircuitInvalidity(
+            "Cannot simulate box " + op->get_name() +
+            ", which couldn't be broken down into a circuit");
+      }
+      run(state, *box_circ, qubits, bits, false);
+      return;
+    }
+  }
+  apply_noise(state, vertex, type, qubits);
+}
+
+void DensityMatrixEvolution::apply_kraus(
+    ClassicalQuantumState& state, const std::vector<Eigen::MatrixXcd>& ops,
+    const std::vector<unsigned>& qubits) const {
+  std::vector<internal::GateNode> nodes;
+  nodes.reserve(ops.size());
+  for (const Eigen::MatrixXcd& k : ops) {
+    nodes.push_back({get_triplets(k, abs_epsilon_), qubits});
+  }
+  DensityMatrix term;
+  for (auto& [key, rho] : state) {
+    DensityMatrix result = DensityMatrix::Zero(rho.rows(), rho.cols());
+    for (const internal::GateNode& node : nodes) {
+      // K rho K^dagger = K (K rho)^dagger.
+      term = rho;
+      node.apply_full_unitary(term, n_qubits_);
+      term.adjointInPlace();
+      node.apply_full_unitary(term, n_qubits_);
+      result += term;
+    }
+    rho = std::move(result);
+  }
+}
+
+void DensityMatrixEvolution::apply_noise(
+    ClassicalQuantumState& state, const Vertex& vertex, OpType type,
+    const std::vector<unsigned>& qubits) const {
+  if (noise_.empty() || qubits.empty()) {
+    return;
+  }
+  qubit_vector_t op_qubits;
+  for (unsigned q : qubits) {
+    op_qubits.push_back(all_qubits_[q]);
+  }
+  const std::optional<KrausChannel> channel =
+      noise_.get_channel(vertex, type, op_qubits);
+  if (channel) {
+    apply_kraus(state, channel->get_kraus_ops(), qubits);
+  }
+}
+
+void DensityMatrixEvolution::apply_measure(
+    ClassicalQuantumState& state, unsigned qubit, unsigned bit) const {
+  const internal::SimUInt mask = qubit_mask(qubit);
+  const readout_error_t flip = noise_.get_readout_error(all_qubits_[qubit]);
+  ClassicalQuantumState new_state;
+  for (const auto& [key, rho] : state) {
+    // Project onto each outcome.
+    std::array<DensityMatrix, 2> projected;
+    for (unsigned outcome = 0; outcome < 2; ++outcome) {
+      projected[outcome] = DensityMatrix::Zero(rho.rows(), rho.cols());
+    }
+    for (Eigen::Index col = 0; col < rho.cols(); ++col) {
+      const bool col_bit = (internal::SimUInt(col) & mask) != 0;
+      for (Eigen::Index row = 0; row < rho.rows(); ++row) {
+        if (((internal::SimUInt(row) & mask) != 0) == col_bit) {
+          projected[col_bit ? 1 : 0](row, col) = rho(row, col);
+        }
+      }
+    }
+    std::vector<bool> new_key = key;
+    for (unsigned outcome = 0; outcome < 2; ++outcome) {
+      new_key[bit] = outcome;
+      if (flip == 0.) {
+        add_branch(new_state, new_key, projected[outcome], abs_epsilon_);
+      } else {
+        // The recorded outcome is wrong with probability "flip".
+        add_branch(
+            new_state, new_key,
+            (1. - flip) * projected[outcome] + flip * projected[1 - outcome],
+            abs_epsilon_);
+      }
+    }
+  }
+  state = std::move(new_state);
+}
+
+void DensityMatrixEvolution::apply_reset(
+    ClassicalQuantumState& state, unsigned qubit) const {
+  const internal::SimUInt mask = qubit_mask(qubit);
+  for (auto& [key, rho] : state) {
+    DensityMatrix result = DensityMatrix::Zero(rho.rows(), rho.cols());
+    for (Eigen::Index col = 0; col < rho.cols(); ++col) {
+      const internal::SimUInt col_bits = col;
+      for (Eigen::Index row = 0; row < rho.rows(); ++row) {
+        const internal::SimUInt row_bits = row;
+        if ((row_bits & mask) == (col_bits & mask)) {
+          // |0><0| rho |0><0| + |0><1| rho |1><0|
+          result(row_bits & ~mask, col_bits & ~mask) += rho(row, col);
+        }
+      }
+    }
+    rho = std::move(result);
+  }
+}
+
+void DensityMatrixEvolution::apply_collapse(
+    ClassicalQuantumState& state, unsigned qubit) const {
+  const internal::SimUInt mask = qubit_mask(qubit);
+  for (auto& [key, rho] : state) {
+    for (Eigen::Index col = 0; col < rho.cols(); ++col) {
+      const bool col_bit = (internal::SimUInt(col) & mask) != 0;
+      for (Eigen::Index row = 0; row < rho.rows(); ++row) {
+        if (((internal::SimUInt(row) & mask) != 0) != col_bit) {
+          rho(row, col) = 0.;
+        }
+      }
+    }
+  }
+}
+
+void DensityMatrixEvolution::apply_classical(
+    ClassicalQuantumState& state, const Op_ptr& op,
+    const std::vector<unsigned>& bits) const {
+  ClassicalQuantumState new_state;
+  for (const auto& [key, rho] : state) {
+    std::vector<bool> new_key = key;
+    internal::apply_classical_op(op, bits, new_key);
+    add_branch(new_state, new_key, rho, abs_epsilon_);
+  }
+  state = std::move(new_state);
+}
+
+void DensityMatrixEvolution::apply_permutation(
+    ClassicalQuantumState& state,
+    const std::map<unsigned, unsigned>& permutation) const {
+  std::map<unsigned, unsigned> full_permutation;
+  for (unsigned q = 0; q < n_qubits_; ++q) {
+    const auto it = permutation.find(q);
+    full_permutation[q] = (it == permutation.end()) ? q : it->second;
+  }
+  const Eigen::PermutationMatrix<Eigen::Dynamic> perm =
+      lift_perm(full_permutation);
+  for (auto& [key, rho] : state) {
+    const DensityMatrix permuted_rows = perm * rho;
+    rho = permuted_rows * perm.transpose();
+  }
+}
+
+}  // namespace
+
+ClassicalQuantumState get_classical_quantum_state(
+    const Circuit& circ, const NoiseModel& noise, double abs_epsilon,
+    unsigned max_number_of_qubits) {
+  if (circ.n_qubits() > max_number_of_qubits) {
+    throw GateUnitaryMatrixError(
+        "Circuit to simulate has too many qubits",
+        GateUnitaryMatrixError::Cause::TOO_MANY_QUBITS);
+  }
+  const DensityMatrixEvolution evolution(circ, noise, abs_epsilon);
+  std::vector<unsigned> qubit_indices(circ.n_qubits());
+  std::iota(qubit_indices.begin(), qubit_indices.end(), 0);
+  std::vector<unsigned> bit_indices(circ.n_bits());
+  std::iota(bit_indices.begin(), bit_indices.end(), 0);
+  ClassicalQuantumState state = evolution.get_initial_state();
+  evolution.run(state, circ, qubit_indices, bit_indices, true);
+  return state;
+}
+
+DensityMatrix get_density_matrix(
+    const Circuit& circ, const NoiseModel& noise, double abs_epsilon,
+    unsigned max_number_of_qubits) {
+  const ClassicalQuantumState state = get_classical_quantum_state(
+      circ, noise, abs_epsilon, max_number_of_qubits);
+  const auto size = get_matrix_size(circ.n_qubits());
+  DensityMatrix result = DensityMatrix::Zero(size, size);
+  for (const auto& [key, rho] : state) {
+    result += rho;
+  }
+  return result;
+}
+
+}  // namespace tket_sim
+}  // namespace tket
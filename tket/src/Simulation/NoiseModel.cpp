@@ -0,0 +1,236 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "NoiseModel.hpp"
+
+#include <cmath>
+#include <stdexcept>
+
+#include "OpType/OpTypeFunctions.hpp"
+#include "OpType/OpTypeInfo.hpp"
+
+namespace tket {
+namespace tket_sim {
+
+KrausChannel::KrausChannel(
+    const std::vector<Eigen::MatrixXcd>& kraus_ops, double tol)
+    : n_qubits_(0), kraus_ops_(kraus_ops) {
+  if (kraus_ops_.empty()) {
+    throw std::invalid_argument("Kraus channel has no operators");
+  }
+  const auto dim = kraus_ops_[0].rows();
+  if (dim < 2) {
+    throw std::invalid_argument("Kraus operators must act on some qubits");
+  }
+  n_qubits_ = get_number_of_qubits(dim);
+  Eigen::MatrixXcd completeness = Eigen::MatrixXcd::Zero(dim, dim);
+  for (const Eigen::MatrixXcd& k : kraus_ops_) {
+    if (k.rows() != dim || k.cols() != dim) {
+      throw std::invalid_argument("Kraus operators have inconsistent sizes");
+    }
+    completeness += k.adjoint() * k;
+  }
+  if (!completeness.isIdentity(tol)) {
+    throw std::invalid_argument(
+        "Kraus operators do not define a trace-preserving channel");
+  }
+}
+
+static Eigen::Matrix2cd single_qubit_pauli(unsigned index) {
+  Eigen::Matrix2cd m;
+  switch (index) {
+    case 0:
+      m << 1, 0, 0, 1;
+      break;
+    case 1:
+      m << 0, 1, 1, 0;
+      break;
+    case 2:
+      m << 0, -i_, i_, 0;
+      break;
+    default:
+      m << 1, 0, 0, -1;
+  }
+  return m;
+}
+
+static void check_probability(double p) {
+  if (p < 0. || p > 1.) {
+    throw std::invalid_argument("Error probability must lie in [0,1]");
+  }
+}
+
+KrausChannel KrausChannel::depolarising(unsigned n_qubits, double p) {
+  check_probability(p);
+  if (n_qubits == 0) {
+    throw std::invalid_argument("Depolarising channel needs qubits");
+  }
+  const unsigned n_paulis = get_matrix_size(2 * n_qubits);
+  const double pauli_weight = std::sqrt(p / (n_paulis - 1));
+  std::vector<Eigen::MatrixXcd> ops;
+  ops.reserve(n_paulis);
+  for (unsigned code = 0; code < n_paulis; ++code) {
+    // Two bits of the code per qubit; qubit 0 is the most significant.
+    Eigen::MatrixXcd pauli = Eigen::MatrixXcd::Identity(1, 1);
+    for (unsigned q = 0; q < n_qubits; ++q) {
+      const unsigned index = (code >> (2 * (n_qubits - 1 - q))) & 3;
+      pauli = Eigen::kroneckerProduct(pauli, single_qubit_pauli(index)).eval();
+    }
+    ops.push_back((code == 0 ? std::sqrt(1. - p) : pauli_weight) * pauli);
+  }
+  return KrausChannel(ops);
+}
+
+KrausChannel KrausChannel::bit_flip(double p) {
+  check_probability(p);
+  return KrausChannel(
+      {std::sqrt(1. - p) * Eigen::MatrixXcd(single_qubit_pauli(0)),
+       std::sqrt(p) * Eigen::MatrixXcd(single_qubit_pauli(1))});
+}
+
+KrausChannel KrausChannel::phase_flip(double p) {
+  check_probability(p);
+  return KrausChannel(
+      {std::sqrt(1. - p) * Eigen::MatrixXcd(single_qubit_pauli(0)),
+       std::sqrt(p) * Eigen::MatrixXcd(single_qubit_pauli(3))});
+}
+
+KrausChannel KrausChannel::amplitude_damping(double gamma) {
+  check_probability(gamma);
+  Eigen::MatrixXcd k0(2, 2), k1(2, 2);
+  k0 << 1, 0, 0, std::sqrt(1. - gamma);
+  k1 << 0, std::sqrt(gamma), 0, 0;
+  return KrausChannel({k0, k1});
+}
+
+KrausChannel KrausChannel::phase_damping(double lambda) {
+  check_probability(lambda);
+  Eigen::MatrixXcd k0(2, 2), k1(2, 2);
+  k0 << 1, 0, 0, std::sqrt(1. - lambda);
+  k1 << 0, 0, 0, std::sqrt(lambda);
+  return KrausChannel({k0, k1});
+}
+
+NoiseModel::NoiseModel() {}
+
+static void check_channel_size(
+    const KrausChannel& channel, const qubit_vector_t& qubits) {
+  if (channel.n_qubits() != qubits.size()) {
+    throw std::invalid_argument(
+        "Kraus channel acts on a different number of qubits");
+  }
+}
+
+void NoiseModel::add_channel(OpType type, const KrausChannel& channel) {
+  op_channels_.insert_or_assign(type, channel);
+}
+
+void NoiseModel::add_channel(
+    const qubit_vector_t& qubits, const KrausChannel& channel) {
+  check_channel_size(channel, qubits);
+  qubit_channels_.insert_or_assign(qubits, channel);
+}
+
+void NoiseModel::add_channel(
+    OpType type, const qubit_vector_t& qubits, const KrausChannel& channel) {
+  check_channel_size(channel, qubits);
+  op_qubit_channels_.insert_or_assign({type, qubits}, channel);
+}
+
+void NoiseModel::add_channel(
+    const Vertex& vertex, const KrausChannel& channel) {
+  vertex_channels_.insert_or_assign(vertex, channel);
+}
+
+void NoiseModel::set_readout_error(const Qubit& qubit, readout_error_t p) {
+  check_probability(p);
+  readout_errors_[qubit] = p;
+}
+
+void NoiseModel::set_characterisation(
+    const DeviceCharacterisation& characterisation) {
+  characterisation_ = characterisation;
+}
+
+static std::optional<KrausChannel> characterisation_channel(
+    const DeviceCharacterisation& characterisation, OpType type,
+    const qubit_vector_t& qubits) {
+  gate_error_t error = 0.;
+  if (qubits.size() == 1) {
+    error = characterisation.get_error(Node(qubits[0]), type);
+  } else if (qubits.size() == 2) {
+    const Node n0(qubits[0]);
+    const Node n1(qubits[1]);
+    error = characterisation.get_error(Architecture::Connection(n0, n1), type);
+    if (error == 0.) {
+      // Fall back to the reversed link.
+      error =
+          characterisation.get_error(Architecture::Connection(n1, n0), type);
+    }
+  }
+  if (error == 0.) {
+    return std::nullopt;
+  }
+  return KrausChannel::depolarising(qubits.size(), error);
+}
+
+std::optional<KrausChannel> NoiseModel::get_channel(
+    const Vertex& vertex, OpType type, const qubit_vector_t& qubits) const {
+  const auto vertex_it = vertex_channels_.find(vertex);
+  if (vertex_it != vertex_channels_.end()) {
+    return vertex_it->second;
+  }
+  const auto op_qubit_it = op_qubit_channels_.find({type, qubits});
+  if (op_qubit_it != op_qubit_channels_.end()) {
+    return op_qubit_it->second;
+  }
+  const auto qubit_it = qubit_channels_.find(qubits);
+  if (qubit_it != qubit_channels_.end()) {
+    return qubit_it->second;
+  }
+  const auto op_it = op_channels_.find(type);
+  if (op_it != op_channels_.end()) {
+    if (op_it->second.n_qubits() != qubits.size()) {
+      throw std::invalid_argument(
+          "Kraus channel for " + optypeinfo().at(type).name +
+          " acts on a different number of qubits from the operation");
+    }
+    return op_it->second;
+  }
+  if (characterisation_ && is_gate_type(type) && type != OpType::Measure &&
+      type != OpType::Reset) {
+    return characterisation_channel(*characterisation_, type, qubits);
+  }
+  return std::nullopt;
+}
+
+readout_error_t NoiseModel::get_readout_error(const Qubit& qubit) const {
+  const auto it = readout_errors_.find(qubit);
+  if (it != readout_errors_.end()) {
+    return it->second;
+  }
+  if (characterisation_) {
+    return characterisation_->get_readout_error(Node(qubit));
+  }
+  return 0.;
+}
+
+bool NoiseModel::empty() const {
+  return vertex_channels_.empty() && op_qubit_channels_.empty() &&
+         qubit_channels_.empty() && op_channels_.empty() &&
+         readout_errors_.empty() && !characterisation_;
+}
+
+}  // namespace tket_sim
+}  // namespace tket
@@ -0,0 +1,336 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "ShotSimulator.hpp"
+
+#include <cmath>
+#include <numeric>
+#include <optional>
+#include <tkassert/Assert.hpp>
+#include <tkrng/RNG.hpp>
+
+#include "BitOperations.hpp"
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Circuit/Conditional.hpp"
+#include "ClassicalEvaluation.hpp"
+#include "DecomposeCircuit.hpp"
+#include "Gate/GateUnitaryMatrixError.hpp"
+#include "Gate/OpPtrFunctions.hpp"
+#include "GateNode.hpp"
+#include "OpType/OpTypeFunctions.hpp"
+
+namespace tket {
+namespace tket_sim {
+
+ShotTable::ShotTable(
+    const bit_vector_t& bits, std::vector<std::vector<bool>> shots)
+    : bits_(bits), shots_(std::move(shots)) {}
+
+std::map<std::vector<bool>, unsigned> ShotTable::get_counts() const {
+  std::map<std::vector<bool>, unsigned> counts;
+  for (const std::vector<bool>& shot : shots_) {
+    ++counts[shot];
+  }
+  return counts;
+}
+
+namespace {
+
+/** A command with its arguments converted to top-level qubit and bit
+ *  indices, and the unitary precomputed if it is a purely quantum op.
+ */
+struct Instruction {
+  Op_ptr op;
+  std::vector<unsigned> qubits;
+  std::vector<unsigned> bits;
+  std::optional<internal::GateNode> unitary;
+  // For boxes with classical wires, the decomposed contents.
+  std::vector<Instruction> sub_instructions;
+  // Nonempty if a qubit permutation must be applied after this instruction
+  // (the implicit permutation at the end of a box circuit).
+  std::map<unsigned, unsigned> permutation;
+};
+
+// Whether every wire of the op is quantum.
+bool is_purely_quantum(const Op_ptr& op) {
+  for (const EdgeType& edge : op->get_signature()) {
+    if (edge != EdgeType::Quantum) {
+      return false;
+    }
+  }
+  return true;
+}
+
+// Whether the instruction acts deterministically, i.e. is a unitary or
+// has no effect at all.
+bool is_deterministic(const Instruction& instruction) {
+  const OpType type = instruction.op->get_type();
+  return instruction.unitary || type == OpType::noop ||
+         type == OpType::Barrier || type == OpType::Delay ||
+         (instruction.qubits.empty() && instruction.bits.empty());
+}
+
+Instruction make_instruction(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    const std::vector<unsigned>& bits, double abs_epsilon);
+
+// Convert every command of the circuit into an instruction.
+std::vector<Instruction> compile_circuit(
+    const Circuit& circ, const std::vector<unsigned>& qubit_indices,
+    const std::vector<unsigned>& bit_indices, double abs_epsilon) {
+  std::map<UnitID, unsigned> qmap;
+  std::map<UnitID, unsigned> bmap;
+  {
+    const qubit_vector_t qubits = circ.all_qubits();
+    TKET_ASSERT(qubits.size() == qubit_indices.size());
+    for (unsigned i = 0; i < qubits.size(); ++i) {
+      qmap[qubits[i]] = qubit_indices[i];
+    }
+    const bit_vector_t bits = circ.all_bits();
+    TKET_ASSERT(bits.size() == bit_indices.size());
+    for (unsigned i = 0; i < bits.size(); ++i) {
+      bmap[bits[i]] = bit_indices[i];
+    }
+  }
+  std::vector<Instruction> instructions;
+  std::vector<unsigned> op_qubits;
+  std::vector<unsigned> op_bits;
+  for (const Command& command : circ) {
+    op_qubits.clear();
+    op_bits.clear();
+    for (const UnitID& unit : command.get_args()) {
+      if (unit.type() == UnitType::Qubit) {
+        op_qubits.push_back(qmap.at(unit));
+      } else {
+        op_bits.push_back(bmap.at(unit));
+      }
+    }
+    instructions.push_back(make_instruction(
+        command.get_op_ptr(), op_qubits, op_bits, abs_epsilon));
+  }
+  std::map<unsigned, unsigned> permutation;
+  for (const auto& [in, out] : circ.implicit_qubit_permutation()) {
+    if (in != out) {
+      permutation[qmap.at(in)] = qmap.at(out);
+    }
+  }
+  if (!permutation.empty()) {
+    Instruction perm_instruction;
+    perm_instruction.op = get_op_ptr(OpType::noop);
+    perm_instruction.permutation = std::move(permutation);
+    instructions.push_back(std::move(perm_instruction));
+  }
+  return instructions;
+}
+
+Instruction make_instruction(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    const std::vector<unsigned>& bits, double abs_epsilon) {
+  Instruction instruction;
+  instruction.op = op;
+  instruction.qubits = qubits;
+  instruction.bits = bits;
+  const OpType type = op->get_type();
+  switch (type) {
+    case OpType::noop:
+    case OpType::Barrier:
+    case OpType::Delay:
+    case OpType::Measure:
+    case OpType::Reset:
+    case OpType::Collapse:
+      break;
+    case OpType::Conditional: {
+      const Conditional& cond = static_cast<const Conditional&>(*op);
+      const std::vector<unsigned> inner_bits(
+          bits.begin() + cond.get_width(), bits.end());
+      instruction.sub_instructions.push_back(
+          make_instruction(cond.get_op(), qubits, inner_bits, abs_epsilon));
+      break;
+    }
+    default: {
+      if (is_classical_type(type) || (qubits.empty() && bits.empty())) {
+        // Classical ops are evaluated directly; ops with no arguments, such
+        // as OpType::Phase, have no observable effect.
+        break;
+      }
+      if (is_purely_quantum(op)) {
+        instruction.unitary = internal::GateNode{
+            internal::get_op_triplets(op, abs_epsilon), qubits};
+        break;
+      }
+      if (!op->get_desc().is_box()) {
+        throw CircuitInvalidity(
+            "Cannot sample from circuit with operation " + op->get_name());
+      }
+      const std::shared_ptr<Circuit> box_circ =
+          static_cast<const Box&>(*op).to_circuit();
+      if (!box_circ) {
+        throw CircuitInvalidity(
+            "Cannot sample from box " + op->get_name() +
+            ", which couldn't be broken down into a circuit");
+      }
+      instruction.sub_instructions =
+          compile_circuit(*box_circ, qubits, bits, abs_epsilon);
+    }
+  }
+  return instruction;
+}
+
+/** The state of a single shot. */
+class ShotState {
+ public:
+  ShotState(
+      const Eigen::MatrixXcd& statevector, unsigned n_qubits, unsigned n_bits,
+      RNG& rng)
+      : statevector_(statevector),
+        n_qubits_(n_qubits),
+        bits_(n_bits, false),
+        rng_(rng) {}
+
+  void apply(const Instruction& instruction);
+
+  const std::vector<bool>& get_bits() const { return bits_; }
+
+ private:
+  Eigen::MatrixXcd statevector_;
+  const unsigned n_qubits_;
+  std::vector<bool> bits_;
+  RNG& rng_;
+
+  internal::SimUInt qubit_mask(unsigned qubit) const {
+    // ILO-BE: qubit 0 is the most significant bit.
+    return internal::SimUInt(1) << (n_qubits_ - 1 - qubit);
+  }
+
+  // Collapse the qubit in the computational basis, returning the outcome.
+  bool measure(unsigned qubit);
+
+  // A uniformly random number in [0,1), from the top 53 bits of the
+  // portable RNG output.
+  double get_uniform_double() {
+    return double(rng_() >> 11) * (1. / double(std::uint64_t(1) << 53));
+  }
+};
+
+bool ShotState::measure(unsigned qubit) {
+  const internal::SimUInt mask = qubit_mask(qubit);
+  double prob_one = 0.;
+  for (Eigen::Index i = 0; i < statevector_.rows(); ++i) {
+    if (internal::SimUInt(i) & mask) {
+      prob_one += std::norm(statevector_(i, 0));
+    }
+  }
+  const bool outcome = get_uniform_double() < prob_one;
+  const double norm = std::sqrt(outcome ? prob_one : 1. - prob_one);
+  for (Eigen::Index i = 0; i < statevector_.rows(); ++i) {
+    if (((internal::SimUInt(i) & mask) != 0) == outcome) {
+      statevector_(i, 0) /= norm;
+    } else {
+      statevector_(i, 0) = 0.;
+    }
+  }
+  return outcome;
+}
+
+void ShotState::apply(const Instruction& instruction) {
+  const OpType type = instruction.op->get_type();
+  if (instruction.unitary) {
+    instruction.unitary->apply_full_unitary(statevector_, n_qubits_);
+  } else if (type == OpType::Measure) {
+    bits_[instruction.bits.at(0)] = measure(instruction.qubits.at(0));
+  } else if (type == OpType::Collapse) {
+    measure(instruction.qubits.at(0));
+  } else if (type == OpType::Reset) {
+    if (measure(instruction.qubits.at(0))) {
+      // Flip the qubit back to |0>.
+      const internal::SimUInt mask = qubit_mask(instruction.qubits[0]);
+      for (Eigen::Index i = 0; i < statevector_.rows(); ++i) {
+        if (internal::SimUInt(i) & mask) {
+          std::swap(statevector_(i, 0), statevector_(i ^ mask, 0));
+        }
+      }
+    }
+  } else if (type == OpType::Conditional) {
+    const Conditional& cond =
+        static_cast<const Conditional&>(*instruction.op);
+    if (internal::condition_holds(cond, instruction.bits, bits_)) {
+      apply(instruction.sub_instructions.at(0));
+    }
+  } else if (is_classical_type(type)) {
+    internal::apply_classical_op(instruction.op, instruction.bits, bits_);
+  } else {
+    for (const Instruction& sub_instruction : instruction.sub_instructions) {
+      apply(sub_instruction);
+    }
+  }
+  if (!instruction.permutation.empty()) {
+    std::map<unsigned, unsigned> full_permutation;
+    for (unsigned q = 0; q < n_qubits_; ++q) {
+      const auto it = instruction.permutation.find(q);
+      full_permutation[q] =
+          (it == instruction.permutation.end()) ? q : it->second;
+    }
+    statevector_ = lift_perm(full_permutation) * statevector_;
+  }
+}
+
+}  // namespace
+
+ShotTable sample_shots(
+    const Circuit& circ, unsigned n_shots, std::size_t seed,
+    double abs_epsilon, unsigned max_number_of_qubits) {
+  if (circ.n_qubits() > max_number_of_qubits) {
+    throw GateUnitaryMatrixError(
+        "Circuit to simulate has too many qubits",
+        GateUnitaryMatrixError::Cause::TOO_MANY_QUBITS);
+  }
+  std::vector<unsigned> qubit_indices(circ.n_qubits());
+  std::iota(qubit_indices.begin(), qubit_indices.end(), 0);
+  std::vector<unsigned> bit_indices(circ.n_bits());
+  std::iota(bit_indices.begin(), bit_indices.end(), 0);
+  const std::vector<Instruction> instructions =
+      compile_circuit(circ, qubit_indices, bit_indices, abs_epsilon);
+
+  // The initial run of deterministic instructions is the same for every
+  // shot, so only simulate it once.
+  Eigen::MatrixXcd initial_state =
+      Eigen::MatrixXcd::Zero(get_matrix_size(circ.n_qubits()), 1);
+  initial_state(0, 0) = 1.;
+  auto first_random = instructions.cbegin();
+  while (first_random != instructions.cend() &&
+         is_deterministic(*first_random) && first_random->permutation.empty()) {
+    if (first_random->unitary) {
+      first_random->unitary->apply_full_unitary(
+          initial_state, circ.n_qubits());
+    }
+    ++first_random;
+  }
+
+  RNG rng;
+  rng.set_seed(seed);
+  std::vector<std::vector<bool>> shots;
+  shots.reserve(n_shots);
+  for (unsigned shot = 0; shot < n_shots; ++shot) {
+    ShotState state(initial_state, circ.n_qubits(), circ.n_bits(), rng);
+    for (auto it = first_random; it != instructions.cend(); ++it) {
+      state.apply(*it);
+    }
+    shots.push_back(state.get_bits());
+  }
+  return ShotTable(circ.all_bits(), std::move(shots));
+}
+
+}  // namespace tket_sim
+}  // namespace tket
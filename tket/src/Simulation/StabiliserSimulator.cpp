@@ -0,0 +1,433 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "StabiliserSimulator.hpp"
+
+#include <cmath>
+#include <functional>
+#include <numeric>
+#include <tkassert/Assert.hpp>
+#include <tkrng/RNG.hpp>
+
+#include "Circuit/Boxes.hpp"
+#include "Circuit/CircUtils.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Circuit/Conditional.hpp"
+#include "ClassicalEvaluation.hpp"
+#include "Clifford/StabiliserTableau.hpp"
+#include "Gate/Gate.hpp"
+#include "Gate/GatePtr.hpp"
+#include "Gate/OpPtrFunctions.hpp"
+#include "OpType/OpTypeFunctions.hpp"
+
+namespace tket {
+namespace tket_sim {
+
+namespace {
+
+typedef std::pair<OpType, std::vector<unsigned>> TableauGate;
+
+/** A command with its arguments converted to top-level qubit and bit
+ *  indices. Clifford gates are precomputed as a sequence of gates accepted
+ *  by StabiliserTableau::apply_gate.
+ */
+struct Instruction {
+  Op_ptr op;
+  std::vector<unsigned> qubits;
+  std::vector<unsigned> bits;
+  std::vector<TableauGate> gates;
+  // For conditionals and boxes, the decomposed contents.
+  std::vector<Instruction> sub_instructions;
+};
+
+CircuitInvalidity unsupported_op(const Op_ptr& op) {
+  return CircuitInvalidity(
+      "Cannot simulate operation " + op->get_name() +
+      " in a stabiliser simulation");
+}
+
+void append_clifford_gates(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    std::vector<TableauGate>& gates);
+
+void append_circuit_gates(
+    const Circuit& circ, const std::vector<unsigned>& qubits,
+    std::vector<TableauGate>& gates);
+
+// Append SWAP gates realising the implicit qubit permutation of a circuit.
+void append_permutation(
+    const Circuit& circ, const std::vector<unsigned>& qubits,
+    std::vector<TableauGate>& gates) {
+  const qubit_vector_t all_qubits = circ.all_qubits();
+  std::map<UnitID, unsigned> index;
+  for (unsigned i = 0; i < all_qubits.size(); ++i) {
+    index[all_qubits[i]] = i;
+  }
+  std::vector<unsigned> target(all_qubits.size());
+  for (const auto& [in, out] : circ.implicit_qubit_permutation()) {
+    target[index.at(in)] = index.at(out);
+  }
+  for (unsigned q = 0; q < target.size(); ++q) {
+    while (target[q] != q) {
+      // The state on q belongs on target[q]; the state swapped in from there
+      // belongs on target[target[q]].
+      const unsigned t = target[q];
+      gates.push_back({OpType::SWAP, {qubits[q], qubits[t]}});
+      target[q] = target[t];
+      target[t] = t;
+    }
+  }
+}
+
+void append_circuit_gates(
+    const Circuit& circ, const std::vector<unsigned>& qubits,
+    std::vector<TableauGate>& gates) {
+  std::map<UnitID, unsigned> qmap;
+  const qubit_vector_t all_qubits = circ.all_qubits();
+  for (unsigned i = 0; i < all_qubits.size(); ++i) {
+    qmap[all_qubits[i]] = qubits.at(i);
+  }
+  std::vector<unsigned> op_qubits;
+  for (const Command& command : circ) {
+    op_qubits.clear();
+    for (const UnitID& unit : command.get_args()) {
+      op_qubits.push_back(qmap.at(unit));
+    }
+    append_clifford_gates(command.get_op_ptr(), op_qubits, gates);
+  }
+  append_permutation(circ, qubits, gates);
+}
+
+// The number of quarter turns of a rotation by the angle (in half-turns),
+// if it is a multiple of a quarter turn.
+std::optional<unsigned> get_quarter_turns(const Expr& angle) {
+  const std::optional<double> reduced = eval_expr_mod(angle, 4);
+  if (!reduced) return std::nullopt;
+  const double turns = 2 * reduced.value();
+  const unsigned rounded = unsigned(turns + 0.5);
+  if (std::abs(turns - rounded) >= EPS) return std::nullopt;
+  return rounded % 4;
+}
+
+void append_clifford_gates(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    std::vector<TableauGate>& gates) {
+  const OpType type = op->get_type();
+  switch (type) {
+    case OpType::Z:
+    case OpType::X:
+    case OpType::Y:
+    case OpType::S:
+    case OpType::Sdg:
+    case OpType::V:
+    case OpType::Vdg:
+    case OpType::H:
+    case OpType::CX:
+    case OpType::CY:
+    case OpType::CZ:
+    case OpType::SWAP:
+    case OpType::BRIDGE: {
+      gates.push_back({type, qubits});
+      return;
+    }
+    case OpType::noop:
+    case OpType::Barrier:
+    case OpType::Delay: {
+      return;
+    }
+    default:
+      break;
+  }
+  if (qubits.empty()) {
+    // Only contributes a global phase.
+    return;
+  }
+  if (!op->is_clifford()) {
+    throw unsupported_op(op);
+  }
+  if (op->get_desc().is_box()) {
+    const std::shared_ptr<Circuit> box_circ =
+        static_cast<const Box&>(*op).to_circuit();
+    if (!box_circ) {
+      throw unsupported_op(op);
+    }
+    append_circuit_gates(*box_circ, qubits, gates);
+    return;
+  }
+  if (!op->get_desc().is_gate()) {
+    throw unsupported_op(op);
+  }
+  const Gate_ptr gate = as_gate_ptr(op);
+  if (qubits.size() > 1) {
+    append_circuit_gates(with_CX(gate), qubits, gates);
+    return;
+  }
+  // TK1(a, b, c) = Rz(a) Rx(b) Rz(c) in matrix order, and up to phase
+  // Rz(1/2) = S and Rx(1/2) = V.
+  const std::vector<Expr> angles = gate->get_tk1_angles();
+  const std::vector<std::pair<OpType, Expr>> rotations = {
+      {OpType::S, angles[2]}, {OpType::V, angles[1]}, {OpType::S, angles[0]}};
+  for (const auto& [rotation_type, angle] : rotations) {
+    const std::optional<unsigned> quarter_turns = get_quarter_turns(angle);
+    if (!quarter_turns) {
+      throw unsupported_op(op);
+    }
+    for (unsigned i = 0; i < quarter_turns.value(); ++i) {
+      gates.push_back({rotation_type, qubits});
+    }
+  }
+}
+
+Instruction make_instruction(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    const std::vector<unsigned>& bits);
+
+// Convert every command of the circuit into an instruction.
+std::vector<Instruction> compile_circuit(
+    const Circuit& circ, const std::vector<unsigned>& qubit_indices,
+    const std::vector<unsigned>& bit_indices) {
+  std::map<UnitID, unsigned> qmap;
+  std::map<UnitID, unsigned> bmap;
+  {
+    const qubit_vector_t qubits = circ.all_qubits();
+    TKET_ASSERT(qubits.size() == qubit_indices.size());
+    for (unsigned i = 0; i < qubits.size(); ++i) {
+      qmap[qubits[i]] = qubit_indices[i];
+    }
+    const bit_vector_t bits = circ.all_bits();
+    TKET_ASSERT(bits.size() == bit_indices.size());
+    for (unsigned i = 0; i < bits.size(); ++i) {
+      bmap[bits[i]] = bit_indices[i];
+    }
+  }
+  std::vector<Instruction> instructions;
+  std::vector<unsigned> op_qubits;
+  std::vector<unsigned> op_bits;
+  for (const Command& command : circ) {
+    op_qubits.clear();
+    op_bits.clear();
+    for (const UnitID& unit : command.get_args()) {
+      if (unit.type() == UnitType::Qubit) {
+        op_qubits.push_back(qmap.at(unit));
+      } else {
+        op_bits.push_back(bmap.at(unit));
+      }
+    }
+    instructions.push_back(
+        make_instruction(command.get_op_ptr(), op_qubits, op_bits));
+  }
+  Instruction perm_instruction;
+  perm_instruction.op = get_op_ptr(OpType::noop);
+  append_permutation(circ, qubit_indices, perm_instruction.gates);
+  if (!perm_instruction.gates.empty()) {
+    instructions.push_back(std::move(perm_instruction));
+  }
+  return instructions;
+}
+
+Instruction make_instruction(
+    const Op_ptr& op, const std::vector<unsigned>& qubits,
+    const std::vector<unsigned>& bits) {
+  Instruction instruction;
+  instruction.op = op;
+  instruction.qubits = qubits;
+  instruction.bits = bits;
+  const OpType type = op->get_type();
+  switch (type) {
+    case OpType::noop:
+    case OpType::Barrier:
+    case OpType::Delay:
+    case OpType::Measure:
+    case OpType::Reset:
+    case OpType::Collapse:
+      break;
+    case OpType::Conditional: {
+      const Conditional& cond = static_cast<const Conditional&>(*op);
+      const std::vector<unsigned> inner_bits(
+          bits.begin() + cond.get_width(), bits.end());
+      instruction.sub_instructions.push_back(
+          make_instruction(cond.get_op(), qubits, inner_bits));
+      break;
+    }
+    default: {
+      if (is_classical_type(type)) {
+        break;
+      }
+      if (bits.empty()) {
+        append_clifford_gates(op, qubits, instruction.gates);
+        break;
+      }
+      if (!op->get_desc().is_box()) {
+        throw unsupported_op(op);
+      }
+      const std::shared_ptr<Circuit> box_circ =
+          static_cast<const Box&>(*op).to_circuit();
+      if (!box_circ) {
+        throw unsupported_op(op);
+      }
+      instruction.sub_instructions = compile_circuit(*box_circ, qubits, bits);
+    }
+  }
+  return instruction;
+}
+
+// Whether the instruction is a sequence of Clifford gates (possibly empty).
+bool is_unitary(const Instruction& instruction) {
+  const OpType type = instruction.op->get_type();
+  return type != OpType::Measure && type != OpType::Reset &&
+         type != OpType::Collapse && type != OpType::Conditional &&
+         !is_classical_type(type) && instruction.sub_instructions.empty();
+}
+
+/** The state of a single run of the circuit. Random measurement outcomes
+ *  are supplied by a callback.
+ */
+class StabiliserRun {
+ public:
+  StabiliserRun(
+      const StabiliserTableau& tableau, unsigned n_bits,
+      std::function<bool()> random_outcome)
+      : tableau_(tableau),
+        bits_(n_bits, false),
+        random_outcome_(std::move(random_outcome)) {}
+
+  void apply(const Instruction& instruction);
+
+  const std::vector<bool>& get_bits() const { return bits_; }
+
+ private:
+  StabiliserTableau tableau_;
+  std::vector<bool> bits_;
+  std::function<bool()> random_outcome_;
+
+  bool measure(unsigned qubit);
+};
+
+bool StabiliserRun::measure(unsigned qubit) {
+  const std::optional<bool> outcome =
+      tableau_.get_deterministic_outcome(qubit);
+  if (outcome) {
+    // Measuring a determined outcome leaves the state unchanged.
+    return outcome.value();
+  }
+  return tableau_.measure(qubit, random_outcome_());
+}
+
+void StabiliserRun::apply(const Instruction& instruction) {
+  const OpType type = instruction.op->get_type();
+  if (type == OpType::Measure) {
+    bits_[instruction.bits.at(0)] = measure(instruction.qubits.at(0));
+  } else if (type == OpType::Collapse) {
+    measure(instruction.qubits.at(0));
+  } else if (type == OpType::Reset) {
+    if (measure(instruction.qubits.at(0))) {
+      tableau_.apply_gate(OpType::X, {instruction.qubits[0]});
+    }
+  } else if (type == OpType::Conditional) {
+    const Conditional& cond =
+        static_cast<const Conditional&>(*instruction.op);
+    if (internal::condition_holds(cond, instruction.bits, bits_)) {
+      apply(instruction.sub_instructions.at(0));
+    }
+  } else if (is_classical_type(type)) {
+    internal::apply_classical_op(instruction.op, instruction.bits, bits_);
+  } else {
+    for (const auto& [gate_type, gate_qubits] : instruction.gates) {
+      tableau_.apply_gate(gate_type, gate_qubits);
+    }
+    for (const Instruction& sub_instruction : instruction.sub_instructions) {
+      apply(sub_instruction);
+    }
+  }
+}
+
+/** The compiled circuit, with the state after its initial run of Clifford
+ *  gates, which is the same for every run.
+ */
+struct CompiledCircuit {
+  std::vector<Instruction> instructions;
+  std::size_t n_initial_unitaries;
+  StabiliserTableau initial_tableau;
+  unsigned n_bits;
+
+  explicit CompiledCircuit(const Circuit& circ)
+      : initial_tableau(circ.n_qubits()), n_bits(circ.n_bits()) {
+    std::vector<unsigned> qubit_indices(circ.n_qubits());
+    std::iota(qubit_indices.begin(), qubit_indices.end(), 0);
+    std::vector<unsigned> bit_indices(circ.n_bits());
+    std::iota(bit_indices.begin(), bit_indices.end(), 0);
+    instructions = compile_circuit(circ, qubit_indices, bit_indices);
+    n_initial_unitaries = 0;
+    while (n_initial_unitaries < instructions.size() &&
+           is_unitary(instructions[n_initial_unitaries])) {
+      for (const auto& [gate_type, gate_qubits] :
+           instructions[n_initial_unitaries].gates) {
+        initial_tableau.apply_gate(gate_type, gate_qubits);
+      }
+      ++n_initial_unitaries;
+    }
+  }
+
+  std::vector<bool> run(std::function<bool()> random_outcome) const {
+    StabiliserRun state(initial_tableau, n_bits, std::move(random_outcome));
+    for (std::size_t i = n_initial_unitaries; i < instructions.size(); ++i) {
+      state.apply(instructions[i]);
+    }
+    return state.get_bits();
+  }
+};
+
+}  // namespace
+
+ShotTable sample_stabiliser_shots(
+    const Circuit& circ, unsigned n_shots, std::size_t seed) {
+  const CompiledCircuit compiled(circ);
+  RNG rng;
+  rng.set_seed(seed);
+  std::vector<std::vector<bool>> shots;
+  shots.reserve(n_shots);
+  for (unsigned shot = 0; shot < n_shots; ++shot) {
+    shots.push_back(compiled.run([&rng]() { return rng.get_size_t(1) == 1; }));
+  }
+  return ShotTable(circ.all_bits(), std::move(shots));
+}
+
+std::map<std::vector<bool>, double> get_stabiliser_outcome_probabilities(
+    const Circuit& circ) {
+  const CompiledCircuit compiled(circ);
+  std::map<std::vector<bool>, double> probabilities;
+  // Enumerate the sequences of random outcomes depth-first: each run follows
+  // the given choices, extending them with "false" as needed.
+  std::vector<bool> choices;
+  while (true) {
+    unsigned next_choice = 0;
+    const std::vector<bool> bits = compiled.run([&]() {
+      if (next_choice == choices.size()) {
+        choices.push_back(false);
+      }
+      return choices[next_choice++];
+    });
+    TKET_ASSERT(next_choice == choices.size());
+    probabilities[bits] += std::ldexp(1., -int(choices.size()));
+    while (!choices.empty() && choices.back()) {
+      choices.pop_back();
+    }
+    if (choices.empty()) break;
+    choices.back() = true;
+  }
+  return probabilities;
+}
+
+}  // namespace tket_sim
+}  // namespace tket
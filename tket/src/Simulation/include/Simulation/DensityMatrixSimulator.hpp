@@ -0,0 +1,73 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <map>
+#include <vector>
+
+#include "NoiseModel.hpp"
+#include "Utils/MatrixAnalysis.hpp"
+
+namespace tket {
+class Circuit;
+typedef Eigen::MatrixXcd DensityMatrix;
+
+namespace tket_sim {
+
+/** The joint state of the qubits and bits of a circuit. Each key holds the
+ *  values of all the bits of the circuit (in the order of
+ *  Circuit::all_bits()), and maps to the unnormalised density matrix of the
+ *  qubits (ILO-BE convention) in that classical branch; its trace is the
+ *  probability of observing those bit values.
+ *  Branches of negligible probability are not stored.
+ */
+typedef std::map<std::vector<bool>, DensityMatrix> ClassicalQuantumState;
+
+/** Simulate the circuit acting on the state |00...0>, with all bits
+ *  initially 0, as a mixed state, optionally applying noise.
+ *
+ *  Unlike get_statevector, this understands OpType::Measure,
+ *  OpType::Reset, OpType::Collapse, Conditional ops and classical ops which
+ *  can be evaluated (see ClassicalEvalOp). Boxes acting on both qubits and
+ *  bits are decomposed.
+ *
+ *  @param circ The circuit to simulate.
+ *  @param noise The channels to apply after each operation, and
+ *      readout errors to apply to measurement outcomes.
+ *  @param abs_epsilon Used to decide if an entry of a sparse matrix is
+ *      too small, i.e. if std::abs(z) <= abs_epsilon then we treat
+ *      z as zero exactly in any intermediate sparse matrices. Also, classical
+ *      branches of probability at most abs_epsilon are discarded.
+ *  @param max_number_of_qubits Throw an exception if this limit is exceeded.
+ */
+ClassicalQuantumState get_classical_quantum_state(
+    const Circuit& circ, const NoiseModel& noise = NoiseModel(),
+    double abs_epsilon = EPS, unsigned max_number_of_qubits = 8);
+
+/** Calculate the final density matrix of the qubits of the circuit
+ *  applied to the state |00...0>, using ILO-BE convention, averaged over
+ *  all measurement outcomes. See get_classical_quantum_state.
+ *
+ *  @param circ The circuit to simulate.
+ *  @param noise The channels to apply after each operation.
+ *  @param abs_epsilon Tolerance for treating values as zero.
+ *  @param max_number_of_qubits Throw an exception if this limit is exceeded.
+ */
+DensityMatrix get_density_matrix(
+    const Circuit& circ, const NoiseModel& noise = NoiseModel(),
+    double abs_epsilon = EPS, unsigned max_number_of_qubits = 8);
+
+}  // namespace tket_sim
+}  // namespace tket
@@ -0,0 +1,153 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <map>
+#include <optional>
+#include <vector>
+
+#include "Characterisation/DeviceCharacterisation.hpp"
+#include "Circuit/DAGDefs.hpp"
+#include "OpType/OpType.hpp"
+#include "Utils/MatrixAnalysis.hpp"
+#include "Utils/UnitID.hpp"
+
+namespace tket {
+namespace tket_sim {
+
+/**
+ * A completely positive trace-preserving map on k qubits, given by a list of
+ * Kraus operators \f$ K_i \f$ (each a \f$ 2^k \times 2^k \f$ matrix in ILO-BE
+ * convention) such that \f$ \rho \mapsto \sum_i K_i \rho K_i^\dagger \f$.
+ */
+class KrausChannel {
+ public:
+  /**
+   * Construct a channel from its Kraus operators.
+   *
+   * @param kraus_ops The Kraus operators, all of the same size 2^k.
+   * @param tol Tolerance used to check that \f$ \sum_i K_i^\dagger K_i = I \f$.
+   *
+   * @throws std::invalid_argument if the operators are empty, of the wrong
+   *    size, or do not satisfy the completeness relation.
+   */
+  explicit KrausChannel(
+      const std::vector<Eigen::MatrixXcd>& kraus_ops, double tol = EPS);
+
+  /** Number of qubits the channel acts on. */
+  unsigned n_qubits() const { return n_qubits_; }
+
+  const std::vector<Eigen::MatrixXcd>& get_kraus_ops() const {
+    return kraus_ops_;
+  }
+
+  /**
+   * Depolarising channel on n qubits: with probability p one of the
+   * \f$ 4^n - 1 \f$ non-identity Pauli strings is applied, uniformly at
+   * random.
+   */
+  static KrausChannel depolarising(unsigned n_qubits, double p);
+
+  /** Single-qubit channel applying X with probability p. */
+  static KrausChannel bit_flip(double p);
+
+  /** Single-qubit channel applying Z with probability p. */
+  static KrausChannel phase_flip(double p);
+
+  /** Single-qubit amplitude damping (decay from |1> to |0>) with rate gamma. */
+  static KrausChannel amplitude_damping(double gamma);
+
+  /** Single-qubit phase damping with rate lambda. */
+  static KrausChannel phase_damping(double lambda);
+
+ private:
+  unsigned n_qubits_;
+  std::vector<Eigen::MatrixXcd> kraus_ops_;
+};
+
+/**
+ * Describes which noise channels to apply during density-matrix simulation.
+ *
+ * A channel is applied immediately after an operation. When several channels
+ * could apply to the same operation, the most specific is chosen, in this
+ * order:
+ *
+ *   1. a channel attached to the specific circuit vertex;
+ *   2. a channel for the OpType acting on exactly the given qubits;
+ *   3. a channel for any operation acting on exactly the given qubits;
+ *   4. a channel for the OpType acting anywhere;
+ *   5. a depolarising channel derived from the DeviceCharacterisation, if
+ *      one has been set (one- and two-qubit gates only).
+ *
+ * Readout errors are modelled classically: the recorded outcome of a
+ * measurement of a qubit is flipped with the given probability, while the
+ * post-measurement quantum state is unaffected.
+ */
+class NoiseModel {
+ public:
+  NoiseModel();
+
+  /** Apply the channel after every operation of the given type. */
+  void add_channel(OpType type, const KrausChannel& channel);
+
+  /** Apply the channel after every operation acting on exactly these qubits
+   * (in this order). */
+  void add_channel(const qubit_vector_t& qubits, const KrausChannel& channel);
+
+  /** Apply the channel after every operation of the given type acting on
+   * exactly these qubits (in this order). */
+  void add_channel(
+      OpType type, const qubit_vector_t& qubits, const KrausChannel& channel);
+
+  /** Apply the channel after the operation at the given circuit vertex. */
+  void add_channel(const Vertex& vertex, const KrausChannel& channel);
+
+  /** Set the probability that a measurement outcome of the qubit is flipped. */
+  void set_readout_error(const Qubit& qubit, readout_error_t p);
+
+  /**
+   * Derive depolarising gate noise and readout errors from device
+   * characterisation data. The circuit qubits are interpreted as Nodes.
+   * Explicitly added channels and readout errors take precedence.
+   */
+  void set_characterisation(const DeviceCharacterisation& characterisation);
+
+  /**
+   * The channel to apply after an operation, if any.
+   *
+   * @param vertex The vertex of the operation in the simulated circuit.
+   * @param type The type of the operation.
+   * @param qubits The qubits the operation acts on.
+   */
+  std::optional<KrausChannel> get_channel(
+      const Vertex& vertex, OpType type, const qubit_vector_t& qubits) const;
+
+  /** The probability that a measurement outcome of the qubit is flipped. */
+  readout_error_t get_readout_error(const Qubit& qubit) const;
+
+  /** Whether no noise at all is described. */
+  bool empty() const;
+
+ private:
+  std::map<Vertex, KrausChannel> vertex_channels_;
+  std::map<std::pair<OpType, qubit_vector_t>, KrausChannel> op_qubit_channels_;
+  std::map<qubit_vector_t, KrausChannel> qubit_channels_;
+  std::map<OpType, KrausChannel> op_channels_;
+  std::map<Qubit, readout_error_t> readout_errors_;
+  std::optional<DeviceCharacterisation> characterisation_;
+};
+
+}  // namespace tket_sim
+}  // namespace tket
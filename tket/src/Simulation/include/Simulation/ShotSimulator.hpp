@@ -0,0 +1,79 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <cstddef>
+#include <map>
+#include <vector>
+
+#include "Utils/Constants.hpp"
+#include "Utils/UnitID.hpp"
+
+namespace tket {
+class Circuit;
+
+namespace tket_sim {
+
+/** The classical results of running a circuit a number of times.
+ *  Each shot holds the final values of all the bits of the circuit,
+ *  in the order given by get_bits().
+ */
+class ShotTable {
+ public:
+  ShotTable(const bit_vector_t& bits, std::vector<std::vector<bool>> shots);
+
+  /** The bits of the circuit, i.e. the columns of the table. */
+  const bit_vector_t& get_bits() const { return bits_; }
+
+  /** One row per shot. */
+  const std::vector<std::vector<bool>>& get_shots() const { return shots_; }
+
+  unsigned n_shots() const { return shots_.size(); }
+
+  /** The number of times each distinct row occurs. */
+  std::map<std::vector<bool>, unsigned> get_counts() const;
+
+ private:
+  bit_vector_t bits_;
+  std::vector<std::vector<bool>> shots_;
+};
+
+/** Run the circuit shot by shot on a statevector, starting each time from
+ *  |00...0> with all bits 0, and record the final bit values.
+ *
+ *  OpType::Measure collapses the state, sampling the outcome;
+ *  OpType::Reset and OpType::Collapse act as measurements whose outcome is
+ *  not recorded (followed by a correction, for Reset). Conditional ops are
+ *  evaluated against the current bit values of the shot, and classical ops
+ *  are evaluated using ClassicalEvalOp::eval (so WASMOp is not supported).
+ *  Boxes with classical wires are decomposed.
+ *
+ *  The outcomes are reproducible: they only depend on the circuit, the
+ *  number of shots and the seed, and not on the platform.
+ *
+ *  @param circ The circuit to simulate.
+ *  @param n_shots The number of shots.
+ *  @param seed The seed for the random number generator.
+ *  @param abs_epsilon Used to decide if an entry of a sparse matrix is
+ *              too small, i.e. if std::abs(z) <= abs_epsilon then we treat
+ *              z as zero exactly in any intermediate sparse matrices.
+ *  @param max_number_of_qubits Throw an exception if this limit is exceeded.
+ */
+ShotTable sample_shots(
+    const Circuit& circ, unsigned n_shots, std::size_t seed = 5489,
+    double abs_epsilon = EPS, unsigned max_number_of_qubits = 16);
+
+}  // namespace tket_sim
+}  // namespace tket
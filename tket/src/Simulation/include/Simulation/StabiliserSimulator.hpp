@@ -0,0 +1,67 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <cstddef>
+#include <map>
+#include <vector>
+
+#include "ShotSimulator.hpp"
+
+namespace tket {
+class Circuit;
+
+namespace tket_sim {
+
+/** Run a stabiliser circuit shot by shot on a StabiliserTableau, starting
+ *  each time from |00...0> with all bits 0, and record the final bit values.
+ *
+ *  The circuit may contain Clifford gates (i.e. any op satisfying
+ *  CliffordCircuitPredicate), Measure, Reset and Collapse, classical ops and
+ *  Conditional versions of any of these, so that mid-circuit measurements
+ *  with Pauli corrections can be simulated. Boxes are decomposed. Clifford
+ *  gates are decomposed into S, V and CX gates, so any gate whose
+ *  decomposition involves non-Clifford angles is rejected even if the gate
+ *  as a whole is Clifford.
+ *
+ *  Each shot takes time O(N^2) per measurement for N qubits, so circuits
+ *  over thousands of qubits are practical.
+ *
+ *  @param circ The circuit to simulate.
+ *  @param n_shots The number of shots.
+ *  @param seed The seed for the random number generator.
+ *  @throw CircuitInvalidity if the circuit contains an unsupported op.
+ */
+ShotTable sample_stabiliser_shots(
+    const Circuit& circ, unsigned n_shots, std::size_t seed = 5489);
+
+/** The exact probability of each possible final value of the bits of a
+ *  stabiliser circuit, for the same circuits as sample_stabiliser_shots.
+ *  Bit values are ordered as circ.all_bits().
+ *
+ *  Every measurement outcome in a stabiliser circuit is either determined
+ *  or uniformly random, so every probability is a power of 1/2. The
+ *  circuit is simulated once for every combination of random outcomes,
+ *  so the running time is exponential in the number of random outcomes
+ *  (but not in the number of qubits).
+ *
+ *  @param circ The circuit to simulate.
+ *  @throw CircuitInvalidity if the circuit contains an unsupported op.
+ */
+std::map<std::vector<bool>, double> get_stabiliser_outcome_probabilities(
+    const Circuit& circ);
+
+}  // namespace tket_sim
+}  // namespace tket
@@ -16,11 +16,17 @@
 
 #include <math.h>
 
+#include <algorithm>
+#include <map>
 #include <numeric>
 #include <optional>
+#include <set>
 
 #include "Circuit/CircPool.hpp"
+#include "Circuit/CircUtils.hpp"
 #include "Circuit/DAGDefs.hpp"
+#include "Circuit/ShannonDecomposition.hpp"
+#include "Gate/Rotation.hpp"
 #include "OpType/OpType.hpp"
 #include "Transform.hpp"
 #include "Utils/EigenConfig.hpp"
@@ -776,6 +782,268 @@ Transform decomp_arbitrary_controlled_gates() {
   return decomp_controlled_Rys() >> decomp_CCX();
 }
 
+Circuit cnx_ancilla_decomp(unsigned n, bool clean, bool relative_phase) {
+  if (n < 3)
+    throw Unsupported(
+        "Cannot decompose a gate with " + std::to_string(n) +
+        " controls using an ancilla ladder");
+  if (relative_phase && !clean)
+    throw Unsupported(
+        "Relative-phase decomposition of a CnX requires clean ancillas");
+  const unsigned target = 2 * n - 2;
+  Circuit ladder(2 * n - 1);
+  if (clean) {
+    // compute the AND of the controls along the ancillas, copy it onto the
+    // target and uncompute
+    ladder.add_op<unsigned>(OpType::CCX, {0, 1, n});
+    for (unsigned i = 2; i < n - 1; ++i) {
+      ladder.add_op<unsigned>(OpType::CCX, {i, n + i - 2, n + i - 1});
+    }
+    ladder.add_op<unsigned>(OpType::CCX, {n - 1, target - 1, target});
+    for (unsigned i = n - 2; i > 1; --i) {
+      ladder.add_op<unsigned>(OpType::CCX, {i, n + i - 2, n + i - 1});
+    }
+    ladder.add_op<unsigned>(OpType::CCX, {0, 1, n});
+  } else {
+    ladder = lemma72(n);
+  }
+  Circuit circ(2 * n - 1);
+  for (const Command& cmd : ladder) {
+    std::vector<unsigned> qbs;
+    for (const Qubit& q : cmd.get_qubits()) qbs.push_back(q.index()[0]);
+    if (qbs[2] == target && !relative_phase) {
+      circ.append_qubits(CircPool::CCX_normal_decomp(), qbs);
+    } else {
+      circ.append_qubits(CircPool::CCX_modulo_phase_shift(), qbs);
+    }
+  }
+  return circ;
+}
+
+// Find a later command that uncomputes the CCX or CnX at `commands[i]`, such
+// that every command in between acts on the gate's qubits only as a control
+// or diagonally and does not act on `ancillas` at all
+static std::optional<unsigned> find_uncompute(
+    const std::vector<Command>& commands, unsigned i,
+    const qubit_vector_t& ancillas) {
+  const Op_ptr op = commands[i].get_op_ptr();
+  const qubit_vector_t qbs = commands[i].get_qubits();
+  const Qubit target = qbs.back();
+  const std::set<Qubit> controls(qbs.begin(), qbs.end() - 1);
+  for (unsigned j = i + 1; j < commands.size(); ++j) {
+    const Op_ptr next_op = commands[j].get_op_ptr();
+    const unit_vector_t args = commands[j].get_args();
+    // the relative phases only cancel if the qubits are in the same order
+    if (next_op->get_type() == op->get_type() &&
+        commands[j].get_qubits() == qbs) {
+      return j;
+    }
+    if (next_op->get_type() == OpType::Barrier) continue;
+    for (unsigned p = 0; p < args.size(); ++p) {
+      if (args[p].type() != UnitType::Qubit) continue;
+      const Qubit q(args[p]);
+      if (std::find(ancillas.begin(), ancillas.end(), q) != ancillas.end()) {
+        return std::nullopt;
+      }
+      if ((q == target || controls.find(q) != controls.end()) &&
+          !next_op->commutes_with_basis(Pauli::Z, p)) {
+        return std::nullopt;
+      }
+    }
+  }
+  return std::nullopt;
+}
+
+// Map the qubits of a replacement circuit to the given qubits
+static unit_map_t replacement_qubit_map(const qubit_vector_t& qbs) {
+  unit_map_t qm;
+  for (unsigned i = 0; i < qbs.size(); ++i) qm.insert({Qubit(i), qbs[i]});
+  return qm;
+}
+
+Transform decomp_arbitrary_controlled_gates(
+    CnXAncillaMode mode, bool relative_phase) {
+  return Transform([mode, relative_phase](Circuit& circ) {
+    const std::vector<Command> commands = circ.get_commands();
+    const qubit_vector_t all_qbs = circ.all_qubits();
+    Circuit new_circ(all_qbs, circ.all_bits());
+    const std::optional<std::string> name = circ.get_name();
+    if (name) new_circ.set_name(*name);
+    new_circ.add_phase(circ.get_phase());
+    // qubits known to be in the |0> state
+    std::map<Qubit, bool> clean;
+    for (const Qubit& q : all_qbs) {
+      clean[q] = circ.is_created(q);
+      if (circ.is_created(q)) new_circ.qubit_create(q);
+      if (circ.is_discarded(q)) new_circ.qubit_discard(q);
+    }
+    // ancillas to use for the second gate of each compute-uncompute pair,
+    // indexed by command
+    std::map<unsigned, qubit_vector_t> uncompute_gates;
+    bool success = false;
+    for (unsigned i = 0; i < commands.size(); ++i) {
+      const Command& cmd = commands[i];
+      const Op_ptr op = cmd.get_op_ptr();
+      const OpType type = op->get_type();
+      const qubit_vector_t args = cmd.get_qubits();
+      if (type != OpType::CnRy && type != OpType::CnX &&
+          type != OpType::CCX) {
+        new_circ.add_op(op, cmd.get_args(), cmd.get_opgroup());
+        if (type == OpType::Reset) {
+          clean[args[0]] = true;
+        } else if (type != OpType::Barrier) {
+          for (const Qubit& q : args) clean[q] = false;
+        }
+        continue;
+      }
+      success = true;
+      for (const Qubit& q : args) clean[q] = false;
+      if (type == OpType::CnRy) {
+        new_circ.append_with_map(
+            decomposed_CnRy(op, args.size()), replacement_qubit_map(args));
+        continue;
+      }
+      const unsigned n = args.size() - 1;
+      qubit_vector_t clean_qbs, borrowed_qbs;
+      if (n >= 3 && mode != CnXAncillaMode::None) {
+        for (const Qubit& q : all_qbs) {
+          if (std::find(args.begin(), args.end(), q) != args.end()) continue;
+          if (clean[q]) {
+            clean_qbs.push_back(q);
+          } else if (mode == CnXAncillaMode::Borrowed) {
+            borrowed_qbs.push_back(q);
+          }
+        }
+      }
+      Circuit rep;
+      qubit_vector_t ancillas;
+      auto uncompute = uncompute_gates.find(i);
+      if (uncompute != uncompute_gates.end()) {
+        ancillas = uncompute->second;
+        rep = (n == 2) ? CircPool::CCX_modulo_phase_shift()
+                       : cnx_ancilla_decomp(n, true, true);
+      } else if (n >= 3 && clean_qbs.size() >= n - 2) {
+        ancillas.assign(clean_qbs.begin(), clean_qbs.begin() + n - 2);
+        rep = cnx_ancilla_decomp(n, true);
+      } else if (n >= 3 && clean_qbs.size() + borrowed_qbs.size() >= n - 2) {
+        ancillas = clean_qbs;
+        ancillas.insert(
+            ancillas.end(), borrowed_qbs.begin(),
+            borrowed_qbs.begin() + (n - 2 - clean_qbs.size()));
+        rep = cnx_ancilla_decomp(n, false);
+      } else {
+        rep = cnx_normal_decomp(n);
+      }
+      if (relative_phase && uncompute == uncompute_gates.end() &&
+          (n == 2 || (n >= 3 && clean_qbs.size() >= n - 2))) {
+        std::optional<unsigned> j = find_uncompute(commands, i, ancillas);
+        if (j) {
+          uncompute_gates.insert({*j, ancillas});
+          rep = (n == 2) ? CircPool::CCX_modulo_phase_shift()
+                         : cnx_ancilla_decomp(n, true, true);
+        }
+      }
+      // the ancillas sit between the controls and the target
+      qubit_vector_t rep_qbs(args.begin(), args.end() - 1);
+      rep_qbs.insert(rep_qbs.end(), ancillas.begin(), ancillas.end());
+      rep_qbs.push_back(args.back());
+      new_circ.append_with_map(rep, replacement_qubit_map(rep_qbs));
+    }
+    if (!success) return false;
+    new_circ.permute_boundary_output(circ.implicit_qubit_permutation());
+    circ = new_circ;
+    return true;
+  });
+}
+
+// Return an n-qubit circuit implementing the diagonal unitary with entries
+// e^{i pi phases[j]}, where phases has size 2^n, using 2^n - 2 CX gates.
+// Each step splits off a multiplexed Rz on the last remaining qubit,
+// controlled by the qubits before it.
+static Circuit diagonal_phase_circ(std::vector<double> phases) {
+  unsigned n = 0;
+  while ((1u << n) < phases.size()) n++;
+  Circuit circ(n);
+  for (unsigned k = n; k > 0; k--) {
+    std::vector<double> angles, averages;
+    for (unsigned m = 0; m < phases.size() / 2; m++) {
+      angles.push_back(phases[2 * m + 1] - phases[2 * m]);
+      averages.push_back((phases[2 * m] + phases[2 * m + 1]) / 2);
+    }
+    unit_map_t qm{{Qubit(0), Qubit(k - 1)}};
+    for (unsigned i = 1; i < k; i++) {
+      qm.insert({Qubit(i), Qubit(i - 1)});
+    }
+    circ.append_with_map(multiplexed_rotation(OpType::Rz, angles), qm);
+    phases = averages;
+  }
+  circ.add_phase(phases[0]);
+  return circ;
+}
+
+Circuit decomposed_multiplexor(const MultiplexorBox& box) {
+  const unsigned n_controls = box.get_n_controls();
+  if (box.get_n_targets() != 1 || !box.free_symbols().empty()) {
+    return *box.to_circuit();
+  }
+  // Write each op as e^{i pi p} Rz(a) Ry(b) Rz(c), using
+  // TK1(a, b, c) = Rz(a) Rx(b) Rz(c) = Rz(a - 1/2) Ry(b) Rz(c + 1/2).
+  const unsigned N = 1u << n_controls;
+  std::vector<double> a(N, 0.), b(N, 0.), c(N, 0.), p(N, 0.);
+  for (const auto& [state, op] : box.get_op_map()) {
+    unsigned j = 0;
+    for (bool bit : state) j = 2 * j + bit;
+    Circuit u(1);
+    u.add_op<unsigned>(op, {0});
+    u.decompose_boxes_recursively();
+    std::vector<double> angles =
+        tk1_angles_from_unitary(get_matrix_from_circ(u));
+    a[j] = angles[0] - 0.5;
+    b[j] = angles[1];
+    c[j] = angles[2] + 0.5;
+    p[j] = angles[3];
+  }
+  // multiplexed_rotation() puts the target first, followed by the controls.
+  unit_map_t target_qm{{Qubit(0), Qubit(n_controls)}};
+  unit_map_t control_qm;
+  for (unsigned i = 0; i < n_controls; i++) {
+    target_qm.insert({Qubit(i + 1), Qubit(i)});
+    control_qm.insert({Qubit(i), Qubit(i)});
+  }
+  Circuit circ(n_controls + 1);
+  circ.append_with_map(multiplexed_rotation(OpType::Rz, c), target_qm);
+  circ.append_with_map(multiplexed_rotation(OpType::Ry, b), target_qm);
+  circ.append_with_map(multiplexed_rotation(OpType::Rz, a), target_qm);
+  circ.append_with_map(diagonal_phase_circ(p), control_qm);
+  return circ;
+}
+
+Transform decomp_multiplexors() {
+  return Transform([](Circuit& circ) {
+    bool success = false;
+    VertexList bin;
+    BGL_FORALL_VERTICES(v, circ.dag, DAG) {
+      const Op_ptr op = circ.get_Op_ptr_from_Vertex(v);
+      const OpType type = op->get_type();
+      if (type == OpType::MultiplexorBox) {
+        const MultiplexorBox& box = static_cast<const MultiplexorBox&>(*op);
+        circ.substitute(
+            decomposed_multiplexor(box), v, Circuit::VertexDeletion::No);
+      } else if (type == OpType::MultiplexedRotationBox) {
+        const Box& box = static_cast<const Box&>(*op);
+        circ.substitute(*box.to_circuit(), v, Circuit::VertexDeletion::No);
+      } else {
+        continue;
+      }
+      bin.push_back(v);
+      success = true;
+    }
+    circ.remove_vertices(
+        bin, Circuit::GraphRewiring::No, Circuit::VertexDeletion::Yes);
+    return success;
+  });
+}
+
 // decompose CnX gate using lemma 7.1
 // `n` = no. of controls
 Circuit cnx_gray_decomp(unsigned n) {
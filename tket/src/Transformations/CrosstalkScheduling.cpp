@@ -0,0 +1,126 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "CrosstalkScheduling.hpp"
+
+#include <algorithm>
+#include <optional>
+
+#include "Circuit/Conditional.hpp"
+#include "Ops/MetaOp.hpp"
+
+namespace tket {
+
+// links of the two-qubit gates of a circuit, in each slice
+static std::vector<std::vector<std::pair<Vertex, Architecture::Connection>>>
+sliced_links(const Circuit& circ) {
+  std::map<Vertex, Architecture::Connection> links;
+  for (const Command& com : circ) {
+    Op_ptr op = com.get_op_ptr();
+    if (op->get_type() == OpType::Conditional) {
+      op = static_cast<const Conditional&>(*op).get_op();
+    }
+    if (op->get_desc().is_meta()) continue;
+    qubit_vector_t qbs = com.get_qubits();
+    if (qbs.size() == 2) {
+      links.insert({com.get_vertex(), {Node(qbs[0]), Node(qbs[1])}});
+    }
+  }
+  std::vector<std::vector<std::pair<Vertex, Architecture::Connection>>>
+      sliced;
+  for (const Slice& slice : circ.get_slices()) {
+    std::vector<std::pair<Vertex, Architecture::Connection>> slice_links;
+    for (const Vertex& v : slice) {
+      auto it = links.find(v);
+      if (it != links.end()) slice_links.push_back(*it);
+    }
+    sliced.push_back(slice_links);
+  }
+  return sliced;
+}
+
+CrosstalkReport get_crosstalk_report(
+    const Circuit& circ, const DeviceCharacterisation& characterisation,
+    gate_error_t threshold) {
+  CrosstalkReport report;
+  for (const auto& slice_links : sliced_links(circ)) {
+    for (unsigned i = 0; i < slice_links.size(); i++) {
+      for (unsigned j = i + 1; j < slice_links.size(); j++) {
+        gate_error_t penalty = characterisation.get_crosstalk_error(
+            slice_links[i].second, slice_links[j].second);
+        report.total_penalty += penalty;
+        report.max_penalty = std::max(report.max_penalty, penalty);
+        if (penalty > threshold) ++report.n_conflicts;
+      }
+    }
+  }
+  return report;
+}
+
+namespace Transforms {
+
+// the pair of gates with the largest penalty above the threshold in the
+// first slice with such a pair
+static std::optional<std::pair<Vertex, Vertex>> first_conflict(
+    const Circuit& circ, const DeviceCharacterisation& characterisation,
+    gate_error_t threshold) {
+  for (const auto& slice_links : sliced_links(circ)) {
+    std::optional<std::pair<Vertex, Vertex>> worst;
+    gate_error_t worst_penalty = threshold;
+    for (unsigned i = 0; i < slice_links.size(); i++) {
+      for (unsigned j = i + 1; j < slice_links.size(); j++) {
+        gate_error_t penalty = characterisation.get_crosstalk_error(
+            slice_links[i].second, slice_links[j].second);
+        if (penalty > worst_penalty) {
+          worst = {slice_links[i].first, slice_links[j].first};
+          worst_penalty = penalty;
+        }
+      }
+    }
+    if (worst) return worst;
+  }
+  return std::nullopt;
+}
+
+Transform schedule_crosstalk(
+    const DeviceCharacterisation& characterisation, gate_error_t threshold) {
+  return Transform([=](Circuit& circ) {
+    bool success = false;
+    while (std::optional<std::pair<Vertex, Vertex>> conflict =
+               first_conflict(circ, characterisation, threshold)) {
+      // delay the gate with fewer slices after it, which is least likely to
+      // increase the depth of the circuit
+      std::map<Vertex, unsigned> rev_depth = circ.vertex_rev_depth_map();
+      Vertex first = conflict->first;
+      Vertex second = conflict->second;
+      if (rev_depth.at(second) > rev_depth.at(first)) {
+        std::swap(first, second);
+      }
+      // a barrier after the first gate and before the second
+      EdgeVec edges = circ.get_out_edges_of_type(first, EdgeType::Quantum);
+      EdgeVec ins = circ.get_in_edges_of_type(second, EdgeType::Quantum);
+      edges.insert(edges.end(), ins.begin(), ins.end());
+      op_signature_t sig(edges.size(), EdgeType::Quantum);
+      Vertex barrier =
+          circ.add_vertex(std::make_shared<MetaOp>(OpType::Barrier, sig));
+      circ.rewire(barrier, edges, sig);
+      success = true;
+    }
+    return success;
+  });
+}
+
+}  // namespace Transforms
+
+}  // namespace tket
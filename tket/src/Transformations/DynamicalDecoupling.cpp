@@ -0,0 +1,120 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "DynamicalDecoupling.hpp"
+
+#include <complex>
+#include <stdexcept>
+
+#include "OpType/OpTypeFunctions.hpp"
+#include "Ops/MetaOp.hpp"
+#include "Utils/Constants.hpp"
+#include "Utils/EigenConfig.hpp"
+
+namespace tket {
+
+Circuit get_dd_sequence(DDSequence sequence) {
+  Circuit circ(1);
+  switch (sequence) {
+    case DDSequence::XY4: {
+      for (unsigned i = 0; i < 2; i++) {
+        circ.add_op<unsigned>(OpType::X, {0});
+        circ.add_op<unsigned>(OpType::Y, {0});
+      }
+      break;
+    }
+    case DDSequence::CPMG: {
+      circ.add_op<unsigned>(OpType::X, {0});
+      circ.add_op<unsigned>(OpType::X, {0});
+      break;
+    }
+  }
+  return circ;
+}
+
+namespace Transforms {
+
+// global phase, in half-turns, of the product of the gates of a sequence
+static double sequence_phase(const Circuit& sequence) {
+  if (sequence.n_qubits() != 1 || sequence.n_bits() != 0 ||
+      sequence.n_gates() == 0) {
+    throw std::invalid_argument(
+        "A dynamical decoupling sequence must be a non-empty circuit on one "
+        "qubit");
+  }
+  Eigen::MatrixXcd u = Eigen::MatrixXcd::Identity(2, 2);
+  for (const Command& com : sequence) {
+    Op_ptr op = com.get_op_ptr();
+    if (!op->get_desc().is_gate() || !op->free_symbols().empty()) {
+      throw std::invalid_argument(
+          "A dynamical decoupling sequence must consist of gates with "
+          "numerical parameters");
+    }
+    u = op->get_unitary() * u;
+  }
+  std::complex<double> phase = u(0, 0);
+  if (std::abs(std::abs(phase) - 1.) > EPS ||
+      !(u / phase).isIdentity(EPS)) {
+    throw std::invalid_argument(
+        "A dynamical decoupling sequence must implement the identity up to "
+        "global phase");
+  }
+  return std::arg(phase) / PI;
+}
+
+Transform dynamical_decoupling(
+    const GateDurations& durations, const Circuit& sequence,
+    ScheduleType type, double min_duration) {
+  double phase = sequence_phase(sequence);
+  std::vector<Op_ptr> pulses;
+  for (const Command& com : sequence) pulses.push_back(com.get_op_ptr());
+  return Transform([=](Circuit& circ) {
+    bool success = false;
+    Schedule schedule(circ, durations, type);
+    for (const IdleWindow& window : schedule.get_idle_windows(min_duration)) {
+      // leave qubits in their initial state, or after their last measurement
+      OpType before = circ.get_OpType_from_Vertex(circ.source(window.edge));
+      OpType after = circ.get_OpType_from_Vertex(circ.target(window.edge));
+      if (is_initial_q_type(before)) continue;
+      if (before == OpType::Measure && is_final_q_type(after)) continue;
+
+      double spare = window.end - window.start;
+      for (const Op_ptr& pulse : pulses) {
+        spare -= durations.get_duration(pulse, {window.qubit});
+      }
+      if (spare < -EPS) continue;
+      double spacing = std::max(spare, 0.) / pulses.size();
+
+      Edge e = window.edge;
+      auto insert = [&](const Op_ptr& op) {
+        Vertex v = circ.add_vertex(op);
+        circ.rewire(v, {e}, {EdgeType::Quantum});
+        e = circ.get_nth_out_edge(v, 0);
+      };
+      for (unsigned i = 0; i < pulses.size(); i++) {
+        double gap = (i == 0) ? spacing / 2 : spacing;
+        if (gap > EPS) insert(std::make_shared<DelayOp>(gap));
+        insert(pulses[i]);
+      }
+      if (spacing / 2 > EPS) insert(std::make_shared<DelayOp>(spacing / 2));
+      circ.add_phase(-phase);
+      success = true;
+    }
+    return success;
+  });
+}
+
+}  // namespace Transforms
+
+}  // namespace tket
@@ -14,6 +14,8 @@
 
 #include "Rebase.hpp"
 
+#include <algorithm>
+#include <map>
 #include <tklog/TketLog.hpp>
 
 #include "BasicOptimisation.hpp"
@@ -62,7 +64,7 @@ static bool standard_rebase(
     }
     OpType type = op->get_type();
     if (allowed_gates.find(type) != allowed_gates.end() || type == OpType::CX ||
-        type == OpType::Barrier)
+        type == OpType::Barrier || type == OpType::Delay)
       continue;
     // need to convert
     Circuit replacement = CX_circ_from_multiq(op);
@@ -109,6 +111,112 @@ static bool standard_rebase(
   return success;
 }
 
+// CX replacements using native gates of a connection, in order of preference
+static const std::vector<std::pair<OpType, const Circuit& (*)()>>&
+edge_cx_replacements() {
+  static const std::vector<std::pair<OpType, const Circuit& (*)()>> reps = {
+      {OpType::CX, &CircPool::CX},
+      {OpType::CZ, &CircPool::H_CZ_H},
+      {OpType::ECR, &CircPool::CX_using_ECR},
+      {OpType::ISWAPMax, &CircPool::CX_using_ISWAPMax},
+      {OpType::ZZMax, &CircPool::CX_using_ZZMax},
+      {OpType::XXPhase, &CircPool::CX_using_XXPhase_0},
+      {OpType::TK2, &CircPool::CX_using_TK2}};
+  return reps;
+}
+
+Transform rebase_edges_factory(
+    const Architecture& arc, const OpTypeSet& allowed_gates,
+    const Circuit& cx_replacement,
+    const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
+        tk1_replacement) {
+  std::map<Architecture::Connection, Circuit> edge_cx;
+  OpTypeSet all_gates(allowed_gates);
+  for (const auto& [con, gate_set] : arc.get_gate_sets()) {
+    auto it = std::find_if(
+        edge_cx_replacements().begin(), edge_cx_replacements().end(),
+        [&gate_set](const std::pair<OpType, const Circuit& (*)()>& rep) {
+          return gate_set.find(rep.first) != gate_set.end();
+        });
+    if (it == edge_cx_replacements().end()) {
+      throw ArchitectureInvalidity(
+          "No known decomposition of CX into the gate set of the connection "
+          "between " +
+          con.first.repr() + " and " + con.second.repr() + ".");
+    }
+    edge_cx.insert({con, it->second()});
+    all_gates.insert(gate_set.begin(), gate_set.end());
+  }
+  // the single-qubit gates of the replacements are rebased afterwards
+  Transform rebase_rest =
+      rebase_factory(all_gates, cx_replacement, tk1_replacement);
+
+  return Transform([=](Circuit& circ) {
+    // the gate set and CX replacement for a gate acting on some qubits
+    auto gates_for = [&](const qubit_vector_t& qbs)
+        -> std::pair<const OpTypeSet&, const Circuit&> {
+      if (qbs.size() == 2) {
+        Node n0(qbs[0]), n1(qbs[1]);
+        Architecture::Connection con =
+            (n1 < n0) ? Architecture::Connection{n1, n0}
+                      : Architecture::Connection{n0, n1};
+        auto it = edge_cx.find(con);
+        if (it != edge_cx.end()) {
+          return {arc.get_gate_sets().at(con), it->second};
+        }
+      }
+      return {allowed_gates, cx_replacement};
+    };
+    auto substitute = [&circ](const Circuit& replacement, Vertex v) {
+      if (circ.get_OpType_from_Vertex(v) == OpType::Conditional) {
+        circ.substitute_conditional(
+            replacement, v, Circuit::VertexDeletion::No);
+      } else {
+        circ.substitute(replacement, v, Circuit::VertexDeletion::No);
+      }
+    };
+
+    bool success = false;
+    VertexList bin;
+    // decompose multi-qubit gates not allowed on their qubits into CXs
+    for (const Command& com : circ.get_commands()) {
+      qubit_vector_t qbs = com.get_qubits();
+      if (qbs.size() <= 1) continue;
+      Op_ptr op = com.get_op_ptr();
+      if (op->get_type() == OpType::Conditional) {
+        op = static_cast<const Conditional&>(*op).get_op();
+      }
+      OpType type = op->get_type();
+      const OpTypeSet& gates = gates_for(qbs).first;
+      if (gates.find(type) != gates.end() || type == OpType::CX ||
+          type == OpType::Barrier || type == OpType::Delay)
+        continue;
+      substitute(CX_circ_from_multiq(op), com.get_vertex());
+      bin.push_back(com.get_vertex());
+      success = true;
+    }
+    circ.remove_vertices(
+        bin, Circuit::GraphRewiring::No, Circuit::VertexDeletion::Yes);
+    bin.clear();
+    // replace CXs not allowed on their qubits using native gates
+    for (const Command& com : circ.get_commands()) {
+      Op_ptr op = com.get_op_ptr();
+      if (op->get_type() == OpType::Conditional) {
+        op = static_cast<const Conditional&>(*op).get_op();
+      }
+      if (op->get_type() != OpType::CX) continue;
+      auto [gates, replacement] = gates_for(com.get_qubits());
+      if (gates.find(OpType::CX) != gates.end()) continue;
+      substitute(replacement, com.get_vertex());
+      bin.push_back(com.get_vertex());
+      success = true;
+    }
+    circ.remove_vertices(
+        bin, Circuit::GraphRewiring::No, Circuit::VertexDeletion::Yes);
+    return rebase_rest.apply(circ) || success;
+  });
+}
+
 Transform rebase_tket() {
   std::function<Circuit(const Expr&, const Expr&, const Expr&)> tk1_to_tk1 =
       [](const Expr& alpha, const Expr& beta, const Expr& gamma) {
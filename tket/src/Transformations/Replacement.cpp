@@ -236,6 +236,7 @@ Circuit CX_ZX_circ_from_op(const Op_ptr op) {
     case OpType::PhaseGadget:
     case OpType::CCX:
     case OpType::SWAP:
+    case OpType::Transport:
     case OpType::CSWAP:
     case OpType::ECR:
     case OpType::ISWAP:
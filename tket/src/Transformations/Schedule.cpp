@@ -0,0 +1,174 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Schedule.hpp"
+
+#include <algorithm>
+#include <stdexcept>
+
+#include "Circuit/Conditional.hpp"
+#include "OpType/OpTypeFunctions.hpp"
+#include "Ops/MetaOp.hpp"
+#include "Utils/Constants.hpp"
+
+namespace tket {
+
+GateDurations::GateDurations(
+    const op_durations_t& op_durations, const node_durations_t& node_durations)
+    : op_durations_(op_durations), node_durations_(node_durations) {}
+
+double GateDurations::get_duration(
+    const Op_ptr& op, const qubit_vector_t& qubits) const {
+  OpType type = op->get_type();
+  if (type == OpType::Conditional) {
+    return get_duration(static_cast<const Conditional&>(*op).get_op(), qubits);
+  }
+  if (type == OpType::Delay) {
+    return static_cast<const DelayOp&>(*op).get_duration();
+  }
+  if (op->get_desc().is_meta()) return 0.;
+  node_vector_t nodes(qubits.begin(), qubits.end());
+  auto node_it = node_durations_.find({type, nodes});
+  if (node_it != node_durations_.end()) return node_it->second;
+  auto op_it = op_durations_.find(type);
+  if (op_it != op_durations_.end()) return op_it->second;
+  if (qubits.empty()) return 0.;
+  throw std::domain_error("No duration given for " + op->get_name());
+}
+
+void to_json(nlohmann::json& j, const GateDurations& durations) {
+  j["op_durations"] = durations.get_op_durations();
+  j["node_durations"] = durations.get_node_durations();
+}
+
+void from_json(const nlohmann::json& j, GateDurations& durations) {
+  durations = GateDurations(
+      j.at("op_durations").get<GateDurations::op_durations_t>(),
+      j.at("node_durations").get<GateDurations::node_durations_t>());
+}
+
+// earliest start time of each command, where the commands start in the given
+// order and each waits for the previous commands on its units
+static std::vector<double> earliest_starts(
+    const std::vector<Command>& commands, const std::vector<double>& lengths) {
+  // when each qubit, or bit being written, is next free
+  std::map<UnitID, double> free;
+  // when the latest read of each bit as a condition ends
+  std::map<UnitID, double> read;
+  std::vector<double> starts;
+  for (unsigned i = 0; i < commands.size(); i++) {
+    op_signature_t sig = commands[i].get_op_ptr()->get_signature();
+    unit_vector_t args = commands[i].get_args();
+    double start = 0.;
+    for (unsigned j = 0; j < args.size(); j++) {
+      start = std::max(start, free[args[j]]);
+      if (sig[j] == EdgeType::Classical) {
+        start = std::max(start, read[args[j]]);
+      }
+    }
+    double end = start + lengths[i];
+    for (unsigned j = 0; j < args.size(); j++) {
+      if (sig[j] == EdgeType::Boolean) {
+        read[args[j]] = std::max(read[args[j]], end);
+      } else {
+        free[args[j]] = end;
+      }
+    }
+    starts.push_back(start);
+  }
+  return starts;
+}
+
+Schedule::Schedule(
+    const Circuit& circ, const GateDurations& durations, ScheduleType type)
+    : commands_(circ.get_commands()), total_duration_(0.) {
+  std::vector<double> lengths;
+  for (const Command& com : commands_) {
+    double length =
+        durations.get_duration(com.get_op_ptr(), com.get_qubits());
+    lengths.push_back(length);
+    durations_.insert({com.get_vertex(), length});
+  }
+  std::vector<double> asap = earliest_starts(commands_, lengths);
+  for (unsigned i = 0; i < commands_.size(); i++) {
+    total_duration_ = std::max(total_duration_, asap[i] + lengths[i]);
+  }
+  if (type == ScheduleType::ASAP) {
+    for (unsigned i = 0; i < commands_.size(); i++) {
+      starts_.insert({commands_[i].get_vertex(), asap[i]});
+    }
+  } else {
+    // the earliest schedule of the reversed circuit, reflected in time
+    std::vector<Command> rev_commands(commands_.rbegin(), commands_.rend());
+    std::vector<double> rev_lengths(lengths.rbegin(), lengths.rend());
+    std::vector<double> rev = earliest_starts(rev_commands, rev_lengths);
+    for (unsigned i = 0; i < rev_commands.size(); i++) {
+      starts_.insert(
+          {rev_commands[i].get_vertex(),
+           total_duration_ - rev[i] - rev_lengths[i]});
+    }
+  }
+
+  // follow the wire of each qubit, looking for gaps between operations
+  for (const Qubit& qb : circ.all_qubits()) {
+    Edge e = circ.get_nth_out_edge(circ.get_in(qb), 0);
+    double free = 0.;
+    while (true) {
+      Vertex next = circ.target(e);
+      bool at_end = is_final_q_type(circ.get_OpType_from_Vertex(next));
+      double start = at_end ? total_duration_ : starts_.at(next);
+      if (start - free > EPS) idle_windows_.push_back({qb, free, start, e});
+      if (at_end) break;
+      free = start + durations_.at(next);
+      e = circ.get_next_edge(next, e);
+    }
+  }
+}
+
+std::vector<ScheduledCommand> Schedule::get_commands() const {
+  std::vector<ScheduledCommand> scheduled;
+  for (const Command& com : commands_) {
+    Vertex v = com.get_vertex();
+    scheduled.push_back({com, starts_.at(v), durations_.at(v)});
+  }
+  return scheduled;
+}
+
+std::vector<IdleWindow> Schedule::get_idle_windows(double min_duration) const {
+  std::vector<IdleWindow> windows;
+  for (const IdleWindow& window : idle_windows_) {
+    if (window.end - window.start >= min_duration) windows.push_back(window);
+  }
+  return windows;
+}
+
+namespace Transforms {
+
+Transform insert_delays(
+    const GateDurations& durations, ScheduleType type, double min_duration) {
+  return Transform([=](Circuit& circ) {
+    std::vector<IdleWindow> windows =
+        Schedule(circ, durations, type).get_idle_windows(min_duration);
+    for (const IdleWindow& window : windows) {
+      Vertex delay = circ.add_vertex(
+          std::make_shared<DelayOp>(window.end - window.start));
+      circ.rewire(delay, {window.edge}, {EdgeType::Quantum});
+    }
+    return !windows.empty();
+  });
+}
+
+}  // namespace Transforms
+
+}  // namespace tket
@@ -73,6 +73,88 @@ Transform decomp_controlled_Rys();
 // returns Ry, CX, H, T, Tdg + any previous gates
 Transform decomp_arbitrary_controlled_gates();
 
+/**
+ * Qubits that may be used as ancillas when decomposing CnX gates
+ */
+enum class CnXAncillaMode {
+  /** Do not use ancillas */
+  None,
+  /**
+   * Use qubits known to be in the |0> state, i.e. qubits that have been
+   * created or reset and not acted on since
+   */
+  Clean,
+  /**
+   * Use clean qubits where possible, otherwise borrow idle qubits in an
+   * arbitrary state and return them to that state
+   */
+  Borrowed
+};
+
+/**
+ * @brief Decompose a CnX gate using a linear ladder of Toffolis on ancillas.
+ *
+ * Qubits 0 to n-1 are the controls, n to 2n-3 are the n-2 ancillas and 2n-2
+ * is the target. With clean ancillas the ladder uses 2n-3 Toffolis; with
+ * borrowed ancillas it uses 4(n-2) Toffolis (Barenco et al. Lemma 7.2). In
+ * both cases the Toffolis targeting ancillas occur in pairs whose phases
+ * cancel, so they are implemented up to relative phase using 3 CX each.
+ *
+ * If \p relative_phase is set, the Toffoli on the target is also
+ * implemented up to relative phase, so that the circuit implements the CnX
+ * up to a diagonal on the controls and target. The circuit is self-inverse,
+ * so the diagonals cancel when it is used in a compute-uncompute pair.
+ *
+ * @param n number of controls, at least 3
+ * @param clean whether the ancillas are known to be in the |0> state
+ * @param relative_phase whether to implement the CnX up to relative phase;
+ *    requires clean ancillas
+ * @return Circuit containing CX, H, T, Tdg and Ry on 2n-1 qubits
+ */
+Circuit cnx_ancilla_decomp(
+    unsigned n, bool clean, bool relative_phase = false);
+
+/**
+ * @brief Decompose CnRy, CnX and CCX gates, using spare qubits as ancillas.
+ *
+ * Each CnX with n >= 3 controls is decomposed with cnx_ancilla_decomp() if
+ * at least n-2 qubits allowed by \p mode are available at that point in the
+ * circuit, preferring clean ancillas to borrowed ones. Other gates are
+ * decomposed without ancillas.
+ *
+ * If \p relative_phase is set, a compute-uncompute pair of identical CCX or
+ * CnX gates, such that every operation between them acts on their qubits
+ * only as a control or diagonally, is implemented with relative-phase
+ * Toffolis (Margolus / RTOF), whose phases cancel between the two gates.
+ * CnX gates in such pairs are only decomposed this way with clean ancillas.
+ *
+ * @param mode qubits that may be used as ancillas
+ * @param relative_phase whether to use relative-phase Toffolis for
+ *    compute-uncompute pairs
+ * @return Transform producing Ry, Rz, CX, H, T, Tdg + any previous gates
+ */
+Transform decomp_arbitrary_controlled_gates(
+    CnXAncillaMode mode, bool relative_phase = false);
+
+/**
+ * @brief Decompose a MultiplexorBox.
+ *
+ * A multiplexor of numerical single-qubit operations is decomposed into
+ * multiplexed Rz, Ry and Rz rotations on the target followed by a diagonal on
+ * the controls, using at most 2^(n+2) - 2 CX gates for n controls
+ * (https://arxiv.org/abs/quant-ph/0406176). Other multiplexors are decomposed
+ * into controlled operations as in the box's own circuit.
+ *
+ * @param box multiplexor to decompose
+ * @return Circuit containing CX, Ry and Rz for single-qubit multiplexors
+ */
+Circuit decomposed_multiplexor(const MultiplexorBox& box);
+
+// Expects: MultiplexorBoxes, MultiplexedRotationBoxes + any other gates
+// returns CX, Ry, Rz, H + any gates from multiplexed operations + any
+// previous gates
+Transform decomp_multiplexors();
+
 }  // namespace Transforms
 
 }  // namespace tket
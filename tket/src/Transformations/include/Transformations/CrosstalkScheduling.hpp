@@ -0,0 +1,70 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Characterisation/DeviceCharacterisation.hpp"
+#include "Transform.hpp"
+
+namespace tket {
+
+/**
+ * Crosstalk between two-qubit gates which share a slice of a circuit, and so
+ * are expected to run at the same time.
+ */
+struct CrosstalkReport {
+  /** Sum of the crosstalk penalties of all pairs of gates sharing a slice */
+  gate_error_t total_penalty = 0.;
+  /** Largest crosstalk penalty of a pair of gates sharing a slice */
+  gate_error_t max_penalty = 0.;
+  /** Number of pairs of gates sharing a slice with penalty above threshold */
+  unsigned n_conflicts = 0;
+};
+
+/**
+ * Report the crosstalk between two-qubit gates in each slice of a circuit, as
+ * given by Circuit::get_slices, using the crosstalk penalties of pairs of
+ * links of a DeviceCharacterisation.
+ *
+ * @param circ Circuit whose qubits are nodes of the device
+ * @param characterisation Device characterisation with crosstalk penalties
+ * @param threshold Penalty above which a pair of gates is a conflict
+ * @return Total and largest penalties, and number of conflicts
+ */
+CrosstalkReport get_crosstalk_report(
+    const Circuit& circ, const DeviceCharacterisation& characterisation,
+    gate_error_t threshold = 0.);
+
+namespace Transforms {
+
+/**
+ * Schedule two-qubit gates so that no pair of gates with a crosstalk penalty
+ * above the threshold shares a slice.
+ *
+ * While some slice has such a pair, a Barrier is inserted after one gate of
+ * the pair and before the other, delaying the gate with fewer slices after it
+ * past gates it does not depend on. Gates are never reordered on a wire, so
+ * the unitary of the circuit is unchanged.
+ *
+ * @param characterisation Device characterisation with crosstalk penalties
+ * @param threshold Penalty above which a pair of gates must not share a slice
+ * @return Transform inserting Barriers
+ */
+Transform schedule_crosstalk(
+    const DeviceCharacterisation& characterisation,
+    gate_error_t threshold = 0.);
+
+}  // namespace Transforms
+
+}  // namespace tket
@@ -0,0 +1,70 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include "Schedule.hpp"
+#include "Transform.hpp"
+
+namespace tket {
+
+/** Standard dynamical decoupling sequences */
+enum class DDSequence {
+  /** X, Y, X, Y */
+  XY4,
+  /** X, X */
+  CPMG
+};
+
+/**
+ * A standard dynamical decoupling sequence as a single-qubit circuit
+ *
+ * @param sequence the sequence
+ * @return single-qubit circuit with the pulses of the sequence
+ */
+Circuit get_dd_sequence(DDSequence sequence);
+
+namespace Transforms {
+
+/**
+ * Fill the idle windows of a schedule of the circuit with a dynamical
+ * decoupling sequence.
+ *
+ * The pulses of the sequence are spread evenly across each idle window long
+ * enough to hold them, with delays of half the spacing before the first pulse
+ * and after the last, and \ref DelayOp operations fill the rest of the window
+ * so that the original operations keep their start times.
+ *
+ * Windows are bounded by every operation on the qubit, including Conditional
+ * operations and barriers, so no pulse is moved past either. Windows before
+ * the first operation on a qubit, and after a final measurement, are left
+ * empty. The global phase of the circuit is corrected for each sequence, so
+ * the unitary of the circuit is unchanged.
+ *
+ * @param durations durations of the operations in the circuit and sequence
+ * @param sequence single-qubit circuit of gates whose product is the identity
+ *  up to global phase
+ * @param type whether operations start as soon or as late as possible
+ * @param min_duration shortest idle window to fill
+ * @return Transform inserting dynamical decoupling sequences
+ * @throws std::invalid_argument if the sequence is not a single-qubit circuit
+ *  of gates implementing the identity up to global phase
+ */
+Transform dynamical_decoupling(
+    const GateDurations &durations, const Circuit &sequence,
+    ScheduleType type = ScheduleType::ASAP, double min_duration = 0.);
+
+}  // namespace Transforms
+
+}  // namespace tket
@@ -14,6 +14,7 @@
 
 #pragma once
 
+#include "Architecture/Architecture.hpp"
 #include "Transform.hpp"
 
 namespace tket {
@@ -29,6 +30,18 @@ Transform rebase_factory(
     const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
         tk1_replacement);
 
+// as rebase_factory, but two-qubit gates acting on a connection of the
+// architecture with a gate set are rebased to a gate of that set, choosing
+// the first of CX, CZ, ECR, ISWAPMax, ZZMax, XXPhase and TK2 in the set. All
+// other gates are rebased to allowed_gates, using cx_replacement for CXs.
+// Expects: any gates
+// Produces: gates in the gate sets of connections and allowed_gates
+Transform rebase_edges_factory(
+    const Architecture& arc, const OpTypeSet& allowed_gates,
+    const Circuit& cx_replacement,
+    const std::function<Circuit(const Expr&, const Expr&, const Expr&)>&
+        tk1_replacement);
+
 // Multiqs: CX
 // Singleqs: TK1
 Transform rebase_tket();
@@ -0,0 +1,181 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#pragma once
+
+#include <map>
+#include <vector>
+
+#include "Circuit/Circuit.hpp"
+#include "Transform.hpp"
+#include "Utils/Json.hpp"
+
+namespace tket {
+
+/**
+ * Durations of operations, used to schedule a circuit in time
+ *
+ * Durations may be given for each type of operation, and for each type of
+ * operation on particular nodes of a device.
+ */
+class GateDurations {
+ public:
+  typedef std::map<OpType, double> op_durations_t;
+  typedef std::map<std::pair<OpType, node_vector_t>, double>
+      node_durations_t;
+
+  /**
+   * Construct gate durations
+   *
+   * @param op_durations duration of each type of operation
+   * @param node_durations duration of each type of operation on a sequence of
+   *  nodes, taking precedence over \p op_durations
+   */
+  explicit GateDurations(
+      const op_durations_t &op_durations = {},
+      const node_durations_t &node_durations = {});
+
+  /**
+   * Duration of an operation acting on some qubits
+   *
+   * A \ref DelayOp lasts for its own duration, other meta operations take no
+   * time, and a Conditional lasts as long as its operation. An operation on
+   * no qubits takes no time unless a duration is given for its type.
+   *
+   * @param op operation
+   * @param qubits qubits the operation acts on, in order
+   * @return duration of the operation
+   * @throws std::domain_error if no duration is given for the operation
+   */
+  double get_duration(const Op_ptr &op, const qubit_vector_t &qubits) const;
+
+  const op_durations_t &get_op_durations() const { return op_durations_; }
+  const node_durations_t &get_node_durations() const {
+    return node_durations_;
+  }
+
+  bool operator==(const GateDurations &other) const {
+    return op_durations_ == other.op_durations_ &&
+           node_durations_ == other.node_durations_;
+  }
+
+ private:
+  op_durations_t op_durations_;
+  node_durations_t node_durations_;
+};
+
+void to_json(nlohmann::json &j, const GateDurations &durations);
+void from_json(const nlohmann::json &j, GateDurations &durations);
+
+/** Whether operations start as soon or as late as their dependencies allow */
+enum class ScheduleType { ASAP, ALAP };
+
+NLOHMANN_JSON_SERIALIZE_ENUM(
+    ScheduleType,
+    {{ScheduleType::ASAP, "ASAP"}, {ScheduleType::ALAP, "ALAP"}});
+
+/** A command of a circuit with its start time and duration */
+struct ScheduledCommand {
+  Command command;
+  double start;
+  double duration;
+};
+
+/** A time during which a qubit is not acted on by any operation */
+struct IdleWindow {
+  Qubit qubit;
+  double start;
+  double end;
+  /** Edge of the qubit's wire during the window */
+  Edge edge;
+};
+
+/**
+ * Start times of the operations of a circuit, given their durations
+ *
+ * An operation waits for all earlier operations on its qubits and bits. An
+ * operation writing to a bit also waits for all earlier operations reading
+ * the bit as a condition, while operations only reading the same bit may
+ * overlap.
+ *
+ * Vertices and edges in a Schedule refer to the circuit it was computed for,
+ * so it is invalidated by changes to that circuit.
+ */
+class Schedule {
+ public:
+  /**
+   * Schedule a circuit
+   *
+   * @param circ circuit to schedule
+   * @param durations durations of the operations in the circuit
+   * @param type whether operations start as soon or as late as possible
+   * @throws std::domain_error if the duration of an operation is unknown
+   */
+  Schedule(
+      const Circuit &circ, const GateDurations &durations,
+      ScheduleType type = ScheduleType::ASAP);
+
+  /** Start time of the operation at a vertex */
+  double get_start(const Vertex &v) const { return starts_.at(v); }
+
+  /** Duration of the operation at a vertex */
+  double get_duration(const Vertex &v) const { return durations_.at(v); }
+
+  /** Time at which the last operation ends */
+  double get_total_duration() const { return total_duration_; }
+
+  /** Commands of the circuit in causal order, with their times */
+  std::vector<ScheduledCommand> get_commands() const;
+
+  /**
+   * Times during which qubits are not acted on
+   *
+   * Each window lies between two consecutive operations on a qubit, or
+   * between the start or end of the circuit and the first or last operation
+   * on the qubit, and is no shorter than \p min_duration.
+   *
+   * @param min_duration shortest window to report
+   * @return idle windows, ordered by qubit and then by time
+   */
+  std::vector<IdleWindow> get_idle_windows(double min_duration = 0.) const;
+
+ private:
+  std::vector<Command> commands_;
+  std::map<Vertex, double> starts_;
+  std::map<Vertex, double> durations_;
+  double total_duration_;
+  std::vector<IdleWindow> idle_windows_;
+};
+
+namespace Transforms {
+
+/**
+ * Fill the idle windows of a schedule of the circuit with \ref DelayOp
+ * operations.
+ *
+ * Scheduling the result in the same way gives the same start times for the
+ * original operations.
+ *
+ * @param durations durations of the operations in the circuit
+ * @param type whether operations start as soon or as late as possible
+ * @param min_duration shortest idle window to fill
+ * @return Transform inserting delays
+ */
+Transform insert_delays(
+    const GateDurations &durations, ScheduleType type = ScheduleType::ASAP,
+    double min_duration = 0.);
+
+}  // namespace Transforms
+
+}  // namespace tket
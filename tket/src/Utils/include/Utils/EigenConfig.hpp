@@ -57,6 +57,8 @@ void to_json(nlohmann::json& j, const Matrix<_Scalar, _Rows, _Cols>& matrix) {
 
 template <typename _Scalar, int _Rows, int _Cols>
 void from_json(const nlohmann::json& j, Matrix<_Scalar, _Rows, _Cols>& matrix) {
+  // Allocate dynamic-size matrices (a no-op for fixed sizes).
+  if (j.size() > 0) matrix.resize(j.size(), j.at(0).size());
   for (size_t i = 0; i < j.size(); ++i) {
     const auto& j_row = j.at(i);
     for (size_t j = 0; j < j_row.size(); ++j) {
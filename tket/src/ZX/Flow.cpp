@@ -343,7 +343,13 @@ std::map<ZXVert, ZXVertSeqSet> Flow::gauss_solve_correctors(
         break;
       }
       case ZXType::PY: {
-        mat(n_preserve + ys.left.at(v), n_correctors + i) = true;
+        auto found = ys.left.find(v);
+        if (found != ys.left.end()) {
+          mat(n_preserve + found->second, n_correctors + i) = true;
+        } else {
+          // Without Pauli flow, Y measurements are treated as XY measurements
+          mat(preserve.left.at(v), n_correctors + i) = true;
+        }
         break;
       }
       default: {
@@ -499,6 +505,95 @@ Flow Flow::identify_pauli_flow(const ZXDiagram& diag) {
   return fl;
 }
 
+Flow Flow::identify_gflow(const ZXDiagram& diag) {
+  // Check diagram has the expected form for gflow
+  if (!diag.is_MBQC())
+    throw ZXError("ZXDiagram must be in MBQC form to identify gflow");
+
+  ZXVertSeqSet solved;
+  std::set<ZXVert> inputs;
+  Flow fl{{}, {}};
+
+  // Tag input measurements
+  for (const ZXVert& i : diag.get_boundary(ZXType::Input)) {
+    ZXVert ni = diag.neighbours(i).at(0);
+    inputs.insert(ni);
+    ZXType nt = diag.get_zxtype(ni);
+    if (nt == ZXType::XZ || nt == ZXType::YZ || nt == ZXType::PZ)
+      throw ZXError(
+          "Inputs measured in XZ, YZ, or Z cannot be corrected with gflow");
+  }
+
+  // Only vertices which have already been solved (including unmeasured
+  // vertices next to outputs) can be used as correctors
+  boost::bimap<ZXVert, unsigned> correctors;
+  BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+    if (diag.get_zxtype(v) == ZXType::Output) {
+      ZXVert n = diag.neighbours(v).at(0);
+      solved.insert(v);
+      if (diag.get_zxtype(n) != ZXType::Input) {
+        solved.insert(n);
+        fl.c_.insert({n, {}});
+        fl.d_.insert({n, 0});
+        if (inputs.find(n) == inputs.end())
+          correctors.insert({n, (unsigned)correctors.size()});
+      }
+    }
+  }
+
+  unsigned depth = 1;
+
+  unsigned n_solved = 0;
+  do {
+    // Construct Gaussian elimination problem, treating Pauli measurements as
+    // measurements in the XY (for X and Y) or YZ (for Z) planes
+    boost::bimap<ZXVert, unsigned> preserve;
+    ZXVertVec to_solve;
+    BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+      if (solved.get<TagKey>().find(v) == solved.get<TagKey>().end() &&
+          diag.get_zxtype(v) != ZXType::Input) {
+        to_solve.push_back(v);
+        preserve.insert({v, (unsigned)preserve.size()});
+      }
+    }
+
+    std::map<ZXVert, ZXVertSeqSet> new_corrections =
+        gauss_solve_correctors(diag, correctors, preserve, to_solve, {});
+
+    n_solved = new_corrections.size();
+
+    for (const std::pair<const ZXVert, ZXVertSeqSet>& nc : new_corrections) {
+      fl.c_.insert(nc);
+      fl.d_.insert({nc.first, depth});
+      solved.insert(nc.first);
+      if (inputs.find(nc.first) == inputs.end())
+        correctors.insert({nc.first, (unsigned)correctors.size()});
+    }
+
+    ++depth;
+  } while (n_solved != 0);
+
+  if (solved.size() + inputs.size() != diag.n_vertices()) {
+    // Report the measured vertices that could not be corrected
+    unsigned n_unsolved = 0;
+    std::string unsolved_names;
+    BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+      if (solved.get<TagKey>().find(v) == solved.get<TagKey>().end() &&
+          diag.get_zxtype(v) != ZXType::Input) {
+        if (n_unsolved != 0) unsolved_names += ", ";
+        unsolved_names += diag.get_name(v);
+        ++n_unsolved;
+      }
+    }
+    throw ZXError(
+        "ZXDiagram does not have gflow: no correction set exists for " +
+        std::to_string(n_unsolved) + " measured vertices (" + unsolved_names +
+        ")");
+  }
+
+  return fl;
+}
+
 std::set<ZXVertSeqSet> Flow::identify_focussed_sets(const ZXDiagram& diag) {
   // Check diagram has the expected form for pauli flow
   if (!diag.is_MBQC())
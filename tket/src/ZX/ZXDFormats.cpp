@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#include <iomanip>
+#include <sstream>
+
 #include "Utils/GraphHeaders.hpp"
 #include "ZX/ZXDiagram.hpp"
 
@@ -54,6 +57,323 @@ bool ZXDiagram::is_MBQC() const {
   return true;
 }
 
+/**
+ * JSON serialisation
+ */
+
+void to_json(nlohmann::json& j, const WireProperties& wp) {
+  j["type"] = wp.type;
+  j["qtype"] = wp.qtype;
+  j["source_port"] = wp.source_port;
+  j["target_port"] = wp.target_port;
+}
+
+void from_json(const nlohmann::json& j, WireProperties& wp) {
+  wp.type = j.at("type").get<ZXWireType>();
+  wp.qtype = j.at("qtype").get<QuantumType>();
+  wp.source_port = j.at("source_port").get<std::optional<unsigned>>();
+  wp.target_port = j.at("target_port").get<std::optional<unsigned>>();
+}
+
+void to_json(nlohmann::json& j, const ZXDiagram& diag) {
+  std::map<ZXVert, unsigned> ids;
+  j["vertices"] = nlohmann::json::array();
+  BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+    ids.insert({v, static_cast<unsigned>(ids.size())});
+    j["vertices"].push_back(diag.get_vertex_ZXGen_ptr(v));
+  }
+  j["wires"] = nlohmann::json::array();
+  BGL_FORALL_EDGES(w, *diag.graph, ZXGraph) {
+    nlohmann::json j_w;
+    j_w["source"] = ids.at(diag.source(w));
+    j_w["target"] = ids.at(diag.target(w));
+    j_w["properties"] = diag.get_wire_info(w);
+    j["wires"].push_back(j_w);
+  }
+  j["boundary"] = nlohmann::json::array();
+  for (const ZXVert& b : diag.boundary) j["boundary"].push_back(ids.at(b));
+  j["scalar"] = diag.scalar;
+}
+
+void from_json(const nlohmann::json& j, ZXDiagram& diag) {
+  diag = ZXDiagram();
+  ZXVertVec verts;
+  for (const nlohmann::json& j_v : j.at("vertices")) {
+    verts.push_back(diag.add_vertex(j_v.get<ZXGen_ptr>()));
+  }
+  for (const nlohmann::json& j_w : j.at("wires")) {
+    diag.add_wire(
+        verts.at(j_w.at("source").get<unsigned>()),
+        verts.at(j_w.at("target").get<unsigned>()),
+        j_w.at("properties").get<WireProperties>());
+  }
+  // Boundary vertices were added to the boundary in iteration order
+  diag.boundary.clear();
+  for (const nlohmann::json& j_b : j.at("boundary")) {
+    diag.boundary.push_back(verts.at(j_b.get<unsigned>()));
+  }
+  diag.scalar = j.at("scalar").get<Expr>();
+}
+
+/**
+ * PyZX .qgraph format
+ */
+
+// PyZX value for a phase in half-turns, i.e. as a multiple of \pi
+static std::string qgraph_value(double phase) {
+  phase = std::fmod(phase, 2.);
+  if (phase < 0.) phase += 2.;
+  if (approx_eq(phase, 0.)) phase = 0.;
+  std::stringstream st;
+  st << std::setprecision(15) << phase << "\\pi";
+  return st.str();
+}
+
+static double qgraph_fraction(const std::string& value) {
+  try {
+    std::size_t slash = value.find('/');
+    if (slash == std::string::npos) return std::stod(value);
+    return std::stod(value.substr(0, slash)) /
+           std::stod(value.substr(slash + 1));
+  } catch (const std::exception&) {
+    throw ZXError("Invalid phase in .qgraph: " + value);
+  }
+}
+
+// Parses a PyZX phase value, e.g. "3\pi/4", into half-turns
+static double qgraph_phase(const std::string& value) {
+  std::size_t pi = value.find("\\pi");
+  if (pi == std::string::npos) return qgraph_fraction(value);
+  std::string r = value.substr(0, pi) + value.substr(pi + 3);
+  if (r.empty() || r.front() == '/') r = "1" + r;
+  if (r == "-" || r.rfind("-/", 0) == 0) r = "-1" + r.substr(1);
+  return qgraph_fraction(r);
+}
+
+// Whether a .qgraph flag (a bool, an index, or "true"/"false") is set
+static bool qgraph_flag(
+    const nlohmann::ordered_json& j, const std::string& key) {
+  auto it = j.find(key);
+  if (it == j.end()) return false;
+  if (it->is_string()) return it->get<std::string>() == "true";
+  if (it->is_number()) return true;
+  return it->is_boolean() && it->get<bool>();
+}
+
+std::string ZXDiagram::to_qgraph_str() const {
+  std::map<ZXVert, std::string> names;
+  std::map<ZXVert, std::pair<double, double>> coords;
+  unsigned n_internal = n_vertices() - boundary.size();
+  nlohmann::ordered_json wire_vs = nlohmann::ordered_json::object();
+  nlohmann::ordered_json node_vs = nlohmann::ordered_json::object();
+  nlohmann::ordered_json edges = nlohmann::ordered_json::object();
+
+  // Inputs on the left and Outputs (then Opens) on the right
+  unsigned n_ins = 0, n_outs = 0, n_opens = 0;
+  for (const ZXVert& b : boundary) {
+    if (get_qtype(b) != QuantumType::Quantum)
+      throw ZXError("Cannot represent Classical boundaries in .qgraph");
+    ZXType type = get_zxtype(b);
+    std::pair<double, double> coord;
+    if (type == ZXType::Input)
+      coord = {0., -(double)n_ins++};
+    else if (type == ZXType::Output)
+      coord = {n_internal + 1., -(double)n_outs++};
+    else
+      coord = {n_internal + 2., -(double)n_opens++};
+    std::string name = "b" + std::to_string(names.size());
+    names.insert({b, name});
+    coords.insert({b, coord});
+    wire_vs[name] = {
+        {"annotation",
+         {{"boundary", true},
+          {"coord", {coord.first, coord.second}},
+          {"input", type == ZXType::Input},
+          {"output", type == ZXType::Output}}}};
+  }
+  BGL_FORALL_VERTICES(v, *graph, ZXGraph) {
+    ZXType type = get_zxtype(v);
+    if (is_boundary_type(type)) continue;
+    if (get_qtype(v) != QuantumType::Quantum)
+      throw ZXError("Cannot represent Classical vertices in .qgraph");
+    nlohmann::ordered_json data;
+    switch (type) {
+      case ZXType::ZSpider:
+      case ZXType::XSpider: {
+        const PhasedGen& spid = get_vertex_ZXGen<PhasedGen>(v);
+        std::optional<double> phase = eval_expr(spid.get_param());
+        if (!phase)
+          throw ZXError("Cannot represent symbolic phases in .qgraph");
+        data["type"] = (type == ZXType::ZSpider) ? "Z" : "X";
+        if (!approx_eq(*phase, 0.)) data["value"] = qgraph_value(*phase);
+        break;
+      }
+      case ZXType::Hbox: {
+        const PhasedGen& hbox = get_vertex_ZXGen<PhasedGen>(v);
+        std::optional<Complex> param = eval_expr_c(hbox.get_param());
+        if (!param || std::abs(std::abs(*param) - 1.) > EPS)
+          throw ZXError(
+              "Can only represent Hboxes with unit-modulus parameters in "
+              ".qgraph");
+        data["type"] = "hadamard";
+        data["value"] = qgraph_value(std::arg(*param) / PI);
+        break;
+      }
+      default:
+        throw ZXError(
+            "Cannot represent " + get_name(v) +
+            " in .qgraph; use Rewrite::rebase_to_zx() first");
+    }
+    unsigned i = names.size() - boundary.size();
+    std::string name = "v" + std::to_string(i);
+    names.insert({v, name});
+    coords.insert({v, {i + 1., 0.}});
+    node_vs[name] = {
+        {"annotation", {{"coord", {i + 1., 0.}}}}, {"data", data}};
+  }
+
+  // Hadamard wires are represented by a node marked as an edge
+  unsigned n_edges = 0, n_hs = 0;
+  auto add_edge = [&](const std::string& src, const std::string& tgt) {
+    edges["e" + std::to_string(n_edges++)] = {{"src", src}, {"tgt", tgt}};
+  };
+  BGL_FORALL_EDGES(w, *graph, ZXGraph) {
+    if (get_qtype(w) != QuantumType::Quantum)
+      throw ZXError("Cannot represent Classical wires in .qgraph");
+    ZXVert s = source(w);
+    ZXVert t = target(w);
+    if (get_wire_type(w) == ZXWireType::Basic) {
+      add_edge(names.at(s), names.at(t));
+    } else {
+      std::string name = "h" + std::to_string(n_hs++);
+      double x = (coords.at(s).first + coords.at(t).first) / 2.;
+      double y = (coords.at(s).second + coords.at(t).second) / 2.;
+      node_vs[name] = {
+          {"annotation", {{"coord", {x, y}}}},
+          {"data", {{"type", "hadamard"}, {"is_edge", "true"}}}};
+      add_edge(names.at(s), name);
+      add_edge(names.at(t), name);
+    }
+  }
+
+  nlohmann::ordered_json j = {
+      {"wire_vertices", wire_vs},
+      {"node_vertices", node_vs},
+      {"undir_edges", edges}};
+  return j.dump(2);
+}
+
+ZXDiagram ZXDiagram::from_qgraph_str(const std::string& qgraph) {
+  nlohmann::ordered_json j = nlohmann::ordered_json::parse(qgraph);
+  ZXDiagram diag;
+  std::map<std::string, ZXVert> verts;
+  auto vert_named = [&verts](const std::string& name) {
+    auto found = verts.find(name);
+    if (found == verts.end())
+      throw ZXError("Unknown vertex in .qgraph: " + name);
+    return found->second;
+  };
+
+  // Boundaries, with their indices for ordering
+  std::vector<std::pair<unsigned, ZXVert>> ins, outs;
+  ZXVertVec opens;
+  nlohmann::ordered_json empty = nlohmann::ordered_json::object();
+  for (const auto& el : j.value("wire_vertices", empty).items()) {
+    nlohmann::ordered_json ann = el.value().value("annotation", empty);
+    ZXType type = ZXType::Open;
+    if (qgraph_flag(ann, "input")) {
+      type = ZXType::Input;
+    } else if (qgraph_flag(ann, "output")) {
+      type = ZXType::Output;
+    }
+    ZXVert b = diag.add_vertex(type);
+    verts.insert({el.key(), b});
+    if (type == ZXType::Input) {
+      unsigned index = ann.at("input").is_number_unsigned()
+                           ? ann.at("input").get<unsigned>()
+                           : ins.size();
+      ins.push_back({index, b});
+    } else if (type == ZXType::Output) {
+      unsigned index = ann.at("output").is_number_unsigned()
+                           ? ann.at("output").get<unsigned>()
+                           : outs.size();
+      outs.push_back({index, b});
+    } else {
+      opens.push_back(b);
+    }
+  }
+
+  // Hadamard nodes representing H wires, with their neighbours
+  std::map<std::string, std::vector<std::string>> h_wires;
+  for (const auto& el : j.value("node_vertices", empty).items()) {
+    nlohmann::ordered_json data = el.value().value("data", empty);
+    std::string type = data.value("type", "Z");
+    std::string value = data.value("value", "");
+    if (type == "hadamard" && qgraph_flag(data, "is_edge")) {
+      h_wires.insert({el.key(), {}});
+      continue;
+    }
+    ZXVert v;
+    if (type == "Z" || type == "X") {
+      double phase = value.empty() ? 0. : qgraph_phase(value);
+      v = diag.add_vertex(
+          (type == "Z") ? ZXType::ZSpider : ZXType::XSpider, phase);
+    } else if (type == "hadamard") {
+      // The default Hbox in PyZX has label -1
+      double phase = value.empty() ? 1. : qgraph_phase(value);
+      Expr param;
+      if (approx_eq(phase, 0.))
+        param = 1.;
+      else if (approx_eq(phase, 1.))
+        param = -1.;
+      else
+        param = SymEngine::exp(
+            Expr(SymEngine::I) * Expr(SymEngine::pi) * Expr(phase));
+      v = diag.add_vertex(ZXType::Hbox, param);
+    } else {
+      throw ZXError("Unsupported vertex type in .qgraph: " + type);
+    }
+    verts.insert({el.key(), v});
+  }
+
+  for (const auto& el : j.value("undir_edges", empty).items()) {
+    std::string src = el.value().at("src").get<std::string>();
+    std::string tgt = el.value().at("tgt").get<std::string>();
+    auto src_h = h_wires.find(src);
+    auto tgt_h = h_wires.find(tgt);
+    if (src_h != h_wires.end() && tgt_h != h_wires.end())
+      throw ZXError("Adjacent Hadamard edges in .qgraph are not supported");
+    if (src_h != h_wires.end())
+      src_h->second.push_back(tgt);
+    else if (tgt_h != h_wires.end())
+      tgt_h->second.push_back(src);
+    else
+      diag.add_wire(vert_named(src), vert_named(tgt));
+  }
+  for (const auto& [name, ends] : h_wires) {
+    if (ends.size() != 2)
+      throw ZXError(
+          "Hadamard edge " + name + " in .qgraph must have two neighbours");
+    diag.add_wire(
+        vert_named(ends.at(0)), vert_named(ends.at(1)), ZXWireType::H);
+  }
+
+  auto by_index = [](const std::pair<unsigned, ZXVert>& a,
+                     const std::pair<unsigned, ZXVert>& b) {
+    return a.first < b.first;
+  };
+  std::stable_sort(ins.begin(), ins.end(), by_index);
+  std::stable_sort(outs.begin(), outs.end(), by_index);
+  diag.boundary.clear();
+  for (const std::pair<unsigned, ZXVert>& in : ins)
+    diag.boundary.push_back(in.second);
+  for (const std::pair<unsigned, ZXVert>& out : outs)
+    diag.boundary.push_back(out.second);
+  diag.boundary.insert(diag.boundary.end(), opens.begin(), opens.end());
+  diag.check_validity();
+  return diag;
+}
+
 }  // namespace zx
 
 }  // namespace tket
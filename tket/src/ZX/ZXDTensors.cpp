@@ -0,0 +1,308 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <tkassert/Assert.hpp>
+
+#include "Utils/GraphHeaders.hpp"
+#include "ZX/Rewrite.hpp"
+#include "ZX/ZXDiagram.hpp"
+
+namespace tket {
+
+namespace zx {
+
+/**
+ * Dense tensor over binary indices, each identified by a label.
+ * Entries are stored with the first label as the most significant bit.
+ */
+struct LabelledTensor {
+  std::vector<unsigned> labels;
+  std::vector<Complex> data;
+};
+
+// Position of `label` in `labels`, or `labels.size()` if absent
+static unsigned label_position(
+    const std::vector<unsigned>& labels, unsigned label) {
+  return static_cast<unsigned>(
+      std::find(labels.begin(), labels.end(), label) - labels.begin());
+}
+
+// Index formed from the bits of the `n`-bit `x` at `positions`
+static std::size_t sub_index(
+    std::size_t x, const std::vector<unsigned>& positions, unsigned n) {
+  std::size_t i = 0;
+  for (unsigned p : positions) i = (i << 1) | ((x >> (n - 1 - p)) & 1);
+  return i;
+}
+
+// Tensor of a ZSpider, XSpider, or Hbox with `rank` legs
+static std::vector<Complex> generator_tensor(const ZXGen& gen, unsigned rank) {
+  std::size_t size = 1ul << rank;
+  const PhasedGen& pg = static_cast<const PhasedGen&>(gen);
+  std::optional<Complex> param = eval_expr_c(pg.get_param());
+  if (!param)
+    throw ZXError("Cannot evaluate a ZXDiagram with symbolic parameters");
+  std::vector<Complex> t;
+  switch (gen.get_type()) {
+    case ZXType::ZSpider: {
+      Complex phase = std::exp(i_ * PI * param->real());
+      t.assign(size, 0.);
+      t.front() += 1.;
+      t.back() += phase;
+      break;
+    }
+    case ZXType::XSpider: {
+      Complex phase = std::exp(i_ * PI * param->real());
+      double constant = std::pow(std::sqrt(0.5), rank);
+      for (std::size_t i = 0; i < size; ++i) {
+        bool odd = false;
+        for (std::size_t j = i; j != 0; j >>= 1) odd ^= (j & 1);
+        t.push_back((odd ? 1. - phase : 1. + phase) * constant);
+      }
+      break;
+    }
+    case ZXType::Hbox: {
+      t.assign(size, 1.);
+      t.back() = *param;
+      break;
+    }
+    default:
+      throw ZXError("Cannot evaluate tensor for generator " + gen.get_name());
+  }
+  return t;
+}
+
+// Tensor of vertex `v`, tracing out any self-loops
+static LabelledTensor vertex_tensor(
+    const ZXDiagram& diag, const ZXVert& v,
+    const std::map<Wire, unsigned>& labels) {
+  // One leg for each wire end at `v`
+  std::vector<unsigned> legs;
+  std::vector<unsigned> loops;
+  for (const Wire& w : diag.adj_wires(v)) {
+    legs.push_back(labels.at(w));
+    if (diag.other_end(w, v) == v) {
+      legs.push_back(labels.at(w));
+      loops.push_back(labels.at(w));
+    }
+  }
+  std::vector<Complex> full =
+      generator_tensor(*diag.get_vertex_ZXGen_ptr(v), legs.size());
+  LabelledTensor res;
+  for (unsigned l : legs) {
+    if (label_position(loops, l) == loops.size()) res.labels.push_back(l);
+  }
+  // Both ends of a self-loop take the same value, so index the full tensor
+  // by the open labels followed by the loops
+  std::vector<unsigned> all = res.labels;
+  all.insert(all.end(), loops.begin(), loops.end());
+  std::vector<unsigned> positions;
+  for (unsigned l : legs) positions.push_back(label_position(all, l));
+  res.data.assign(1ul << res.labels.size(), 0.);
+  for (std::size_t x = 0; x < (1ul << all.size()); ++x) {
+    res.data[x >> loops.size()] += full[sub_index(x, positions, all.size())];
+  }
+  return res;
+}
+
+// Contracts all shared labels between `a` and `b`
+static LabelledTensor contract(
+    const LabelledTensor& a, const LabelledTensor& b) {
+  std::vector<unsigned> all = a.labels;
+  for (unsigned l : b.labels) {
+    if (label_position(a.labels, l) == a.labels.size()) all.push_back(l);
+  }
+  LabelledTensor res;
+  for (unsigned l : all) {
+    bool in_a = label_position(a.labels, l) != a.labels.size();
+    bool in_b = label_position(b.labels, l) != b.labels.size();
+    if (in_a != in_b) res.labels.push_back(l);
+  }
+  std::vector<unsigned> pa, pb, pr;
+  for (unsigned l : a.labels) pa.push_back(label_position(all, l));
+  for (unsigned l : b.labels) pb.push_back(label_position(all, l));
+  for (unsigned l : res.labels) pr.push_back(label_position(all, l));
+  unsigned n = all.size();
+  res.data.assign(1ul << res.labels.size(), 0.);
+  for (std::size_t x = 0; x < (1ul << n); ++x) {
+    const Complex& ax = a.data[sub_index(x, pa, n)];
+    if (ax == 0.) continue;
+    res.data[sub_index(x, pr, n)] += ax * b.data[sub_index(x, pb, n)];
+  }
+  return res;
+}
+
+bool ZXDiagram::is_quantum() const {
+  BGL_FORALL_VERTICES(v, *graph, ZXGraph) {
+    if (get_qtype(v) != QuantumType::Quantum) return false;
+  }
+  BGL_FORALL_EDGES(w, *graph, ZXGraph) {
+    if (get_qtype(w) != QuantumType::Quantum) return false;
+  }
+  return true;
+}
+
+std::pair<ZXDiagram, Complex> ZXDiagram::to_tensor_network(
+    bool doubled) const {
+  ZXDiagram net = doubled ? to_doubled_diagram() : ZXDiagram(*this);
+  // The doubled diagram does not inherit the scalar
+  if (doubled) net.multiply_scalar(scalar);
+  // MBQC vertices and Triangles are expanded into spiders, but Hboxes can be
+  // evaluated directly
+  bool needs_rebase = false;
+  BGL_FORALL_VERTICES(v, *net.graph, ZXGraph) {
+    ZXType type = net.get_zxtype(v);
+    if (!is_boundary_type(type) && !is_spider_type(type) &&
+        type != ZXType::Hbox)
+      needs_rebase = true;
+  }
+  if (needs_rebase) Rewrite::rebase_to_zx().apply(net);
+  Rewrite::basic_wires().apply(net);
+  std::optional<Complex> sc = eval_expr_c(net.get_scalar());
+  if (!sc)
+    throw ZXError("Cannot evaluate a ZXDiagram with a symbolic scalar");
+  return {std::move(net), doubled ? *sc : std::sqrt(*sc)};
+}
+
+Eigen::VectorXcd ZXDiagram::contract_tensor_network(const Complex& sc) const {
+  std::map<Wire, unsigned> labels;
+  unsigned n_labels = 0;
+  BGL_FORALL_EDGES(w, *graph, ZXGraph) { labels.insert({w, n_labels++}); }
+  std::list<LabelledTensor> tensors;
+  std::vector<unsigned> open;
+  std::set<Wire> id_wires;
+  for (const ZXVert& b : boundary) {
+    Wire bw = adj_wires(b).at(0);
+    ZXVert other = other_end(bw, b);
+    if (is_boundary_type(get_zxtype(other)) && id_wires.insert(bw).second) {
+      // Two boundaries are directly connected, so split the wire with an
+      // identity to give each boundary its own index
+      tensors.push_back({{labels.at(bw), n_labels}, {1., 0., 0., 1.}});
+      open.push_back(n_labels++);
+    } else {
+      open.push_back(labels.at(bw));
+    }
+  }
+  BGL_FORALL_VERTICES(v, *graph, ZXGraph) {
+    if (!is_boundary_type(get_zxtype(v)))
+      tensors.push_back(vertex_tensor(*this, v, labels));
+  }
+
+  // Greedily contract whichever tensor gives the smallest intermediate result
+  LabelledTensor result{{}, {sc}};
+  while (!tensors.empty()) {
+    std::list<LabelledTensor>::iterator best = tensors.end();
+    unsigned best_size = 0;
+    for (auto it = tensors.begin(); it != tensors.end(); ++it) {
+      unsigned shared = 0;
+      for (unsigned l : it->labels) {
+        if (label_position(result.labels, l) != result.labels.size())
+          ++shared;
+      }
+      unsigned size = result.labels.size() + it->labels.size() - 2 * shared;
+      if (best == tensors.end() || size < best_size) {
+        best = it;
+        best_size = size;
+      }
+    }
+    result = contract(result, *best);
+    tensors.erase(best);
+  }
+
+  // Reorder the remaining indices to match the boundary
+  TKET_ASSERT(result.labels.size() == open.size());
+  std::vector<unsigned> positions;
+  for (unsigned l : result.labels) positions.push_back(label_position(open, l));
+  Eigen::VectorXcd tensor(1ul << open.size());
+  for (std::size_t x = 0; x < (1ul << open.size()); ++x) {
+    tensor(x) = result.data[sub_index(x, positions, open.size())];
+  }
+  return tensor;
+}
+
+Eigen::VectorXcd ZXDiagram::to_tensor() const {
+  ZXDiagram flat(*this);
+  Rewrite::decompose_boxes().apply(flat);
+  ZXDiagram net;
+  Complex sc;
+  std::tie(net, sc) = flat.to_tensor_network(!flat.is_quantum());
+  return net.contract_tensor_network(sc);
+}
+
+Eigen::MatrixXcd ZXDiagram::to_matrix() const {
+  ZXDiagram flat(*this);
+  Rewrite::decompose_boxes().apply(flat);
+  ZXDiagram net;
+  Complex sc;
+  std::tie(net, sc) = flat.to_tensor_network(!flat.is_quantum());
+  Eigen::VectorXcd tensor = net.contract_tensor_network(sc);
+  std::vector<unsigned> ins, outs;
+  for (unsigned i = 0; i < net.boundary.size(); ++i) {
+    if (net.get_zxtype(net.boundary.at(i)) == ZXType::Input)
+      ins.push_back(i);
+    else
+      outs.push_back(i);
+  }
+  unsigned n = net.boundary.size();
+  Eigen::MatrixXcd mat(1ul << outs.size(), 1ul << ins.size());
+  for (std::size_t x = 0; x < (1ul << n); ++x) {
+    mat(sub_index(x, outs, n), sub_index(x, ins, n)) = tensor(x);
+  }
+  return mat;
+}
+
+bool ZXDiagram::is_equivalent_to(
+    const ZXDiagram& other, EquivalenceOption equiv, double tol) const {
+  if (boundary.size() != other.boundary.size()) return false;
+  for (unsigned i = 0; i < boundary.size(); ++i) {
+    const ZXVert& b = boundary.at(i);
+    const ZXVert& ob = other.boundary.at(i);
+    if (get_zxtype(b) != other.get_zxtype(ob) ||
+        get_qtype(b) != other.get_qtype(ob))
+      return false;
+  }
+  ZXDiagram flat(*this);
+  Rewrite::decompose_boxes().apply(flat);
+  ZXDiagram other_flat(other);
+  Rewrite::decompose_boxes().apply(other_flat);
+  bool doubled = !flat.is_quantum() || !other_flat.is_quantum();
+  ZXDiagram net;
+  Complex sc;
+  std::tie(net, sc) = flat.to_tensor_network(doubled);
+  Eigen::VectorXcd a = net.contract_tensor_network(sc);
+  std::tie(net, sc) = other_flat.to_tensor_network(doubled);
+  Eigen::VectorXcd b = net.contract_tensor_network(sc);
+
+  if (equiv == EquivalenceOption::UP_TO_SCALAR) {
+    double na = a.cwiseAbs().maxCoeff();
+    double nb = b.cwiseAbs().maxCoeff();
+    if (na < tol || nb < tol) return na < tol && nb < tol;
+    a /= na;
+    b /= nb;
+  }
+  if (equiv != EquivalenceOption::EXACT) {
+    // Align the phases of the largest entries
+    Eigen::Index k;
+    double na = a.cwiseAbs().maxCoeff(&k);
+    if (na < tol) return b.cwiseAbs().maxCoeff() < tol;
+    Complex ratio = b(k) / a(k);
+    if (std::abs(std::abs(ratio) - 1.) >= tol) return false;
+    a *= ratio / std::abs(ratio);
+  }
+  return (a - b).cwiseAbs().maxCoeff() < tol;
+}
+
+}  // namespace zx
+
+}  // namespace tket
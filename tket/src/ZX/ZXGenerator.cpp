@@ -449,6 +449,44 @@ std::vector<QuantumType> ZXBox::get_signature() const {
   return sig;
 }
 
+/**
+ * JSON serialisation
+ */
+
+void to_json(nlohmann::json& j, const ZXGen_ptr& op) {
+  ZXType type = op->get_type();
+  j["type"] = type;
+  if (type == ZXType::ZXBox) {
+    const ZXBox& box = static_cast<const ZXBox&>(*op);
+    j["diagram"] = *box.get_diagram();
+    return;
+  }
+  j["qtype"] = *op->get_qtype();
+  if (is_phase_type(type)) {
+    const PhasedGen& pg = static_cast<const PhasedGen&>(*op);
+    j["param"] = pg.get_param();
+  } else if (is_Clifford_gen_type(type)) {
+    const CliffordGen& cg = static_cast<const CliffordGen&>(*op);
+    j["param"] = cg.get_param();
+  }
+}
+
+void from_json(const nlohmann::json& j, ZXGen_ptr& op) {
+  ZXType type = j.at("type").get<ZXType>();
+  if (type == ZXType::ZXBox) {
+    op = std::make_shared<const ZXBox>(j.at("diagram").get<ZXDiagram>());
+    return;
+  }
+  QuantumType qtype = j.at("qtype").get<QuantumType>();
+  if (is_phase_type(type)) {
+    op = ZXGen::create_gen(type, j.at("param").get<Expr>(), qtype);
+  } else if (is_Clifford_gen_type(type)) {
+    op = ZXGen::create_gen(type, j.at("param").get<bool>(), qtype);
+  } else {
+    op = ZXGen::create_gen(type, qtype);
+  }
+}
+
 }  // namespace zx
 
 }  // namespace tket
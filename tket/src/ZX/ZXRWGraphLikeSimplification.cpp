@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#include <cmath>
+#include <map>
+#include <set>
+
 #include "Utils/GraphHeaders.hpp"
 #include "ZX/Rewrite.hpp"
 
@@ -123,6 +127,44 @@ static void bipartite_complementation(
   }
 }
 
+/**
+ * Helper method for pivoting.
+ * Pivots about the edge between the Pauli spiders `u` and `v`, with
+ * neighbourhoods `u_ns` and `v_ns`, and removes both of them.
+ * Assumes the neighbourhoods of both can be complemented.
+ */
+static void pivot(
+    ZXDiagram& diag, const ZXVert& u, const ZXVert& v, const ZXVertVec& u_ns,
+    const ZXVertVec& v_ns, QuantumType qtype) {
+  // Identify the three sets from the neighbourhoods of `u` and `v`
+  ZXVertSeqSet excl_v{v_ns.begin(), v_ns.end()};
+  excl_v.erase(u);
+  ZXVertSeqSet excl_u, joint;
+  auto& lookup_v = excl_v.get<TagKey>();
+  for (const ZXVert& nu : u_ns) {
+    if (lookup_v.find(nu) != lookup_v.end())
+      joint.insert(nu);
+    else
+      excl_u.insert(nu);
+  }
+  excl_u.erase(v);
+  excl_v.erase(joint.begin(), joint.end());
+  const PhasedGen& v_spid = diag.get_vertex_ZXGen<PhasedGen>(v);
+  const PhasedGen& u_spid = diag.get_vertex_ZXGen<PhasedGen>(u);
+
+  add_phase_to_vertices(
+      diag, joint, v_spid.get_param() + u_spid.get_param() + 1.);
+  add_phase_to_vertices(diag, excl_u, v_spid.get_param());
+  add_phase_to_vertices(diag, excl_v, u_spid.get_param());
+
+  bipartite_complementation(diag, joint, excl_u, qtype);
+  bipartite_complementation(diag, joint, excl_v, qtype);
+  bipartite_complementation(diag, excl_u, excl_v, qtype);
+
+  diag.remove_vertex(u);
+  diag.remove_vertex(v);
+}
+
 bool Rewrite::remove_interior_paulis_fun(ZXDiagram& diag) {
   if (!diag.is_graphlike()) return false;
   bool success = false;
@@ -156,35 +198,9 @@ bool Rewrite::remove_interior_paulis_fun(ZXDiagram& diag) {
     }
     if (!pair_found) continue;
     // Found a valid pair
-    // Identify the three sets from the neighbourhoods of `u` and `v`
-    ZXVertSeqSet excl_v{v_ns.begin(), v_ns.end()};
-    excl_v.erase(u);
-    ZXVertSeqSet excl_u, joint;
-    auto& lookup_v = excl_v.get<TagKey>();
-    for (const ZXVert& nu : u_ns) {
-      if (lookup_v.find(nu) != lookup_v.end())
-        joint.insert(nu);
-      else
-        excl_u.insert(nu);
-    }
-    excl_u.erase(v);
-    excl_v.erase(joint.begin(), joint.end());
-    const PhasedGen& v_spid = diag.get_vertex_ZXGen<PhasedGen>(v);
-    const PhasedGen& u_spid = diag.get_vertex_ZXGen<PhasedGen>(u);
-
-    add_phase_to_vertices(
-        diag, joint, v_spid.get_param() + u_spid.get_param() + 1.);
-    add_phase_to_vertices(diag, excl_u, v_spid.get_param());
-    add_phase_to_vertices(diag, excl_v, u_spid.get_param());
-
     // Because `can_complement_neighbourhood` checks all neighbours,
     // v and u have the same QuantumType
-    bipartite_complementation(diag, joint, excl_u, vqtype);
-    bipartite_complementation(diag, joint, excl_v, vqtype);
-    bipartite_complementation(diag, excl_u, excl_v, vqtype);
-
-    diag.remove_vertex(u);
-    diag.remove_vertex(v);
+    pivot(diag, u, v, u_ns, v_ns, vqtype);
     candidates.erase(u);
     success = true;
   }
@@ -241,6 +257,143 @@ Rewrite Rewrite::extend_at_boundary_paulis() {
   return Rewrite(extend_at_boundary_paulis_fun);
 }
 
+/**
+ * Helper method for phase gadgets.
+ * Checks whether a vertex with neighbours `neighbours` is part of a phase
+ * gadget, i.e. it is either a degree-1 leaf or adjacent to one.
+ */
+static bool is_in_gadget(const ZXDiagram& diag, const ZXVertVec& neighbours) {
+  if (neighbours.size() == 1) return true;
+  for (const ZXVert& n : neighbours) {
+    if (diag.degree(n) == 1) return true;
+  }
+  return false;
+}
+
+/**
+ * Helper method for phase gadgets.
+ * Removes any pi phase from the hub of a gadget and returns the phase of the
+ * leaf after moving it through, without updating the leaf itself.
+ */
+static Expr normalise_gadget(
+    ZXDiagram& diag, const ZXVert& hub, const ZXVert& leaf) {
+  Expr phase = diag.get_vertex_ZXGen<PhasedGen>(leaf).get_param();
+  const PhasedGen& hub_spid = diag.get_vertex_ZXGen<PhasedGen>(hub);
+  if (!equiv_0(hub_spid.get_param())) {
+    // Copying the pi through the leaf negates its phase up to a scalar
+    diag.multiply_scalar(Expr(SymEngine::exp(
+        Expr(SymEngine::I) * Expr(SymEngine::pi) * phase)));
+    phase = -phase;
+    diag.set_vertex_ZXGen_ptr(
+        hub, ZXGen::create_gen(ZXType::ZSpider, *hub_spid.get_qtype()));
+  }
+  return phase;
+}
+
+bool Rewrite::gadgetise_interior_paulis_fun(ZXDiagram& diag) {
+  if (!diag.is_graphlike()) return false;
+  bool success = false;
+  ZXVertSeqSet candidates;
+  BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) { candidates.insert(v); }
+  auto& view = candidates.get<TagSeq>();
+  while (!candidates.empty()) {
+    auto it = view.begin();
+    ZXVert v = *it;
+    view.erase(it);
+    // Check `v` is an interior non-Pauli outside of any gadget
+    if (diag.get_zxtype(v) != ZXType::ZSpider || diag.is_pauli_spider(v) ||
+        *diag.get_qtype(v) != QuantumType::Quantum)
+      continue;
+    ZXVertVec v_ns = diag.neighbours(v);
+    if (!can_complement_neighbourhood(diag, QuantumType::Quantum, v_ns) ||
+        is_in_gadget(diag, v_ns))
+      continue;
+    // Look for an interior Pauli neighbour outside of any gadget
+    std::optional<ZXVert> u;
+    ZXVertVec u_ns;
+    for (const ZXVert& n : v_ns) {
+      if (!diag.is_pauli_spider(n) ||
+          *diag.get_qtype(n) != QuantumType::Quantum)
+        continue;
+      ZXVertVec n_ns = diag.neighbours(n);
+      if (can_complement_neighbourhood(diag, QuantumType::Quantum, n_ns) &&
+          !is_in_gadget(diag, n_ns)) {
+        u = n;
+        u_ns = n_ns;
+        break;
+      }
+    }
+    if (!u) continue;
+    // Unfuse the phase of `v` onto a new gadget, leaving `v` as a Pauli
+    const PhasedGen& v_spid = diag.get_vertex_ZXGen<PhasedGen>(v);
+    ZXVert hub = diag.add_vertex(ZXType::ZSpider, 0.);
+    ZXVert leaf = diag.add_vertex(ZXType::ZSpider, v_spid.get_param());
+    diag.add_wire(v, hub, ZXWireType::H);
+    diag.add_wire(hub, leaf, ZXWireType::H);
+    diag.set_vertex_ZXGen_ptr(
+        v, ZXGen::create_gen(ZXType::ZSpider, QuantumType::Quantum));
+    v_ns.push_back(hub);
+    // The hub picks up the phase of `u` and becomes adjacent to its neighbours
+    pivot(diag, *u, v, u_ns, v_ns, QuantumType::Quantum);
+    candidates.erase(*u);
+    success = true;
+  }
+  return success;
+}
+
+Rewrite Rewrite::gadgetise_interior_paulis() {
+  return Rewrite(gadgetise_interior_paulis_fun);
+}
+
+bool Rewrite::merge_gadgets_fun(ZXDiagram& diag) {
+  if (!diag.is_graphlike()) return false;
+  // Find each gadget as a pair of its hub and leaf
+  std::vector<std::pair<ZXVert, ZXVert>> gadgets;
+  BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+    if (diag.get_zxtype(v) != ZXType::ZSpider || diag.degree(v) != 1 ||
+        *diag.get_qtype(v) != QuantumType::Quantum)
+      continue;
+    ZXVert hub = diag.neighbours(v).at(0);
+    if (!diag.is_pauli_spider(hub) ||
+        *diag.get_qtype(hub) != QuantumType::Quantum)
+      continue;
+    ZXVertVec hub_ns = diag.neighbours(hub);
+    unsigned n_leaves = 0;
+    for (const ZXVert& n : hub_ns) {
+      if (diag.degree(n) == 1) ++n_leaves;
+    }
+    // Targets of the gadget must all be spiders
+    if (hub_ns.size() < 2 || n_leaves != 1 ||
+        !can_complement_neighbourhood(diag, QuantumType::Quantum, hub_ns))
+      continue;
+    gadgets.push_back({hub, v});
+  }
+  bool success = false;
+  std::map<std::set<ZXVert>, std::pair<ZXVert, ZXVert>> by_targets;
+  for (const std::pair<ZXVert, ZXVert>& gadget : gadgets) {
+    ZXVertVec hub_ns = diag.neighbours(gadget.first);
+    std::set<ZXVert> targets{hub_ns.begin(), hub_ns.end()};
+    targets.erase(gadget.second);
+    auto inserted = by_targets.insert({targets, gadget});
+    if (inserted.second) continue;
+    // Merge into the first gadget found on the same targets
+    const std::pair<ZXVert, ZXVert>& kept = inserted.first->second;
+    Expr phase = normalise_gadget(diag, kept.first, kept.second) +
+                 normalise_gadget(diag, gadget.first, gadget.second);
+    diag.set_vertex_ZXGen_ptr(
+        kept.second,
+        ZXGen::create_gen(ZXType::ZSpider, phase, QuantumType::Quantum));
+    diag.remove_vertex(gadget.first);
+    diag.remove_vertex(gadget.second);
+    // Each gadget on n targets contributes a scalar of sqrt(2)^(1-n)
+    diag.multiply_scalar(std::pow(2., (1. - targets.size()) / 2.));
+    success = true;
+  }
+  return success;
+}
+
+Rewrite Rewrite::merge_gadgets() { return Rewrite(merge_gadgets_fun); }
+
 }  // namespace zx
 
 }  // namespace tket
@@ -0,0 +1,70 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include "Utils/GraphHeaders.hpp"
+#include "ZX/Rewrite.hpp"
+
+namespace tket {
+
+namespace zx {
+
+bool Rewrite::internalise_gadgets_fun(ZXDiagram& diag) {
+  if (!diag.is_MBQC()) return false;
+  bool success = false;
+  ZXVertVec leaves;
+  BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+    if (diag.get_zxtype(v) == ZXType::XY && diag.degree(v) == 1)
+      leaves.push_back(v);
+  }
+  for (const ZXVert& leaf : leaves) {
+    ZXVert hub = diag.neighbours(leaf).at(0);
+    if (diag.get_zxtype(hub) != ZXType::XY || diag.degree(hub) < 2) continue;
+    const PhasedGen& hub_gen = diag.get_vertex_ZXGen<PhasedGen>(hub);
+    std::optional<unsigned> pi2_mult = equiv_Clifford(hub_gen.get_param());
+    if (!pi2_mult || (*pi2_mult % 2) == 1) continue;
+    // The hub must have no other leaves and not be next to a boundary
+    bool valid = true;
+    for (const ZXVert& n : diag.neighbours(hub)) {
+      if (n != leaf &&
+          (diag.degree(n) == 1 || is_boundary_type(diag.get_zxtype(n)))) {
+        valid = false;
+        break;
+      }
+    }
+    if (!valid) continue;
+    // An XY vertex with angle -a is a ZSpider with phase a, and a ZSpider hub
+    // with no phase attached to a leaf with phase a is a YZ vertex with angle a
+    Expr angle = diag.get_vertex_ZXGen<PhasedGen>(leaf).get_param();
+    if (*pi2_mult == 2) {
+      // Copying the pi on the hub through the leaf negates its phase
+      diag.multiply_scalar(Expr(SymEngine::exp(
+          -Expr(SymEngine::I) * Expr(SymEngine::pi) * angle)));
+    } else {
+      angle = -angle;
+    }
+    diag.set_vertex_ZXGen_ptr(
+        hub, ZXGen::create_gen(ZXType::YZ, angle, QuantumType::Quantum));
+    diag.remove_vertex(leaf);
+    success = true;
+  }
+  return success;
+}
+
+Rewrite Rewrite::internalise_gadgets() {
+  return Rewrite(internalise_gadgets_fun);
+}
+
+}  // namespace zx
+
+}  // namespace tket
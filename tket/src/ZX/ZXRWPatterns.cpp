@@ -0,0 +1,383 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <limits>
+#include <tkwsm/EndToEndWrappers/MainSolver.hpp>
+
+#include "Utils/GraphHeaders.hpp"
+#include "ZX/Rewrite.hpp"
+#include "ZXDiagramImpl.hpp"
+
+namespace tket {
+
+namespace zx {
+
+using namespace WeightedSubgraphMonomorphism;
+
+/**
+ * A wire from a boundary of the left-hand side of a rule to the interior
+ * vertex `vert`, with the port it uses on that vertex.
+ */
+struct PatternLeg {
+  ZXVert vert;
+  std::optional<unsigned> port;
+  QuantumType qtype;
+  ZXWireType type;
+};
+
+/**
+ * Data for a rule, precomputed from its left-hand side.
+ * The interior (non-boundary) vertices of `lhs` are indexed contiguously as
+ * pattern vertices for the subgraph monomorphism solver. Vertices and wires
+ * refer to the copy of `lhs` held here, so a rule is never copied.
+ */
+struct PatternRule {
+  ZXDiagram lhs;
+  ZXDiagram rhs;
+  ZXVertVec interior;
+  std::map<ZXVert, unsigned> index;
+  // Wires between interior vertices, including self-loops
+  WireVec interior_wires;
+  // Wires to each boundary, in boundary order
+  std::vector<PatternLeg> legs;
+  // Simple graph between distinct interior vertices
+  GraphEdgeWeights edges;
+  Expr scalar_ratio;
+};
+
+/**
+ * A match of the left-hand side of a rule, giving the image of each interior
+ * vertex, the wire ends at the boundary in the order of the boundary of the
+ * rule, and the values of the phase variables.
+ * `cut_types` gives the type each cut wire should have once the part of it
+ * belonging to the left-hand side is removed.
+ */
+struct PatternMatch {
+  ZXVertVec image;
+  std::vector<std::pair<Wire, WireEnd>> cut;
+  std::map<Wire, ZXWireType> cut_types;
+  SymEngine::map_basic_basic bindings;
+};
+
+static bool equiv_param(const Expr& a, const Expr& b, ZXType type) {
+  if (type == ZXType::Hbox) {
+    // Hbox parameters are complex values rather than phases
+    std::optional<Complex> a_val = eval_expr_c(a);
+    std::optional<Complex> b_val = eval_expr_c(b);
+    if (!a_val || !b_val) return a == b;
+    return std::abs(*a_val - *b_val) < EPS;
+  }
+  return equiv_expr(a, b);
+}
+
+static bool match_param(
+    const Expr& pattern, const Expr& param, ZXType type,
+    SymEngine::map_basic_basic& bindings) {
+  SymSet symbols = expr_free_symbols(pattern);
+  if (symbols.empty()) return equiv_param(pattern, param, type);
+  // The rule guarantees that non-constant parameters are a single variable
+  Sym var = *symbols.begin();
+  auto [it, inserted] = bindings.insert({var, param.get_basic()});
+  if (inserted) return true;
+  return equiv_param(Expr(it->second), param, type);
+}
+
+static bool match_gen(
+    const ZXGen_ptr& pattern, const ZXGen_ptr& gen,
+    SymEngine::map_basic_basic& bindings) {
+  ZXType type = pattern->get_type();
+  if (gen->get_type() != type || gen->get_qtype() != pattern->get_qtype())
+    return false;
+  if (is_phase_type(type)) {
+    return match_param(
+        static_cast<const PhasedGen&>(*pattern).get_param(),
+        static_cast<const PhasedGen&>(*gen).get_param(), type, bindings);
+  }
+  return *pattern == *gen;
+}
+
+static std::optional<PatternMatch> try_match(
+    const ZXDiagram& diag, const PatternRule& rule, const ZXVertVec& image) {
+  const ZXDiagram& lhs = rule.lhs;
+  PatternMatch match{image, {}, {}, {}};
+  for (unsigned i = 0; i < image.size(); ++i) {
+    if (is_boundary_type(diag.get_zxtype(image.at(i)))) return std::nullopt;
+    if (!match_gen(
+            lhs.get_vertex_ZXGen_ptr(rule.interior.at(i)),
+            diag.get_vertex_ZXGen_ptr(image.at(i)), match.bindings))
+      return std::nullopt;
+  }
+
+  // Every wire between interior vertices must be matched exactly
+  std::set<std::pair<Wire, WireEnd>> used;
+  for (const Wire& pw : rule.interior_wires) {
+    WireProperties pwp = lhs.get_wire_info(pw);
+    ZXVert s = image.at(rule.index.at(lhs.source(pw)));
+    ZXVert t = image.at(rule.index.at(lhs.target(pw)));
+    bool found = false;
+    for (const Wire& w : diag.wires_between(s, t)) {
+      if (used.contains({w, WireEnd::Source})) continue;
+      WireProperties wp = diag.get_wire_info(w);
+      if (wp.type != pwp.type || wp.qtype != pwp.qtype) continue;
+      bool aligned = diag.source(w) == s &&
+                     wp.source_port == pwp.source_port &&
+                     wp.target_port == pwp.target_port;
+      bool reversed = diag.source(w) == t &&
+                      wp.source_port == pwp.target_port &&
+                      wp.target_port == pwp.source_port;
+      if (aligned || reversed) {
+        used.insert({w, WireEnd::Source});
+        used.insert({w, WireEnd::Target});
+        found = true;
+        break;
+      }
+    }
+    if (!found) return std::nullopt;
+  }
+
+  // Each boundary of the rule takes some other wire end at its vertex
+  for (const PatternLeg& leg : rule.legs) {
+    ZXVert v = image.at(rule.index.at(leg.vert));
+    std::optional<std::pair<Wire, WireEnd>> end;
+    for (const Wire& w : diag.adj_wires(v)) {
+      if (diag.get_qtype(w) != leg.qtype) continue;
+      for (WireEnd we : {WireEnd::Source, WireEnd::Target}) {
+        if (diag.vertex_at_end(w, we) != v || used.contains({w, we})) continue;
+        std::optional<unsigned> port = (we == WireEnd::Source)
+                                           ? diag.source_port(w)
+                                           : diag.target_port(w);
+        if (port == leg.port) {
+          end = {w, we};
+          break;
+        }
+      }
+      if (end) break;
+    }
+    if (!end) return std::nullopt;
+    used.insert(*end);
+    match.cut.push_back(*end);
+    // A Hadamard on the leg of the rule is removed along with the match
+    auto [it, inserted] = match.cut_types.insert(
+        {end->first, diag.get_wire_type(end->first)});
+    if (leg.type == ZXWireType::H) {
+      it->second = (it->second == ZXWireType::H) ? ZXWireType::Basic
+                                                 : ZXWireType::H;
+    }
+  }
+
+  // Any remaining wire would be lost by the substitution
+  for (const ZXVert& v : image) {
+    for (const Wire& w : diag.adj_wires(v)) {
+      for (WireEnd we : {WireEnd::Source, WireEnd::Target}) {
+        if (diag.vertex_at_end(w, we) == v && !used.contains({w, we}))
+          return std::nullopt;
+      }
+    }
+  }
+  return match;
+}
+
+/**
+ * Finds candidate images of the interior of the rule within `candidates`,
+ * respecting adjacency but not yet checking generators or wires.
+ */
+static std::vector<ZXVertVec> find_images(
+    const ZXDiagram& diag, const PatternRule& rule,
+    const ZXVertVec& candidates) {
+  std::vector<ZXVertVec> images;
+  if (rule.interior.size() == 1) {
+    for (const ZXVert& v : candidates) images.push_back({v});
+    return images;
+  }
+  std::map<ZXVert, unsigned> index;
+  for (const ZXVert& v : candidates) {
+    index.insert({v, static_cast<unsigned>(index.size())});
+  }
+  GraphEdgeWeights edges;
+  for (const ZXVert& v : candidates) {
+    for (const Wire& w : diag.adj_wires(v)) {
+      auto found = index.find(diag.other_end(w, v));
+      if (found == index.end() || found->first == v) continue;
+      edges[get_edge(index.at(v), found->second)] = 1;
+    }
+  }
+  if (rule.interior.size() > candidates.size() ||
+      rule.edges.size() > edges.size())
+    return images;
+
+  MainSolverParameters solver_parameters;
+  solver_parameters.terminate_with_first_full_solution = false;
+  solver_parameters.for_multiple_full_solutions_the_max_number_to_obtain =
+      std::numeric_limits<std::size_t>::max();
+  const MainSolver main_solver(rule.edges, edges, solver_parameters);
+
+  // The interior of the rule is connected, so no vertex is isolated
+  for (const SolutionWSM& solution :
+       main_solver.get_solution_data().solutions) {
+    ZXVertVec image(rule.interior.size());
+    for (const auto& [pv, tv] : solution.assignments) {
+      image.at(pv) = candidates.at(tv);
+    }
+    images.push_back(image);
+  }
+  return images;
+}
+
+static bool apply_rule(
+    ZXDiagram& diag, const PatternRule& rule, const ZXVertVec& candidates) {
+  bool success = false;
+  // Vertices removed or given new wires by a substitution
+  std::set<ZXVert> touched;
+  for (const ZXVertVec& image : find_images(diag, rule, candidates)) {
+    if (std::any_of(image.begin(), image.end(), [&](const ZXVert& v) {
+          return touched.contains(v);
+        }))
+      continue;
+    std::optional<PatternMatch> match = try_match(diag, rule, image);
+    if (!match) continue;
+    for (const ZXVert& v : image) {
+      touched.insert(v);
+      for (const Wire& w : diag.adj_wires(v)) {
+        touched.insert(diag.other_end(w, v));
+      }
+    }
+    for (const auto& [w, type] : match->cut_types) diag.set_wire_type(w, type);
+    ZXDiagram replacement(rule.rhs);
+    replacement.symbol_substitution(match->bindings);
+    ZXVertSeqSet verts;
+    for (const ZXVert& v : image) verts.insert(v);
+    diag.substitute(replacement, ZXDiagram::Subdiagram(match->cut, verts));
+    diag.multiply_scalar(rule.scalar_ratio.subs(match->bindings));
+    success = true;
+  }
+  return success;
+}
+
+Rewrite Rewrite::from_rule(const ZXDiagram& lhs, const ZXDiagram& rhs) {
+  lhs.check_validity();
+  rhs.check_validity();
+  std::shared_ptr<PatternRule> rule = std::make_shared<PatternRule>();
+  rule->lhs = lhs;
+  rule->rhs = rhs;
+  const ZXDiagram& pattern = rule->lhs;
+
+  ZXVertVec bounds = pattern.get_boundary();
+  ZXVertVec rhs_bounds = rhs.get_boundary();
+  if (bounds.size() != rhs_bounds.size())
+    throw ZXError(
+        "Rewrite rule error: left- and right-hand sides have different numbers "
+        "of boundaries");
+  for (unsigned i = 0; i < bounds.size(); ++i) {
+    if (pattern.get_qtype(bounds.at(i)) != rhs.get_qtype(rhs_bounds.at(i)))
+      throw ZXError("Rewrite rule error: QuantumType mismatch at a boundary");
+  }
+
+  BGL_FORALL_VERTICES(v, *pattern.graph, ZXGraph) {
+    ZXType type = pattern.get_zxtype(v);
+    if (is_boundary_type(type)) {
+      if (std::find(bounds.begin(), bounds.end(), v) == bounds.end())
+        throw ZXError(
+            "Rewrite rule error: left-hand side has a boundary vertex "
+            "missing from its boundary");
+      continue;
+    }
+    if (is_phase_type(type)) {
+      const Expr& param = pattern.get_vertex_ZXGen<PhasedGen>(v).get_param();
+      SymSet symbols = expr_free_symbols(param);
+      if (!symbols.empty() &&
+          !(symbols.size() == 1 && Expr(*symbols.begin()) == param))
+        throw ZXError(
+            "Rewrite rule error: parameters on the left-hand side must be "
+            "constants or single variables");
+    }
+    rule->index.insert({v, static_cast<unsigned>(rule->interior.size())});
+    rule->interior.push_back(v);
+  }
+  if (rule->interior.empty())
+    throw ZXError(
+        "Rewrite rule error: left-hand side has no interior vertices");
+
+  for (const ZXVert& b : bounds) {
+    WireVec adj = pattern.adj_wires(b);
+    if (adj.size() != 1)
+      throw ZXError(
+          "Rewrite rule error: each boundary of the left-hand side must have "
+          "exactly one wire");
+    Wire w = adj.at(0);
+    ZXVert v = pattern.other_end(w, b);
+    if (is_boundary_type(pattern.get_zxtype(v)))
+      throw ZXError(
+          "Rewrite rule error: boundaries of the left-hand side must not be "
+          "connected directly");
+    std::optional<unsigned> port = (pattern.end_of(w, v) == WireEnd::Source)
+                                       ? pattern.source_port(w)
+                                       : pattern.target_port(w);
+    rule->legs.push_back(
+        {v, port, pattern.get_qtype(w), pattern.get_wire_type(w)});
+  }
+
+  BGL_FORALL_EDGES(w, *pattern.graph, ZXGraph) {
+    ZXVert s = pattern.source(w);
+    ZXVert t = pattern.target(w);
+    if (is_boundary_type(pattern.get_zxtype(s)) ||
+        is_boundary_type(pattern.get_zxtype(t)))
+      continue;
+    rule->interior_wires.push_back(w);
+    if (s != t)
+      rule->edges[get_edge(rule->index.at(s), rule->index.at(t))] = 1;
+  }
+
+  // The solver ignores isolated vertices, so require a connected interior
+  std::set<ZXVert> reached{rule->interior.front()};
+  std::list<ZXVert> to_visit{rule->interior.front()};
+  while (!to_visit.empty()) {
+    ZXVert v = to_visit.front();
+    to_visit.pop_front();
+    for (const Wire& w : pattern.adj_wires(v)) {
+      ZXVert n = pattern.other_end(w, v);
+      if (!is_boundary_type(pattern.get_zxtype(n)) && reached.insert(n).second)
+        to_visit.push_back(n);
+    }
+  }
+  if (reached.size() != rule->interior.size())
+    throw ZXError(
+        "Rewrite rule error: interior of the left-hand side must be connected");
+
+  std::optional<Complex> lhs_scalar = eval_expr_c(pattern.get_scalar());
+  if (!lhs_scalar || std::abs(*lhs_scalar) < EPS)
+    throw ZXError(
+        "Rewrite rule error: left-hand side must have a non-zero numerical "
+        "scalar");
+  rule->scalar_ratio = rhs.get_scalar() / pattern.get_scalar();
+
+  SymSet lhs_symbols = pattern.free_symbols();
+  for (const Sym& s : rhs.free_symbols()) {
+    if (!lhs_symbols.contains(s))
+      throw ZXError(
+          "Rewrite rule error: right-hand side has variables not bound by the "
+          "left-hand side");
+  }
+
+  return Rewrite([rule](ZXDiagram& diag) {
+    ZXVertVec candidates;
+    BGL_FORALL_VERTICES(v, *diag.graph, ZXGraph) {
+      if (!is_boundary_type(diag.get_zxtype(v))) candidates.push_back(v);
+    }
+    return apply_rule(diag, *rule, candidates);
+  });
+}
+
+}  // namespace zx
+
+}  // namespace tket
@@ -71,6 +71,15 @@ class Flow {
   // Follows Algorithm 1 from Simmons "Relating Measurement Patterns to Circuits
   // via Pauli Flow" https://arxiv.org/pdf/2109.05654.pdf O(n^4) for n vertices
   static Flow identify_pauli_flow(const ZXDiagram& diag);
+  // Attempts to identify a gflow for a diagram, treating X and Y measurements
+  // as XY measurements and Z measurements as YZ measurements
+  // Generalises the layer-by-layer search of Mhalla & Perdrix to the three
+  // measurement planes as in Backens et al. "There and back again: A circuit
+  // extraction tale" https://arxiv.org/pdf/2003.01664.pdf O(n^4) for n
+  // vertices
+  // Throws a ZXError listing the vertices that cannot be corrected if no gflow
+  // exists
+  static Flow identify_gflow(const ZXDiagram& diag);
 
   // Attempts to identify focussed sets according to Lemma B.10, Simmons
   // "Relating Measurement Patterns to Circuits via Pauli Flow"
@@ -86,7 +95,7 @@ class Flow {
   std::map<ZXVert, unsigned> d_;
 
   // Solve for corrections using Gaussian elimination and back substitution
-  // Used within identify_pauli_flow
+  // Used within identify_pauli_flow, identify_gflow and zx_to_circuit
   // correctors are those vertices which may be included in the correction sets
   // preserve are those vertices which may not be included in the odd
   // neighbourhood (unless being corrected) to_solve are those vertices that are
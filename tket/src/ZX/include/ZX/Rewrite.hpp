@@ -161,6 +161,80 @@ class Rewrite {
    */
   static Rewrite extend_at_boundary_paulis();
 
+  /**
+   * Identifies an interior Pauli adjacent to an interior non-Pauli, where
+   * neither is part of a phase gadget (a degree-1 spider attached to a Pauli
+   * spider). The phase of the non-Pauli is unfused onto a new phase gadget and
+   * the pair is removed by pivoting as in `remove_interior_paulis`.
+   */
+  static Rewrite gadgetise_interior_paulis();
+
+  /**
+   * Merges phase gadgets acting on the same set of spiders into a single
+   * gadget, summing their phases. A pi phase on the Pauli hub of a gadget is
+   * first moved onto its degree-1 leaf by negating the phase of the leaf.
+   */
+  static Rewrite merge_gadgets();
+
+  ////////////////
+  // MBQCRewrites//
+  ////////////////
+
+  /**
+   * Identifies phase gadgets in MBQC form (a degree-1 XY vertex attached to an
+   * interior XY vertex with a Pauli angle) and replaces each by a single YZ
+   * vertex, as expected by circuit extraction.
+   */
+  static Rewrite internalise_gadgets();
+
+  //////////////
+  // Strategies//
+  //////////////
+
+  /**
+   * Converts a diagram of ZX generators into graph-like form.
+   * Boxes and other generators are decomposed into spiders, which are then
+   * turned green and fused, before separating the boundaries.
+   */
+  static Rewrite to_graphlike_form();
+
+  /**
+   * Reduces a graph-like diagram, removing interior Cliffords and Paulis
+   * (including those next to a boundary) and gadgetising the remaining interior
+   * non-Paulis where possible. This is repeated for as long as merging phase
+   * gadgets reduces the number of non-Clifford spiders.
+   */
+  static Rewrite reduce_graphlike_form();
+
+  /**
+   * Converts a graph-like diagram into MBQC form, with phase gadgets
+   * internalised as YZ vertices, as expected by `zx_to_circuit`.
+   */
+  static Rewrite to_MBQC_diag();
+
+  ////////////
+  // Patterns//
+  ////////////
+
+  /**
+   * Builds a rewrite from a user-defined rule `lhs` = `rhs`, where both sides
+   * are diagrams with the same number of boundaries (matched up by their
+   * order in the boundary, with equal QuantumTypes).
+   * Matches of the interior of `lhs` are found using the subgraph
+   * monomorphism solver, checking generators and wires (including types and
+   * ports) and requiring that each remaining wire at a matched vertex is
+   * taken by a boundary of `lhs`. Each match is replaced by `rhs`, with the
+   * global scalar multiplied by the ratio of the scalars of `rhs` and `lhs`.
+   *
+   * Parameters on the left-hand side may be constants or single variables,
+   * which bind to the corresponding parameters of each match (consistently
+   * across vertices) and are substituted into `rhs`.
+   * Throws a ZXError if `lhs` has boundaries wired directly to each other, a
+   * disconnected or empty interior, or a zero or symbolic scalar, or if `rhs`
+   * uses variables which are not bound by `lhs`.
+   */
+  static Rewrite from_rule(const ZXDiagram& lhs, const ZXDiagram& rhs);
+
  private:
   Rewrite(const RewriteFun& fun);
 
@@ -177,6 +251,9 @@ class Rewrite {
   static bool remove_interior_cliffords_fun(ZXDiagram& diag);
   static bool remove_interior_paulis_fun(ZXDiagram& diag);
   static bool extend_at_boundary_paulis_fun(ZXDiagram& diag);
+  static bool gadgetise_interior_paulis_fun(ZXDiagram& diag);
+  static bool merge_gadgets_fun(ZXDiagram& diag);
+  static bool internalise_gadgets_fun(ZXDiagram& diag);
 };
 
 }  // namespace zx
@@ -19,6 +19,8 @@
 #include <string>
 #include <vector>
 
+#include "Utils/Json.hpp"
+
 namespace tket {
 
 namespace zx {
@@ -38,12 +40,19 @@ class ZXError : public std::logic_error {
  **/
 enum class ZXWireType { Basic, H };
 
+NLOHMANN_JSON_SERIALIZE_ENUM(
+    ZXWireType, {{ZXWireType::Basic, "Basic"}, {ZXWireType::H, "H"}});
+
 /**
  * Provides distinction between classical and quantum diagrams,
  * wires, and operators, relating to the doubling construction for CP-maps.
  **/
 enum class QuantumType { Quantum, Classical };
 
+NLOHMANN_JSON_SERIALIZE_ENUM(
+    QuantumType, {{QuantumType::Quantum, "Quantum"},
+                  {QuantumType::Classical, "Classical"}});
+
 /**
  * Ports are used for non-commutative generators, such as
  * directed generators, to distinguish the incident wires.
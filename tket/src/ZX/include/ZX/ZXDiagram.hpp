@@ -14,6 +14,7 @@
 
 #pragma once
 
+#include "Utils/EigenConfig.hpp"
 #include "ZX/ZXDiagramImpl.hpp"
 
 namespace tket {
@@ -189,6 +190,28 @@ class ZXDiagram {
    **/
   std::string to_graphviz_str(const std::set<ZXVert>& highlights = {}) const;
 
+  /**
+   * Produces a string in the JSON-based `.qgraph` format used by PyZX.
+   * Only diagrams of Quantum boundaries, ZSpiders, XSpiders, and Hboxes (with
+   * unit-modulus parameters) connected by Basic and H wires are supported;
+   * other generators should first be expanded, e.g. by
+   * `Rewrite::rebase_to_zx()`.
+   * Inputs and Outputs are flagged in the order of the boundary, and any Open
+   * boundaries are flagged as neither. The global scalar is not included.
+   */
+  std::string to_qgraph_str() const;
+
+  /**
+   * Reads a diagram from the `.qgraph` format used by PyZX.
+   * Boundaries flagged as inputs or outputs become Inputs or Outputs, ordered
+   * by the index if given or their order in the file otherwise. Any other
+   * boundaries become Open. The boundary lists all Inputs, then Outputs, then
+   * Open vertices.
+   * Hadamard nodes marked as edges become H wires, and other Hadamard nodes
+   * become Hboxes.
+   */
+  static ZXDiagram from_qgraph_str(const std::string& qgraph);
+
   /**
    * Diagram manipulation
    */
@@ -276,6 +299,52 @@ class ZXDiagram {
    */
   ZXDiagram to_quantum_embedding() const;
 
+  /**
+   * Tensor evaluation
+   *
+   * These evaluate the diagram numerically, so throw a `ZXError` if it has
+   * any symbolic parameters or scalar. The cost is exponential in the size of
+   * the diagram, so they are only intended for small diagrams, e.g. for
+   * checking the correctness of rewrites.
+   */
+
+  /**
+   * Evaluates the diagram as a tensor with one index for each boundary
+   * vertex, flattened with the first boundary vertex as the most significant
+   * bit.
+   * If every vertex and wire is Quantum, this is the pure linear map of the
+   * diagram. The scalar is that of the CP-map semantics, so the tensor is
+   * multiplied by its square root.
+   * Otherwise, `to_doubled_diagram` is evaluated instead, giving an index for
+   * each of its boundary vertices and multiplying by the full scalar.
+   */
+  Eigen::VectorXcd to_tensor() const;
+
+  /**
+   * Evaluates the diagram as in `to_tensor`, reshaped into a matrix from
+   * the Input boundary vertices (columns) to the Output and Open boundary
+   * vertices (rows). Both are big-endian in the order of the boundary (of the
+   * doubled diagram for diagrams with Classical components).
+   */
+  Eigen::MatrixXcd to_matrix() const;
+
+  /**
+   * Checks whether `this` and `other` evaluate to the same tensor, matching
+   * boundary vertices by their position in the boundary.
+   * Diagrams with different numbers or types of boundary vertices are never
+   * equivalent. If either diagram has Classical components, both are
+   * compared as doubled diagrams.
+   * `equiv` allows the tensors to differ by a global phase or by an
+   * arbitrary non-zero scalar (with a zero diagram only equivalent to
+   * another zero diagram). Entries are compared up to `tol` after
+   * normalising the largest entry to 1 when comparing up to a scalar.
+   */
+  enum class EquivalenceOption { EXACT, UP_TO_GLOBAL_PHASE, UP_TO_SCALAR };
+  bool is_equivalent_to(
+      const ZXDiagram& other,
+      EquivalenceOption equiv = EquivalenceOption::UP_TO_SCALAR,
+      double tol = EPS) const;
+
   /**
    * Subdiagram
    *
@@ -321,6 +390,8 @@ class ZXDiagram {
   friend Rewrite;
   friend ZXDiagramPybind;
   friend Flow;
+  friend void to_json(nlohmann::json& j, const ZXDiagram& diag);
+  friend void from_json(const nlohmann::json& j, ZXDiagram& diag);
 
  private:
   /**
@@ -341,8 +412,32 @@ class ZXDiagram {
    **/
   std::pair<std::map<ZXVert, ZXVert>, std::map<Wire, Wire>> copy_graph(
       const ZXDiagram& other, bool merge_boundaries = true);
+
+  // Whether every vertex and wire is Quantum
+  bool is_quantum() const;
+
+  /**
+   * Copies the diagram into a tensor network that can be evaluated by
+   * `contract_tensor_network`, i.e. with only boundaries, ZSpiders, XSpiders,
+   * and Hboxes connected by Basic wires. Assumes the diagram contains no
+   * ZXBoxes. If `doubled`, the diagram is replaced by `to_doubled_diagram`.
+   * Returns the network and the factor to multiply its tensor by.
+   */
+  std::pair<ZXDiagram, Complex> to_tensor_network(bool doubled) const;
+
+  /**
+   * Contracts a tensor network produced by `to_tensor_network`, with indices
+   * in the order of `boundary`, multiplying the result by `sc`.
+   */
+  Eigen::VectorXcd contract_tensor_network(const Complex& sc) const;
 };
 
+/**
+ * Diagrams are serialised with vertices and wires in iteration order, with
+ * wires and the boundary referring to vertices by their index.
+ */
+JSON_DECL(ZXDiagram)
+
 template <typename T, typename>
 const T& ZXDiagram::get_vertex_ZXGen(const ZXVert& v) const {
   ZXGen_ptr pt = get_vertex_ZXGen_ptr(v);
@@ -86,6 +86,8 @@ struct WireProperties {
   bool operator==(const WireProperties& other) const;
 };
 
+JSON_DECL(WireProperties)
+
 /**
  * A ZX diagram is semantically undirected, but the implementation uses a
  * directed graph in order to represent directed and non-commutative vertices.
@@ -96,6 +96,22 @@ enum class ZXType {
   ZXBox
 };
 
+NLOHMANN_JSON_SERIALIZE_ENUM(
+    ZXType, {{ZXType::Input, "Input"},
+             {ZXType::Output, "Output"},
+             {ZXType::Open, "Open"},
+             {ZXType::ZSpider, "ZSpider"},
+             {ZXType::XSpider, "XSpider"},
+             {ZXType::Hbox, "Hbox"},
+             {ZXType::XY, "XY"},
+             {ZXType::XZ, "XZ"},
+             {ZXType::YZ, "YZ"},
+             {ZXType::PX, "PX"},
+             {ZXType::PY, "PY"},
+             {ZXType::PZ, "PZ"},
+             {ZXType::Triangle, "Triangle"},
+             {ZXType::ZXBox, "ZXBox"}});
+
 typedef std::unordered_set<ZXType> ZXTypeSet;
 
 /**
@@ -113,6 +129,12 @@ bool is_Clifford_gen_type(ZXType type);
 class ZXGen;
 typedef std::shared_ptr<const ZXGen> ZXGen_ptr;
 
+/**
+ * Generators are serialised as an object with their ZXType and, where
+ * applicable, QuantumType and parameter. ZXBoxes contain their inner diagram.
+ */
+JSON_DECL(ZXGen_ptr)
+
 /**
  * Abstract class for a ZX generator.
  * Each ZXType has a single possible subclass that can realise it, allowing us
@@ -704,6 +704,176 @@ SCENARIO("Unitary3qBox", "[boxes]") {
   }
 }
 
+SCENARIO("MultiplexorBox", "[boxes]") {
+  GIVEN("A multiplexor of single-qubit gates") {
+    ctrl_op_map_t op_map = {
+        {{false, true}, get_op_ptr(OpType::H)},
+        {{true, false}, get_op_ptr(OpType::Rx, 0.3)},
+        {{true, true}, get_op_ptr(OpType::T)}};
+    MultiplexorBox mbox(op_map);
+    REQUIRE(mbox.n_qubits() == 3);
+    REQUIRE(mbox.get_n_controls() == 2);
+    Eigen::MatrixXcd U = Eigen::MatrixXcd::Identity(8, 8);
+    U.block(2, 2, 2, 2) = get_op_ptr(OpType::H)->get_unitary();
+    U.block(4, 4, 2, 2) = get_op_ptr(OpType::Rx, 0.3)->get_unitary();
+    U.block(6, 6, 2, 2) = get_op_ptr(OpType::T)->get_unitary();
+    Circuit circ(3);
+    circ.add_box(mbox, {0, 1, 2});
+    REQUIRE(tket_sim::get_unitary(circ).isApprox(U));
+    REQUIRE(tket_sim::get_unitary(*mbox.to_circuit()).isApprox(U));
+    const auto dag = std::dynamic_pointer_cast<const Box>(mbox.dagger());
+    REQUIRE(tket_sim::get_unitary(*dag->to_circuit()).isApprox(U.adjoint()));
+  }
+  GIVEN("A symbolic multiplexor") {
+    Sym a = SymTable::fresh_symbol("a");
+    ctrl_op_map_t op_map = {{{true}, get_op_ptr(OpType::Ry, Expr(a))}};
+    MultiplexorBox mbox(op_map);
+    REQUIRE(mbox.free_symbols() == SymSet{a});
+    SymEngine::map_basic_basic sub_map{{a, Expr(0.5)}};
+    const auto sub_box = std::dynamic_pointer_cast<const MultiplexorBox>(
+        mbox.symbol_substitution(sub_map));
+    REQUIRE(sub_box->free_symbols().empty());
+    Circuit expected(2);
+    expected.add_op<unsigned>(OpType::CRy, 0.5, {0, 1});
+    REQUIRE(tket_sim::get_unitary(*sub_box->to_circuit())
+                .isApprox(tket_sim::get_unitary(expected)));
+  }
+  GIVEN("Invalid op maps") {
+    REQUIRE_THROWS_AS(MultiplexorBox(ctrl_op_map_t{}), CircuitInvalidity);
+    ctrl_op_map_t sizes = {
+        {{true}, get_op_ptr(OpType::X)}, {{true, true}, get_op_ptr(OpType::Y)}};
+    REQUIRE_THROWS_AS(MultiplexorBox(sizes), CircuitInvalidity);
+    ctrl_op_map_t arities = {
+        {{true}, get_op_ptr(OpType::X)}, {{false}, get_op_ptr(OpType::CX)}};
+    REQUIRE_THROWS_AS(MultiplexorBox(arities), CircuitInvalidity);
+  }
+}
+
+SCENARIO("MultiplexedRotationBox", "[boxes]") {
+  GIVEN("Rotations about each axis") {
+    const std::vector<double> angles = {0.3, -1.1, 0.7, 1.9};
+    for (OpType axis : {OpType::Rx, OpType::Ry, OpType::Rz}) {
+      MultiplexedRotationBox mbox(angles, axis);
+      REQUIRE(mbox.n_qubits() == 3);
+      Eigen::MatrixXcd U = Eigen::MatrixXcd::Zero(8, 8);
+      for (unsigned j = 0; j < 4; j++) {
+        U.block(2 * j, 2 * j, 2, 2) =
+            get_op_ptr(axis, angles[j])->get_unitary();
+      }
+      REQUIRE(mbox.get_unitary().isApprox(U));
+      std::shared_ptr<const Circuit> c = mbox.to_circuit();
+      REQUIRE(c->count_gates(OpType::CX) <= 4);
+      REQUIRE(tket_sim::get_unitary(*c).isApprox(U));
+      REQUIRE(mbox.dagger()->get_unitary().isApprox(U.adjoint()));
+      REQUIRE(mbox.transpose()->get_unitary().isApprox(U.transpose()));
+    }
+  }
+  GIVEN("Invalid arguments") {
+    REQUIRE_THROWS_AS(
+        MultiplexedRotationBox({0.1, 0.2}, OpType::H), CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        MultiplexedRotationBox({0.1, 0.2, 0.3}, OpType::Ry),
+        CircuitInvalidity);
+  }
+}
+
+SCENARIO("StatePreparationBox", "[boxes]") {
+  GIVEN("A random 3-qubit state") {
+    const Eigen::VectorXcd state = random_unitary(8, 3).col(0);
+    StatePreparationBox sbox(state);
+    REQUIRE(sbox.n_qubits() == 3);
+    std::shared_ptr<const Circuit> c = sbox.to_circuit();
+    REQUIRE(c->count_gates(OpType::CX) <= 12);
+    REQUIRE(tket_sim::get_statevector(*c).isApprox(state));
+    Circuit circ(3);
+    circ.add_box(sbox, {0, 1, 2});
+    REQUIRE(tket_sim::get_statevector(circ).isApprox(state));
+  }
+  GIVEN("A sparse state") {
+    Eigen::VectorXcd state = Eigen::VectorXcd::Zero(16);
+    state(3) = -std::sqrt(0.5);
+    state(12) = std::sqrt(0.5) * i_;
+    StatePreparationBox sbox(state);
+    std::shared_ptr<const Circuit> c = sbox.to_circuit();
+    REQUIRE(tket_sim::get_statevector(*c).isApprox(state));
+  }
+  GIVEN("A box with initial reset") {
+    Eigen::VectorXcd state(2);
+    state << std::sqrt(0.3), std::sqrt(0.7) * i_;
+    StatePreparationBox sbox(state, true);
+    REQUIRE(sbox.with_initial_reset());
+    std::shared_ptr<const Circuit> c = sbox.to_circuit();
+    REQUIRE(c->count_gates(OpType::Reset) == 1);
+    REQUIRE_THROWS_AS(sbox.dagger(), BadOpType);
+  }
+  GIVEN("Invalid arguments") {
+    REQUIRE_THROWS_AS(
+        StatePreparationBox(Eigen::VectorXcd::Ones(1)), CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        StatePreparationBox(Eigen::VectorXcd::Ones(3) / std::sqrt(3.)),
+        CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        StatePreparationBox(Eigen::VectorXcd::Ones(4)), CircuitInvalidity);
+  }
+}
+
+SCENARIO("ToffoliBox", "[boxes]") {
+  GIVEN("A random permutation of 3-qubit basis states") {
+    std::vector<std::vector<bool>> states;
+    for (unsigned i = 0; i < 8; i++) {
+      states.push_back({bool(i & 4), bool(i & 2), bool(i & 1)});
+    }
+    const std::vector<unsigned> images = {5, 2, 7, 0, 4, 1, 6, 3};
+    state_perm_t perm;
+    Eigen::MatrixXcd U = Eigen::MatrixXcd::Zero(8, 8);
+    for (unsigned i = 0; i < 8; i++) {
+      perm.insert({states[i], states[images[i]]});
+      U(images[i], i) = 1.;
+    }
+    for (ToffoliBoxSynthStrat strat :
+         {ToffoliBoxSynthStrat::Naive, ToffoliBoxSynthStrat::CostAware}) {
+      ToffoliBox tbox(perm, strat);
+      REQUIRE(tbox.n_qubits() == 3);
+      REQUIRE(tbox.get_unitary().isApprox(U));
+      std::shared_ptr<const Circuit> c = tbox.to_circuit();
+      REQUIRE(tket_sim::get_unitary(*c).isApprox(U));
+      Circuit circ(3);
+      circ.add_box(tbox, {0, 1, 2});
+      REQUIRE(tket_sim::get_unitary(circ).isApprox(U));
+      REQUIRE(tbox.dagger()->get_unitary().isApprox(U.adjoint()));
+      REQUIRE(tbox.transpose()->get_unitary().isApprox(U.transpose()));
+    }
+  }
+  GIVEN("A cycle with one expensive transposition") {
+    // 000 -> 111 -> 001 -> 000
+    const std::vector<bool> s0 = {false, false, false};
+    const std::vector<bool> s1 = {true, true, true};
+    const std::vector<bool> s2 = {false, false, true};
+    const state_perm_t perm = {{s0, s1}, {s1, s2}, {s2, s0}};
+    ToffoliBox naive(perm, ToffoliBoxSynthStrat::Naive);
+    ToffoliBox cost_aware(perm, ToffoliBoxSynthStrat::CostAware);
+    REQUIRE(naive.to_circuit()->count_gates(OpType::CCX) == 8);
+    REQUIRE(cost_aware.to_circuit()->count_gates(OpType::CCX) == 4);
+    REQUIRE(tket_sim::get_unitary(*cost_aware.to_circuit())
+                .isApprox(naive.get_unitary()));
+  }
+  GIVEN("A single qubit") {
+    ToffoliBox tbox(state_perm_t{{{false}, {true}}, {{true}, {false}}});
+    std::shared_ptr<const Circuit> c = tbox.to_circuit();
+    REQUIRE(c->n_gates() == 1);
+    REQUIRE(c->count_gates(OpType::X) == 1);
+  }
+  GIVEN("Invalid permutations") {
+    REQUIRE_THROWS_AS(ToffoliBox(state_perm_t{}), CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        ToffoliBox(state_perm_t{{{false, true}, {true}}, {{true}, {false}}}),
+        CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        ToffoliBox(state_perm_t{{{false, true}, {true, true}}}),
+        CircuitInvalidity);
+  }
+}
+
 SCENARIO("Checking equality", "[boxes]") {
   GIVEN("Some different types") {
     Circuit u(2);
@@ -0,0 +1,152 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+#include <stdexcept>
+#include <vector>
+
+#include "../Simulation/ComparisonFunctions.hpp"
+#include "../testutil.hpp"
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Circuit/Command.hpp"
+#include "Circuit/ShannonDecomposition.hpp"
+#include "OpType/OpType.hpp"
+#include "Simulation/CircuitSimulator.hpp"
+#include "Transformations/Decomposition.hpp"
+#include "Transformations/Transform.hpp"
+#include "Utils/EigenConfig.hpp"
+
+namespace tket {
+namespace test_ShannonDecomposition {
+
+// Synthesise U, decompose TK2 gates and return the number of CX gates.
+static unsigned check_shannon_synthesis(const Eigen::MatrixXcd &U) {
+  Circuit c = quantum_shannon_synthesis(U);
+  Transforms::decompose_TK2().apply(c);
+  unsigned n_cx = 0;
+  for (const Command &cmd : c) {
+    if (cmd.get_op_ptr()->get_type() == OpType::CX) {
+      n_cx++;
+    } else {
+      CHECK(cmd.get_args().size() == 1);
+    }
+  }
+  Eigen::MatrixXcd U1 = tket_sim::get_unitary(c);
+  CHECK(tket_sim::compare_statevectors_or_unitaries(U, U1));
+  return n_cx;
+}
+
+SCENARIO("Multiplexed rotations") {
+  GIVEN("Distinct angles") {
+    const std::vector<double> angles = {0.1, -0.7, 1.3, 0.25};
+    for (OpType type : {OpType::Ry, OpType::Rz}) {
+      Circuit c = multiplexed_rotation(type, angles);
+      REQUIRE(c.n_qubits() == 3);
+      CHECK(c.count_gates(OpType::CX) == 4);
+      Eigen::MatrixXcd U = Eigen::MatrixXcd::Zero(8, 8);
+      for (unsigned j = 0; j < 4; j++) {
+        Circuit r(1);
+        r.add_op<unsigned>(type, angles[j], {0});
+        Eigen::Matrix2cd m = tket_sim::get_unitary(r);
+        U(j, j) = m(0, 0);
+        U(j, j + 4) = m(0, 1);
+        U(j + 4, j) = m(1, 0);
+        U(j + 4, j + 4) = m(1, 1);
+      }
+      CHECK(tket_sim::get_unitary(c).isApprox(U));
+    }
+  }
+  GIVEN("Equal angles") {
+    Circuit c = multiplexed_rotation(OpType::Rz, {0.3, 0.3, 0.3, 0.3});
+    REQUIRE(c.n_qubits() == 3);
+    CHECK(c.n_gates() == 1);
+  }
+  GIVEN("Invalid arguments") {
+    REQUIRE_THROWS_AS(
+        multiplexed_rotation(OpType::Rx, {0.1, 0.2}), std::invalid_argument);
+    REQUIRE_THROWS_AS(
+        multiplexed_rotation(OpType::Ry, {0.1, 0.2, 0.3}),
+        std::invalid_argument);
+  }
+}
+
+SCENARIO("Quantum Shannon decomposition") {
+  GIVEN("Random unitaries on 1 to 3 qubits") {
+    for (unsigned n = 1; n <= 3; n++) {
+      check_shannon_synthesis(random_unitary(1u << n, n));
+    }
+  }
+  GIVEN("A random 4-qubit unitary") {
+    CHECK(check_shannon_synthesis(random_unitary(16, 4)) <= 103);
+  }
+  GIVEN("A random 5-qubit unitary") {
+    CHECK(check_shannon_synthesis(random_unitary(32, 5)) <= 459);
+  }
+  GIVEN("A controlled 3-qubit unitary") {
+    Eigen::MatrixXcd U = Eigen::MatrixXcd::Identity(16, 16);
+    U.bottomRightCorner(8, 8) = random_unitary(8, 1);
+    CHECK(check_shannon_synthesis(U) <= 48);
+  }
+  GIVEN("A 4-qubit unitary acting on the last 3 qubits") {
+    Eigen::MatrixXcd V = random_unitary(8, 2);
+    Eigen::MatrixXcd U = Eigen::MatrixXcd::Zero(16, 16);
+    U.topLeftCorner(8, 8) = V;
+    U.bottomRightCorner(8, 8) = V;
+    CHECK(check_shannon_synthesis(U) <= 20);
+  }
+  GIVEN("A multiple of the identity") {
+    Eigen::MatrixXcd U = Eigen::MatrixXcd::Identity(16, 16) * i_;
+    Circuit c = quantum_shannon_synthesis(U);
+    CHECK(c.n_gates() == 0);
+    CHECK(tket_sim::get_unitary(c).isApprox(U));
+  }
+  GIVEN("Invalid matrices") {
+    REQUIRE_THROWS_AS(
+        quantum_shannon_synthesis(Eigen::MatrixXcd::Identity(12, 12)),
+        std::invalid_argument);
+    REQUIRE_THROWS_AS(
+        quantum_shannon_synthesis(Eigen::MatrixXcd::Ones(16, 16)),
+        std::invalid_argument);
+  }
+}
+
+SCENARIO("UnitaryNqBox") {
+  GIVEN("A random 4-qubit unitary") {
+    Eigen::MatrixXcd U = random_unitary(16, 3);
+    UnitaryNqBox ubox(U);
+    REQUIRE(ubox.n_qubits() == 4);
+    std::shared_ptr<const Circuit> c = ubox.to_circuit();
+    CHECK(tket_sim::get_unitary(*c).isApprox(U));
+    Circuit circ(4);
+    circ.add_box(ubox, {0, 1, 2, 3});
+    CHECK(tket_sim::get_unitary(circ).isApprox(U));
+    Op_ptr dag = ubox.dagger();
+    CHECK(dag->get_unitary().isApprox(U.adjoint()));
+  }
+  GIVEN("A matrix in DLO-BE order") {
+    Eigen::MatrixXcd U = random_unitary(16, 6);
+    UnitaryNqBox ubox(U, BasisOrder::dlo);
+    CHECK(ubox.get_matrix().isApprox(reverse_indexing(U)));
+  }
+  GIVEN("Invalid matrices") {
+    REQUIRE_THROWS_AS(
+        UnitaryNqBox(Eigen::MatrixXcd::Identity(6, 6)), CircuitInvalidity);
+    REQUIRE_THROWS_AS(
+        UnitaryNqBox(Eigen::MatrixXcd::Ones(4, 4)), CircuitInvalidity);
+  }
+}
+
+}  // namespace test_ShannonDecomposition
+}  // namespace tket
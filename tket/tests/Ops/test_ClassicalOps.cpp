@@ -497,6 +497,26 @@ SCENARIO("Pure classical operations") {
     REQUIRE(!y[0]);
     REQUIRE(y[1]);
   }
+  GIVEN("Evaluation of a circuit with predicates and multi-bit operations") {
+    Circuit circ(0, 5);
+    circ.add_op<unsigned>(
+        std::make_shared<RangePredicateOp>(2, 1, 2), {0, 1, 2});
+    circ.add_op<unsigned>(
+        std::make_shared<MultiBitOp>(AndOp(), 2), {0, 2, 3, 1, 2, 4});
+    std::map<Bit, bool> values = {
+        {Bit(0), true},
+        {Bit(1), false},
+        {Bit(2), false},
+        {Bit(3), false},
+        {Bit(4), true}};
+    std::map<Bit, bool> result = circ.classical_eval(values);
+    REQUIRE(result.at(Bit(0)));
+    REQUIRE(!result.at(Bit(1)));
+    // 1 is in the range [1, 2]
+    REQUIRE(result.at(Bit(2)));
+    REQUIRE(result.at(Bit(3)));
+    REQUIRE(!result.at(Bit(4)));
+  }
 }
 
 }  // namespace test_ClassicalOps
@@ -0,0 +1,210 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_approx.hpp>
+#include <catch2/catch_test_macros.hpp>
+
+#include "../testutil.hpp"
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Ops/ClassicalOps.hpp"
+#include "Simulation/CircuitSimulator.hpp"
+#include "Simulation/DensityMatrixSimulator.hpp"
+
+namespace tket {
+namespace test_DensityMatrixSimulator {
+using Catch::Approx;
+
+static double branch_probability(
+    const tket_sim::ClassicalQuantumState& state,
+    const std::vector<bool>& key) {
+  const auto it = state.find(key);
+  if (it == state.end()) return 0.;
+  return it->second.trace().real();
+}
+
+SCENARIO("Density matrices of unitary circuits") {
+  GIVEN("A circuit without measurements") {
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::Rz, 0.3, {1});
+    circ.add_op<unsigned>(OpType::CRy, 0.7, {1, 2});
+    circ.add_op<unsigned>(OpType::SWAP, {0, 2});
+    const StateVector sv = tket_sim::get_statevector(circ);
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ);
+    CHECK(rho.isApprox(sv * sv.adjoint(), ERR_EPS));
+  }
+  GIVEN("A circuit with an implicit permutation") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::X, {0});
+    circ.add_op<unsigned>(OpType::Ry, 0.4, {1});
+    circ.add_op<unsigned>(OpType::SWAP, {0, 1});
+    circ.replace_SWAPs();
+    REQUIRE(circ.has_implicit_wireswaps());
+    const StateVector sv = tket_sim::get_statevector(circ);
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ);
+    CHECK(rho.isApprox(sv * sv.adjoint(), ERR_EPS));
+  }
+  GIVEN("Too many qubits") {
+    Circuit circ(4);
+    REQUIRE_THROWS(tket_sim::get_density_matrix(circ, {}, EPS, 3));
+  }
+}
+
+SCENARIO("Density matrices with measurement, reset and classical control") {
+  GIVEN("A measured Bell state") {
+    Circuit circ(2, 2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_measure(0, 0);
+    circ.add_measure(1, 1);
+    const auto state = tket_sim::get_classical_quantum_state(circ);
+    CHECK(state.size() == 2);
+    CHECK(branch_probability(state, {false, false}) == Approx(0.5));
+    CHECK(branch_probability(state, {true, true}) == Approx(0.5));
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ);
+    DensityMatrix expected = DensityMatrix::Zero(4, 4);
+    expected(0, 0) = 0.5;
+    expected(3, 3) = 0.5;
+    CHECK(rho.isApprox(expected, ERR_EPS));
+  }
+  GIVEN("A reset after a Hadamard") {
+    Circuit circ(1);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::Reset, {0});
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ);
+    DensityMatrix expected = DensityMatrix::Zero(2, 2);
+    expected(0, 0) = 1.;
+    CHECK(rho.isApprox(expected, ERR_EPS));
+  }
+  GIVEN("A measurement-controlled correction") {
+    // Measure a |+> state, then flip it back to |0> if the outcome was 1.
+    Circuit circ(2, 1);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {0}, {0}, 1);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {0}, 1);
+    const auto state = tket_sim::get_classical_quantum_state(circ);
+    REQUIRE(state.size() == 2);
+    DensityMatrix expected0 = DensityMatrix::Zero(4, 4);
+    expected0(0, 0) = 0.5;
+    DensityMatrix expected1 = DensityMatrix::Zero(4, 4);
+    expected1(1, 1) = 0.5;
+    CHECK(state.at({false}).isApprox(expected0, ERR_EPS));
+    CHECK(state.at({true}).isApprox(expected1, ERR_EPS));
+  }
+  GIVEN("Classical operations") {
+    Circuit circ(1, 2);
+    circ.add_op<unsigned>(
+        std::make_shared<SetBitsOp>(std::vector<bool>{true}), {1});
+    circ.add_op<unsigned>(std::make_shared<CopyBitsOp>(1), {1, 0});
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {0}, {0}, 1);
+    const auto state = tket_sim::get_classical_quantum_state(circ);
+    REQUIRE(state.size() == 1);
+    DensityMatrix expected = DensityMatrix::Zero(2, 2);
+    expected(1, 1) = 1.;
+    CHECK(state.at({true, true}).isApprox(expected, ERR_EPS));
+  }
+  GIVEN("A CircBox containing a measurement") {
+    Circuit inner(1, 1);
+    inner.add_op<unsigned>(OpType::X, {0});
+    inner.add_measure(0, 0);
+    Circuit circ(2, 2);
+    circ.add_box(CircBox(inner), std::vector<unsigned>{1, 1});
+    const auto state = tket_sim::get_classical_quantum_state(circ);
+    REQUIRE(state.size() == 1);
+    CHECK(branch_probability(state, {false, true}) == Approx(1.));
+  }
+}
+
+SCENARIO("Noisy density matrices") {
+  GIVEN("A depolarising channel after X gates") {
+    const double p = 0.3;
+    Circuit circ(1);
+    circ.add_op<unsigned>(OpType::X, {0});
+    tket_sim::NoiseModel noise;
+    noise.add_channel(OpType::X, tket_sim::KrausChannel::depolarising(1, p));
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ, noise);
+    // X and Y leave |1><1| in |0><0|; Z and I leave it alone.
+    CHECK(rho(0, 0).real() == Approx(2 * p / 3));
+    CHECK(rho(1, 1).real() == Approx(1 - 2 * p / 3));
+    CHECK(std::abs(rho(0, 1)) == Approx(0.).margin(ERR_EPS));
+  }
+  GIVEN("Channels with different precedence") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::X, {0});
+    const Vertex v = circ.add_op<unsigned>(OpType::X, {1});
+    tket_sim::NoiseModel noise;
+    noise.add_channel(OpType::X, tket_sim::KrausChannel::bit_flip(1.));
+    noise.add_channel(v, tket_sim::KrausChannel::bit_flip(0.));
+    // Qubit 0 is flipped back; qubit 1 is not.
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ, noise);
+    CHECK(rho(1, 1).real() == Approx(1.));
+  }
+  GIVEN("Amplitude damping on a qubit") {
+    Circuit circ(1);
+    circ.add_op<unsigned>(OpType::X, {0});
+    tket_sim::NoiseModel noise;
+    noise.add_channel(
+        {Qubit(0)}, tket_sim::KrausChannel::amplitude_damping(0.25));
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ, noise);
+    CHECK(rho(0, 0).real() == Approx(0.25));
+    CHECK(rho(1, 1).real() == Approx(0.75));
+  }
+  GIVEN("A readout error") {
+    Circuit circ(1, 1);
+    circ.add_measure(0, 0);
+    tket_sim::NoiseModel noise;
+    noise.set_readout_error(Qubit(0), 0.1);
+    const auto state = tket_sim::get_classical_quantum_state(circ, noise);
+    CHECK(branch_probability(state, {false}) == Approx(0.9));
+    CHECK(branch_probability(state, {true}) == Approx(0.1));
+    // The qubit itself is still in |0>.
+    CHECK(std::abs(state.at({true})(1, 1)) == Approx(0.).margin(ERR_EPS));
+  }
+  GIVEN("Noise derived from a DeviceCharacterisation") {
+    const Node n0(0), n1(1);
+    DeviceCharacterisation characterisation(
+        {{n0, 0.}, {n1, 0.15}}, {{{n0, n1}, 0.}}, {{n0, 0.2}});
+    Circuit circ;
+    circ.add_qubit(n0);
+    circ.add_qubit(n1);
+    circ.add_bit(Bit(0));
+    circ.add_op<UnitID>(OpType::X, {n1});
+    circ.add_op<UnitID>(OpType::CX, {n0, n1});
+    circ.add_measure(n0, Bit(0));
+    tket_sim::NoiseModel noise;
+    noise.set_characterisation(characterisation);
+    const auto state = tket_sim::get_classical_quantum_state(circ, noise);
+    CHECK(branch_probability(state, {true}) == Approx(0.2));
+    const DensityMatrix rho = tket_sim::get_density_matrix(circ, noise);
+    CHECK(rho(0, 0).real() == Approx(0.1));
+    CHECK(rho(1, 1).real() == Approx(0.9));
+  }
+  GIVEN("Invalid Kraus operators") {
+    Eigen::MatrixXcd k = Eigen::MatrixXcd::Identity(2, 2);
+    REQUIRE_THROWS_AS(tket_sim::KrausChannel({k, k}), std::invalid_argument);
+    REQUIRE_THROWS_AS(
+        tket_sim::KrausChannel::depolarising(1, 1.5), std::invalid_argument);
+    tket_sim::NoiseModel noise;
+    REQUIRE_THROWS_AS(
+        noise.add_channel(
+            {Qubit(0), Qubit(1)}, tket_sim::KrausChannel::bit_flip(0.1)),
+        std::invalid_argument);
+  }
+}
+
+}  // namespace test_DensityMatrixSimulator
+}  // namespace tket
@@ -0,0 +1,145 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Ops/ClassicalOps.hpp"
+#include "Simulation/ShotSimulator.hpp"
+
+namespace tket {
+namespace test_ShotSimulator {
+
+SCENARIO("Sampling shots from circuits with measurements") {
+  GIVEN("A deterministic circuit") {
+    Circuit circ(3, 3);
+    circ.add_op<unsigned>(OpType::X, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    for (unsigned i = 0; i < 3; ++i) {
+      circ.add_measure(i, i);
+    }
+    const tket_sim::ShotTable table = tket_sim::sample_shots(circ, 20);
+    CHECK(table.n_shots() == 20);
+    CHECK(table.get_bits() == circ.all_bits());
+    const auto counts = table.get_counts();
+    REQUIRE(counts.size() == 1);
+    CHECK(counts.at({true, false, true}) == 20);
+  }
+  GIVEN("A Bell state") {
+    Circuit circ(2, 2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_measure(0, 0);
+    circ.add_measure(1, 1);
+    const auto counts = tket_sim::sample_shots(circ, 1000).get_counts();
+    REQUIRE(counts.size() == 2);
+    const unsigned n_00 = counts.at({false, false});
+    const unsigned n_11 = counts.at({true, true});
+    CHECK(n_00 + n_11 == 1000);
+    CHECK(n_00 > 400);
+    CHECK(n_11 > 400);
+  }
+  GIVEN("The same seed") {
+    Circuit circ(3, 3);
+    for (unsigned i = 0; i < 3; ++i) {
+      circ.add_op<unsigned>(OpType::Ry, 0.3 * (i + 1), {i});
+      circ.add_measure(i, i);
+    }
+    const auto shots0 = tket_sim::sample_shots(circ, 50, 7).get_shots();
+    const auto shots1 = tket_sim::sample_shots(circ, 50, 7).get_shots();
+    const auto shots2 = tket_sim::sample_shots(circ, 50, 8).get_shots();
+    CHECK(shots0 == shots1);
+    CHECK(shots0 != shots2);
+  }
+  GIVEN("Too many qubits") {
+    Circuit circ(5);
+    REQUIRE_THROWS(tket_sim::sample_shots(circ, 1, 0, EPS, 4));
+  }
+}
+
+SCENARIO("Sampling shots with classical feed-forward") {
+  GIVEN("Mid-circuit measurement with a conditional correction") {
+    // Prepare |+>, measure, correct back to |0> and measure again.
+    Circuit circ(1, 2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {0}, {0}, 1);
+    circ.add_measure(0, 1);
+    const auto counts = tket_sim::sample_shots(circ, 200).get_counts();
+    REQUIRE(counts.size() == 2);
+    CHECK(counts.count({false, false}) == 1);
+    CHECK(counts.count({true, false}) == 1);
+  }
+  GIVEN("Reset between measurements") {
+    Circuit circ(1, 2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_op<unsigned>(OpType::Reset, {0});
+    circ.add_measure(0, 1);
+    const auto counts = tket_sim::sample_shots(circ, 100).get_counts();
+    for (const auto& [shot, count] : counts) {
+      CHECK_FALSE(shot[1]);
+    }
+  }
+  GIVEN("Classical operations controlling gates") {
+    // c[2] := (c[0] c[1] in [2,3]), i.e. c[1]; then X on q[1] if c[2].
+    Circuit circ(2, 3);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 1);
+    circ.add_op<unsigned>(
+        std::make_shared<RangePredicateOp>(2, 2, 3), {0, 1, 2});
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {2}, 1);
+    circ.add_measure(1, 0);
+    const auto counts = tket_sim::sample_shots(circ, 200).get_counts();
+    REQUIRE(counts.size() == 2);
+    CHECK(counts.count({false, false, false}) == 1);
+    CHECK(counts.count({true, true, true}) == 1);
+  }
+  GIVEN("Multi-bit conditions and SetBits") {
+    Circuit circ(1, 2);
+    circ.add_op<unsigned>(
+        std::make_shared<SetBitsOp>(std::vector<bool>{false, true}), {0, 1});
+    // Value 2 means c[0] = 0 and c[1] = 1.
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {0}, {0, 1}, 2);
+    circ.add_measure(0, 0);
+    const auto counts = tket_sim::sample_shots(circ, 10).get_counts();
+    REQUIRE(counts.size() == 1);
+    CHECK(counts.at({true, true}) == 10);
+  }
+  GIVEN("A CircBox with a measurement") {
+    Circuit inner(2, 1);
+    inner.add_op<unsigned>(OpType::X, {1});
+    inner.add_op<unsigned>(OpType::SWAP, {0, 1});
+    inner.replace_SWAPs();
+    inner.add_measure(0, 0);
+    Circuit circ(2, 2);
+    circ.add_box(CircBox(inner), std::vector<unsigned>{0, 1, 1});
+    circ.add_measure(0, 0);
+    const auto counts = tket_sim::sample_shots(circ, 10).get_counts();
+    REQUIRE(counts.size() == 1);
+    CHECK(counts.at({true, true}) == 10);
+  }
+  GIVEN("A WASM op") {
+    Circuit circ(1, 1);
+    circ.add_op<unsigned>(
+        std::make_shared<WASMOp>(
+            1, std::vector<unsigned>{1}, std::vector<unsigned>{}, "f", "id"),
+        {0});
+    REQUIRE_THROWS_AS(tket_sim::sample_shots(circ, 1), CircuitInvalidity);
+  }
+}
+
+}  // namespace test_ShotSimulator
+}  // namespace tket
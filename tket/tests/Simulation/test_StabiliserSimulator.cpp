@@ -0,0 +1,161 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <algorithm>
+#include <catch2/catch_approx.hpp>
+#include <catch2/catch_test_macros.hpp>
+
+#include "../testutil.hpp"
+#include "Circuit/Boxes.hpp"
+#include "Circuit/Circuit.hpp"
+#include "Clifford/StabiliserTableau.hpp"
+#include "Simulation/DensityMatrixSimulator.hpp"
+#include "Simulation/StabiliserSimulator.hpp"
+
+namespace tket {
+namespace test_StabiliserSimulator {
+using Catch::Approx;
+
+SCENARIO("Measuring a StabiliserTableau") {
+  GIVEN("A Bell state") {
+    StabiliserTableau tab(2);
+    tab.apply_gate(OpType::H, {0});
+    tab.apply_gate(OpType::CX, {0, 1});
+    const PauliStabiliserList stabs = tab.get_stabilisers();
+    const PauliStabiliserList expected = {
+        {{Pauli::X, Pauli::X}, true}, {{Pauli::Z, Pauli::Z}, true}};
+    CHECK(stabs == expected);
+    REQUIRE_FALSE(tab.get_deterministic_outcome(0));
+    CHECK(tab.measure(0, true));
+    CHECK(tab.get_deterministic_outcome(0) == true);
+    CHECK(tab.get_deterministic_outcome(1) == true);
+    tab.reset(1, false);
+    CHECK(tab.get_deterministic_outcome(1) == false);
+  }
+}
+
+SCENARIO("Stabiliser simulation of circuits") {
+  GIVEN("A GHZ state") {
+    Circuit circ(3, 3);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::CX, {1, 2});
+    for (unsigned i = 0; i < 3; ++i) {
+      circ.add_measure(i, i);
+    }
+    const auto probs = tket_sim::get_stabiliser_outcome_probabilities(circ);
+    REQUIRE(probs.size() == 2);
+    CHECK(probs.at({false, false, false}) == Approx(0.5));
+    CHECK(probs.at({true, true, true}) == Approx(0.5));
+    const auto counts =
+        tket_sim::sample_stabiliser_shots(circ, 100).get_counts();
+    REQUIRE(counts.size() == 2);
+    CHECK(counts.count({false, false, false}) == 1);
+    CHECK(counts.count({true, true, true}) == 1);
+  }
+  GIVEN("A circuit with a variety of Clifford gates") {
+    Circuit circ(3, 3);
+    circ.add_op<unsigned>(OpType::Rx, 0.5, {0});
+    circ.add_op<unsigned>(OpType::TK1, {0.5, 1.5, 1.}, {1});
+    circ.add_op<unsigned>(OpType::ZZMax, {0, 1});
+    circ.add_op<unsigned>(OpType::ECR, {1, 2});
+    circ.add_op<unsigned>(OpType::PhasedX, {0.5, 0.5}, {2});
+    circ.add_op<unsigned>(OpType::ISWAPMax, {0, 2});
+    circ.add_op<unsigned>(OpType::SX, {1});
+    circ.add_op<unsigned>(OpType::ZZPhase, -0.5, {1, 2});
+    circ.add_op<unsigned>(OpType::Sdg, {0});
+    circ.add_op<unsigned>(OpType::H, {1});
+    for (unsigned i = 0; i < 3; ++i) {
+      circ.add_measure(i, i);
+    }
+    const auto probs = tket_sim::get_stabiliser_outcome_probabilities(circ);
+    const auto state = tket_sim::get_classical_quantum_state(circ);
+    double total = 0.;
+    for (const auto& [bits, rho] : state) {
+      const double p = rho.trace().real();
+      const auto it = probs.find(bits);
+      const double q = (it == probs.end()) ? 0. : it->second;
+      CHECK(q == Approx(p).margin(ERR_EPS));
+      total += q;
+    }
+    CHECK(total == Approx(1.));
+  }
+  GIVEN("Teleportation with conditional Pauli corrections") {
+    Circuit circ(3, 3);
+    // Prepare the eigenstate of Y with eigenvalue -1 on q[0].
+    circ.add_op<unsigned>(OpType::X, {0});
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::S, {0});
+    circ.add_op<unsigned>(OpType::H, {1});
+    circ.add_op<unsigned>(OpType::CX, {1, 2});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_measure(1, 1);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {2}, {1}, 1);
+    circ.add_conditional_gate<unsigned>(OpType::Z, {}, {2}, {0}, 1);
+    // Undo the preparation on q[2], which should then be in |0>.
+    circ.add_op<unsigned>(OpType::Sdg, {2});
+    circ.add_op<unsigned>(OpType::H, {2});
+    circ.add_op<unsigned>(OpType::X, {2});
+    circ.add_measure(2, 2);
+    const auto probs = tket_sim::get_stabiliser_outcome_probabilities(circ);
+    REQUIRE(probs.size() == 4);
+    for (const auto& [bits, p] : probs) {
+      CHECK_FALSE(bits[2]);
+      CHECK(p == Approx(0.25));
+    }
+  }
+  GIVEN("Resets and a CircBox with an implicit permutation") {
+    Circuit inner(2);
+    inner.add_op<unsigned>(OpType::X, {0});
+    inner.add_op<unsigned>(OpType::SWAP, {0, 1});
+    inner.replace_SWAPs();
+    Circuit circ(2, 3);
+    circ.add_box(CircBox(inner), std::vector<unsigned>{0, 1});
+    circ.add_measure(0, 0);
+    circ.add_measure(1, 1);
+    circ.add_op<unsigned>(OpType::H, {1});
+    circ.add_op<unsigned>(OpType::Reset, {1});
+    circ.add_measure(1, 2);
+    const auto probs = tket_sim::get_stabiliser_outcome_probabilities(circ);
+    REQUIRE(probs.size() == 1);
+    CHECK(probs.at({false, true, false}) == Approx(1.));
+  }
+  GIVEN("A circuit too large for statevector simulation") {
+    const unsigned n = 100;
+    Circuit circ(n, n);
+    circ.add_op<unsigned>(OpType::H, {0});
+    for (unsigned i = 0; i + 1 < n; ++i) {
+      circ.add_op<unsigned>(OpType::CX, {i, i + 1});
+    }
+    for (unsigned i = 0; i < n; ++i) {
+      circ.add_measure(i, i);
+    }
+    const auto shots = tket_sim::sample_stabiliser_shots(circ, 4).get_shots();
+    for (const std::vector<bool>& shot : shots) {
+      CHECK(std::all_of(
+          shot.begin(), shot.end(), [&](bool b) { return b == shot[0]; }));
+    }
+  }
+  GIVEN("A non-Clifford gate") {
+    Circuit circ(1);
+    circ.add_op<unsigned>(OpType::T, {0});
+    REQUIRE_THROWS_AS(
+        tket_sim::sample_stabiliser_shots(circ, 1), CircuitInvalidity);
+  }
+}
+
+}  // namespace test_StabiliserSimulator
+}  // namespace tket
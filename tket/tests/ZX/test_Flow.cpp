@@ -280,6 +280,59 @@ SCENARIO("Testing Pauli flow identification and focussing") {
   REQUIRE_NOTHROW(f.verify(diag));
 }
 
+SCENARIO("Testing gflow identification") {
+  GIVEN("A diagram with measurements in all three planes") {
+    // d is a phase gadget on a, and c is corrected by the output vertex
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a = diag.add_vertex(ZXType::XY, 0.3);
+    ZXVert c = diag.add_vertex(ZXType::XZ, 1.4);
+    ZXVert d = diag.add_vertex(ZXType::YZ, 0.9);
+    ZXVert o = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a);
+    diag.add_wire(a, c, ZXWireType::H);
+    diag.add_wire(a, d, ZXWireType::H);
+    diag.add_wire(a, o, ZXWireType::H);
+    diag.add_wire(c, o, ZXWireType::H);
+    diag.add_wire(o, outs.at(0));
+    Flow f = Flow::identify_gflow(diag);
+    REQUIRE_NOTHROW(f.verify(diag));
+    CHECK(f.d(o) == 0);
+    // c receives a Z correction from a, so is measured after it
+    CHECK(f.d(c) < f.d(a));
+    CHECK(f.c(c).get<TagKey>().count(c) == 1);
+    CHECK(f.c(d).get<TagKey>().count(d) == 1);
+  }
+  GIVEN("A measured vertex without corrections") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a = diag.add_vertex(ZXType::XY, 0.3);
+    ZXVert b = diag.add_vertex(ZXType::XY, 0.7);
+    ZXVert o = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a);
+    diag.add_wire(a, b, ZXWireType::H);
+    diag.add_wire(a, o, ZXWireType::H);
+    diag.add_wire(o, outs.at(0));
+    // b has no neighbour other than a, which is in its past
+    REQUIRE_THROWS_AS(Flow::identify_gflow(diag), ZXError);
+  }
+  GIVEN("An input measured in the YZ plane") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a = diag.add_vertex(ZXType::YZ, 0.3);
+    ZXVert o = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a);
+    diag.add_wire(a, o, ZXWireType::H);
+    diag.add_wire(o, outs.at(0));
+    REQUIRE_THROWS_WITH(
+        Flow::identify_gflow(diag),
+        "Inputs measured in XZ, YZ, or Z cannot be corrected with gflow");
+  }
+}
+
 SCENARIO("Test focussed set identificaiton") {
   // Diagram combines Ex. 2.43, "There and back again: a circuit extraction
   // tale", Backens et al. 2021 and Ex. C.13, "Relating measurement patterns to
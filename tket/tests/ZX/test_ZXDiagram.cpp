@@ -394,6 +394,130 @@ SCENARIO("Subdiagram substitutions") {
   }
 }
 
+SCENARIO("Serialising diagrams") {
+  GIVEN("JSON") {
+    ZXDiagram inner(1, 1, 0, 0);
+    ZXVertVec inner_b = inner.get_boundary();
+    ZXVert tri = inner.add_vertex(ZXType::Triangle);
+    inner.add_wire(
+        inner_b.at(0), tri, ZXWireType::Basic, QuantumType::Quantum,
+        std::nullopt, 0);
+    inner.add_wire(
+        tri, inner_b.at(1), ZXWireType::Basic, QuantumType::Quantum, 1);
+    ZXDiagram diag(1, 1, 0, 1);
+    ZXVertVec b = diag.get_boundary();
+    Sym a = SymEngine::symbol("a");
+    ZXVert z = diag.add_vertex(ZXType::ZSpider, Expr(a));
+    ZXVert py = diag.add_vertex(ZXGen::create_gen(ZXType::PY, true));
+    ZXVert m = diag.add_vertex(ZXType::ZSpider, QuantumType::Classical);
+    ZXVert box = diag.add_vertex(std::make_shared<const ZXBox>(inner));
+    diag.add_wire(
+        b.at(0), box, ZXWireType::Basic, QuantumType::Quantum, std::nullopt,
+        0);
+    diag.add_wire(box, z, ZXWireType::Basic, QuantumType::Quantum, 1);
+    diag.add_wire(z, py, ZXWireType::H);
+    diag.add_wire(z, b.at(1));
+    diag.add_wire(py, m);
+    diag.add_wire(m, b.at(2), ZXWireType::Basic, QuantumType::Classical);
+    diag.multiply_scalar(0.5);
+    REQUIRE_NOTHROW(diag.check_validity());
+
+    nlohmann::json j = diag;
+    REQUIRE(j.at("vertices").size() == 7);
+    REQUIRE(j.at("wires").size() == 6);
+    ZXDiagram loaded = j.get<ZXDiagram>();
+    REQUIRE_NOTHROW(loaded.check_validity());
+    CHECK(loaded.n_vertices() == diag.n_vertices());
+    CHECK(loaded.count_wires(ZXWireType::H) == 1);
+    CHECK(loaded.free_symbols() == diag.free_symbols());
+    CHECK(loaded.get_scalar() == diag.get_scalar());
+    ZXVertVec lb = loaded.get_boundary();
+    REQUIRE(lb.size() == 3);
+    CHECK(loaded.get_zxtype(lb.at(0)) == ZXType::Input);
+    CHECK(loaded.get_zxtype(lb.at(2)) == ZXType::Output);
+    CHECK(loaded.get_qtype(lb.at(2)) == QuantumType::Classical);
+    nlohmann::json j2 = loaded;
+    CHECK(j2 == j);
+
+    SymEngine::map_basic_basic sub_map;
+    sub_map[a] = Expr(0.3);
+    diag.symbol_substitution(sub_map);
+    loaded.symbol_substitution(sub_map);
+    CHECK(loaded.is_equivalent_to(diag, ZXDiagram::EquivalenceOption::EXACT));
+  }
+  GIVEN("PyZX .qgraph round trip") {
+    ZXDiagram diag(2, 2, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert z = diag.add_vertex(ZXType::ZSpider, 0.25);
+    ZXVert x = diag.add_vertex(ZXType::XSpider, 1.5);
+    ZXVert h = diag.add_vertex(ZXType::Hbox);
+    ZXVert leaf = diag.add_vertex(ZXType::ZSpider, -0.5);
+    diag.add_wire(ins.at(0), z);
+    diag.add_wire(z, outs.at(0));
+    diag.add_wire(ins.at(1), x);
+    diag.add_wire(x, outs.at(1));
+    diag.add_wire(z, x, ZXWireType::H);
+    diag.add_wire(h, z);
+    diag.add_wire(h, x);
+    diag.add_wire(h, leaf);
+    std::string qgraph = diag.to_qgraph_str();
+    ZXDiagram loaded = ZXDiagram::from_qgraph_str(qgraph);
+    CHECK(loaded.n_vertices() == diag.n_vertices());
+    CHECK(loaded.count_wires(ZXWireType::H) == 1);
+    CHECK(loaded.count_vertices(ZXType::Hbox) == 1);
+    CHECK(loaded.get_boundary(ZXType::Input).size() == 2);
+    CHECK(loaded.get_boundary(ZXType::Output).size() == 2);
+    CHECK(loaded.is_equivalent_to(diag, ZXDiagram::EquivalenceOption::EXACT));
+  }
+  GIVEN("A diagram written by PyZX") {
+    const std::string qgraph = R"({
+      "wire_vertices": {
+        "b0": {"annotation": {"boundary": true, "coord": [0, 0],
+                              "input": true, "output": false}},
+        "b1": {"annotation": {"boundary": true, "coord": [3, 0],
+                              "input": false, "output": true}}
+      },
+      "node_vertices": {
+        "v0": {"annotation": {"coord": [1, 0]},
+               "data": {"type": "Z", "value": "\\pi/2"}},
+        "v1": {"annotation": {"coord": [2, 0]},
+               "data": {"type": "X", "value": "-\\pi"}},
+        "v2": {"annotation": {"coord": [1.5, 0]},
+               "data": {"type": "hadamard", "is_edge": "true"}}
+      },
+      "undir_edges": {
+        "e0": {"src": "b0", "tgt": "v0"},
+        "e1": {"src": "v0", "tgt": "v2"},
+        "e2": {"src": "v2", "tgt": "v1"},
+        "e3": {"src": "v1", "tgt": "b1"}
+      }
+    })";
+    ZXDiagram diag = ZXDiagram::from_qgraph_str(qgraph);
+    CHECK(diag.n_vertices() == 4);
+    CHECK(diag.count_wires(ZXWireType::H) == 1);
+    ZXVertVec b = diag.get_boundary();
+    REQUIRE(b.size() == 2);
+    CHECK(diag.get_zxtype(b.at(0)) == ZXType::Input);
+    CHECK(diag.get_zxtype(b.at(1)) == ZXType::Output);
+    ZXVert z = diag.neighbours(b.at(0)).at(0);
+    ZXVert x = diag.neighbours(b.at(1)).at(0);
+    CHECK(
+        diag.get_vertex_ZXGen<PhasedGen>(z) ==
+        PhasedGen(ZXType::ZSpider, 0.5, QuantumType::Quantum));
+    CHECK(
+        diag.get_vertex_ZXGen<PhasedGen>(x) ==
+        PhasedGen(ZXType::XSpider, -1., QuantumType::Quantum));
+  }
+  GIVEN("Unsupported generators for .qgraph") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVert xy = diag.add_vertex(ZXType::XY, 0.3);
+    diag.add_wire(diag.get_boundary().at(0), xy);
+    diag.add_wire(xy, diag.get_boundary().at(1));
+    REQUIRE_THROWS_AS(diag.to_qgraph_str(), ZXError);
+  }
+}
+
 }  // namespace test_ZXDiagram
 }  // namespace zx
 }  // namespace tket
@@ -161,4 +161,62 @@ SCENARIO("Extracting a circuit from XY/YZ diagram with gflow") {
   CHECK(c.n_qubits() == 5);
 }
 
+SCENARIO("Extracting a circuit from a diagram with gflow in all planes") {
+  GIVEN("XZ and YZ measurements") {
+    // d is a phase gadget on a, and c is corrected by the output vertex
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a = diag.add_vertex(ZXType::XY, 0.3);
+    ZXVert c = diag.add_vertex(ZXType::XZ, 1.4);
+    ZXVert d = diag.add_vertex(ZXType::YZ, 0.9);
+    ZXVert o = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a);
+    diag.add_wire(a, c, ZXWireType::H);
+    diag.add_wire(a, d, ZXWireType::H);
+    diag.add_wire(a, o, ZXWireType::H);
+    diag.add_wire(c, o, ZXWireType::H);
+    diag.add_wire(o, outs.at(0));
+    REQUIRE_NOTHROW(Flow::identify_gflow(diag));
+    Circuit circ = zx_to_circuit(diag);
+    REQUIRE_NOTHROW(circ.assert_valid());
+    CHECK(circ.n_qubits() == 1);
+  }
+  GIVEN("A Z measurement on a phase gadget") {
+    ZXDiagram diag(2, 2, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a0 = diag.add_vertex(ZXType::XY, 0.25);
+    ZXVert a1 = diag.add_vertex(ZXType::XY);
+    ZXVert z = diag.add_vertex(ZXGen::create_gen(ZXType::PZ, true));
+    ZXVert o0 = diag.add_vertex(ZXType::PX);
+    ZXVert o1 = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a0);
+    diag.add_wire(ins.at(1), a1);
+    diag.add_wire(a0, o0, ZXWireType::H);
+    diag.add_wire(a1, o1, ZXWireType::H);
+    diag.add_wire(z, a0, ZXWireType::H);
+    diag.add_wire(z, a1, ZXWireType::H);
+    diag.add_wire(o0, outs.at(0));
+    diag.add_wire(o1, outs.at(1));
+    REQUIRE_NOTHROW(Flow::identify_gflow(diag));
+    Circuit circ = zx_to_circuit(diag);
+    REQUIRE_NOTHROW(circ.assert_valid());
+    CHECK(circ.n_qubits() == 2);
+  }
+  GIVEN("No gflow") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert a = diag.add_vertex(ZXType::XY, 0.3);
+    ZXVert b = diag.add_vertex(ZXType::XY, 0.7);
+    ZXVert o = diag.add_vertex(ZXType::PX);
+    diag.add_wire(ins.at(0), a);
+    diag.add_wire(a, b, ZXWireType::H);
+    diag.add_wire(a, o, ZXWireType::H);
+    diag.add_wire(o, outs.at(0));
+    REQUIRE_THROWS_AS(zx_to_circuit(diag), ZXError);
+  }
+}
+
 }  // namespace tket::zx::test_ZXExtraction
@@ -0,0 +1,205 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "../testutil.hpp"
+#include "Converters/Converters.hpp"
+#include "Predicates/CompilationUnit.hpp"
+#include "Predicates/PassGenerators.hpp"
+#include "ZX/Rewrite.hpp"
+
+namespace tket {
+namespace zx {
+namespace test_ZXOptimisation {
+
+// Number of rotations in the circuit by non-Clifford angles
+static unsigned count_non_clifford(const Circuit& circ) {
+  unsigned count = 0;
+  for (const Command& com : circ) {
+    Op_ptr op = com.get_op_ptr();
+    for (const Expr& e : op->get_params()) {
+      if (!equiv_Clifford(e)) ++count;
+    }
+  }
+  return count;
+}
+
+SCENARIO("Moving interior non-Clifford phases onto gadgets") {
+  ZXDiagram diag(2, 2, 0, 0);
+  ZXVertVec ins = diag.get_boundary(ZXType::Input);
+  ZXVertVec outs = diag.get_boundary(ZXType::Output);
+  ZXVert a0 = diag.add_vertex(ZXType::ZSpider);
+  ZXVert a1 = diag.add_vertex(ZXType::ZSpider);
+  ZXVert u = diag.add_vertex(ZXType::ZSpider, 1.);
+  ZXVert v = diag.add_vertex(ZXType::ZSpider, 0.25);
+  diag.add_wire(ins.at(0), a0);
+  diag.add_wire(a0, outs.at(0));
+  diag.add_wire(ins.at(1), a1);
+  diag.add_wire(a1, outs.at(1));
+  diag.add_wire(u, v, ZXWireType::H);
+  diag.add_wire(u, a0, ZXWireType::H);
+  diag.add_wire(u, a1, ZXWireType::H);
+  diag.add_wire(v, a0, ZXWireType::H);
+  REQUIRE(diag.is_graphlike());
+  // u is an interior Pauli spider, but v is not Pauli
+  CHECK_FALSE(Rewrite::remove_interior_paulis().apply(diag));
+  CHECK(Rewrite::gadgetise_interior_paulis().apply(diag));
+  REQUIRE_NOTHROW(diag.check_validity());
+  CHECK(diag.is_graphlike());
+  // u and v are replaced by the hub and leaf of a gadget on a0 and a1
+  CHECK(diag.count_vertices(ZXType::ZSpider) == 4);
+  CHECK(diag.degree(a0) == 4);
+  CHECK(diag.degree(a1) == 4);
+  CHECK_FALSE(Rewrite::gadgetise_interior_paulis().apply(diag));
+  CHECK_FALSE(Rewrite::merge_gadgets().apply(diag));
+}
+
+SCENARIO("Merging phase gadgets on the same spiders") {
+  ZXDiagram diag(2, 2, 0, 0);
+  ZXVertVec ins = diag.get_boundary(ZXType::Input);
+  ZXVertVec outs = diag.get_boundary(ZXType::Output);
+  ZXVert a0 = diag.add_vertex(ZXType::ZSpider);
+  ZXVert a1 = diag.add_vertex(ZXType::ZSpider);
+  ZXVert h0 = diag.add_vertex(ZXType::ZSpider);
+  ZXVert l0 = diag.add_vertex(ZXType::ZSpider, 0.25);
+  ZXVert h1 = diag.add_vertex(ZXType::ZSpider, 1.);
+  ZXVert l1 = diag.add_vertex(ZXType::ZSpider, 0.5);
+  diag.add_wire(ins.at(0), a0);
+  diag.add_wire(a0, outs.at(0));
+  diag.add_wire(ins.at(1), a1);
+  diag.add_wire(a1, outs.at(1));
+  for (const ZXVert& hub : {h0, h1}) {
+    diag.add_wire(hub, a0, ZXWireType::H);
+    diag.add_wire(hub, a1, ZXWireType::H);
+  }
+  diag.add_wire(h0, l0, ZXWireType::H);
+  diag.add_wire(h1, l1, ZXWireType::H);
+  REQUIRE(diag.is_graphlike());
+  CHECK(Rewrite::merge_gadgets().apply(diag));
+  REQUIRE_NOTHROW(diag.check_validity());
+  CHECK(diag.n_vertices() == 8);
+  CHECK(diag.degree(a0) == 3);
+  // The pi on the second hub negates its leaf
+  const PhasedGen& hub = diag.get_vertex_ZXGen<PhasedGen>(h0);
+  const PhasedGen& leaf = diag.get_vertex_ZXGen<PhasedGen>(l0);
+  CHECK(test_equiv_0(hub.get_param()));
+  CHECK(test_equiv_val(leaf.get_param(), 1.75));
+  CHECK_FALSE(Rewrite::merge_gadgets().apply(diag));
+}
+
+SCENARIO("Turning phase gadgets into YZ vertices") {
+  ZXDiagram diag(1, 1, 0, 0);
+  ZXVertVec ins = diag.get_boundary(ZXType::Input);
+  ZXVertVec outs = diag.get_boundary(ZXType::Output);
+  ZXVert a = diag.add_vertex(ZXType::XY, 0.3);
+  ZXVert o = diag.add_vertex(ZXType::PX);
+  ZXVert hub = diag.add_vertex(ZXType::XY);
+  diag.add_wire(ins.at(0), a);
+  diag.add_wire(a, o, ZXWireType::H);
+  diag.add_wire(o, outs.at(0));
+  diag.add_wire(hub, a, ZXWireType::H);
+  diag.add_wire(hub, o, ZXWireType::H);
+  diag.add_wire(hub, diag.add_vertex(ZXType::XY, 0.25), ZXWireType::H);
+  REQUIRE(diag.is_MBQC());
+  CHECK(Rewrite::internalise_gadgets().apply(diag));
+  REQUIRE_NOTHROW(diag.check_validity());
+  CHECK(diag.n_vertices() == 5);
+  CHECK(diag.get_zxtype(hub) == ZXType::YZ);
+  const PhasedGen& yz = diag.get_vertex_ZXGen<PhasedGen>(hub);
+  CHECK(test_equiv_val(yz.get_param(), -0.25));
+  CHECK_FALSE(Rewrite::internalise_gadgets().apply(diag));
+  Circuit circ = zx_to_circuit(diag);
+  REQUIRE_NOTHROW(circ.assert_valid());
+  CHECK(circ.n_qubits() == 1);
+}
+
+SCENARIO("Optimising circuits via ZX diagrams") {
+  GIVEN("Phase gadgets on the same parity") {
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::H, {2});
+    circ.add_op<unsigned>(OpType::CZ, {1, 2});
+    circ.add_op<unsigned>(OpType::Rx, 0.5, {2});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::H, {1});
+    CompilationUnit cu(circ);
+    REQUIRE(ZXGraphlikeOptimisation()->apply(cu));
+    const Circuit& result = cu.get_circ_ref();
+    CHECK(count_non_clifford(result) < count_non_clifford(circ));
+    CHECK(test_unitary_comparison(circ, result, true));
+  }
+  GIVEN("Non-Clifford rotations and a permutation") {
+    Circuit circ(4);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::Rz, 0.1, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    circ.add_op<unsigned>(OpType::Rx, 0.75, {2});
+    circ.add_op<unsigned>(OpType::SWAP, {1, 3});
+    circ.add_op<unsigned>(OpType::CZ, {2, 3});
+    circ.add_op<unsigned>(OpType::Rz, 1.25, {3});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+    circ.add_op<unsigned>(OpType::X, {1});
+    circ.add_op<unsigned>(OpType::CX, {3, 0});
+    circ.add_op<unsigned>(OpType::Z, {2});
+    CompilationUnit cu(circ);
+    REQUIRE(ZXGraphlikeOptimisation()->apply(cu));
+    const Circuit& result = cu.get_circ_ref();
+    CHECK(result.all_qubits() == circ.all_qubits());
+    CHECK(count_non_clifford(result) < count_non_clifford(circ));
+    CHECK(test_unitary_comparison(circ, result, true));
+  }
+  GIVEN("A circuit which is not improved") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    CompilationUnit cu(circ);
+    REQUIRE_FALSE(ZXGraphlikeOptimisation()->apply(cu));
+    CHECK(cu.get_circ_ref() == circ);
+  }
+  GIVEN("Serialisation") {
+    PassPtr pp = ZXGraphlikeOptimisation();
+    nlohmann::json j = pp;
+    REQUIRE(j["StandardPass"]["name"] == "ZXGraphlikeOptimisation");
+    Circuit circ(2);
+    for (unsigned i = 0; i < 2; ++i) {
+      circ.add_op<unsigned>(OpType::CX, {0, 1});
+      circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+      circ.add_op<unsigned>(OpType::CX, {0, 1});
+    }
+    CompilationUnit cu(circ);
+    CompilationUnit copy(circ);
+    REQUIRE(pp->apply(cu));
+    REQUIRE(j.get<PassPtr>()->apply(copy));
+    CHECK(cu.get_circ_ref() == copy.get_circ_ref());
+  }
+  GIVEN("An unsupported gate") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::CRz, 0.3, {0, 1});
+    CompilationUnit cu(circ);
+    REQUIRE_THROWS_AS(
+        ZXGraphlikeOptimisation()->apply(cu), UnsatisfiedPredicate);
+  }
+}
+
+}  // namespace test_ZXOptimisation
+}  // namespace zx
+}  // namespace tket
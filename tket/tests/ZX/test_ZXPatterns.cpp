@@ -0,0 +1,174 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "../testutil.hpp"
+#include "ZX/Rewrite.hpp"
+
+namespace tket {
+namespace zx {
+namespace test_ZXPatterns {
+
+// A chain of spiders from the input to the output of a single wire
+static ZXDiagram spider_chain(
+    const std::vector<std::pair<ZXType, Expr>>& spiders,
+    ZXWireType type = ZXWireType::Basic) {
+  ZXDiagram diag(1, 1, 0, 0);
+  ZXVert prev = diag.get_boundary(ZXType::Input).at(0);
+  for (const auto& [t, param] : spiders) {
+    ZXVert v = diag.add_vertex(t, param);
+    diag.add_wire(prev, v, type);
+    prev = v;
+  }
+  diag.add_wire(prev, diag.get_boundary(ZXType::Output).at(0), type);
+  return diag;
+}
+
+SCENARIO("Rewriting with user-defined rules") {
+  Sym a = SymEngine::symbol("a");
+  Sym b = SymEngine::symbol("b");
+  GIVEN("Fusion of spiders on a wire") {
+    ZXDiagram lhs = spider_chain(
+        {{ZXType::ZSpider, Expr(a)}, {ZXType::ZSpider, Expr(b)}});
+    ZXDiagram rhs = spider_chain({{ZXType::ZSpider, Expr(a) + Expr(b)}});
+    Rewrite fusion = Rewrite::from_rule(lhs, rhs);
+    ZXDiagram diag =
+        spider_chain({{ZXType::ZSpider, 0.25}, {ZXType::ZSpider, 0.5}});
+    ZXDiagram original(diag);
+    REQUIRE(fusion.apply(diag));
+    CHECK(diag.n_vertices() == 3);
+    CHECK(diag.is_equivalent_to(
+        original, ZXDiagram::EquivalenceOption::EXACT));
+    CHECK_FALSE(fusion.apply(diag));
+    THEN("Repeating the rule fuses a longer chain") {
+      ZXDiagram chain = spider_chain(
+          {{ZXType::ZSpider, 0.1},
+           {ZXType::ZSpider, 0.2},
+           {ZXType::ZSpider, 0.3},
+           {ZXType::ZSpider, 0.4}});
+      ZXDiagram chain_original(chain);
+      REQUIRE(Rewrite::repeat(fusion).apply(chain));
+      CHECK(chain.n_vertices() == 3);
+      CHECK(chain.is_equivalent_to(
+          chain_original, ZXDiagram::EquivalenceOption::EXACT));
+    }
+    THEN("Spiders of the wrong colour or arity are not matched") {
+      ZXDiagram mixed =
+          spider_chain({{ZXType::ZSpider, 0.25}, {ZXType::XSpider, 0.5}});
+      CHECK_FALSE(fusion.apply(mixed));
+      ZXDiagram branched(1, 2, 0, 0);
+      ZXVertVec bounds = branched.get_boundary();
+      ZXVert z0 = branched.add_vertex(ZXType::ZSpider, 0.25);
+      ZXVert z1 = branched.add_vertex(ZXType::ZSpider, 0.5);
+      branched.add_wire(bounds.at(0), z0);
+      branched.add_wire(z0, z1);
+      branched.add_wire(z1, bounds.at(1));
+      branched.add_wire(z0, bounds.at(2));
+      CHECK_FALSE(fusion.apply(branched));
+    }
+  }
+  GIVEN("A variable used twice") {
+    ZXDiagram lhs = spider_chain(
+        {{ZXType::ZSpider, Expr(a)}, {ZXType::ZSpider, Expr(a)}});
+    ZXDiagram rhs = spider_chain({{ZXType::ZSpider, 2 * Expr(a)}});
+    Rewrite doubling = Rewrite::from_rule(lhs, rhs);
+    ZXDiagram diag =
+        spider_chain({{ZXType::ZSpider, 0.25}, {ZXType::ZSpider, 0.5}});
+    CHECK_FALSE(doubling.apply(diag));
+    // Phases are compared modulo 2
+    diag = spider_chain({{ZXType::ZSpider, 0.25}, {ZXType::ZSpider, 2.25}});
+    ZXDiagram original(diag);
+    REQUIRE(doubling.apply(diag));
+    CHECK(diag.is_equivalent_to(
+        original, ZXDiagram::EquivalenceOption::EXACT));
+  }
+  GIVEN("Hadamard wires on the boundary of a rule") {
+    // Colour change: an X spider is a Z spider with Hadamards on its legs
+    ZXDiagram lhs = spider_chain({{ZXType::XSpider, Expr(a)}});
+    ZXDiagram rhs =
+        spider_chain({{ZXType::ZSpider, Expr(a)}}, ZXWireType::H);
+    Rewrite colour_change = Rewrite::from_rule(lhs, rhs);
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVert x = diag.add_vertex(ZXType::XSpider, 0.3);
+    diag.add_wire(diag.get_boundary().at(0), x, ZXWireType::H);
+    diag.add_wire(x, diag.get_boundary().at(1));
+    ZXDiagram original(diag);
+    REQUIRE(colour_change.apply(diag));
+    CHECK(diag.count_vertices(ZXType::XSpider) == 0);
+    CHECK(diag.count_vertices(ZXType::ZSpider) == 1);
+    CHECK(diag.count_wires(ZXWireType::H) == 1);
+    CHECK(diag.is_equivalent_to(
+        original, ZXDiagram::EquivalenceOption::EXACT));
+  }
+  GIVEN("Rules changing the number of vertices and the scalar") {
+    // Identity removal leaves a plain wire between the boundaries
+    ZXDiagram lhs = spider_chain({{ZXType::ZSpider, 0.}});
+    ZXDiagram rhs(1, 1, 0, 0);
+    rhs.add_wire(rhs.get_boundary().at(0), rhs.get_boundary().at(1));
+    Rewrite identity = Rewrite::from_rule(lhs, rhs);
+    ZXDiagram diag = spider_chain(
+        {{ZXType::ZSpider, 0.3},
+         {ZXType::ZSpider, 0.},
+         {ZXType::XSpider, 0.2}});
+    ZXDiagram original(diag);
+    REQUIRE(identity.apply(diag));
+    CHECK(diag.n_vertices() == 4);
+    CHECK(diag.is_equivalent_to(
+        original, ZXDiagram::EquivalenceOption::EXACT));
+
+    // A disconnected Z spider with no legs evaluates to 2, i.e. 4 in the
+    // doubled semantics of the scalar
+    ZXDiagram spider(0, 0, 0, 0);
+    spider.add_vertex(ZXType::ZSpider);
+    ZXDiagram value(0, 0, 0, 0);
+    value.multiply_scalar(4.);
+    Rewrite evaluate = Rewrite::from_rule(spider, value);
+    ZXDiagram with_scalar(original);
+    with_scalar.add_vertex(ZXType::ZSpider);
+    ZXDiagram unevaluated(with_scalar);
+    REQUIRE(evaluate.apply(with_scalar));
+    CHECK(with_scalar.n_vertices() == original.n_vertices());
+    CHECK(with_scalar.is_equivalent_to(
+        unevaluated, ZXDiagram::EquivalenceOption::EXACT));
+    CHECK_FALSE(with_scalar.is_equivalent_to(
+        original, ZXDiagram::EquivalenceOption::UP_TO_GLOBAL_PHASE));
+  }
+  GIVEN("Invalid rules") {
+    ZXDiagram one_spider = spider_chain({{ZXType::ZSpider, Expr(a)}});
+    ZXDiagram no_legs(0, 0, 0, 0);
+    no_legs.add_vertex(ZXType::ZSpider);
+    // Mismatched boundaries
+    REQUIRE_THROWS_AS(Rewrite::from_rule(one_spider, no_legs), ZXError);
+    // Unbound variables
+    ZXDiagram unbound = spider_chain({{ZXType::ZSpider, Expr(b)}});
+    REQUIRE_THROWS_AS(Rewrite::from_rule(one_spider, unbound), ZXError);
+    // Parameters which are not a single variable
+    ZXDiagram shifted = spider_chain({{ZXType::ZSpider, Expr(a) + 1}});
+    REQUIRE_THROWS_AS(Rewrite::from_rule(shifted, one_spider), ZXError);
+    // Boundaries connected directly
+    ZXDiagram wire(1, 1, 0, 0);
+    wire.add_wire(wire.get_boundary().at(0), wire.get_boundary().at(1));
+    REQUIRE_THROWS_AS(Rewrite::from_rule(wire, wire), ZXError);
+    // Disconnected interior
+    ZXDiagram two_spiders(0, 0, 0, 0);
+    two_spiders.add_vertex(ZXType::ZSpider);
+    two_spiders.add_vertex(ZXType::ZSpider);
+    REQUIRE_THROWS_AS(Rewrite::from_rule(two_spiders, no_legs), ZXError);
+  }
+}
+
+}  // namespace test_ZXPatterns
+}  // namespace zx
+}  // namespace tket
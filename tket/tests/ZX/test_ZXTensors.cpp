@@ -0,0 +1,203 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "../testutil.hpp"
+#include "Converters/Converters.hpp"
+#include "Simulation/CircuitSimulator.hpp"
+#include "ZX/Rewrite.hpp"
+
+namespace tket {
+namespace zx {
+namespace test_ZXTensors {
+
+SCENARIO("Evaluating known tensors") {
+  GIVEN("A single wire") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVertVec bounds = diag.get_boundary();
+    Wire w = diag.add_wire(bounds.at(0), bounds.at(1));
+    CHECK(diag.to_matrix().isApprox(Eigen::Matrix2cd::Identity(), ERR_EPS));
+    diag.set_wire_type(w, ZXWireType::H);
+    diag.multiply_scalar(0.5);
+    Eigen::Matrix2cd correct;
+    correct << 1, 1, 1, -1;
+    correct *= std::sqrt(0.5);
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+  }
+  GIVEN("A pair of wires to test endianness") {
+    ZXDiagram diag(2, 2, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    diag.add_wire(ins.at(0), outs.at(0));
+    diag.add_wire(ins.at(1), outs.at(1), ZXWireType::H);
+    Eigen::Matrix4cd correct;
+    correct << 1, 1, 0, 0, 1, -1, 0, 0, 0, 0, 1, 1, 0, 0, 1, -1;
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+  }
+  GIVEN("A Bell effect") {
+    ZXDiagram diag(2, 0, 0, 0);
+    ZXVertVec ins = diag.get_boundary();
+    diag.add_wire(ins.at(0), ins.at(1));
+    Eigen::Vector4cd correct(1, 0, 0, 1);
+    CHECK(diag.to_tensor().isApprox(correct, ERR_EPS));
+    CHECK(diag.to_matrix().isApprox(correct.transpose(), ERR_EPS));
+  }
+  GIVEN("A Z spider with self-loops") {
+    ZXDiagram diag(2, 3, 0, 0);
+    ZXVert spid = diag.add_vertex(ZXType::ZSpider, 0.3);
+    for (const ZXVert& b : diag.get_boundary()) diag.add_wire(spid, b);
+    Eigen::MatrixXcd correct = Eigen::MatrixXcd::Zero(8, 4);
+    correct(0, 0) = 1.;
+    correct(7, 3) = std::exp(i_ * PI * 0.3);
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+    Wire loop = diag.add_wire(spid, spid);
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+    diag.set_wire_type(loop, ZXWireType::H);
+    correct(7, 3) = std::exp(i_ * PI * 1.3);
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+  }
+  GIVEN("A CX from spiders") {
+    ZXDiagram diag(2, 2, 0, 0);
+    ZXVertVec ins = diag.get_boundary(ZXType::Input);
+    ZXVertVec outs = diag.get_boundary(ZXType::Output);
+    ZXVert z = diag.add_vertex(ZXType::ZSpider);
+    ZXVert x = diag.add_vertex(ZXType::XSpider);
+    diag.add_wire(ins.at(0), z);
+    diag.add_wire(z, outs.at(0));
+    diag.add_wire(ins.at(1), x);
+    diag.add_wire(x, outs.at(1));
+    diag.add_wire(z, x);
+    diag.multiply_scalar(2.);
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    CHECK(diag.to_matrix().isApprox(tket_sim::get_unitary(circ), ERR_EPS));
+  }
+  GIVEN("MBQC vertices") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVert xy = diag.add_vertex(ZXType::XY, 0.3);
+    diag.add_wire(diag.get_boundary().at(0), xy);
+    diag.add_wire(xy, diag.get_boundary().at(1));
+    Eigen::Matrix2cd correct = Eigen::Matrix2cd::Zero();
+    correct(0, 0) = 1.;
+    correct(1, 1) = std::exp(-i_ * PI * 0.3);
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+  }
+  GIVEN("A symbolic parameter") {
+    ZXDiagram diag(1, 1, 0, 0);
+    Sym a = SymEngine::symbol("a");
+    ZXVert z = diag.add_vertex(ZXType::ZSpider, Expr(a));
+    diag.add_wire(diag.get_boundary().at(0), z);
+    diag.add_wire(z, diag.get_boundary().at(1));
+    REQUIRE_THROWS_AS(diag.to_tensor(), ZXError);
+  }
+}
+
+SCENARIO("Evaluating diagrams with classical components") {
+  GIVEN("A classical NOT") {
+    ZXDiagram diag(0, 0, 1, 1);
+    ZXVertVec bounds = diag.get_boundary();
+    ZXVert x = diag.add_vertex(ZXType::XSpider, 1., QuantumType::Classical);
+    diag.add_wire(bounds.at(0), x, ZXWireType::Basic, QuantumType::Classical);
+    diag.add_wire(x, bounds.at(1), ZXWireType::Basic, QuantumType::Classical);
+    Eigen::Matrix2cd correct;
+    correct << 0, 1, 1, 0;
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+  }
+  GIVEN("A measurement") {
+    ZXDiagram diag(1, 0, 0, 1);
+    ZXVertVec bounds = diag.get_boundary();
+    ZXVert z = diag.add_vertex(ZXType::ZSpider, QuantumType::Classical);
+    diag.add_wire(bounds.at(0), z);
+    diag.add_wire(z, bounds.at(1), ZXWireType::Basic, QuantumType::Classical);
+    REQUIRE(diag.to_doubled_diagram().get_boundary().size() == 3);
+    // Columns are indexed by the input and its conjugate
+    Eigen::MatrixXcd correct = Eigen::MatrixXcd::Zero(2, 4);
+    correct(0, 0) = 1.;
+    correct(1, 3) = 1.;
+    CHECK(diag.to_matrix().isApprox(correct, ERR_EPS));
+    // The scalar applies to the doubled diagram directly
+    diag.multiply_scalar(0.5);
+    CHECK(diag.to_matrix().isApprox(0.5 * correct, ERR_EPS));
+  }
+}
+
+SCENARIO("Checking equivalence of diagrams") {
+  GIVEN("Scalars and global phases") {
+    ZXDiagram diag(1, 1, 0, 0);
+    ZXVert z = diag.add_vertex(ZXType::ZSpider, 0.25);
+    diag.add_wire(diag.get_boundary().at(0), z);
+    diag.add_wire(z, diag.get_boundary().at(1));
+    ZXDiagram phased(diag);
+    // The scalar is for the doubled semantics, so this is a phase of i
+    phased.multiply_scalar(-1.);
+    CHECK(diag.is_equivalent_to(
+        phased, ZXDiagram::EquivalenceOption::UP_TO_GLOBAL_PHASE));
+    CHECK_FALSE(
+        diag.is_equivalent_to(phased, ZXDiagram::EquivalenceOption::EXACT));
+    ZXDiagram scaled(diag);
+    scaled.multiply_scalar(4.);
+    CHECK(diag.is_equivalent_to(scaled));
+    CHECK_FALSE(diag.is_equivalent_to(
+        scaled, ZXDiagram::EquivalenceOption::UP_TO_GLOBAL_PHASE));
+    ZXDiagram zero(diag);
+    zero.multiply_scalar(0.);
+    CHECK_FALSE(diag.is_equivalent_to(zero));
+    CHECK(zero.is_equivalent_to(zero));
+  }
+  GIVEN("Mismatched boundaries") {
+    ZXDiagram diag(1, 1, 0, 0);
+    diag.add_wire(diag.get_boundary().at(0), diag.get_boundary().at(1));
+    ZXDiagram open(0, 0, 0, 0);
+    ZXVert o0 = open.add_vertex(ZXType::Open);
+    ZXVert o1 = open.add_vertex(ZXType::Open);
+    open.add_wire(o0, o1);
+    CHECK(open.to_matrix().rows() == 4);
+    CHECK_FALSE(diag.is_equivalent_to(open));
+    ZXDiagram classical(0, 0, 1, 1);
+    classical.add_wire(
+        classical.get_boundary().at(0), classical.get_boundary().at(1),
+        ZXWireType::Basic, QuantumType::Classical);
+    CHECK_FALSE(diag.is_equivalent_to(classical));
+  }
+  GIVEN("Diagrams from circuits") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::Rz, 0.25, {1});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::Rx, 0.3, {0});
+    ZXDiagram diag = circuit_to_zx(circ).first;
+    Circuit other(circ);
+    other.add_op<unsigned>(OpType::Rz, 0.1, {1});
+    ZXDiagram other_diag = circuit_to_zx(other).first;
+    CHECK_FALSE(diag.is_equivalent_to(other_diag));
+    WHEN("Simplifying to graphlike form") {
+      ZXDiagram simplified(diag);
+      Rewrite::to_graphlike_form().apply(simplified);
+      Rewrite::reduce_graphlike_form().apply(simplified);
+      REQUIRE(simplified.is_graphlike());
+      CHECK(simplified.is_equivalent_to(diag));
+      THEN("Converting to MBQC") {
+        Rewrite::to_MBQC_diag().apply(simplified);
+        REQUIRE(simplified.is_MBQC());
+        CHECK(simplified.is_equivalent_to(diag));
+      }
+    }
+  }
+}
+
+}  // namespace test_ZXTensors
+}  // namespace zx
+}  // namespace tket
@@ -125,6 +125,31 @@ SCENARIO("Diameters") {
   }
 }
 
+SCENARIO("Connections with native gate sets") {
+  Architecture arc(
+      {{Node(0), Node(1)}, {Node(2), Node(1)}, {Node(2), Node(3)}});
+  arc.set_gate_set(Node(1), Node(0), {OpType::CZ});
+  arc.set_gate_set(Node(1), Node(2), {OpType::ECR, OpType::ISWAPMax});
+  REQUIRE(arc.get_gate_set(Node(0), Node(1)) == OpTypeSet{OpType::CZ});
+  REQUIRE(arc.get_gate_set(Node(2), Node(1))->size() == 2);
+  REQUIRE_FALSE(arc.get_gate_set(Node(2), Node(3)));
+  REQUIRE(arc.get_gate_sets().size() == 2);
+  REQUIRE_THROWS_AS(
+      arc.set_gate_set(Node(0), Node(3), {OpType::CX}), ArchitectureInvalidity);
+  GIVEN("A sub-architecture") {
+    Architecture subarc = arc.create_subarch({Node(0), Node(1), Node(3)});
+    REQUIRE(subarc.get_gate_sets().size() == 1);
+    REQUIRE(subarc.get_gate_set(Node(0), Node(1)));
+  }
+  GIVEN("Serialisation") {
+    nlohmann::json j = arc;
+    Architecture loaded = j.get<Architecture>();
+    REQUIRE(loaded.get_gate_sets() == arc.get_gate_sets());
+    nlohmann::json j_plain = Architecture({{Node(0), Node(1)}});
+    REQUIRE_FALSE(j_plain.contains("gate_sets"));
+  }
+}
+
 SCENARIO("connectivity") {
   GIVEN("simple architecture") {
     const Architecture archi(
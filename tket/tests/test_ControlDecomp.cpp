@@ -16,6 +16,7 @@
 #include <catch2/catch_test_macros.hpp>
 #include <numeric>
 
+#include "Circuit/Boxes.hpp"
 #include "Circuit/CircPool.hpp"
 #include "Gate/GateUnitaryMatrix.hpp"
 #include "Simulation/CircuitSimulator.hpp"
@@ -581,5 +582,227 @@ SCENARIO("Test a CnX is decomposed correctly using the Gray code method") {
   }
 }
 
+// Whether two unitaries agree on the basis states where the given qubits are
+// in the |0> state, optionally up to a phase for each basis state
+static bool agree_on_clean_ancillas(
+    const Eigen::MatrixXcd& u, const Eigen::MatrixXcd& v, unsigned n_qubits,
+    const std::vector<unsigned>& ancillas, bool up_to_phase = false) {
+  const unsigned dim = u.cols();
+  for (unsigned k = 0; k < dim; ++k) {
+    bool clean = true;
+    for (unsigned a : ancillas) {
+      if ((k >> (n_qubits - 1 - a)) & 1) clean = false;
+    }
+    if (!clean) continue;
+    if (up_to_phase) {
+      if (std::abs(std::abs(u.col(k).dot(v.col(k))) - 1) > ERR_EPS) {
+        return false;
+      }
+    } else if (!u.col(k).isApprox(v.col(k), ERR_EPS)) {
+      return false;
+    }
+  }
+  return true;
+}
+
+SCENARIO("Test a CnX is decomposed correctly using an ancilla ladder") {
+  GIVEN("Clean ancillas") {
+    for (unsigned n = 3; n < 6; ++n) {
+      Circuit circ = Transforms::cnx_ancilla_decomp(n, true);
+      REQUIRE(circ.n_qubits() == 2 * n - 1);
+      CHECK(circ.count_gates(OpType::CX) == 6 * n - 6);
+      Circuit expected(2 * n - 1);
+      std::vector<unsigned> qbs(n);
+      std::iota(qbs.begin(), qbs.end(), 0);
+      qbs.push_back(2 * n - 2);
+      expected.add_op<unsigned>(OpType::CnX, qbs);
+      std::vector<unsigned> ancillas(n - 2);
+      std::iota(ancillas.begin(), ancillas.end(), n);
+      const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+      const Eigen::MatrixXcd v = tket_sim::get_unitary(expected);
+      CHECK(agree_on_clean_ancillas(u, v, 2 * n - 1, ancillas));
+      WHEN("The CnX is implemented up to relative phase") {
+        Circuit rel = Transforms::cnx_ancilla_decomp(n, true, true);
+        CHECK(rel.count_gates(OpType::CX) == 9 * n - 18);
+        const Eigen::MatrixXcd w = tket_sim::get_unitary(rel);
+        CHECK(agree_on_clean_ancillas(w, v, 2 * n - 1, ancillas, true));
+        rel.append(rel);
+        CHECK(agree_on_clean_ancillas(
+            tket_sim::get_unitary(rel),
+            Eigen::MatrixXcd::Identity(u.rows(), u.cols()), 2 * n - 1,
+            ancillas));
+      }
+    }
+  }
+  GIVEN("Borrowed ancillas") {
+    for (unsigned n = 3; n < 6; ++n) {
+      Circuit circ = Transforms::cnx_ancilla_decomp(n, false);
+      CHECK(circ.count_gates(OpType::CX) == 12 * n - 18);
+      Circuit expected(2 * n - 1);
+      std::vector<unsigned> qbs(n);
+      std::iota(qbs.begin(), qbs.end(), 0);
+      qbs.push_back(2 * n - 2);
+      expected.add_op<unsigned>(OpType::CnX, qbs);
+      CHECK(tket_sim::compare_statevectors_or_unitaries(
+          tket_sim::get_unitary(circ), tket_sim::get_unitary(expected)));
+    }
+    REQUIRE_THROWS_AS(
+        Transforms::cnx_ancilla_decomp(3, false, true), Unsupported);
+  }
+}
+
+SCENARIO("Decompose controlled gates using spare qubits as ancillas") {
+  GIVEN("A CnX with a created qubit") {
+    Circuit circ(5);
+    circ.qubit_create(Qubit(3));
+    circ.add_op<unsigned>(OpType::CnX, {0, 1, 2, 4});
+    const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::Clean)
+                .apply(circ));
+    CHECK(circ.count_gates(OpType::CX) == 12);
+    CHECK(circ.is_created(Qubit(3)));
+    CHECK(agree_on_clean_ancillas(tket_sim::get_unitary(circ), u, 5, {3}));
+  }
+  GIVEN("A CnX with idle qubits") {
+    Circuit circ(9);
+    circ.add_op<unsigned>(OpType::H, {5});
+    circ.add_op<unsigned>(OpType::CnX, {0, 1, 2, 3, 4, 8});
+    circ.add_op<unsigned>(OpType::CnRy, 0.3, {6, 7, 5});
+    const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+    Circuit clean_circ = circ;
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::Borrowed)
+                .apply(circ));
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::Clean)
+                .apply(clean_circ));
+    for (const Command& cmd : circ) {
+      OpType type = cmd.get_op_ptr()->get_type();
+      CHECK((type == OpType::CX || type == OpType::H || type == OpType::T ||
+             type == OpType::Tdg || type == OpType::Ry));
+    }
+    CHECK(clean_circ.count_gates(OpType::CX) > circ.count_gates(OpType::CX));
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(circ), u));
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(clean_circ), u));
+  }
+  GIVEN("A compute-uncompute pair of CCX gates") {
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    circ.add_op<unsigned>(OpType::Rz, 0.3, {2});
+    circ.add_op<unsigned>(OpType::CZ, {0, 2});
+    circ.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+    Circuit exact = circ;
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::None, true)
+                .apply(circ));
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::None)
+                .apply(exact));
+    CHECK(circ.count_gates(OpType::CX) == 6);
+    CHECK(exact.count_gates(OpType::CX) == 12);
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(circ), u));
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(exact), u));
+  }
+  GIVEN("A pair of CCX gates separated by a non-diagonal gate") {
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    circ.add_op<unsigned>(OpType::H, {2});
+    circ.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::None, true)
+                .apply(circ));
+    CHECK(circ.count_gates(OpType::CX) == 12);
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(circ), u));
+  }
+  GIVEN("A compute-uncompute pair of CnX gates with a clean ancilla") {
+    Circuit circ(5);
+    circ.qubit_create(Qubit(3));
+    circ.add_op<unsigned>(OpType::CnX, {0, 1, 2, 4});
+    circ.add_op<unsigned>(OpType::CRz, 0.4, {4, 0});
+    circ.add_op<unsigned>(OpType::CnX, {0, 1, 2, 4});
+    const Eigen::MatrixXcd u = tket_sim::get_unitary(circ);
+    REQUIRE(Transforms::decomp_arbitrary_controlled_gates(
+                Transforms::CnXAncillaMode::Clean, true)
+                .apply(circ));
+    CHECK(circ.count_gates(OpType::CX) == 18);
+    CHECK(agree_on_clean_ancillas(tket_sim::get_unitary(circ), u, 5, {3}));
+  }
+}
+
+// A multiplexor of random single-qubit unitaries, leaving out the last
+// control state so that it acts as the identity there. Also return the
+// expected unitary.
+static std::pair<MultiplexorBox, Eigen::MatrixXcd> random_multiplexor(
+    unsigned n_controls, int seed) {
+  const unsigned N = 1u << n_controls;
+  ctrl_op_map_t op_map;
+  Eigen::MatrixXcd U = Eigen::MatrixXcd::Identity(2 * N, 2 * N);
+  for (unsigned j = 0; j + 1 < N; j++) {
+    std::vector<bool> state(n_controls);
+    for (unsigned i = 0; i < n_controls; i++) {
+      state[i] = (j >> (n_controls - 1 - i)) & 1;
+    }
+    Eigen::Matrix2cd u = random_unitary(2, seed + j);
+    op_map.insert({state, std::make_shared<Unitary1qBox>(u)});
+    U.block(2 * j, 2 * j, 2, 2) = u;
+  }
+  return {MultiplexorBox(op_map), U};
+}
+
+SCENARIO("Test multiplexor decomposition") {
+  GIVEN("Multiplexors of single-qubit unitaries") {
+    for (unsigned n_controls = 1; n_controls <= 4; n_controls++) {
+      auto [box, U] = random_multiplexor(n_controls, n_controls);
+      Circuit circ = Transforms::decomposed_multiplexor(box);
+      REQUIRE(circ.n_qubits() == n_controls + 1);
+      for (const Command& cmd : circ) {
+        OpType type = cmd.get_op_ptr()->get_type();
+        CHECK((type == OpType::CX || type == OpType::Ry || type == OpType::Rz));
+      }
+      CHECK(circ.count_gates(OpType::CX) <= (1u << (n_controls + 2)) - 2);
+      CHECK(tket_sim::compare_statevectors_or_unitaries(
+          tket_sim::get_unitary(circ), U));
+    }
+  }
+  GIVEN("A multiplexor with a two-qubit target") {
+    ctrl_op_map_t op_map = {
+        {{true}, get_op_ptr(OpType::CX)}, {{false}, get_op_ptr(OpType::CZ)}};
+    MultiplexorBox box(op_map);
+    Circuit circ = Transforms::decomposed_multiplexor(box);
+    Circuit expected(3);
+    expected.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    expected.add_op<unsigned>(OpType::X, {0});
+    expected.add_op<unsigned>(OpType::H, {2});
+    expected.add_op<unsigned>(OpType::CCX, {0, 1, 2});
+    expected.add_op<unsigned>(OpType::H, {2});
+    expected.add_op<unsigned>(OpType::X, {0});
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(circ), tket_sim::get_unitary(expected)));
+  }
+  GIVEN("A circuit containing multiplexors") {
+    auto [box, U] = random_multiplexor(2, 7);
+    Circuit circ(4);
+    circ.add_box(box, {3, 0, 1});
+    circ.add_box(
+        MultiplexedRotationBox({0.1, 0.2, 0.3, 0.4}, OpType::Rx), {1, 2, 3});
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    const Eigen::MatrixXcd before = tket_sim::get_unitary(circ);
+    REQUIRE(Transforms::decomp_multiplexors().apply(circ));
+    for (const Command& cmd : circ) {
+      CHECK_FALSE(is_box_type(cmd.get_op_ptr()->get_type()));
+    }
+    CHECK(tket_sim::compare_statevectors_or_unitaries(
+        tket_sim::get_unitary(circ), before));
+  }
+}
+
 }  // namespace test_ControlDecomp
 }  // namespace tket
@@ -0,0 +1,146 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+#include <cmath>
+
+#include "Predicates/CompilationUnit.hpp"
+#include "Predicates/PassGenerators.hpp"
+#include "Predicates/Predicates.hpp"
+#include "Transformations/CrosstalkScheduling.hpp"
+#include "testutil.hpp"
+
+namespace tket {
+namespace test_CrosstalkScheduling {
+
+// links (0, 1) and (2, 3) of a line interfere; links (0, 1) and (3, 4) barely
+static DeviceCharacterisation line_characterisation() {
+  crosstalk_errors_t crosstalk{
+      {{{Node(0), Node(1)}, {Node(2), Node(3)}}, 0.05},
+      {{{Node(0), Node(1)}, {Node(3), Node(4)}}, 0.001}};
+  return DeviceCharacterisation(avg_node_errors_t(), {}, {}, crosstalk);
+}
+
+static Circuit on_nodes(unsigned n) {
+  Circuit circ;
+  for (unsigned i = 0; i < n; i++) circ.add_qubit(Node(i));
+  return circ;
+}
+
+SCENARIO("Crosstalk penalties in a DeviceCharacterisation") {
+  DeviceCharacterisation characterisation = line_characterisation();
+  // either order of links, and either direction of each link
+  REQUIRE(
+      characterisation.get_crosstalk_error(
+          {Node(3), Node(2)}, {Node(1), Node(0)}) == 0.05);
+  REQUIRE(
+      characterisation.get_crosstalk_error(
+          {Node(0), Node(1)}, {Node(1), Node(2)}) == 0.);
+  GIVEN("Serialisation") {
+    nlohmann::json j = characterisation;
+    REQUIRE(j.get<DeviceCharacterisation>() == characterisation);
+    // characterisations without crosstalk are serialised as before
+    nlohmann::json j_plain = DeviceCharacterisation();
+    REQUIRE_FALSE(j_plain.contains("crosstalk_errors"));
+    REQUIRE(j_plain.get<DeviceCharacterisation>() == DeviceCharacterisation());
+  }
+}
+
+SCENARIO("Reporting crosstalk") {
+  DeviceCharacterisation characterisation = line_characterisation();
+  Circuit circ = on_nodes(5);
+  circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+  circ.add_op<UnitID>(OpType::CZ, {Node(2), Node(3)});
+  circ.add_op<UnitID>(OpType::CX, {Node(3), Node(4)});
+  CrosstalkReport report = get_crosstalk_report(circ, characterisation, 0.01);
+  REQUIRE(std::abs(report.total_penalty - 0.051) < ERR_EPS);
+  REQUIRE(report.max_penalty == 0.05);
+  REQUIRE(report.n_conflicts == 1);
+  REQUIRE_FALSE(CrosstalkPredicate(characterisation, 0.01).verify(circ));
+  REQUIRE(CrosstalkPredicate(characterisation, 0.1).verify(circ));
+}
+
+SCENARIO("Scheduling gates to avoid crosstalk") {
+  DeviceCharacterisation characterisation = line_characterisation();
+  GIVEN("Two gates in one slice") {
+    Circuit circ = on_nodes(4);
+    circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+    circ.add_op<UnitID>(OpType::CX, {Node(2), Node(3)});
+    REQUIRE(Transforms::schedule_crosstalk(characterisation).apply(circ));
+    REQUIRE(circ.count_gates(OpType::Barrier) == 1);
+    REQUIRE(circ.depth() == 2);
+    REQUIRE(CrosstalkPredicate(characterisation).verify(circ));
+    REQUIRE(get_crosstalk_report(circ, characterisation).total_penalty == 0.);
+    REQUIRE_FALSE(Transforms::schedule_crosstalk(characterisation).apply(circ));
+  }
+  GIVEN("A gate with more gates after it") {
+    Circuit circ = on_nodes(4);
+    circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+    circ.add_op<UnitID>(OpType::CX, {Node(2), Node(3)});
+    circ.add_op<UnitID>(OpType::H, {Node(2)});
+    circ.add_op<UnitID>(OpType::CX, {Node(2), Node(3)});
+    REQUIRE(Transforms::schedule_crosstalk(characterisation).apply(circ));
+    // the gate on (0, 1) is delayed, alongside the H gate
+    std::vector<Command> coms = circ.get_commands();
+    REQUIRE(coms[0].get_args() == unit_vector_t{Node(2), Node(3)});
+    REQUIRE(circ.depth() == 3);
+    REQUIRE(CrosstalkPredicate(characterisation).verify(circ));
+  }
+  GIVEN("A penalty below the threshold") {
+    Circuit circ = on_nodes(5);
+    circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+    circ.add_op<UnitID>(OpType::CX, {Node(3), Node(4)});
+    REQUIRE_FALSE(
+        Transforms::schedule_crosstalk(characterisation, 0.01).apply(circ));
+    REQUIRE(Transforms::schedule_crosstalk(characterisation).apply(circ));
+  }
+}
+
+SCENARIO("CrosstalkPredicate and CrosstalkScheduling pass") {
+  DeviceCharacterisation characterisation = line_characterisation();
+  CrosstalkPredicate strict(characterisation);
+  CrosstalkPredicate loose(characterisation, 0.01);
+  REQUIRE(strict.implies(loose));
+  REQUIRE_FALSE(loose.implies(strict));
+  PredicatePtr met = loose.meet(strict);
+  REQUIRE(met->implies(strict));
+  REQUIRE_THROWS_AS(
+      strict.meet(CrosstalkPredicate(DeviceCharacterisation())),
+      IncorrectPredicate);
+  GIVEN("Serialisation of the predicate") {
+    PredicatePtr pp = std::make_shared<CrosstalkPredicate>(loose);
+    nlohmann::json j = pp;
+    PredicatePtr loaded = j.get<PredicatePtr>();
+    REQUIRE(loaded->implies(*pp));
+    REQUIRE(pp->implies(*loaded));
+  }
+  GIVEN("The pass") {
+    Circuit circ = on_nodes(4);
+    circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+    circ.add_op<UnitID>(OpType::CX, {Node(2), Node(3)});
+    CompilationUnit cu(circ);
+    PassPtr pp = gen_crosstalk_scheduling_pass(characterisation, 0.01);
+    REQUIRE(pp->apply(cu));
+    REQUIRE(loose.verify(cu.get_circ_ref()));
+    nlohmann::json j = pp;
+    REQUIRE(j["StandardPass"]["name"] == "CrosstalkScheduling");
+    PassPtr loaded = j.get<PassPtr>();
+    CompilationUnit copy(circ);
+    REQUIRE(loaded->apply(copy));
+    REQUIRE(copy.get_circ_ref() == cu.get_circ_ref());
+  }
+}
+
+}  // namespace test_CrosstalkScheduling
+}  // namespace tket
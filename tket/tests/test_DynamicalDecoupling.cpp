@@ -0,0 +1,154 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+#include <stdexcept>
+
+#include "Ops/MetaOp.hpp"
+#include "Predicates/CompilationUnit.hpp"
+#include "Predicates/PassGenerators.hpp"
+#include "Simulation/CircuitSimulator.hpp"
+#include "Simulation/ComparisonFunctions.hpp"
+#include "Transformations/DynamicalDecoupling.hpp"
+#include "testutil.hpp"
+
+namespace tket {
+namespace test_DynamicalDecoupling {
+
+static const GateDurations durations(
+    {{OpType::H, 50.},
+     {OpType::X, 20.},
+     {OpType::Y, 20.},
+     {OpType::CX, 300.},
+     {OpType::Measure, 1000.}});
+
+// qubit 2 idles from 50 to 350, and qubit 0 from 350 to 650
+static Circuit two_windows() {
+  Circuit circ(3);
+  for (unsigned i = 0; i < 3; i++) circ.add_op<unsigned>(OpType::H, {i});
+  circ.add_op<unsigned>(OpType::CX, {0, 1});
+  circ.add_op<unsigned>(OpType::CX, {1, 2});
+  return circ;
+}
+
+static bool same_unitary(const Circuit& a, const Circuit& b) {
+  return tket_sim::compare_statevectors_or_unitaries(
+      tket_sim::get_unitary(a), tket_sim::get_unitary(b));
+}
+
+SCENARIO("Dynamical decoupling sequences") {
+  REQUIRE(get_dd_sequence(DDSequence::XY4).n_gates() == 4);
+  REQUIRE(get_dd_sequence(DDSequence::CPMG).n_gates() == 2);
+  Circuit single(1);
+  single.add_op<unsigned>(OpType::X, {0});
+  Circuit wide(2);
+  wide.add_op<unsigned>(OpType::X, {0});
+  wide.add_op<unsigned>(OpType::X, {0});
+  Circuit barrier(1);
+  barrier.add_barrier({0});
+  for (const Circuit& seq : {single, wide, barrier, Circuit(1)}) {
+    REQUIRE_THROWS_AS(
+        Transforms::dynamical_decoupling(durations, seq),
+        std::invalid_argument);
+  }
+}
+
+SCENARIO("Filling idle windows with a sequence") {
+  GIVEN("XY4") {
+    Circuit circ = two_windows();
+    Circuit original(circ);
+    Circuit xy4 = get_dd_sequence(DDSequence::XY4);
+    REQUIRE(Transforms::dynamical_decoupling(durations, xy4).apply(circ));
+    REQUIRE(circ.count_gates(OpType::X) == 4);
+    REQUIRE(circ.count_gates(OpType::Y) == 4);
+    // 220 spare in each window, spread as 27.5, 55, 55, 55, 27.5
+    REQUIRE(circ.count_gates(OpType::Delay) == 10);
+    for (const Command& com : circ) {
+      if (com.get_op_ptr()->get_type() != OpType::Delay) continue;
+      double d = static_cast<const DelayOp&>(*com.get_op_ptr()).get_duration();
+      REQUIRE((d == 27.5 || d == 55.));
+    }
+    Schedule schedule(circ, durations);
+    REQUIRE(schedule.get_total_duration() == 650.);
+    REQUIRE(schedule.get_idle_windows().empty());
+    REQUIRE(same_unitary(circ, original));
+  }
+  GIVEN("Windows too short for the sequence") {
+    Circuit circ = two_windows();
+    REQUIRE_FALSE(Transforms::dynamical_decoupling(
+                      durations, get_dd_sequence(DDSequence::CPMG),
+                      ScheduleType::ASAP, 310.)
+                      .apply(circ));
+    GateDurations slow(
+        {{OpType::H, 50.}, {OpType::X, 200.}, {OpType::CX, 300.}});
+    REQUIRE_FALSE(Transforms::dynamical_decoupling(
+                      slow, get_dd_sequence(DDSequence::CPMG))
+                      .apply(circ));
+  }
+  GIVEN("A barrier") {
+    // the barrier ends the window of qubit 2 and starts that of qubit 0
+    Circuit circ(3);
+    for (unsigned i = 0; i < 3; i++) circ.add_op<unsigned>(OpType::H, {i});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_barrier({0, 2});
+    circ.add_op<unsigned>(OpType::CX, {1, 2});
+    Circuit original(circ);
+    REQUIRE(Transforms::dynamical_decoupling(
+                durations, get_dd_sequence(DDSequence::CPMG))
+                .apply(circ));
+    REQUIRE(circ.count_gates(OpType::Barrier) == 1);
+    REQUIRE(circ.count_gates(OpType::X) == 4);
+    REQUIRE(Schedule(circ, durations).get_total_duration() == 650.);
+    REQUIRE(same_unitary(circ, original));
+  }
+  GIVEN("Measurements and conditions") {
+    Circuit circ(2, 1);
+    circ.add_op<unsigned>(OpType::H, {1});
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_measure(0, 0);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {0}, 1);
+    // qubit 0 idles before the CX and after its measurement, which are left
+    // empty, and qubit 1 idles until the condition is known
+    REQUIRE(Transforms::dynamical_decoupling(
+                durations, get_dd_sequence(DDSequence::XY4))
+                .apply(circ));
+    REQUIRE(circ.count_gates(OpType::Y) == 2);
+    REQUIRE(circ.count_gates(OpType::Delay) == 5);
+    Schedule schedule(circ, durations);
+    REQUIRE(schedule.get_total_duration() == 1370.);
+    std::vector<IdleWindow> windows = schedule.get_idle_windows();
+    REQUIRE(windows.size() == 2);
+    REQUIRE(windows[0].qubit == Qubit(0));
+    REQUIRE(windows[1].qubit == Qubit(0));
+  }
+}
+
+SCENARIO("DynamicalDecoupling pass") {
+  Circuit circ = two_windows();
+  CompilationUnit cu(circ);
+  PassPtr pp = gen_dynamical_decoupling_pass(
+      durations, get_dd_sequence(DDSequence::XY4), ScheduleType::ALAP);
+  REQUIRE(pp->apply(cu));
+  REQUIRE(same_unitary(cu.get_circ_ref(), circ));
+  nlohmann::json j = pp;
+  REQUIRE(j["StandardPass"]["name"] == "DynamicalDecoupling");
+  REQUIRE(j["StandardPass"]["durations"].get<GateDurations>() == durations);
+  PassPtr loaded = j.get<PassPtr>();
+  CompilationUnit copy(circ);
+  REQUIRE(loaded->apply(copy));
+  REQUIRE(copy.get_circ_ref() == cu.get_circ_ref());
+}
+
+}  // namespace test_DynamicalDecoupling
+}  // namespace tket
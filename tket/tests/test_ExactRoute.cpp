@@ -0,0 +1,109 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Mapping/ExactRoutingMethod.hpp"
+#include "Mapping/LexiRouteRoutingMethod.hpp"
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/RoutingMethodJson.hpp"
+#include "Predicates/Predicates.hpp"
+
+namespace tket {
+namespace test_ExactRoute {
+
+// relabel qubit i of the circuit to node i of the architecture
+static void place_in_order(Circuit& circ, const Architecture& arc) {
+  std::vector<Qubit> qubits = circ.all_qubits();
+  std::vector<Node> nodes = arc.get_all_nodes_vec();
+  std::map<UnitID, UnitID> rename_map;
+  for (unsigned i = 0; i < qubits.size(); i++) {
+    rename_map.insert({qubits[i], nodes[i]});
+  }
+  circ.rename_units(rename_map);
+}
+
+SCENARIO("Exact routing finds the fewest SWAP gates") {
+  GIVEN("A gate between the ends of a line") {
+    Architecture arc({{0, 1}, {1, 2}, {2, 3}});
+    Circuit circ(4);
+    circ.add_op<unsigned>(OpType::CZ, {0, 3});
+    circ.add_op<unsigned>(OpType::CZ, {1, 2});
+    place_in_order(circ, arc);
+    MappingManager mm(std::make_shared<Architecture>(arc));
+    REQUIRE(
+        mm.route_circuit(circ, {std::make_shared<ExactRoutingMethod>()}));
+    REQUIRE(circ.count_gates(OpType::SWAP) == 2);
+    REQUIRE(ConnectivityPredicate(arc).verify(circ));
+  }
+  GIVEN("Circuits on a grid") {
+    SquareGrid sg(2, 3);
+    MappingManager mm(std::make_shared<Architecture>(sg));
+    for (unsigned seed = 1; seed < 4; seed++) {
+      Circuit circ(6);
+      for (unsigned i = 0; i < 8; i++) {
+        unsigned q0 = (seed * i + 1) % 6;
+        unsigned q1 = (q0 + 1 + (seed + i) % 5) % 6;
+        circ.add_op<unsigned>(OpType::CZ, {q0, q1});
+        circ.add_op<unsigned>(OpType::H, {q1});
+      }
+      place_in_order(circ, sg);
+      Circuit lexi_circ(circ);
+      REQUIRE(mm.route_circuit(
+          circ, {std::make_shared<ExactRoutingMethod>(10000)}));
+      REQUIRE(ConnectivityPredicate(sg).verify(circ));
+      REQUIRE(circ.count_gates(OpType::CZ) == 8);
+      mm.route_circuit(
+          lexi_circ, {std::make_shared<LexiRouteRoutingMethod>(10)});
+      REQUIRE(
+          circ.count_gates(OpType::SWAP) <=
+          lexi_circ.count_gates(OpType::SWAP));
+    }
+  }
+}
+
+SCENARIO("Exact routing falls back to LexiRoute") {
+  SquareGrid sg(2, 3);
+  MappingManager mm(std::make_shared<Architecture>(sg));
+  Circuit circ(6);
+  circ.add_op<unsigned>(OpType::CZ, {0, 5});
+  circ.add_op<unsigned>(OpType::CZ, {1, 4});
+  circ.add_op<unsigned>(OpType::CZ, {2, 3});
+  place_in_order(circ, sg);
+  Circuit lexi_circ(circ);
+  // no time at all for the search
+  REQUIRE(mm.route_circuit(
+      circ, {std::make_shared<ExactRoutingMethod>(0, 100, 100, 10)}));
+  REQUIRE(mm.route_circuit(
+      lexi_circ, {std::make_shared<LexiRouteRoutingMethod>(10)}));
+  REQUIRE(circ == lexi_circ);
+}
+
+SCENARIO("Serialise an ExactRoutingMethod") {
+  std::vector<RoutingMethodPtr> rmp = {
+      std::make_shared<ExactRoutingMethod>(500, 20, 30, 5)};
+  nlohmann::json j = rmp;
+  std::vector<RoutingMethodPtr> loaded = j.get<std::vector<RoutingMethodPtr>>();
+  REQUIRE(loaded.size() == 1);
+  const ExactRoutingMethod& rm =
+      dynamic_cast<const ExactRoutingMethod&>(*loaded[0]);
+  REQUIRE(rm.get_timeout() == 500);
+  REQUIRE(rm.get_max_depth() == 20);
+  REQUIRE(rm.get_max_size() == 30);
+  REQUIRE(rm.get_lookahead() == 5);
+  REQUIRE(rm.serialize() == rmp[0]->serialize());
+}
+
+}  // namespace test_ExactRoute
+}  // namespace tket
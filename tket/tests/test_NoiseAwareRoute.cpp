@@ -0,0 +1,188 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+#include <cmath>
+
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/NoiseAwareRoutingMethod.hpp"
+#include "Mapping/RoutingMethodJson.hpp"
+#include "Predicates/Predicates.hpp"
+
+namespace tket {
+namespace test_NoiseAwareRoute {
+
+// relabel qubit i of the circuit to node i
+static void place_trivially(Circuit& circ) {
+  std::map<UnitID, UnitID> rename_map;
+  for (const Qubit& qb : circ.all_qubits()) {
+    rename_map.insert({qb, Node(qb.index()[0])});
+  }
+  circ.rename_units(rename_map);
+}
+
+static std::vector<Command> swaps_of(const Circuit& circ) {
+  std::vector<Command> swaps;
+  for (const Command& com : circ.get_commands()) {
+    if (com.get_op_ptr()->get_type() == OpType::SWAP) {
+      swaps.push_back(com);
+    }
+  }
+  return swaps;
+}
+
+SCENARIO("Costs of interactions on a characterised device") {
+  // n0 -- n1 -- n2 -- n3 -- n0, with unreliable connections through n1
+  Architecture arc({{0, 1}, {1, 2}, {2, 3}, {3, 0}});
+  avg_link_errors_t link_errors = {
+      {{Node(0), Node(1)}, 0.1},
+      {{Node(1), Node(2)}, 0.1},
+      {{Node(2), Node(3)}, 0.01},
+      {{Node(3), Node(0)}, 0.01}};
+  avg_readout_errors_t readout_errors = {{Node(2), 0.05}};
+  DeviceCharacterisation characterisation(
+      avg_node_errors_t{}, link_errors, readout_errors);
+  NoiseAwareCosts costs(arc, characterisation);
+
+  const double good = -std::log(0.99);
+  const double bad = -std::log(0.9);
+  REQUIRE(std::abs(costs.gate_cost(Node(0), Node(1)) - bad) < 1e-9);
+  REQUIRE(std::abs(costs.gate_cost(Node(1), Node(0)) - bad) < 1e-9);
+  REQUIRE(std::abs(costs.swap_cost(Node(2), Node(3)) - 3 * good) < 1e-5);
+  REQUIRE(std::abs(costs.interaction_cost(Node(3), Node(0)) - good) < 1e-9);
+  // the cheapest way of interacting n0 and n2 is through n3
+  REQUIRE(std::abs(costs.interaction_cost(Node(0), Node(2)) - 4 * good) < 1e-5);
+  REQUIRE(costs.next_step(Node(0), Node(2)) == Node(3));
+  REQUIRE(costs.next_step(Node(2), Node(0)) == Node(3));
+  REQUIRE(costs.interaction_cost(Node(1), Node(1)) == 0.);
+  REQUIRE(std::abs(costs.readout_cost(Node(2)) + std::log(0.95)) < 1e-9);
+  REQUIRE(costs.readout_cost(Node(0)) == 0.);
+}
+
+SCENARIO("Noise-aware routing") {
+  GIVEN("A ring with unreliable connections on one side") {
+    Architecture arc({{0, 1}, {1, 2}, {2, 3}, {3, 0}});
+    avg_link_errors_t link_errors = {
+        {{Node(0), Node(1)}, 0.1},
+        {{Node(1), Node(2)}, 0.1},
+        {{Node(2), Node(3)}, 0.01},
+        {{Node(3), Node(0)}, 0.01}};
+    DeviceCharacterisation characterisation(avg_node_errors_t{}, link_errors);
+    Circuit circ(4);
+    circ.add_op<unsigned>(OpType::CZ, {0, 2});
+    circ.add_op<unsigned>(OpType::CZ, {1, 3});
+    place_trivially(circ);
+
+    MappingManager mm(std::make_shared<Architecture>(arc));
+    REQUIRE(mm.route_circuit(
+        circ, {std::make_shared<NoiseAwareRoutingMethod>(characterisation)}));
+    std::vector<Command> swaps = swaps_of(circ);
+    REQUIRE(swaps.size() == 1);
+    unit_vector_t args = swaps[0].get_args();
+    REQUIRE(std::find(args.begin(), args.end(), Node(3)) != args.end());
+    REQUIRE(std::find(args.begin(), args.end(), Node(1)) == args.end());
+    REQUIRE(ConnectivityPredicate(arc).verify(circ));
+  }
+  GIVEN("A line with one unreliable readout") {
+    // n0 -- n1 -- n2 -- n3
+    Architecture arc({{0, 1}, {1, 2}, {2, 3}});
+    avg_readout_errors_t readout_errors = {{Node(2), 0.2}};
+    DeviceCharacterisation characterisation(
+        avg_node_errors_t{}, avg_link_errors_t{}, readout_errors);
+    Circuit circ(4, 1);
+    circ.add_op<unsigned>(OpType::CZ, {1, 3});
+    circ.add_measure(1, 0);
+    place_trivially(circ);
+
+    MappingManager mm(std::make_shared<Architecture>(arc));
+    REQUIRE(mm.route_circuit(
+        circ, {std::make_shared<NoiseAwareRoutingMethod>(characterisation)}));
+    // the measured qubit stays on n1
+    std::vector<Command> swaps = swaps_of(circ);
+    REQUIRE(swaps.size() == 1);
+    unit_vector_t args = swaps[0].get_args();
+    REQUIRE(std::find(args.begin(), args.end(), Node(3)) != args.end());
+    for (const Command& com : circ.get_commands()) {
+      if (com.get_op_ptr()->get_type() == OpType::Measure) {
+        REQUIRE(com.get_args()[0] == Node(1));
+      }
+    }
+  }
+  GIVEN("A CX at distance two") {
+    Architecture arc({{0, 1}, {1, 2}});
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    place_trivially(circ);
+
+    MappingManager mm(std::make_shared<Architecture>(arc));
+    REQUIRE(mm.route_circuit(
+        circ, {std::make_shared<NoiseAwareRoutingMethod>(
+                  DeviceCharacterisation())}));
+    REQUIRE(circ.n_gates() == 1);
+    REQUIRE(circ.count_gates(OpType::BRIDGE) == 1);
+  }
+  GIVEN("A longer circuit on a grid") {
+    SquareGrid sg(3, 3);
+    avg_link_errors_t link_errors;
+    avg_node_errors_t node_errors;
+    unsigned i = 0;
+    for (const Architecture::Connection& link : sg.get_all_edges_vec()) {
+      link_errors.insert({link, 0.001 * (i++ % 7)});
+    }
+    for (const Node& node : sg.get_all_nodes_vec()) {
+      node_errors.insert({node, 0.0001 * (i++ % 3)});
+    }
+    DeviceCharacterisation characterisation(node_errors, link_errors);
+    Circuit circ(9);
+    std::vector<Qubit> qubits = circ.all_qubits();
+    std::vector<Node> nodes = sg.get_all_nodes_vec();
+    for (unsigned j = 0; j < 9; j++) {
+      circ.add_op<unsigned>(OpType::CX, {j, (j + 1 + 2 * (j % 3)) % 9});
+      circ.add_op<unsigned>(OpType::CZ, {(j + 5) % 9, (j + 2) % 9});
+    }
+    std::map<UnitID, UnitID> rename_map;
+    for (unsigned j = 0; j < 9; j++) {
+      rename_map.insert({qubits[j], nodes[j]});
+    }
+    circ.rename_units(rename_map);
+
+    MappingManager mm(std::make_shared<Architecture>(sg));
+    REQUIRE(mm.route_circuit(
+        circ,
+        {std::make_shared<NoiseAwareRoutingMethod>(characterisation, 5)}));
+    REQUIRE(circ.count_gates(OpType::CX) + circ.count_gates(OpType::BRIDGE) ==
+            9);
+    REQUIRE(ConnectivityPredicate(sg).verify(circ));
+  }
+}
+
+SCENARIO("Serialise a NoiseAwareRoutingMethod") {
+  avg_link_errors_t link_errors = {{{Node(0), Node(1)}, 0.1}};
+  avg_readout_errors_t readout_errors = {{Node(1), 0.05}};
+  DeviceCharacterisation characterisation(
+      avg_node_errors_t{}, link_errors, readout_errors);
+  std::vector<RoutingMethodPtr> rmp = {
+      std::make_shared<NoiseAwareRoutingMethod>(characterisation, 7)};
+  nlohmann::json j = rmp;
+  std::vector<RoutingMethodPtr> loaded = j.get<std::vector<RoutingMethodPtr>>();
+  REQUIRE(loaded.size() == 1);
+  const NoiseAwareRoutingMethod& rm =
+      dynamic_cast<const NoiseAwareRoutingMethod&>(*loaded[0]);
+  REQUIRE(rm.get_max_depth() == 7);
+  REQUIRE(rm.get_characterisation() == characterisation);
+  REQUIRE(rm.serialize() == rmp[0]->serialize());
+}
+
+}  // namespace test_NoiseAwareRoute
+}  // namespace tket
@@ -17,8 +17,10 @@
 #include "Circuit/Boxes.hpp"
 #include "Circuit/CircUtils.hpp"
 #include "CircuitsForTesting.hpp"
+#include "Converters/DiagonalBox.hpp"
 #include "Converters/PhasePoly.hpp"
 #include "Predicates/CompilerPass.hpp"
+#include "Simulation/CircuitSimulator.hpp"
 #include "Transformations/Decomposition.hpp"
 #include "Transformations/Rebase.hpp"
 #include "Transformations/Transform.hpp"
@@ -937,5 +939,57 @@ SCENARIO("Phase polynomial synthesis without architecture") {
   }
 }
 
+SCENARIO("DiagonalBox") {
+  GIVEN("Numerical phases on 3 qubits") {
+    const std::vector<Expr> phases = {0.1, -0.3, 1.2, 0., 0.7, 0.25, -1.5, 0.9};
+    Eigen::VectorXcd diag(8);
+    for (unsigned j = 0; j < 8; j++) {
+      diag(j) = std::exp(i_ * PI * eval_expr(phases[j]).value());
+    }
+    const Eigen::MatrixXcd U = diag.asDiagonal();
+    DiagonalBox dbox(phases);
+    REQUIRE(dbox.n_qubits() == 3);
+    REQUIRE(dbox.get_unitary().isApprox(U));
+    std::shared_ptr<const Circuit> c = dbox.to_circuit();
+    for (const Command &cmd : *c) {
+      OpType type = cmd.get_op_ptr()->get_type();
+      REQUIRE((type == OpType::CX || type == OpType::Rz));
+    }
+    REQUIRE(c->count_gates(OpType::Rz) == 7);
+    REQUIRE(tket_sim::get_unitary(*c).isApprox(U));
+    REQUIRE(dbox.dagger()->get_unitary().isApprox(U.adjoint()));
+    REQUIRE(dbox.transpose()->get_unitary().isApprox(U));
+    // Lowering to a PhasePolyBox
+    Circuit circ(3);
+    circ.add_box(dbox.to_phase_poly_box(), {0, 1, 2});
+    circ.add_phase(dbox.get_global_phase());
+    REQUIRE(tket_sim::get_unitary(circ).isApprox(U));
+  }
+  GIVEN("Phases depending on a single qubit") {
+    DiagonalBox dbox({0., 0., 0.5, 0.5});
+    std::shared_ptr<const Circuit> c = dbox.to_circuit();
+    REQUIRE(c->n_gates() == 1);
+    REQUIRE(c->count_gates(OpType::Rz) == 1);
+    REQUIRE(tket_sim::get_unitary(*c).isApprox(dbox.get_unitary()));
+  }
+  GIVEN("Symbolic phases") {
+    Sym a = SymEngine::symbol("a");
+    DiagonalBox dbox({0., Expr(a), Expr(a), 0.});
+    REQUIRE(dbox.free_symbols() == SymSet{a});
+    REQUIRE_THROWS_AS(dbox.get_unitary(), SymbolsNotSupported);
+    REQUIRE(dbox.get_phase_polynomial().size() == 2);
+    SymEngine::map_basic_basic sub_map{{a, Expr(0.4)}};
+    Op_ptr op = dbox.symbol_substitution(sub_map);
+    const Complex z = std::exp(0.4 * i_ * PI);
+    Eigen::Vector4cd diag(1., z, z, 1.);
+    Eigen::MatrixXcd U = diag.asDiagonal();
+    REQUIRE(op->get_unitary().isApprox(U));
+  }
+  GIVEN("Invalid arguments") {
+    REQUIRE_THROWS_AS(DiagonalBox({0.1}), CircuitInvalidity);
+    REQUIRE_THROWS_AS(DiagonalBox({0.1, 0.2, 0.3}), CircuitInvalidity);
+  }
+}
+
 }  // namespace test_PhasePolynomials
 }  // namespace tket
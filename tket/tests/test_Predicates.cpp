@@ -353,6 +353,47 @@ SCENARIO("Test routing-related predicates' meet and implication") {
   }
 }
 
+SCENARIO("Test EdgeGateSetPredicate") {
+  Architecture arc({{Node(0), Node(1)}, {Node(1), Node(2)}});
+  arc.set_gate_set(Node(0), Node(1), {OpType::CZ});
+  EdgeGateSetPredicate pred({OpType::CX, OpType::Rz, OpType::PhasedX}, arc);
+  Circuit circ;
+  for (unsigned i = 0; i < 3; i++) circ.add_qubit(Node(i));
+  circ.add_op<UnitID>(OpType::CZ, {Node(1), Node(0)});
+  circ.add_op<UnitID>(OpType::CX, {Node(1), Node(2)});
+  circ.add_op<UnitID>(OpType::Rz, 0.3, {Node(0)});
+  REQUIRE(pred.verify(circ));
+  GIVEN("A gate outside the gate set of its connection") {
+    circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("A gate of a connection used elsewhere") {
+    circ.add_op<UnitID>(OpType::CZ, {Node(1), Node(2)});
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("Implication and meet") {
+    Architecture wider(arc);
+    wider.set_gate_set(Node(0), Node(1), {OpType::CZ, OpType::ECR});
+    EdgeGateSetPredicate weaker(
+        {OpType::CX, OpType::Rz, OpType::PhasedX, OpType::H}, wider);
+    REQUIRE(pred.implies(weaker));
+    REQUIRE_FALSE(weaker.implies(pred));
+    PredicatePtr met = weaker.meet(pred);
+    REQUIRE(met->implies(pred));
+    REQUIRE(pred.implies(*met));
+    REQUIRE_THROWS_AS(
+        pred.implies(GateSetPredicate({OpType::CX})), IncorrectPredicate);
+  }
+  GIVEN("Serialisation") {
+    PredicatePtr pp = std::make_shared<EdgeGateSetPredicate>(pred);
+    nlohmann::json j = pp;
+    PredicatePtr loaded = j.get<PredicatePtr>();
+    REQUIRE(loaded->implies(*pp));
+    REQUIRE(pp->implies(*loaded));
+    REQUIRE(loaded->verify(circ));
+  }
+}
+
 SCENARIO("Test basic functionality of CompilationUnit") {
   GIVEN("A satisfied/unsatisfied predicate in a CompilationUnit") {
     OpTypeSet ots = {OpType::CX};
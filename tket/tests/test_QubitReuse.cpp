@@ -0,0 +1,161 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Mapping/LexiLabelling.hpp"
+#include "Mapping/LexiRouteRoutingMethod.hpp"
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/QubitReuse.hpp"
+#include "Predicates/CompilationUnit.hpp"
+#include "Predicates/PassGenerators.hpp"
+#include "Predicates/Predicates.hpp"
+
+namespace tket {
+namespace test_QubitReuse {
+
+static std::vector<OpType> command_types(const Circuit& circ) {
+  std::vector<OpType> types;
+  for (const Command& com : circ.get_commands()) {
+    types.push_back(com.get_op_ptr()->get_type());
+  }
+  return types;
+}
+
+SCENARIO("Reusing measured qubits") {
+  GIVEN("A created qubit after a measured one") {
+    Circuit circ(2, 1);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_op<unsigned>(OpType::X, {1});
+    circ.qubit_create(Qubit(1));
+    unit_map_t merged = reuse_qubits(circ);
+    REQUIRE(merged == unit_map_t{{Qubit(1), Qubit(0)}});
+    REQUIRE(circ.n_qubits() == 1);
+    std::vector<OpType> expected = {
+        OpType::H, OpType::Measure, OpType::Reset, OpType::X};
+    REQUIRE(command_types(circ) == expected);
+  }
+  GIVEN("A qubit starting with a Reset") {
+    Circuit circ(2, 1);
+    circ.add_measure(0, 0);
+    circ.add_op<unsigned>(OpType::Reset, {1});
+    circ.add_op<unsigned>(OpType::X, {1});
+    REQUIRE(reuse_qubits(circ).size() == 1);
+    REQUIRE(circ.n_qubits() == 1);
+    REQUIRE(circ.count_gates(OpType::Reset) == 1);
+  }
+  GIVEN("A discarded qubit") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::H, {1});
+    circ.qubit_discard(Qubit(0));
+    circ.qubit_create(Qubit(1));
+    // qubit 1 starts with the last gate on qubit 0
+    REQUIRE(reuse_qubits(circ).empty());
+    REQUIRE(circ.n_qubits() == 2);
+  }
+  GIVEN("A qubit which is neither created nor reset") {
+    Circuit circ(2, 1);
+    circ.add_measure(0, 0);
+    circ.add_op<unsigned>(OpType::X, {1});
+    REQUIRE(reuse_qubits(circ).empty());
+    REQUIRE(circ.n_qubits() == 2);
+  }
+  GIVEN("A qubit used before the other is measured") {
+    Circuit circ(2, 1);
+    circ.add_op<unsigned>(OpType::X, {1});
+    circ.add_op<unsigned>(OpType::CX, {1, 0});
+    circ.add_measure(0, 0);
+    circ.qubit_create(Qubit(1));
+    REQUIRE(reuse_qubits(circ).empty());
+    REQUIRE(circ.n_qubits() == 2);
+  }
+  GIVEN("A qubit conditioned on a measurement") {
+    Circuit circ(2, 1);
+    circ.add_op<unsigned>(OpType::H, {0});
+    circ.add_measure(0, 0);
+    circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {0}, 1);
+    circ.qubit_create(Qubit(1));
+    REQUIRE(reuse_qubits(circ).size() == 1);
+    REQUIRE(circ.n_qubits() == 1);
+    std::vector<OpType> expected = {
+        OpType::H, OpType::Measure, OpType::Reset, OpType::Conditional};
+    REQUIRE(command_types(circ) == expected);
+  }
+  GIVEN("A maximum width") {
+    Circuit circ(3, 3);
+    for (unsigned i = 0; i < 3; i++) {
+      circ.add_op<unsigned>(OpType::H, {i});
+      circ.add_measure(i, i);
+      circ.qubit_create(Qubit(i));
+    }
+    Circuit narrowest(circ);
+    REQUIRE(reuse_qubits(circ, 2).size() == 1);
+    REQUIRE(circ.n_qubits() == 2);
+    unit_map_t merged = reuse_qubits(narrowest);
+    REQUIRE(narrowest.n_qubits() == 1);
+    REQUIRE(merged.size() == 2);
+    // both merged qubits follow the remaining qubit
+    for (const std::pair<const UnitID, UnitID>& pair : merged) {
+      REQUIRE(pair.second == narrowest.all_qubits()[0]);
+    }
+  }
+}
+
+SCENARIO("Routing circuits wider than the architecture") {
+  Architecture arc({{0, 1}});
+  MappingManager mm(std::make_shared<Architecture>(arc));
+  std::vector<RoutingMethodPtr> config = {
+      std::make_shared<LexiLabellingMethod>(),
+      std::make_shared<LexiRouteRoutingMethod>()};
+  Circuit circ(3, 2);
+  circ.add_op<unsigned>(OpType::H, {0});
+  circ.add_op<unsigned>(OpType::CX, {0, 1});
+  circ.add_measure(0, 0);
+  circ.add_op<unsigned>(OpType::CX, {2, 1});
+  circ.add_measure(2, 1);
+  circ.qubit_create(Qubit(2));
+  Circuit copy(circ);
+  REQUIRE_THROWS_AS(mm.route_circuit(copy, config), MappingManagerError);
+  REQUIRE(mm.route_circuit(circ, config, true, true));
+  REQUIRE(circ.n_qubits() == 2);
+  REQUIRE(circ.count_gates(OpType::Reset) == 1);
+  REQUIRE(circ.count_gates(OpType::CX) == 2);
+  REQUIRE(ConnectivityPredicate(arc).verify(circ));
+}
+
+SCENARIO("QubitReusePass") {
+  Circuit circ(3, 2);
+  circ.add_op<unsigned>(OpType::H, {0});
+  circ.add_measure(0, 0);
+  circ.add_op<unsigned>(OpType::CX, {1, 2});
+  circ.add_measure(2, 1);
+  circ.qubit_create(Qubit(2));
+  CompilationUnit cu(circ);
+  PassPtr pass = gen_qubit_reuse_pass();
+  REQUIRE(pass->apply(cu));
+  REQUIRE(cu.get_circ_ref().n_qubits() == 2);
+  REQUIRE(cu.get_circ_ref().count_gates(OpType::Reset) == 1);
+  const unit_bimap_t& initial = cu.get_initial_map_ref();
+  const unit_bimap_t& final_map = cu.get_final_map_ref();
+  REQUIRE(initial.left.find(Qubit(2)) == initial.left.end());
+  REQUIRE(initial.left.find(Qubit(0)) != initial.left.end());
+  // qubit 2 ends on the wire of the measured qubit 0
+  REQUIRE(final_map.left.at(Qubit(2)) == Qubit(0));
+  REQUIRE(final_map.left.find(Qubit(0)) == final_map.left.end());
+}
+
+}  // namespace test_QubitReuse
+}  // namespace tket
@@ -303,6 +303,48 @@ SCENARIO("Building rebases with rebase_factory") {
   }
 }
 
+SCENARIO("Building rebases with rebase_edges_factory") {
+  Architecture arc(
+      {{Node(0), Node(1)}, {Node(1), Node(2)}, {Node(0), Node(2)}});
+  arc.set_gate_set(Node(0), Node(1), {OpType::CZ});
+  arc.set_gate_set(Node(1), Node(2), {OpType::ISWAPMax});
+  OpTypeSet allowed = {OpType::CX, OpType::TK1};
+  Transform t = Transforms::rebase_edges_factory(
+      arc, allowed, CircPool::CX(), CircPool::tk1_to_tk1);
+  Circuit circ;
+  for (unsigned i = 0; i < 3; i++) circ.add_qubit(Node(i));
+  circ.add_op<UnitID>(OpType::H, {Node(0)});
+  circ.add_op<UnitID>(OpType::CX, {Node(0), Node(1)});
+  circ.add_op<UnitID>(OpType::CX, {Node(2), Node(1)});
+  circ.add_op<UnitID>(OpType::CZ, {Node(1), Node(2)});
+  circ.add_op<UnitID>(OpType::CY, {Node(2), Node(0)});
+  Circuit copy = circ;
+  REQUIRE(t.apply(circ));
+  REQUIRE(circ.count_gates(OpType::CZ) == 1);
+  REQUIRE(circ.count_gates(OpType::ISWAPMax) == 4);
+  REQUIRE(circ.count_gates(OpType::CX) == 1);
+  for (const Command& com : circ) {
+    OpType type = com.get_op_ptr()->get_type();
+    REQUIRE(
+        (type == OpType::TK1 || type == OpType::CZ ||
+         type == OpType::ISWAPMax || type == OpType::CX));
+  }
+  REQUIRE(test_unitary_comparison(circ, copy));
+  GIVEN("A gate set without a known CX decomposition") {
+    arc.set_gate_set(Node(0), Node(2), {OpType::SWAP});
+    REQUIRE_THROWS_AS(
+        Transforms::rebase_edges_factory(
+            arc, allowed, CircPool::CX(), CircPool::tk1_to_tk1),
+        ArchitectureInvalidity);
+  }
+}
+
+SCENARIO("CX_using_ISWAPMax is equivalent to CX") {
+  Circuit circ = CircPool::CX_using_ISWAPMax();
+  REQUIRE(circ.count_gates(OpType::ISWAPMax) == 2);
+  REQUIRE(test_unitary_comparison(circ, CircPool::CX()));
+}
+
 SCENARIO("Decompose all boxes") {
   GIVEN("A quantum-only CircBox") {
     Circuit u(2);
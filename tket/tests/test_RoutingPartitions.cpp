@@ -0,0 +1,165 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Mapping/LexiLabelling.hpp"
+#include "Mapping/LexiRouteRoutingMethod.hpp"
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/RoutingPartitions.hpp"
+#include "Mapping/Verification.hpp"
+
+namespace tket {
+namespace test_RoutingPartitions {
+
+// two groups of three qubits, each with gates needing SWAPs on a line
+static Circuit two_groups() {
+  Circuit circ(6, 2);
+  for (unsigned i : {0, 3}) {
+    circ.add_op<unsigned>(OpType::CX, {i, i + 1});
+    circ.add_op<unsigned>(OpType::CX, {i + 1, i + 2});
+    circ.add_op<unsigned>(OpType::CZ, {i, i + 2});
+    circ.add_op<unsigned>(OpType::H, {i + 2});
+  }
+  circ.add_measure(0, 0);
+  circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {0}, 1);
+  circ.add_measure(5, 1);
+  return circ;
+}
+
+SCENARIO("Finding interaction components") {
+  Circuit circ = two_groups();
+  circ.add_qubit(Qubit("idle", 0));
+  std::vector<unit_vector_t> components = get_interaction_components(circ);
+  REQUIRE(components.size() == 3);
+  // ordered by least unit, with measured bits joined to their qubits
+  REQUIRE(
+      components[0] == unit_vector_t{Bit(0), Qubit(0), Qubit(1), Qubit(2)});
+  REQUIRE(
+      components[1] == unit_vector_t{Bit(1), Qubit(3), Qubit(4), Qubit(5)});
+  REQUIRE(components[2] == unit_vector_t{Qubit("idle", 0)});
+  Circuit sub = get_interaction_subcircuit(circ, components[1]);
+  REQUIRE(sub.n_qubits() == 3);
+  REQUIRE(sub.n_bits() == 1);
+  REQUIRE(sub.n_gates() == 5);
+}
+
+SCENARIO("Finding disjoint subarchitectures") {
+  GIVEN("A line") {
+    std::vector<std::pair<unsigned, unsigned>> edges;
+    for (unsigned i = 0; i < 7; i++) edges.push_back({i, i + 1});
+    Architecture arc(edges);
+    auto subarcs = get_disjoint_subarchitectures(arc, {3, 0, 4});
+    REQUIRE(subarcs);
+    REQUIRE((*subarcs)[0].size() == 3);
+    REQUIRE((*subarcs)[1].empty());
+    REQUIRE((*subarcs)[2].size() == 4);
+    // the larger set is taken from an end so the rest stays connected
+    for (const node_vector_t& nodes : {(*subarcs)[0], (*subarcs)[2]}) {
+      Architecture subarc = arc.create_subarch(nodes);
+      REQUIRE(subarc.n_connections() == nodes.size() - 1);
+    }
+    REQUIRE_FALSE(get_disjoint_subarchitectures(arc, {5, 4}));
+  }
+  GIVEN("A grid") {
+    SquareGrid arc(3, 3);
+    auto subarcs = get_disjoint_subarchitectures(arc, {4, 2, 3});
+    REQUIRE(subarcs);
+    std::set<Node> used;
+    for (const node_vector_t& nodes : *subarcs) {
+      used.insert(nodes.begin(), nodes.end());
+      REQUIRE(arc.create_subarch(nodes).get_diameter() < nodes.size());
+    }
+    REQUIRE(used.size() == 9);
+  }
+}
+
+SCENARIO("Routing independent partitions concurrently") {
+  std::vector<std::pair<unsigned, unsigned>> edges;
+  for (unsigned i = 0; i < 7; i++) edges.push_back({i, i + 1});
+  ArchitecturePtr arc = std::make_shared<Architecture>(edges);
+  MappingManager mm(arc);
+  std::vector<RoutingMethodPtr> config = {
+      std::make_shared<LexiLabellingMethod>(),
+      std::make_shared<LexiRouteRoutingMethod>()};
+  auto identity_maps = [](const Circuit& circ) {
+    std::shared_ptr<unit_bimaps_t> maps = std::make_shared<unit_bimaps_t>();
+    for (const Qubit& qb : circ.all_qubits()) {
+      maps->initial.insert({qb, qb});
+      maps->final.insert({qb, qb});
+    }
+    return maps;
+  };
+  GIVEN("Two groups of qubits") {
+    Circuit circ = two_groups();
+    std::shared_ptr<unit_bimaps_t> maps = identity_maps(circ);
+    Circuit single_thread(circ);
+    REQUIRE(mm.route_circuit_in_partitions(circ, config, maps, 4));
+    REQUIRE(respects_connectivity_constraints(circ, *arc, false, true));
+    REQUIRE(circ.n_qubits() == 6);
+    REQUIRE(circ.n_bits() == 2);
+    // each group is placed on its own part of the line
+    Node q0 = Node(maps->initial.left.at(Qubit(0)));
+    Node q3 = Node(maps->initial.left.at(Qubit(3)));
+    REQUIRE(maps->initial.size() == 6);
+    REQUIRE(q0 != q3);
+    // the result does not depend on the number of threads
+    REQUIRE(mm.route_circuit_in_partitions(
+        single_thread, config, identity_maps(single_thread), 1));
+    REQUIRE(single_thread == circ);
+  }
+  GIVEN("A symbolic circuit") {
+    // the groups share the symbol, so are routed on a single thread unless
+    // SymEngine is built thread-safe
+    Sym a = SymEngine::symbol("a");
+    Circuit circ = two_groups();
+    circ.add_op<unsigned>(OpType::Rz, Expr(a), {1});
+    circ.add_op<unsigned>(OpType::ZZPhase, Expr(a), {3, 5});
+    Circuit single_thread(circ);
+    REQUIRE(mm.route_circuit_in_partitions(circ, config, identity_maps(circ)));
+    REQUIRE(respects_connectivity_constraints(circ, *arc, false, true));
+    REQUIRE(circ.free_symbols() == SymSet{a});
+    REQUIRE(mm.route_circuit_in_partitions(
+        single_thread, config, identity_maps(single_thread), 1));
+    REQUIRE(single_thread == circ);
+  }
+  GIVEN("Groups which do not fit on disjoint parts") {
+    // a star, from which any pair of nodes includes the centre
+    ArchitecturePtr star = std::make_shared<Architecture>(
+        std::vector<std::pair<unsigned, unsigned>>{
+            {0, 1}, {0, 2}, {0, 3}, {0, 4}});
+    MappingManager star_mm(star);
+    REQUIRE_FALSE(get_disjoint_subarchitectures(*star, {2, 2}));
+    Circuit circ(4);
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::CX, {2, 3});
+    Circuit whole(circ);
+    REQUIRE(star_mm.route_circuit_in_partitions(
+        circ, config, std::make_shared<unit_bimaps_t>()));
+    REQUIRE(respects_connectivity_constraints(circ, *star, false, true));
+    star_mm.route_circuit(whole, config);
+    REQUIRE(whole == circ);
+  }
+  GIVEN("A circuit with one group") {
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    circ.add_op<unsigned>(OpType::CX, {1, 2});
+    REQUIRE(mm.route_circuit_in_partitions(
+        circ, config, std::make_shared<unit_bimaps_t>()));
+    REQUIRE(respects_connectivity_constraints(circ, *arc, false, true));
+  }
+}
+
+}  // namespace test_RoutingPartitions
+}  // namespace tket
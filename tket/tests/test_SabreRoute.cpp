@@ -0,0 +1,136 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/RoutingMethodJson.hpp"
+#include "Mapping/SabreRoutingMethod.hpp"
+#include "Predicates/Predicates.hpp"
+
+namespace tket {
+namespace test_SabreRoute {
+
+static bool all_placed(const Circuit& circ, const Architecture& arc) {
+  for (const Qubit& qb : circ.all_qubits()) {
+    if (!arc.node_exists(Node(qb))) {
+      return false;
+    }
+  }
+  return true;
+}
+
+SCENARIO("SABRE routing of unplaced circuits") {
+  // n0 -- n1 -- n2 -- n3 -- n4
+  Architecture arc({{0, 1}, {1, 2}, {2, 3}, {3, 4}});
+  ArchitecturePtr shared_arc = std::make_shared<Architecture>(arc);
+  Circuit circ(5);
+  for (unsigned i = 0; i < 4; i++) {
+    circ.add_op<unsigned>(OpType::CX, {i, i + 1});
+  }
+  MappingManager mm(shared_arc);
+  GIVEN("The greedy layout alone") {
+    Circuit routed(circ);
+    REQUIRE(mm.route_circuit(
+        routed, {std::make_shared<SabreRoutingMethod>(100, 100, 0, 0)}));
+    REQUIRE(routed.count_gates(OpType::SWAP) == 1);
+    REQUIRE(all_placed(routed, arc));
+    REQUIRE(ConnectivityPredicate(arc).verify(routed));
+  }
+  GIVEN("The final mapping") {
+    Circuit routed(circ);
+    MappingFrontier_ptr mf = std::make_shared<MappingFrontier>(routed);
+    std::pair<bool, unit_map_t> result =
+        SabreRoutingMethod(100, 100, 0, 0).routing_method(mf, shared_arc);
+    REQUIRE(result.first);
+    // every qubit ends on a distinct node, where the boundary labels it
+    REQUIRE(result.second.size() == 5);
+    for (const std::pair<const UnitID, UnitID>& pair : result.second) {
+      REQUIRE(pair.first == pair.second);
+      REQUIRE(arc.node_exists(Node(pair.second)));
+      REQUIRE(mf->linear_boundary->find(pair.first) !=
+              mf->linear_boundary->end());
+    }
+  }
+  GIVEN("A layout refined by forward and backward passes") {
+    Circuit routed(circ);
+    REQUIRE(mm.route_circuit(
+        routed, {std::make_shared<SabreRoutingMethod>(100, 100, 1, 0)}));
+    REQUIRE(routed.count_gates(OpType::SWAP) == 0);
+    REQUIRE(all_placed(routed, arc));
+    REQUIRE(ConnectivityPredicate(arc).verify(routed));
+  }
+}
+
+SCENARIO("SABRE routing with random restarts") {
+  SquareGrid sg(3, 4);
+  ArchitecturePtr shared_arc = std::make_shared<Architecture>(sg);
+  Circuit circ(12);
+  for (unsigned i = 0; i < 30; i++) {
+    circ.add_op<unsigned>(OpType::CX, {(7 * i) % 12, (5 * i + 3) % 12});
+    circ.add_op<unsigned>(OpType::H, {(3 * i) % 12});
+  }
+  MappingManager mm(shared_arc);
+  Circuit routed_0(circ), routed_1(circ);
+  REQUIRE(mm.route_circuit(
+      routed_0, {std::make_shared<SabreRoutingMethod>(100, 100, 3, 5, 11)}));
+  REQUIRE(mm.route_circuit(
+      routed_1, {std::make_shared<SabreRoutingMethod>(100, 100, 3, 5, 11)}));
+  REQUIRE(routed_0 == routed_1);
+  REQUIRE(routed_0.count_gates(OpType::CX) == 30);
+  REQUIRE(all_placed(routed_0, sg));
+  REQUIRE(ConnectivityPredicate(sg).verify(routed_0));
+}
+
+SCENARIO("SABRE routing keeps placed qubits on their nodes") {
+  Architecture arc({{0, 1}, {1, 2}, {2, 3}, {3, 4}});
+  ArchitecturePtr shared_arc = std::make_shared<Architecture>(arc);
+  Circuit circ(5);
+  for (unsigned i = 0; i < 4; i++) {
+    circ.add_op<unsigned>(OpType::CZ, {i, i + 1});
+  }
+  std::map<UnitID, UnitID> rename_map = {{Qubit(0), Node(4)}};
+  circ.rename_units(rename_map);
+  std::shared_ptr<unit_bimaps_t> maps = std::make_shared<unit_bimaps_t>();
+  for (const UnitID& u : circ.all_units()) {
+    maps->initial.insert({u, u});
+    maps->final.insert({u, u});
+  }
+  MappingManager mm(shared_arc);
+  REQUIRE(mm.route_circuit_with_maps(
+      circ, {std::make_shared<SabreRoutingMethod>()}, maps));
+  REQUIRE(maps->initial.left.find(Node(4))->second == Node(4));
+  REQUIRE(circ.count_gates(OpType::SWAP) == 0);
+  REQUIRE(all_placed(circ, arc));
+  REQUIRE(ConnectivityPredicate(arc).verify(circ));
+}
+
+SCENARIO("Serialise a SabreRoutingMethod") {
+  std::vector<RoutingMethodPtr> rmp = {
+      std::make_shared<SabreRoutingMethod>(20, 50, 2, 3, 7)};
+  nlohmann::json j = rmp;
+  std::vector<RoutingMethodPtr> loaded = j.get<std::vector<RoutingMethodPtr>>();
+  REQUIRE(loaded.size() == 1);
+  const SabreRoutingMethod& rm =
+      dynamic_cast<const SabreRoutingMethod&>(*loaded[0]);
+  REQUIRE(rm.get_max_depth() == 20);
+  REQUIRE(rm.get_max_size() == 50);
+  REQUIRE(rm.get_iterations() == 2);
+  REQUIRE(rm.get_restarts() == 3);
+  REQUIRE(rm.get_seed() == 7);
+  REQUIRE(rm.serialize() == rmp[0]->serialize());
+}
+
+}  // namespace test_SabreRoute
+}  // namespace tket
@@ -0,0 +1,164 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+#include <cmath>
+
+#include "Circuit/Conditional.hpp"
+#include "Ops/MetaOp.hpp"
+#include "Simulation/CircuitSimulator.hpp"
+#include "Simulation/ComparisonFunctions.hpp"
+#include "Transformations/Schedule.hpp"
+#include "testutil.hpp"
+
+namespace tket {
+namespace test_Schedule {
+
+static const GateDurations durations(
+    {{OpType::H, 50.}, {OpType::X, 20.}, {OpType::CX, 300.},
+     {OpType::Measure, 1000.}});
+
+static bool approx(double a, double b) { return std::abs(a - b) < ERR_EPS; }
+
+SCENARIO("Durations of operations") {
+  GateDurations with_nodes(
+      {{OpType::X, 20.}}, {{{OpType::X, {Node(1)}}, 35.}});
+  REQUIRE(with_nodes.get_duration(get_op_ptr(OpType::X), {Node(0)}) == 20.);
+  REQUIRE(with_nodes.get_duration(get_op_ptr(OpType::X), {Node(1)}) == 35.);
+  Op_ptr cond = std::make_shared<Conditional>(get_op_ptr(OpType::X), 1, 1);
+  REQUIRE(with_nodes.get_duration(cond, {Node(1)}) == 35.);
+  Op_ptr delay = std::make_shared<DelayOp>(120.);
+  REQUIRE(with_nodes.get_duration(delay, {Node(0)}) == 120.);
+  Op_ptr barrier = std::make_shared<MetaOp>(
+      OpType::Barrier, op_signature_t{EdgeType::Quantum});
+  REQUIRE(with_nodes.get_duration(barrier, {Node(0)}) == 0.);
+  REQUIRE_THROWS_AS(
+      with_nodes.get_duration(get_op_ptr(OpType::H), {Node(0)}),
+      std::domain_error);
+}
+
+SCENARIO("Scheduling a circuit") {
+  Circuit circ(3);
+  Vertex h = circ.add_op<unsigned>(OpType::H, {0});
+  Vertex cx = circ.add_op<unsigned>(OpType::CX, {0, 1});
+  Vertex x = circ.add_op<unsigned>(OpType::X, {2});
+  GIVEN("As soon as possible") {
+    Schedule schedule(circ, durations);
+    REQUIRE(schedule.get_start(h) == 0.);
+    REQUIRE(schedule.get_start(cx) == 50.);
+    REQUIRE(schedule.get_start(x) == 0.);
+    REQUIRE(schedule.get_duration(cx) == 300.);
+    REQUIRE(schedule.get_total_duration() == 350.);
+    std::vector<IdleWindow> windows = schedule.get_idle_windows();
+    REQUIRE(windows.size() == 2);
+    REQUIRE(windows[0].qubit == Qubit(1));
+    REQUIRE(windows[0].start == 0.);
+    REQUIRE(windows[0].end == 50.);
+    REQUIRE(windows[1].qubit == Qubit(2));
+    REQUIRE(windows[1].start == 20.);
+    REQUIRE(windows[1].end == 350.);
+    REQUIRE(schedule.get_idle_windows(100.).size() == 1);
+  }
+  GIVEN("As late as possible") {
+    Schedule schedule(circ, durations, ScheduleType::ALAP);
+    REQUIRE(schedule.get_start(h) == 0.);
+    REQUIRE(schedule.get_start(x) == 330.);
+    std::vector<IdleWindow> windows = schedule.get_idle_windows();
+    REQUIRE(windows.size() == 2);
+    REQUIRE(windows[1].start == 0.);
+    REQUIRE(windows[1].end == 330.);
+  }
+  GIVEN("Commands with times") {
+    std::vector<ScheduledCommand> coms =
+        Schedule(circ, durations).get_commands();
+    REQUIRE(coms.size() == 3);
+    for (const ScheduledCommand& com : coms) {
+      if (com.command.get_vertex() == cx) {
+        REQUIRE(com.start == 50.);
+        REQUIRE(com.duration == 300.);
+      }
+    }
+  }
+}
+
+SCENARIO("Scheduling with classical dependencies") {
+  GIVEN("A condition on a measurement") {
+    Circuit circ(2, 1);
+    circ.add_measure(0, 0);
+    Vertex x = circ.add_conditional_gate<unsigned>(OpType::X, {}, {1}, {0}, 1);
+    Schedule schedule(circ, durations);
+    REQUIRE(schedule.get_start(x) == 1000.);
+    REQUIRE(schedule.get_total_duration() == 1020.);
+    std::vector<IdleWindow> windows = schedule.get_idle_windows();
+    REQUIRE(windows.size() == 2);
+    REQUIRE(windows[0].qubit == Qubit(0));
+    REQUIRE(windows[0].start == 1000.);
+    REQUIRE(windows[1].qubit == Qubit(1));
+    REQUIRE(windows[1].end == 1000.);
+  }
+  GIVEN("A measurement overwriting a condition") {
+    Circuit circ(3, 1);
+    Vertex x0 =
+        circ.add_conditional_gate<unsigned>(OpType::X, {}, {0}, {0}, 1);
+    Vertex x1 =
+        circ.add_conditional_gate<unsigned>(OpType::H, {}, {1}, {0}, 1);
+    Vertex m = circ.add_measure(2, 0);
+    Schedule schedule(circ, durations);
+    // both reads of the bit may overlap, but the write waits for them
+    REQUIRE(schedule.get_start(x0) == 0.);
+    REQUIRE(schedule.get_start(x1) == 0.);
+    REQUIRE(schedule.get_start(m) == 50.);
+    Schedule late(circ, durations, ScheduleType::ALAP);
+    REQUIRE(late.get_start(x0) == 30.);
+    REQUIRE(late.get_start(m) == 50.);
+  }
+}
+
+SCENARIO("Filling idle windows with delays") {
+  Circuit circ(3);
+  circ.add_op<unsigned>(OpType::H, {0});
+  circ.add_op<unsigned>(OpType::CX, {0, 1});
+  circ.add_op<unsigned>(OpType::X, {2});
+  Circuit original(circ);
+  REQUIRE(Transforms::insert_delays(durations).apply(circ));
+  REQUIRE(circ.count_gates(OpType::Delay) == 2);
+  REQUIRE(circ.depth() == original.depth());
+  Schedule schedule(circ, durations);
+  REQUIRE(approx(schedule.get_total_duration(), 350.));
+  REQUIRE(schedule.get_idle_windows().empty());
+  REQUIRE_FALSE(Transforms::insert_delays(durations).apply(circ));
+  REQUIRE(tket_sim::compare_statevectors_or_unitaries(
+      tket_sim::get_unitary(circ), tket_sim::get_unitary(original)));
+  GIVEN("Serialisation") {
+    nlohmann::json j = circ;
+    REQUIRE(j.get<Circuit>() == circ);
+    Op_ptr delay = std::make_shared<DelayOp>(20., 2);
+    REQUIRE(delay->get_name() == "Delay(20)");
+    nlohmann::json j_op = delay;
+    REQUIRE(*j_op.get<Op_ptr>() == *delay);
+    REQUIRE_FALSE(*j_op.get<Op_ptr>() == DelayOp(30., 2));
+    // a plain MetaOp of the same type is not a delay
+    REQUIRE_FALSE(
+        *delay == MetaOp(OpType::Delay, op_signature_t(2, EdgeType::Quantum)));
+  }
+  GIVEN("Only long windows") {
+    Circuit long_only(original);
+    REQUIRE(Transforms::insert_delays(durations, ScheduleType::ASAP, 100.)
+                .apply(long_only));
+    REQUIRE(long_only.count_gates(OpType::Delay) == 1);
+  }
+}
+
+}  // namespace test_Schedule
+}  // namespace tket
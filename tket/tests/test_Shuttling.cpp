@@ -0,0 +1,217 @@
+// Copyright 2019-2022 Cambridge Quantum Computing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#include <catch2/catch_test_macros.hpp>
+
+#include "Architecture/ShuttlingArchitecture.hpp"
+#include "Mapping/MappingManager.hpp"
+#include "Mapping/RoutingMethodJson.hpp"
+#include "Mapping/ShuttlingRoutingMethod.hpp"
+#include "Predicates/CompilationUnit.hpp"
+#include "Predicates/PassGenerators.hpp"
+#include "Predicates/PassLibrary.hpp"
+#include "Predicates/Predicates.hpp"
+
+namespace tket {
+namespace test_Shuttling {
+
+// load -- gate -- store, with a transport between each pair of sites in
+// neighbouring zones
+static ShuttlingArchitecture three_zones() {
+  std::vector<Zone> zones = {
+      {"load", {Node("l", 0), Node("l", 1)}, false},
+      {"gate", {Node("g", 0), Node("g", 1)}, true},
+      {"store", {Node("s", 0), Node("s", 1)}, false}};
+  std::vector<ShuttlingArchitecture::Transport> transports;
+  for (unsigned i = 0; i < 2; i++) {
+    transports.push_back({{Node("l", i), Node("g", i)}, {1., 0.5}});
+    transports.push_back({{Node("g", i), Node("s", i)}, {1., 0.5}});
+  }
+  return ShuttlingArchitecture(zones, transports);
+}
+
+SCENARIO("Constructing a ShuttlingArchitecture") {
+  ShuttlingArchitecture arc = three_zones();
+  REQUIRE(arc.n_nodes() == 6);
+  REQUIRE(arc.get_zone_index(Node("g", 1)) == 1);
+  REQUIRE_THROWS_AS(arc.get_zone_index(Node("x", 0)), ArchitectureInvalidity);
+  // only sites of interaction zones are connected
+  REQUIRE(arc.valid_operation({Node("g", 0), Node("g", 1)}));
+  REQUIRE_FALSE(arc.valid_operation({Node("l", 0), Node("l", 1)}));
+  REQUIRE_FALSE(arc.valid_operation({Node("l", 0), Node("g", 0)}));
+  REQUIRE(arc.transport_exists(Node("g", 0), Node("l", 0)));
+  REQUIRE_FALSE(arc.transport_exists(Node("l", 0), Node("l", 1)));
+  REQUIRE(arc.get_transport_cost(Node("s", 1), Node("g", 1)).time == 1.);
+  REQUIRE(arc.get_transport_neighbours(Node("g", 0)).size() == 2);
+  REQUIRE(arc.get_transports().size() == 4);
+  GIVEN("A site in two zones") {
+    std::vector<Zone> zones = {
+        {"a", {Node(0)}, true}, {"b", {Node(0), Node(1)}, true}};
+    REQUIRE_THROWS_AS(
+        ShuttlingArchitecture(zones, {}), ArchitectureInvalidity);
+  }
+  GIVEN("A transport to an unknown site") {
+    std::vector<Zone> zones = {{"a", {Node(0)}, true}};
+    REQUIRE_THROWS_AS(
+        ShuttlingArchitecture(zones, {{{Node(0), Node(1)}, {1., 1.}}}),
+        ArchitectureInvalidity);
+  }
+  GIVEN("Serialisation") {
+    nlohmann::json j = arc;
+    ShuttlingArchitecture loaded = j.get<ShuttlingArchitecture>();
+    REQUIRE(loaded == arc);
+    REQUIRE(loaded.n_connections() == arc.n_connections());
+  }
+}
+
+SCENARIO("ZoneConstraintsPredicate") {
+  ShuttlingArchitecture arc = three_zones();
+  ZoneConstraintsPredicate pred(arc);
+  Node l0("l", 0), l1("l", 1), g0("g", 0), g1("g", 1);
+  Circuit circ;
+  for (const Node& n : {l0, l1, g0, g1}) circ.add_qubit(n);
+  circ.add_op<UnitID>(OpType::Transport, {l0, g0});
+  circ.add_op<UnitID>(OpType::CX, {g0, g1});
+  REQUIRE(pred.verify(circ));
+  GIVEN("A gate outside an interaction zone") {
+    circ.add_op<UnitID>(OpType::CZ, {l0, l1});
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("A gate across zones") {
+    circ.add_op<UnitID>(OpType::CZ, {l0, g1});
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("A transport without a transport connection") {
+    circ.add_op<UnitID>(OpType::Transport, {l0, l1});
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("A qubit which is not a site") {
+    circ.add_qubit(Qubit(0));
+    REQUIRE_FALSE(pred.verify(circ));
+  }
+  GIVEN("Serialisation") {
+    PredicatePtr pp = std::make_shared<ZoneConstraintsPredicate>(arc);
+    nlohmann::json j = pp;
+    PredicatePtr loaded = j.get<PredicatePtr>();
+    REQUIRE(
+        dynamic_cast<const ZoneConstraintsPredicate&>(*loaded).get_arch() ==
+        arc);
+    REQUIRE(loaded->implies(*pp));
+  }
+}
+
+SCENARIO("Routing with transports") {
+  ShuttlingArchitecture arc = three_zones();
+  ArchitecturePtr shared_arc = std::make_shared<ShuttlingArchitecture>(arc);
+  MappingManager mm(shared_arc);
+  std::vector<RoutingMethodPtr> config = {
+      std::make_shared<ShuttlingRoutingMethod>()};
+  Node l0("l", 0), l1("l", 1), g0("g", 0), g1("g", 1), s1("s", 1);
+  GIVEN("Qubits in different zones") {
+    Circuit circ;
+    for (const Node& n : {l0, s1}) circ.add_qubit(n);
+    circ.add_op<UnitID>(OpType::CX, {l0, s1});
+    REQUIRE(mm.route_circuit(circ, config));
+    REQUIRE(circ.count_gates(OpType::Transport) == 2);
+    REQUIRE(circ.count_gates(OpType::SWAP) == 0);
+    REQUIRE(ZoneConstraintsPredicate(arc).verify(circ));
+  }
+  GIVEN("A full interaction zone") {
+    Circuit circ;
+    for (const Node& n : {l0, l1, g0, g1}) circ.add_qubit(n);
+    circ.add_op<UnitID>(OpType::H, {g0});
+    circ.add_op<UnitID>(OpType::H, {g1});
+    circ.add_op<UnitID>(OpType::CX, {l0, l1});
+    REQUIRE(mm.route_circuit(circ, config));
+    // both idle qubits are moved to the store first
+    REQUIRE(circ.count_gates(OpType::Transport) == 4);
+    REQUIRE(ZoneConstraintsPredicate(arc).verify(circ));
+  }
+  GIVEN("Unplaced qubits") {
+    Circuit circ(2);
+    circ.add_op<unsigned>(OpType::CX, {0, 1});
+    circ.add_op<unsigned>(OpType::CZ, {1, 0});
+    REQUIRE(mm.route_circuit(circ, config));
+    REQUIRE(circ.count_gates(OpType::Transport) == 0);
+    REQUIRE(ZoneConstraintsPredicate(arc).verify(circ));
+  }
+  GIVEN("An architecture without zones") {
+    MappingManager line_mm(std::make_shared<Architecture>(
+        Architecture({{Node(0), Node(1)}, {Node(1), Node(2)}})));
+    Circuit circ(3);
+    circ.add_op<unsigned>(OpType::CX, {0, 2});
+    REQUIRE_THROWS_AS(line_mm.route_circuit(circ, config), MappingManagerError);
+  }
+}
+
+SCENARIO("Trading transport time against heating") {
+  // a0 -- g0 directly, or through m0 without heating
+  Node a0("a", 0), m0("m", 0), g0("g", 0), g1("g", 1);
+  ShuttlingArchitecture arc(
+      {{"a", {a0}, false}, {"m", {m0}, false}, {"g", {g0, g1}, true}},
+      {{{a0, g0}, {1., 5.}}, {{a0, m0}, {1., 0.}}, {{m0, g0}, {1., 0.}}});
+  MappingManager mm(std::make_shared<ShuttlingArchitecture>(arc));
+  Circuit circ;
+  for (const Node& n : {a0, g1}) circ.add_qubit(n);
+  circ.add_op<UnitID>(OpType::CX, {a0, g1});
+  Circuit fastest(circ);
+  REQUIRE(mm.route_circuit(
+      fastest, {std::make_shared<ShuttlingRoutingMethod>(0.)}));
+  REQUIRE(fastest.count_gates(OpType::Transport) == 1);
+  Circuit coolest(circ);
+  REQUIRE(mm.route_circuit(
+      coolest, {std::make_shared<ShuttlingRoutingMethod>(1.)}));
+  REQUIRE(coolest.count_gates(OpType::Transport) == 2);
+  REQUIRE(ZoneConstraintsPredicate(arc).verify(coolest));
+}
+
+SCENARIO("Shuttling routing pass") {
+  ShuttlingArchitecture arc = three_zones();
+  PassPtr pass = gen_shuttling_routing_pass(
+      arc, {std::make_shared<ShuttlingRoutingMethod>()});
+  Node l0("l", 0), s1("s", 1);
+  Circuit circ;
+  for (const Node& n : {l0, s1}) circ.add_qubit(n);
+  circ.add_op<UnitID>(OpType::CX, {l0, s1});
+  CompilationUnit cu(circ);
+  REQUIRE(pass->apply(cu));
+  REQUIRE(cu.get_circ_ref().count_gates(OpType::Transport) == 2);
+  REQUIRE(cu.check_all_predicates());
+  REQUIRE(ZoneConstraintsPredicate(arc).verify(cu.get_circ_ref()));
+  GIVEN("A rebase after routing") {
+    // transports are decomposed as SWAPs
+    REQUIRE(RebaseTket()->apply(cu));
+    REQUIRE(cu.get_circ_ref().count_gates(OpType::Transport) == 0);
+    REQUIRE(cu.get_circ_ref().count_gates(OpType::SWAP) == 0);
+  }
+  GIVEN("Serialisation") {
+    nlohmann::json j = pass;
+    PassPtr loaded = j.get<PassPtr>();
+    REQUIRE(loaded->get_config() == pass->get_config());
+  }
+}
+
+SCENARIO("Serialising ShuttlingRoutingMethod") {
+  std::vector<RoutingMethodPtr> rmp = {
+      std::make_shared<ShuttlingRoutingMethod>(0.25)};
+  nlohmann::json j = rmp;
+  std::vector<RoutingMethodPtr> loaded = j.get<std::vector<RoutingMethodPtr>>();
+  REQUIRE(loaded.size() == 1);
+  REQUIRE(
+      std::dynamic_pointer_cast<ShuttlingRoutingMethod>(loaded[0])
+          ->get_heating_weight() == 0.25);
+}
+
+}  // namespace test_Shuttling
+}  // namespace tket
@@ -22,6 +22,7 @@
 #include "Circuit/Circuit.hpp"
 #include "Circuit/Command.hpp"
 #include "CircuitsForTesting.hpp"
+#include "Converters/DiagonalBox.hpp"
 #include "Converters/PhasePoly.hpp"
 #include "Gate/SymTable.hpp"
 #include "Mapping/LexiLabelling.hpp"
@@ -68,9 +69,14 @@ SCENARIO("Test Op serialization") {
         OpType::Barrier};
     const OpTypeSet boxes = {OpType::CircBox,      OpType::Unitary1qBox,
                              OpType::Unitary2qBox, OpType::Unitary3qBox,
-                             OpType::ExpBox,       OpType::PauliExpBox,
-                             OpType::CustomGate,   OpType::CliffBox,
-                             OpType::PhasePolyBox, OpType::QControlBox};
+                             OpType::UnitaryNqBox, OpType::ExpBox,
+                             OpType::PauliExpBox,  OpType::CustomGate,
+                             OpType::CliffBox,     OpType::PhasePolyBox,
+                             OpType::QControlBox,  OpType::MultiplexorBox,
+                             OpType::MultiplexedRotationBox,
+                             OpType::StatePreparationBox,
+                             OpType::DiagonalBox,
+                             OpType::ToffoliBox};
 
     std::set<std::string> type_names;
     for (auto type :
@@ -249,6 +255,83 @@ SCENARIO("Test Circuit serialization") {
     REQUIRE(ebox_m_p.second == exp_b_m_p.second);
 
     REQUIRE(ebox == exp_b);
+
+    UnitaryNqBox mboxn(random_unitary(8, 1));
+    c.add_box(mboxn, {2, 0, 1});
+    nlohmann::json j_mboxn = c;
+    const Circuit new_cn = j_mboxn.get<Circuit>();
+    const auto& mn_b = static_cast<const UnitaryNqBox&>(
+        *new_cn.get_commands()[5].get_op_ptr());
+    REQUIRE(matrices_are_equal(mboxn.get_matrix(), mn_b.get_matrix()));
+    REQUIRE(mboxn == mn_b);
+  }
+  GIVEN("Multiplexor boxes") {
+    Circuit c(3, "multiplexors");
+    ctrl_op_map_t op_map = {
+        {{false, true}, get_op_ptr(OpType::Rz, 0.3)},
+        {{true, true}, get_op_ptr(OpType::H)}};
+    MultiplexorBox mbox(op_map);
+    c.add_box(mbox, {0, 1, 2});
+    MultiplexedRotationBox rbox({0.1, -0.4}, OpType::Ry);
+    c.add_box(rbox, {2, 0});
+    nlohmann::json j_box = c;
+    const Circuit new_c = j_box.get<Circuit>();
+
+    const std::vector<Command> coms = new_c.get_commands();
+    const auto& m_b = static_cast<const MultiplexorBox&>(*coms[0].get_op_ptr());
+    REQUIRE(m_b.get_op_map().size() == 2);
+    REQUIRE(*m_b.get_op_map().at({false, true}) == *op_map.at({false, true}));
+    REQUIRE(*m_b.get_op_map().at({true, true}) == *op_map.at({true, true}));
+    REQUIRE(mbox == m_b);
+    const auto& r_b =
+        static_cast<const MultiplexedRotationBox&>(*coms[1].get_op_ptr());
+    REQUIRE(r_b.get_angles() == rbox.get_angles());
+    REQUIRE(r_b.get_axis() == OpType::Ry);
+    REQUIRE(rbox == r_b);
+  }
+  GIVEN("State preparation boxes") {
+    Circuit c(2, "stateprep");
+    Eigen::VectorXcd state(4);
+    state << 0.5, -0.5 * i_, 0., std::sqrt(0.5);
+    StatePreparationBox sbox(state, true);
+    c.add_box(sbox, {1, 0});
+    nlohmann::json j_box = c;
+    const Circuit new_c = j_box.get<Circuit>();
+
+    const auto& s_b = static_cast<const StatePreparationBox&>(
+        *new_c.get_commands()[0].get_op_ptr());
+    REQUIRE(s_b.get_statevector().isApprox(state));
+    REQUIRE(s_b.with_initial_reset());
+    REQUIRE(sbox == s_b);
+  }
+  GIVEN("Diagonal boxes") {
+    Circuit c(2, "diagonal");
+    DiagonalBox dbox({0.1, Expr("a"), -0.4, 1.5});
+    c.add_box(dbox, {0, 1});
+    nlohmann::json j_box = c;
+    const Circuit new_c = j_box.get<Circuit>();
+
+    const auto& d_b =
+        static_cast<const DiagonalBox&>(*new_c.get_commands()[0].get_op_ptr());
+    REQUIRE(d_b.get_phases() == dbox.get_phases());
+    REQUIRE(dbox == d_b);
+  }
+  GIVEN("Toffoli boxes") {
+    Circuit c(2, "toffoli");
+    const state_perm_t perm = {
+        {{false, false}, {true, true}},
+        {{true, true}, {false, true}},
+        {{false, true}, {false, false}}};
+    ToffoliBox tbox(perm, ToffoliBoxSynthStrat::Naive);
+    c.add_box(tbox, {1, 0});
+    nlohmann::json j_box = c;
+    const Circuit new_c = j_box.get<Circuit>();
+
+    const auto& t_b =
+        static_cast<const ToffoliBox&>(*new_c.get_commands()[0].get_op_ptr());
+    REQUIRE(t_b.get_permutation() == perm);
+    REQUIRE(t_b.get_strat() == ToffoliBoxSynthStrat::Naive);
+    REQUIRE(tbox == t_b);
   }
   GIVEN("Pauli ExpBoxes") {
     Circuit c(4, 2, "paulibox");
@@ -595,6 +678,7 @@ SCENARIO("Test compiler pass serializations") {
   COMPPASSJSONTEST(
       EulerAngleReduction, gen_euler_pass(OpType::Rx, OpType::Ry, false))
   COMPPASSJSONTEST(RenameQubitsPass, gen_rename_qubits_pass(qmap))
+  COMPPASSJSONTEST(QubitReusePass, gen_qubit_reuse_pass(2))
   COMPPASSJSONTEST(CliffordSimp, gen_clifford_simp_pass(true))
   COMPPASSJSONTEST(
       DecomposeSwapsToCXs, gen_decompose_routing_gates_to_cxs_pass(arc, false))
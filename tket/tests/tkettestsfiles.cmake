@@ -47,12 +47,16 @@ set(TEST_SOURCES
     ${TKET_TESTS_DIR}/Passes/test_SynthesiseTket.cpp
     ${TKET_TESTS_DIR}/Gate/test_GateUnitaryMatrix.cpp
     ${TKET_TESTS_DIR}/Simulation/test_CircuitSimulator.cpp
+    ${TKET_TESTS_DIR}/Simulation/test_DensityMatrixSimulator.cpp
+    ${TKET_TESTS_DIR}/Simulation/test_ShotSimulator.cpp
+    ${TKET_TESTS_DIR}/Simulation/test_StabiliserSimulator.cpp
     ${TKET_TESTS_DIR}/Simulation/test_PauliExpBoxUnitaryCalculator.cpp
     ${TKET_TESTS_DIR}/Circuit/test_Boxes.cpp
     ${TKET_TESTS_DIR}/Circuit/test_Circ.cpp
     ${TKET_TESTS_DIR}/Circuit/test_CircPool.cpp
     ${TKET_TESTS_DIR}/Circuit/test_Symbolic.cpp
     ${TKET_TESTS_DIR}/Circuit/test_ThreeQubitConversion.cpp
+    ${TKET_TESTS_DIR}/Circuit/test_ShannonDecomposition.cpp
     ${TKET_TESTS_DIR}/test_CliffTableau.cpp
     ${TKET_TESTS_DIR}/test_UnitaryTableau.cpp
     ${TKET_TESTS_DIR}/test_PhasePolynomials.cpp
@@ -68,6 +72,15 @@ set(TEST_SOURCES
     ${TKET_TESTS_DIR}/test_MappingManager.cpp
     ${TKET_TESTS_DIR}/test_LexicographicalComparison.cpp
     ${TKET_TESTS_DIR}/test_LexiRoute.cpp
+    ${TKET_TESTS_DIR}/test_ExactRoute.cpp
+    ${TKET_TESTS_DIR}/test_NoiseAwareRoute.cpp
+    ${TKET_TESTS_DIR}/test_SabreRoute.cpp
+    ${TKET_TESTS_DIR}/test_QubitReuse.cpp
+    ${TKET_TESTS_DIR}/test_Shuttling.cpp
+    ${TKET_TESTS_DIR}/test_RoutingPartitions.cpp
+    ${TKET_TESTS_DIR}/test_CrosstalkScheduling.cpp
+    ${TKET_TESTS_DIR}/test_Schedule.cpp
+    ${TKET_TESTS_DIR}/test_DynamicalDecoupling.cpp
     ${TKET_TESTS_DIR}/test_AASRoute.cpp
     ${TKET_TESTS_DIR}/test_MultiGateReorder.cpp
     ${TKET_TESTS_DIR}/test_BoxDecompRoutingMethod.cpp
@@ -102,4 +115,7 @@ set(TEST_SOURCES
     ${TKET_TESTS_DIR}/ZX/test_Flow.cpp
     ${TKET_TESTS_DIR}/ZX/test_ZXConverters.cpp
     ${TKET_TESTS_DIR}/ZX/test_ZXExtraction.cpp
+    ${TKET_TESTS_DIR}/ZX/test_ZXOptimisation.cpp
+    ${TKET_TESTS_DIR}/ZX/test_ZXTensors.cpp
+    ${TKET_TESTS_DIR}/ZX/test_ZXPatterns.cpp
 )